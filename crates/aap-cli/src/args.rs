@@ -0,0 +1,228 @@
+use std::path::PathBuf;
+
+use clap::{Args, Parser, Subcommand, ValueEnum};
+use solana_sdk::pubkey::Pubkey;
+
+use crate::parse;
+
+#[derive(Parser)]
+#[command(name = "aap-cli", version, about = "Drive the Agent Agreement Protocol from a shell")]
+pub struct Cli {
+    /// Which program to target
+    #[arg(long, value_enum, global = true, default_value = "v1", env = "AAP_PROGRAM")]
+    pub program: Program,
+
+    /// Solana JSON-RPC endpoint
+    #[arg(long, global = true, default_value = "https://api.devnet.solana.com", env = "AAP_RPC_URL")]
+    pub url: String,
+
+    /// Photon indexer endpoint (compressed only; defaults to `--url`)
+    #[arg(long, global = true, env = "AAP_PHOTON_URL")]
+    pub photon_url: Option<String>,
+
+    /// Fee payer keypair file
+    #[arg(long, global = true, default_value = "~/.config/solana/id.json", env = "AAP_KEYPAIR")]
+    pub keypair: PathBuf,
+
+    /// Acting signer keypair file (agent key or authority) if it differs from the fee payer
+    #[arg(long, global = true)]
+    pub signer: Option<PathBuf>,
+
+    #[command(subcommand)]
+    pub command: Command,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum Program {
+    V1,
+    Compressed,
+}
+
+#[derive(Subcommand)]
+pub enum Command {
+    /// Agent identities
+    #[command(subcommand)]
+    Agent(AgentCommand),
+    /// Agreements and parties
+    #[command(subcommand)]
+    Agreement(AgreementCommand),
+    /// SOL vaults (V1 only)
+    #[command(subcommand)]
+    Vault(VaultCommand),
+    /// Fetch any AAP account and decode it by discriminator
+    Decode {
+        /// V1 account pubkey, or compressed address (base58 or hex)
+        address: String,
+    },
+}
+
+// ============================================================
+// Agent
+// ============================================================
+
+#[derive(Subcommand)]
+pub enum AgentCommand {
+    /// Register an agent under the signer (authority), or under `--parent` as a sub-agent
+    Register {
+        /// Agent signing key; defaults to the signer (human signer mode)
+        #[arg(long)]
+        agent: Option<Pubkey>,
+        /// Parent agent key; the signer must be that agent's key
+        #[arg(long)]
+        parent: Option<Pubkey>,
+        /// SHA-256 of the off-chain metadata JSON (hex)
+        #[arg(long, value_parser = parse::hash32, default_value = parse::ZERO_HASH)]
+        metadata_hash: [u8; 32],
+        #[command(flatten)]
+        scope: ScopeArgs,
+    },
+    /// Replace an agent's delegation scope (signed by its authority)
+    Update {
+        #[arg(long)]
+        agent: Pubkey,
+        #[command(flatten)]
+        scope: ScopeArgs,
+    },
+    /// Revoke an agent and reclaim its identity (signed by its authority)
+    Revoke {
+        #[arg(long)]
+        agent: Pubkey,
+    },
+    /// Show an agent identity
+    Show {
+        #[arg(long)]
+        agent: Pubkey,
+    },
+}
+
+#[derive(Args, Clone, Copy)]
+pub struct ScopeArgs {
+    /// Allow the agent to sign agreements
+    #[arg(long)]
+    pub can_sign: bool,
+    /// Allow the agent to commit funds
+    #[arg(long)]
+    pub can_commit: bool,
+    /// Max lamports per commitment (0 = unlimited)
+    #[arg(long, default_value_t = 0)]
+    pub max_commit_lamports: u64,
+    /// Unix timestamp the delegation expires at (0 = never)
+    #[arg(long, default_value_t = 0)]
+    pub expires_at: i64,
+}
+
+// ============================================================
+// Agreement
+// ============================================================
+
+#[derive(Subcommand)]
+pub enum AgreementCommand {
+    /// Propose an agreement; the proposer's party is created and signed
+    Propose {
+        #[arg(long, value_parser = parse::agreement_id)]
+        id: [u8; 16],
+        /// Proposer agent key; defaults to the signer
+        #[arg(long)]
+        agent: Option<Pubkey>,
+        #[arg(long = "type", value_parser = parse::agreement_type, default_value = "service")]
+        agreement_type: u8,
+        #[arg(long, value_parser = parse::visibility, default_value = "public")]
+        visibility: u8,
+        /// SHA-256 of the terms document (hex)
+        #[arg(long, value_parser = parse::hash32, default_value = parse::ZERO_HASH)]
+        terms_hash: [u8; 32],
+        /// Terms location (≤ 64 bytes)
+        #[arg(long, value_parser = parse::terms_uri, default_value = "")]
+        terms_uri: [u8; 64],
+        #[arg(long, default_value_t = 2)]
+        parties: u8,
+        /// Unix timestamp (0 = no expiry)
+        #[arg(long, default_value_t = 0)]
+        expires_at: i64,
+    },
+    /// Add a party (signed by the proposer)
+    AddParty {
+        #[arg(long, value_parser = parse::agreement_id)]
+        id: [u8; 16],
+        /// Party agent key (or raw pubkey with `--direct`)
+        #[arg(long)]
+        party: Pubkey,
+        #[arg(long, value_parser = parse::role, default_value = "counterparty")]
+        role: u8,
+        /// Proposer agent key; defaults to the signer
+        #[arg(long)]
+        agent: Option<Pubkey>,
+        /// Add a raw pubkey without an AgentIdentity (V1 only)
+        #[arg(long)]
+        direct: bool,
+    },
+    /// Sign as a party
+    Sign {
+        #[arg(long, value_parser = parse::agreement_id)]
+        id: [u8; 16],
+        /// Party agent key; defaults to the signer
+        #[arg(long)]
+        agent: Option<Pubkey>,
+        /// Sign as a party added with `add-party --direct` (V1 only)
+        #[arg(long)]
+        direct: bool,
+    },
+    /// Cancel a proposed agreement (proposer agent or its authority)
+    Cancel {
+        #[arg(long, value_parser = parse::agreement_id)]
+        id: [u8; 16],
+        /// Proposer agent key; defaults to the signer
+        #[arg(long)]
+        agent: Option<Pubkey>,
+    },
+    /// Mark an active agreement fulfilled (any party or its authority)
+    Fulfill {
+        #[arg(long, value_parser = parse::agreement_id)]
+        id: [u8; 16],
+        /// Party agent key; defaults to the signer
+        #[arg(long)]
+        agent: Option<Pubkey>,
+    },
+    /// Close a finished agreement and the signer's party record
+    Close {
+        #[arg(long, value_parser = parse::agreement_id)]
+        id: [u8; 16],
+        /// Party agent key; defaults to the signer
+        #[arg(long)]
+        agent: Option<Pubkey>,
+    },
+    /// Show an agreement, or one party of it with `--agent`
+    Show {
+        #[arg(long, value_parser = parse::agreement_id)]
+        id: [u8; 16],
+        #[arg(long)]
+        agent: Option<Pubkey>,
+    },
+}
+
+// ============================================================
+// Vault
+// ============================================================
+
+#[derive(Subcommand)]
+pub enum VaultCommand {
+    /// Deposit lamports into an agent's vault (signed by its authority)
+    Deposit {
+        #[arg(long)]
+        agent: Pubkey,
+        #[arg(long)]
+        lamports: u64,
+    },
+    /// Withdraw lamports from an agent's vault (signed by its authority)
+    Withdraw {
+        #[arg(long)]
+        agent: Pubkey,
+        #[arg(long)]
+        lamports: u64,
+    },
+    /// Show an agent's vault
+    Show {
+        #[arg(long)]
+        agent: Pubkey,
+    },
+}
@@ -0,0 +1,205 @@
+//! `--program compressed`: instructions from `CompressedBuilder`, proven via the
+//! Photon indexer and sent over `LightClient`.
+
+use aap_client::compressed::indexer::CompressedBuilder;
+use aap_client::compressed::{
+    decode, CompressedAgentIdentity, CompressedAgreement, CompressedAgreementParty,
+    CompressedDelegationScope,
+};
+use aap_client::instructions::ProposeAgreementArgs;
+use aap_client::pda::find_config;
+use aap_client::ProtocolConfig;
+use anyhow::{bail, Context, Result};
+use light_client::rpc::{LightClient, LightClientConfig, Rpc};
+use light_sdk::LightDiscriminator;
+use serde_json::{json, Value};
+use solana_sdk::instruction::Instruction;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::Signer;
+
+use crate::args::{AgentCommand, AgreementCommand, Command, ScopeArgs};
+use crate::{output, parse, Keys};
+
+impl From<ScopeArgs> for CompressedDelegationScope {
+    fn from(s: ScopeArgs) -> Self {
+        CompressedDelegationScope {
+            can_sign_agreements: s.can_sign,
+            can_commit_funds: s.can_commit,
+            max_commit_lamports: s.max_commit_lamports,
+            expires_at: s.expires_at,
+        }
+    }
+}
+
+fn builder(rpc: &LightClient) -> Result<CompressedBuilder<'_, LightClient>> {
+    Ok(CompressedBuilder::new(
+        rpc,
+        rpc.get_address_tree_v2(),
+        rpc.get_random_state_tree_info()?,
+    ))
+}
+
+/// Fee recipient from the V1 `ProtocolConfig`, which both programs read.
+async fn treasury(rpc: &LightClient) -> Result<Pubkey> {
+    let address = find_config().0;
+    let account = rpc
+        .get_account(address)
+        .await?
+        .with_context(|| format!("protocol config not found: {address}"))?;
+    Ok(aap_client::accounts::decode::<ProtocolConfig>(&account.data)?.treasury)
+}
+
+async fn send(rpc: &mut LightClient, keys: &Keys, ix: Instruction, fields: Value) -> Result<Value> {
+    let signature = rpc
+        .create_and_send_transaction(&[ix], &keys.payer.pubkey(), &keys.all_signers())
+        .await?;
+    Ok(output::transaction(&signature, fields))
+}
+
+pub async fn run(
+    command: Command,
+    keys: &Keys,
+    url: String,
+    photon_url: Option<String>,
+) -> Result<Value> {
+    let photon_url = photon_url.unwrap_or_else(|| url.clone());
+    let mut rpc = LightClient::new(LightClientConfig::new(url, Some(photon_url), None))
+        .await
+        .context("connecting to RPC / Photon indexer")?;
+    let me = keys.signer().pubkey();
+
+    match command {
+        Command::Agent(cmd) => agent(&mut rpc, keys, me, cmd).await,
+        Command::Agreement(cmd) => agreement(&mut rpc, keys, me, cmd).await,
+        Command::Vault(_) => bail!("vaults exist only on V1; use --program v1"),
+        Command::Decode { address } => {
+            let address = parse::compressed_address(&address).map_err(anyhow::Error::msg)?;
+            decode_any(&rpc, address).await
+        }
+    }
+}
+
+// ============================================================
+// Agent
+// ============================================================
+
+async fn agent(rpc: &mut LightClient, keys: &Keys, me: Pubkey, cmd: AgentCommand) -> Result<Value> {
+    match cmd {
+        AgentCommand::Register { agent, parent, metadata_hash, scope } => {
+            let agent = agent.unwrap_or(me);
+            let b = builder(rpc)?;
+            let address = b.agent_address(&agent);
+            let ix = match parent {
+                None => b.register_agent(&me, &agent, metadata_hash, scope.into()).await?,
+                Some(parent) => {
+                    b.register_sub_agent(&me, &parent, &agent, metadata_hash, scope.into())
+                        .await?
+                }
+            };
+            send(rpc, keys, ix, json!({ "agent_identity": hex::encode(address) })).await
+        }
+        AgentCommand::Update { agent, scope } => {
+            let b = builder(rpc)?;
+            let address = b.agent_address(&agent);
+            let ix = b.update_delegation(&me, &agent, scope.into()).await?;
+            send(rpc, keys, ix, json!({ "agent_identity": hex::encode(address) })).await
+        }
+        AgentCommand::Revoke { agent } => {
+            let b = builder(rpc)?;
+            let address = b.agent_address(&agent);
+            let ix = b.revoke_agent(&me, &agent).await?;
+            send(rpc, keys, ix, json!({ "agent_identity": hex::encode(address) })).await
+        }
+        AgentCommand::Show { agent } => {
+            let b = builder(rpc)?;
+            let identity = b.get_agent_identity(&agent).await?;
+            Ok(output::compressed_identity(&b.agent_address(&agent), &identity))
+        }
+    }
+}
+
+// ============================================================
+// Agreement
+// ============================================================
+
+async fn agreement(
+    rpc: &mut LightClient,
+    keys: &Keys,
+    me: Pubkey,
+    cmd: AgreementCommand,
+) -> Result<Value> {
+    let b = builder(rpc)?;
+    let (ix, id) = match cmd {
+        AgreementCommand::Propose {
+            id,
+            agent,
+            agreement_type,
+            visibility,
+            terms_hash,
+            terms_uri,
+            parties,
+            expires_at,
+        } => {
+            let args = ProposeAgreementArgs {
+                agreement_id: id,
+                agreement_type,
+                visibility,
+                terms_hash,
+                terms_uri,
+                num_parties: parties,
+                expires_at,
+            };
+            let treasury = treasury(rpc).await?;
+            (b.propose_agreement(&me, &agent.unwrap_or(me), &treasury, args).await?, id)
+        }
+        AgreementCommand::AddParty { direct: true, .. } | AgreementCommand::Sign { direct: true, .. } => {
+            bail!("--direct parties exist only on V1; use --program v1")
+        }
+        AgreementCommand::AddParty { id, party, role, agent, .. } => {
+            let ix = b.add_party(&me, &agent.unwrap_or(me), id, &party, role).await?;
+            let party = b.party_address(&id, &party);
+            return send(rpc, keys, ix, json!({ "party": hex::encode(party) })).await;
+        }
+        AgreementCommand::Sign { id, agent, .. } => {
+            (b.sign_agreement(&me, &agent.unwrap_or(me), id).await?, id)
+        }
+        AgreementCommand::Cancel { id, agent } => {
+            (b.cancel_agreement(&me, &agent.unwrap_or(me), id).await?, id)
+        }
+        AgreementCommand::Fulfill { id, agent } => {
+            (b.fulfill_agreement(&me, &agent.unwrap_or(me), id).await?, id)
+        }
+        AgreementCommand::Close { id, agent } => {
+            (b.close_agreement(&me, &agent.unwrap_or(me), id).await?, id)
+        }
+        AgreementCommand::Show { id, agent: None } => {
+            let agreement = b.get_agreement(&id).await?;
+            return Ok(output::compressed_agreement(&b.agreement_address(&id), &agreement));
+        }
+        AgreementCommand::Show { id, agent: Some(agent) } => {
+            let party = b.get_agreement_party(&id, &agent).await?;
+            return Ok(output::compressed_party(&b.party_address(&id, &agent), &party));
+        }
+    };
+    let agreement = b.agreement_address(&id);
+    send(rpc, keys, ix, json!({ "agreement": hex::encode(agreement) })).await
+}
+
+async fn decode_any(rpc: &LightClient, address: [u8; 32]) -> Result<Value> {
+    let account = builder(rpc)?.fetch(address).await?;
+    let Some(data) = account.data else {
+        bail!("{} has no data (closed?)", hex::encode(address));
+    };
+    match data.discriminator {
+        d if d == CompressedAgentIdentity::LIGHT_DISCRIMINATOR => {
+            Ok(output::compressed_identity(&address, &decode(&data.data)?))
+        }
+        d if d == CompressedAgreement::LIGHT_DISCRIMINATOR => {
+            Ok(output::compressed_agreement(&address, &decode(&data.data)?))
+        }
+        d if d == CompressedAgreementParty::LIGHT_DISCRIMINATOR => {
+            Ok(output::compressed_party(&address, &decode(&data.data)?))
+        }
+        _ => bail!("{} is not an aap-compressed account", hex::encode(address)),
+    }
+}
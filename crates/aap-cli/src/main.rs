@@ -0,0 +1,124 @@
+//! `aap-cli` — drive the V1 or compressed AAP program from a shell.
+//!
+//! Every command prints one JSON object on stdout (`{"error": …}` and exit code 1
+//! on failure), so output can be piped into `jq`. Keypairs are read from Solana
+//! CLI keypair files: `--keypair` pays fees, `--signer` (defaults to the payer) is
+//! the acting agent key or authority.
+
+mod args;
+mod compressed;
+mod output;
+mod parse;
+mod v1;
+
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+
+use anyhow::{anyhow, Result};
+use clap::Parser;
+use serde_json::json;
+use solana_sdk::signature::{read_keypair_file, Keypair, Signer};
+
+use args::{Cli, Program};
+
+pub struct Keys {
+    pub payer: Keypair,
+    signer: Option<Keypair>,
+}
+
+impl Keys {
+    /// The acting key: `--signer`, or the payer if none was given.
+    pub fn signer(&self) -> &Keypair {
+        self.signer.as_ref().unwrap_or(&self.payer)
+    }
+
+    /// Signers besides the fee payer.
+    pub fn extra_signers(&self) -> Vec<&Keypair> {
+        self.signer.iter().collect()
+    }
+
+    /// Fee payer first, then `--signer` if it is a different key.
+    pub fn all_signers(&self) -> Vec<&Keypair> {
+        let mut all = vec![&self.payer];
+        all.extend(self.signer.iter().filter(|s| s.pubkey() != self.payer.pubkey()));
+        all
+    }
+}
+
+fn expand_home(path: &Path) -> PathBuf {
+    match (path.strip_prefix("~"), std::env::var_os("HOME")) {
+        (Ok(rest), Some(home)) => PathBuf::from(home).join(rest),
+        _ => path.to_path_buf(),
+    }
+}
+
+fn read_keypair(path: &Path) -> Result<Keypair> {
+    let path = expand_home(path);
+    read_keypair_file(&path).map_err(|e| anyhow!("reading keypair {}: {e}", path.display()))
+}
+
+async fn run(cli: Cli) -> Result<serde_json::Value> {
+    let keys = Keys {
+        payer: read_keypair(&cli.keypair)?,
+        signer: cli.signer.as_deref().map(read_keypair).transpose()?,
+    };
+    match cli.program {
+        Program::V1 => v1::run(cli.command, &keys, cli.url).await,
+        Program::Compressed => compressed::run(cli.command, &keys, cli.url, cli.photon_url).await,
+    }
+}
+
+#[tokio::main]
+async fn main() -> ExitCode {
+    match run(Cli::parse()).await {
+        Ok(out) => {
+            println!("{out:#}");
+            ExitCode::SUCCESS
+        }
+        Err(e) => {
+            println!("{:#}", json!({ "error": format!("{e:#}") }));
+            ExitCode::FAILURE
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clap::CommandFactory;
+
+    #[test]
+    fn cli_definition_is_valid() {
+        Cli::command().debug_assert();
+    }
+
+    #[test]
+    fn parses_compressed_propose() {
+        let cli = Cli::try_parse_from([
+            "aap-cli",
+            "--program",
+            "compressed",
+            "agreement",
+            "propose",
+            "--id",
+            "0190f3c4-7b2a-7e11-8a3c-5d6e7f809102",
+            "--type",
+            "revenue-share",
+            "--terms-uri",
+            "ar://terms",
+        ])
+        .unwrap();
+        assert!(cli.program == Program::Compressed);
+        match cli.command {
+            args::Command::Agreement(args::AgreementCommand::Propose {
+                agreement_type,
+                parties,
+                ..
+            }) => {
+                assert_eq!(agreement_type, aap_core::constants::AGREEMENT_TYPE_REVENUE_SHARE);
+                assert_eq!(parties, 2);
+            }
+            _ => panic!("wrong subcommand"),
+        }
+    }
+}
@@ -0,0 +1,195 @@
+//! JSON views of AAP accounts. Pubkeys are base58, hashes and compressed
+//! addresses hex, enums by name (falling back to the raw u8).
+
+use aap_client::compressed::{
+    CompressedAgentIdentity, CompressedAgreement, CompressedAgreementParty,
+    CompressedDelegationScope,
+};
+use aap_client::{AgentIdentity, AgentVault, Agreement, AgreementParty, DelegationScope, MigrationTombstone};
+use aap_core::{AgreementStatus, AgreementType, PartyRole, Visibility};
+use serde_json::{json, Value};
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::Signature;
+
+fn name<T: TryFrom<u8> + std::fmt::Debug>(value: u8) -> Value {
+    match T::try_from(value) {
+        Ok(v) => json!(format!("{v:?}")),
+        Err(_) => json!(value),
+    }
+}
+
+/// Zero-padded `terms_uri` as text.
+fn uri(bytes: &[u8; 64]) -> String {
+    let end = bytes.iter().position(|b| *b == 0).unwrap_or(bytes.len());
+    String::from_utf8_lossy(&bytes[..end]).into_owned()
+}
+
+pub fn transaction(signature: &Signature, fields: Value) -> Value {
+    let mut out = json!({ "signature": signature.to_string() });
+    if let (Some(out), Value::Object(fields)) = (out.as_object_mut(), fields) {
+        out.extend(fields);
+    }
+    out
+}
+
+// ============================================================
+// V1
+// ============================================================
+
+fn scope(s: &DelegationScope) -> Value {
+    json!({
+        "can_sign_agreements": s.can_sign_agreements,
+        "can_commit_funds": s.can_commit_funds,
+        "max_commit_lamports": s.max_commit_lamports,
+        "expires_at": s.expires_at,
+    })
+}
+
+pub fn agent_identity(address: &Pubkey, a: &AgentIdentity) -> Value {
+    json!({
+        "type": "AgentIdentity",
+        "address": address.to_string(),
+        "authority": a.authority.to_string(),
+        "agent_key": a.agent_key.to_string(),
+        "metadata_hash": hex::encode(a.metadata_hash),
+        "scope": scope(&a.scope),
+        "parent": (a.parent != Pubkey::default()).then(|| a.parent.to_string()),
+        "created_at": a.created_at,
+    })
+}
+
+pub fn agreement(address: &Pubkey, a: &Agreement) -> Value {
+    json!({
+        "type": "Agreement",
+        "address": address.to_string(),
+        "agreement_id": hex::encode(a.agreement_id),
+        "agreement_type": name::<AgreementType>(a.agreement_type),
+        "status": name::<AgreementStatus>(a.status),
+        "visibility": name::<Visibility>(a.visibility),
+        "proposer": a.proposer.to_string(),
+        "terms_hash": hex::encode(a.terms_hash),
+        "terms_uri": uri(&a.terms_uri),
+        "escrow_total": a.escrow_total,
+        "num_parties": a.num_parties,
+        "num_signed": a.num_signed,
+        "parties_added": a.parties_added,
+        "created_at": a.created_at,
+        "expires_at": a.expires_at,
+    })
+}
+
+pub fn agreement_party(address: &Pubkey, p: &AgreementParty) -> Value {
+    json!({
+        "type": "AgreementParty",
+        "address": address.to_string(),
+        "agreement": p.agreement.to_string(),
+        "agent_identity": p.agent_identity.to_string(),
+        "role": name::<PartyRole>(p.role),
+        "signed": p.signed,
+        "signed_at": p.signed_at,
+        "escrow_deposited": p.escrow_deposited,
+    })
+}
+
+pub fn vault(address: &Pubkey, v: &AgentVault, lamports: Option<u64>) -> Value {
+    json!({
+        "type": "AgentVault",
+        "address": address.to_string(),
+        "agent_identity": v.agent_identity.to_string(),
+        "authority": v.authority.to_string(),
+        "total_deposited": v.total_deposited,
+        "total_withdrawn": v.total_withdrawn,
+        "total_committed": v.total_committed,
+        "lamports": lamports,
+    })
+}
+
+pub fn tombstone(address: &Pubkey, t: &MigrationTombstone) -> Value {
+    json!({
+        "type": "MigrationTombstone",
+        "address": address.to_string(),
+        "original": t.original.to_string(),
+        "kind": t.kind,
+        "key": hex::encode(t.key),
+        "authority": t.authority.to_string(),
+        "migrated_at": t.migrated_at,
+    })
+}
+
+// ============================================================
+// Compressed
+// ============================================================
+
+fn compressed_scope(s: &CompressedDelegationScope) -> Value {
+    json!({
+        "can_sign_agreements": s.can_sign_agreements,
+        "can_commit_funds": s.can_commit_funds,
+        "max_commit_lamports": s.max_commit_lamports,
+        "expires_at": s.expires_at,
+    })
+}
+
+pub fn compressed_identity(address: &[u8; 32], a: &CompressedAgentIdentity) -> Value {
+    json!({
+        "type": "CompressedAgentIdentity",
+        "address": hex::encode(address),
+        "authority": a.authority.to_string(),
+        "agent_key": a.agent_key.to_string(),
+        "metadata_hash": hex::encode(a.metadata_hash),
+        "scope": compressed_scope(&a.scope),
+        "parent": (a.parent != Pubkey::default()).then(|| a.parent.to_string()),
+        "created_at": a.created_at,
+    })
+}
+
+pub fn compressed_agreement(address: &[u8; 32], a: &CompressedAgreement) -> Value {
+    json!({
+        "type": "CompressedAgreement",
+        "address": hex::encode(address),
+        "agreement_id": hex::encode(a.agreement_id),
+        "agreement_type": name::<AgreementType>(a.agreement_type),
+        "status": name::<AgreementStatus>(a.status),
+        "visibility": name::<Visibility>(a.visibility),
+        "proposer": a.proposer.to_string(),
+        "terms_hash": hex::encode(a.terms_hash),
+        "terms_uri": uri(&a.terms_uri),
+        "num_parties": a.num_parties,
+        "num_signed": a.num_signed,
+        "parties_added": a.parties_added,
+        "created_at": a.created_at,
+        "expires_at": a.expires_at,
+    })
+}
+
+pub fn compressed_party(address: &[u8; 32], p: &CompressedAgreementParty) -> Value {
+    json!({
+        "type": "CompressedAgreementParty",
+        "address": hex::encode(address),
+        "agreement_address": hex::encode(p.agreement_address),
+        "agent_identity_address": hex::encode(p.agent_identity_address),
+        "role": name::<PartyRole>(p.role),
+        "signed": p.signed,
+        "signed_at": p.signed_at,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn enums_render_by_name_and_unknown_as_number() {
+        assert_eq!(name::<AgreementStatus>(1), json!("Active"));
+        assert_eq!(name::<PartyRole>(9), json!(9));
+        let mut raw = [0u8; 64];
+        raw[..4].copy_from_slice(b"ar:/");
+        assert_eq!(uri(&raw), "ar:/");
+    }
+
+    #[test]
+    fn transaction_merges_fields() {
+        let out = transaction(&Signature::default(), json!({ "agreement": "x" }));
+        assert_eq!(out["agreement"], "x");
+        assert!(out["signature"].is_string());
+    }
+}
@@ -0,0 +1,122 @@
+//! Argument parsers: hex hashes, agreement IDs and the protocol's u8 enums by name.
+
+use aap_core::constants::*;
+
+pub const ZERO_HASH: &str = "0000000000000000000000000000000000000000000000000000000000000000";
+
+fn hex_array<const N: usize>(s: &str) -> Result<[u8; N], String> {
+    let bytes = hex::decode(s.trim_start_matches("0x")).map_err(|e| e.to_string())?;
+    bytes
+        .try_into()
+        .map_err(|b: Vec<u8>| format!("expected {N} bytes of hex, got {}", b.len()))
+}
+
+/// 32-byte hash as 64 hex characters.
+pub fn hash32(s: &str) -> Result<[u8; 32], String> {
+    hex_array(s)
+}
+
+/// 16-byte agreement ID as 32 hex characters or a dashed UUID.
+pub fn agreement_id(s: &str) -> Result<[u8; 16], String> {
+    hex_array(&s.replace('-', ""))
+}
+
+/// UTF-8 string, zero-padded to the on-chain 64 bytes.
+pub fn terms_uri(s: &str) -> Result<[u8; 64], String> {
+    if s.len() > 64 {
+        return Err(format!("terms URI is {} bytes, max 64", s.len()));
+    }
+    let mut uri = [0u8; 64];
+    uri[..s.len()].copy_from_slice(s.as_bytes());
+    Ok(uri)
+}
+
+/// Name or raw u8 from a `(name, value)` table.
+fn named(s: &str, kind: &str, table: &[(&str, u8)]) -> Result<u8, String> {
+    let key = s.to_ascii_lowercase().replace('_', "-");
+    table
+        .iter()
+        .find(|(name, _)| *name == key)
+        .map(|(_, value)| *value)
+        .or_else(|| s.parse().ok().filter(|v| table.iter().any(|(_, t)| t == v)))
+        .ok_or_else(|| {
+            let names: Vec<_> = table.iter().map(|(name, _)| *name).collect();
+            format!("unknown {kind} `{s}` (expected one of: {})", names.join(", "))
+        })
+}
+
+pub fn agreement_type(s: &str) -> Result<u8, String> {
+    named(
+        s,
+        "agreement type",
+        &[
+            ("safe", AGREEMENT_TYPE_SAFE),
+            ("service", AGREEMENT_TYPE_SERVICE),
+            ("revenue-share", AGREEMENT_TYPE_REVENUE_SHARE),
+            ("joint-venture", AGREEMENT_TYPE_JOINT_VENTURE),
+            ("custom", AGREEMENT_TYPE_CUSTOM),
+        ],
+    )
+}
+
+pub fn visibility(s: &str) -> Result<u8, String> {
+    named(
+        s,
+        "visibility",
+        &[("public", VISIBILITY_PUBLIC), ("private", VISIBILITY_PRIVATE)],
+    )
+}
+
+pub fn role(s: &str) -> Result<u8, String> {
+    named(
+        s,
+        "role",
+        &[
+            ("proposer", ROLE_PROPOSER),
+            ("counterparty", ROLE_COUNTERPARTY),
+            ("witness", ROLE_WITNESS),
+            ("arbitrator", ROLE_ARBITRATOR),
+        ],
+    )
+}
+
+/// Compressed addresses are printed as hex; accept that or base58.
+pub fn compressed_address(s: &str) -> Result<[u8; 32], String> {
+    hex_array(s).or_else(|_| {
+        s.parse::<solana_sdk::pubkey::Pubkey>()
+            .map(|p| p.to_bytes())
+            .map_err(|_| format!("`{s}` is neither 32-byte hex nor base58"))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn agreement_id_accepts_hex_and_uuid() {
+        let uuid = "0190f3c4-7b2a-7e11-8a3c-5d6e7f809102";
+        let id = agreement_id(uuid).unwrap();
+        assert_eq!(id, agreement_id(&uuid.replace('-', "")).unwrap());
+        assert_eq!(id[0], 0x01);
+        assert!(agreement_id("abcd").is_err());
+    }
+
+    #[test]
+    fn enums_parse_by_name_or_value() {
+        assert_eq!(agreement_type("Revenue_Share"), Ok(AGREEMENT_TYPE_REVENUE_SHARE));
+        assert_eq!(agreement_type("4"), Ok(AGREEMENT_TYPE_CUSTOM));
+        assert!(agreement_type("5").is_err());
+        assert_eq!(role("witness"), Ok(ROLE_WITNESS));
+        assert_eq!(visibility("private"), Ok(VISIBILITY_PRIVATE));
+    }
+
+    #[test]
+    fn terms_uri_is_zero_padded() {
+        let uri = terms_uri("ar://abc").unwrap();
+        assert_eq!(&uri[..8], b"ar://abc");
+        assert!(uri[8..].iter().all(|b| *b == 0));
+        assert!(terms_uri(&"x".repeat(65)).is_err());
+        assert_eq!(hash32(ZERO_HASH), Ok([0u8; 32]));
+    }
+}
@@ -0,0 +1,225 @@
+//! `--program v1`: instructions from `aap_client::instructions`, sent over `RpcClient`.
+
+use aap_client::accounts::{decode, is_account};
+use aap_client::instructions::{self, ProposeAgreementArgs};
+use aap_client::pda::*;
+use aap_client::rpc::AapRpc;
+use aap_client::{
+    AapClient, AgentIdentity, AgentVault, Agreement, AgreementParty, DelegationScope,
+    MigrationTombstone,
+};
+use anyhow::{bail, ensure, Result};
+use serde_json::{json, Value};
+use solana_rpc_client::nonblocking::rpc_client::RpcClient;
+use solana_sdk::commitment_config::CommitmentConfig;
+use solana_sdk::instruction::Instruction;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::Signer;
+
+use crate::args::{AgentCommand, AgreementCommand, Command, ScopeArgs, VaultCommand};
+use crate::output;
+use crate::Keys;
+
+impl From<ScopeArgs> for DelegationScope {
+    fn from(s: ScopeArgs) -> Self {
+        DelegationScope {
+            can_sign_agreements: s.can_sign,
+            can_commit_funds: s.can_commit,
+            max_commit_lamports: s.max_commit_lamports,
+            expires_at: s.expires_at,
+        }
+    }
+}
+
+struct V1 {
+    client: AapClient<RpcClient>,
+}
+
+impl V1 {
+    async fn send(&self, keys: &Keys, ix: Instruction, fields: Value) -> Result<Value> {
+        // `RpcClient` has an inherent `send`; call the trait method explicitly
+        let signature = AapRpc::send(self.client.rpc(), &[ix], &keys.payer, &keys.extra_signers()).await?;
+        Ok(output::transaction(&signature, fields))
+    }
+}
+
+fn found<T>(address: Pubkey, account: Option<T>) -> Result<T> {
+    match account {
+        Some(account) => Ok(account),
+        None => bail!("account not found: {address}"),
+    }
+}
+
+pub async fn run(command: Command, keys: &Keys, url: String) -> Result<Value> {
+    let v1 = V1 {
+        client: AapClient::new(RpcClient::new_with_commitment(url, CommitmentConfig::confirmed())),
+    };
+    let me = keys.signer().pubkey();
+
+    match command {
+        Command::Agent(cmd) => agent(&v1, keys, me, cmd).await,
+        Command::Agreement(cmd) => agreement(&v1, keys, me, cmd).await,
+        Command::Vault(cmd) => vault(&v1, keys, me, cmd).await,
+        Command::Decode { address } => decode_any(&v1, address.parse()?).await,
+    }
+}
+
+// ============================================================
+// Agent
+// ============================================================
+
+async fn agent(v1: &V1, keys: &Keys, me: Pubkey, cmd: AgentCommand) -> Result<Value> {
+    match cmd {
+        AgentCommand::Register { agent, parent, metadata_hash, scope } => {
+            let agent = agent.unwrap_or(me);
+            let ix = match parent {
+                None => instructions::register_agent(&me, &agent, metadata_hash, scope.into()),
+                Some(parent) => {
+                    ensure!(parent == me, "V1 sub-agents are registered by the parent agent key; pass it as --signer");
+                    instructions::register_sub_agent(&parent, &agent, metadata_hash, scope.into())
+                }
+            };
+            let identity = find_agent_identity(&agent).0;
+            v1.send(keys, ix, json!({ "agent_identity": identity.to_string() })).await
+        }
+        AgentCommand::Update { agent, scope } => {
+            let ix = instructions::update_delegation(&me, &agent, scope.into());
+            v1.send(keys, ix, json!({ "agent_identity": find_agent_identity(&agent).0.to_string() })).await
+        }
+        AgentCommand::Revoke { agent } => {
+            let ix = instructions::revoke_agent(&me, &agent);
+            v1.send(keys, ix, json!({ "agent_identity": find_agent_identity(&agent).0.to_string() })).await
+        }
+        AgentCommand::Show { agent } => {
+            let address = find_agent_identity(&agent).0;
+            let identity = found(address, v1.client.get_agent_identity(&agent).await?)?;
+            Ok(output::agent_identity(&address, &identity))
+        }
+    }
+}
+
+// ============================================================
+// Agreement
+// ============================================================
+
+async fn agreement(v1: &V1, keys: &Keys, me: Pubkey, cmd: AgreementCommand) -> Result<Value> {
+    match cmd {
+        AgreementCommand::Propose {
+            id,
+            agent,
+            agreement_type,
+            visibility,
+            terms_hash,
+            terms_uri,
+            parties,
+            expires_at,
+        } => {
+            ensure!(agent.is_none_or(|a| a == me), "V1 proposals are signed by the proposer agent key; pass it as --signer");
+            let ix = instructions::propose_agreement(
+                &me,
+                v1.client.freeze_scope(&me).await?,
+                &v1.client.treasury().await?,
+                ProposeAgreementArgs {
+                    agreement_id: id,
+                    agreement_type,
+                    visibility,
+                    terms_hash,
+                    terms_uri,
+                    num_parties: parties,
+                    expires_at,
+                },
+            );
+            v1.send(keys, ix, json!({ "agreement": find_agreement(&id).0.to_string() })).await
+        }
+        AgreementCommand::AddParty { id, party, role, agent, direct } => {
+            ensure!(agent.is_none_or(|a| a == me), "V1 parties are added by the proposer agent key; pass it as --signer");
+            let (ix, party_pda) = if direct {
+                (
+                    instructions::add_party_direct(&me, v1.client.freeze_scope(&me).await?, id, &party, role),
+                    find_agreement_party(&id, &party).0,
+                )
+            } else {
+                (
+                    instructions::add_party(&me, v1.client.freeze_scope(&me).await?, id, &party, role),
+                    find_agreement_party(&id, &find_agent_identity(&party).0).0,
+                )
+            };
+            v1.send(keys, ix, json!({ "party": party_pda.to_string() })).await
+        }
+        AgreementCommand::Sign { id, agent, direct } => {
+            ensure!(agent.is_none_or(|a| a == me), "V1 parties sign with their own key; pass it as --signer");
+            let ix = if direct {
+                instructions::sign_agreement_direct(&me, id)
+            } else {
+                instructions::sign_agreement(&me, v1.client.freeze_scope(&me).await?, id)
+            };
+            v1.send(keys, ix, json!({ "agreement": find_agreement(&id).0.to_string() })).await
+        }
+        AgreementCommand::Cancel { id, agent } => {
+            let ix = instructions::cancel_agreement(&me, &agent.unwrap_or(me), id);
+            v1.send(keys, ix, json!({ "agreement": find_agreement(&id).0.to_string() })).await
+        }
+        AgreementCommand::Fulfill { id, agent } => {
+            let ix = instructions::fulfill_agreement(&me, &agent.unwrap_or(me), id);
+            v1.send(keys, ix, json!({ "agreement": find_agreement(&id).0.to_string() })).await
+        }
+        AgreementCommand::Close { id, agent } => {
+            let ix = instructions::close_agreement(&me, &agent.unwrap_or(me), id);
+            v1.send(keys, ix, json!({ "agreement": find_agreement(&id).0.to_string() })).await
+        }
+        AgreementCommand::Show { id, agent: None } => {
+            let address = find_agreement(&id).0;
+            let agreement = found(address, v1.client.get_agreement(&id).await?)?;
+            Ok(output::agreement(&address, &agreement))
+        }
+        AgreementCommand::Show { id, agent: Some(agent) } => {
+            let address = find_agreement_party(&id, &find_agent_identity(&agent).0).0;
+            let party = found(address, v1.client.get_agreement_party(&id, &agent).await?)?;
+            Ok(output::agreement_party(&address, &party))
+        }
+    }
+}
+
+// ============================================================
+// Vault
+// ============================================================
+
+async fn vault(v1: &V1, keys: &Keys, me: Pubkey, cmd: VaultCommand) -> Result<Value> {
+    match cmd {
+        VaultCommand::Deposit { agent, lamports } => {
+            let ix = instructions::deposit_to_vault(&me, &agent, lamports);
+            let vault = find_vault(&find_agent_identity(&agent).0).0;
+            v1.send(keys, ix, json!({ "vault": vault.to_string(), "lamports": lamports })).await
+        }
+        VaultCommand::Withdraw { agent, lamports } => {
+            let ix = instructions::withdraw_from_vault(&me, &agent, &me, lamports);
+            let vault = find_vault(&find_agent_identity(&agent).0).0;
+            v1.send(keys, ix, json!({ "vault": vault.to_string(), "lamports": lamports })).await
+        }
+        VaultCommand::Show { agent } => {
+            let address = find_vault(&find_agent_identity(&agent).0).0;
+            let vault = found(address, v1.client.get_vault(&agent).await?)?;
+            let lamports = v1.client.rpc().get_balance(&address).await?;
+            Ok(output::vault(&address, &vault, Some(lamports)))
+        }
+    }
+}
+
+async fn decode_any(v1: &V1, address: Pubkey) -> Result<Value> {
+    let Some(data) = AapRpc::get_account_data(v1.client.rpc(), &address).await? else {
+        bail!("account not found: {address}");
+    };
+    if is_account::<AgentIdentity>(&data) {
+        Ok(output::agent_identity(&address, &decode(&data)?))
+    } else if is_account::<Agreement>(&data) {
+        Ok(output::agreement(&address, &decode(&data)?))
+    } else if is_account::<AgreementParty>(&data) {
+        Ok(output::agreement_party(&address, &decode(&data)?))
+    } else if is_account::<AgentVault>(&data) {
+        Ok(output::vault(&address, &decode(&data)?, None))
+    } else if is_account::<MigrationTombstone>(&data) {
+        Ok(output::tombstone(&address, &decode(&data)?))
+    } else {
+        bail!("{address} is not an AAP V1 account")
+    }
+}
@@ -0,0 +1,51 @@
+use anchor_lang::{AccountDeserialize, Discriminator};
+
+use crate::error::ClientError;
+
+/// Decode an Anchor account (discriminator checked) from raw account data.
+pub fn decode<T: AccountDeserialize + Discriminator>(data: &[u8]) -> Result<T, ClientError> {
+    let mut slice = data;
+    T::try_deserialize(&mut slice).map_err(|e| ClientError::Deserialize(e.to_string()))
+}
+
+/// `true` if `data` starts with `T`'s discriminator — for filtering
+/// `getProgramAccounts` results without fully decoding them.
+pub fn is_account<T: Discriminator>(data: &[u8]) -> bool {
+    data.starts_with(T::DISCRIMINATOR)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{AgentIdentity, Agreement, DelegationScope};
+    use anchor_lang::AccountSerialize;
+    use solana_sdk::pubkey::Pubkey;
+
+    #[test]
+    fn roundtrip_and_type_check() {
+        let identity = AgentIdentity {
+            authority: Pubkey::new_unique(),
+            agent_key: Pubkey::new_unique(),
+            metadata_hash: [1u8; 32],
+            scope: DelegationScope {
+                can_sign_agreements: true,
+                can_commit_funds: false,
+                max_commit_lamports: 0,
+                expires_at: 0,
+            },
+            parent: Pubkey::default(),
+            created_at: 42,
+            bump: 255,
+        };
+        let mut data = Vec::new();
+        identity.try_serialize(&mut data).unwrap();
+
+        let decoded: AgentIdentity = decode(&data).unwrap();
+        assert_eq!(decoded.agent_key, identity.agent_key);
+        assert_eq!(decoded.created_at, 42);
+
+        assert!(is_account::<AgentIdentity>(&data));
+        assert!(!is_account::<Agreement>(&data));
+        assert!(matches!(decode::<Agreement>(&data), Err(ClientError::Deserialize(_))));
+    }
+}
@@ -0,0 +1,1024 @@
+use anchor_lang::{AccountDeserialize, Discriminator};
+use solana_sdk::instruction::Instruction;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::{Keypair, Signature, Signer};
+
+use crate::accounts::{decode, is_account};
+use crate::error::ClientError;
+use crate::instructions::{
+    self, CounterProposeArgs, FreezeScope, ProposeAgreementArgs, ProposeAmendmentArgs, StreamEnds,
+};
+use crate::pda::*;
+use crate::rpc::AapRpc;
+use crate::{
+    AgentIdentity, AgentKeyLookup, AgentVault, Agreement, AgreementAmendment, AgreementParty,
+    AuthorityAction, AuthoritySet, AuthorityTransfer, ConfigParams, CounterProposal, Decline,
+    DelegationScope, EscrowStream, Freeze, MigrationTombstone, MilestoneSchedule, MilestoneTerms,
+    ProtocolConfig, RecurringPayment, RecurringTerms, RevenueShare, RevenueSplit, Safe, SafeTerms,
+    Termination,
+};
+
+/// Async V1 client over any [`AapRpc`] backend.
+///
+/// Read methods return `Ok(None)` for missing accounts. Write methods send one
+/// transaction, with the acting keypair as fee payer.
+pub struct AapClient<C> {
+    rpc: C,
+}
+
+impl<C: AapRpc> AapClient<C> {
+    pub fn new(rpc: C) -> Self {
+        Self { rpc }
+    }
+
+    pub fn rpc(&self) -> &C {
+        &self.rpc
+    }
+
+    // ============================================================
+    // Reads
+    // ============================================================
+
+    pub async fn fetch<T: AccountDeserialize + Discriminator>(
+        &self,
+        address: &Pubkey,
+    ) -> Result<Option<T>, ClientError> {
+        match self.rpc.get_account_data(address).await? {
+            Some(data) => decode(&data).map(Some),
+            None => Ok(None),
+        }
+    }
+
+    /// Address of the identity currently holding `agent_key`, following the
+    /// key lookup left by `rotate_agent_key`.
+    pub async fn resolve_agent_identity(
+        &self,
+        agent_key: &Pubkey,
+    ) -> Result<Option<Pubkey>, ClientError> {
+        let address = find_agent_identity(agent_key).0;
+        match self.rpc.get_account_data(&address).await? {
+            Some(data) if is_account::<AgentKeyLookup>(&data) => {
+                Ok(Some(decode::<AgentKeyLookup>(&data)?.identity))
+            }
+            Some(_) => Ok(Some(address)),
+            None => Ok(None),
+        }
+    }
+
+    /// Identity registered with `agent_key` or rotated to it.
+    pub async fn get_agent_identity(
+        &self,
+        agent_key: &Pubkey,
+    ) -> Result<Option<AgentIdentity>, ClientError> {
+        match self.resolve_agent_identity(agent_key).await? {
+            Some(address) => self.fetch(&address).await,
+            None => Ok(None),
+        }
+    }
+
+    pub async fn get_agreement(
+        &self,
+        agreement_id: &[u8; 16],
+    ) -> Result<Option<Agreement>, ClientError> {
+        self.fetch(&find_agreement(agreement_id).0).await
+    }
+
+    pub async fn get_agreement_party(
+        &self,
+        agreement_id: &[u8; 16],
+        agent_key: &Pubkey,
+    ) -> Result<Option<AgreementParty>, ClientError> {
+        let identity = find_agent_identity(agent_key).0;
+        self.fetch(&find_agreement_party(agreement_id, &identity).0).await
+    }
+
+    /// Decline record of `party`: an identity PDA, or the wallet of a direct party.
+    pub async fn get_decline(
+        &self,
+        agreement_id: &[u8; 16],
+        party: &Pubkey,
+    ) -> Result<Option<Decline>, ClientError> {
+        self.fetch(&find_decline(&find_agreement_party(agreement_id, party).0).0).await
+    }
+
+    /// Amendment `version` (from 1) of an agreement's terms.
+    pub async fn get_amendment(
+        &self,
+        agreement_id: &[u8; 16],
+        version: u16,
+    ) -> Result<Option<AgreementAmendment>, ClientError> {
+        self.fetch(&find_amendment(&find_agreement(agreement_id).0, version).0).await
+    }
+
+    /// Round `round` (from 1) of the counter-proposals by `party_agent_key`.
+    pub async fn get_counter_proposal(
+        &self,
+        agreement_id: &[u8; 16],
+        party_agent_key: &Pubkey,
+        round: u16,
+    ) -> Result<Option<CounterProposal>, ClientError> {
+        let party = find_agreement_party(agreement_id, &find_agent_identity(party_agent_key).0).0;
+        self.fetch(&find_counter_proposal(&party, round).0).await
+    }
+
+    /// Termination proposed (or applied) for the agreement.
+    pub async fn get_termination(&self, agreement_id: &[u8; 16]) -> Result<Option<Termination>, ClientError> {
+        self.fetch(&find_termination(&find_agreement(agreement_id).0).0).await
+    }
+
+    /// Milestone schedule of the agreement, if it has one.
+    pub async fn get_milestones(&self, agreement_id: &[u8; 16]) -> Result<Option<MilestoneSchedule>, ClientError> {
+        self.fetch(&find_milestones(&find_agreement(agreement_id).0).0).await
+    }
+
+    /// Recurring payment of the agreement, if it has one.
+    pub async fn get_recurring_payment(
+        &self,
+        agreement_id: &[u8; 16],
+    ) -> Result<Option<RecurringPayment>, ClientError> {
+        self.fetch(&find_recurring_payment(&find_agreement(agreement_id).0).0).await
+    }
+
+    /// Escrow stream of the agreement, if one was funded.
+    pub async fn get_stream(&self, agreement_id: &[u8; 16]) -> Result<Option<EscrowStream>, ClientError> {
+        self.fetch(&find_stream(&find_agreement(agreement_id).0).0).await
+    }
+
+    /// Revenue-share splits of the agreement, if it has them.
+    pub async fn get_revenue_share(&self, agreement_id: &[u8; 16]) -> Result<Option<RevenueShare>, ClientError> {
+        self.fetch(&find_revenue_share(&find_agreement(agreement_id).0).0).await
+    }
+
+    /// SAFE terms of the agreement, if it has them.
+    pub async fn get_safe(&self, agreement_id: &[u8; 16]) -> Result<Option<Safe>, ClientError> {
+        self.fetch(&find_safe(&find_agreement(agreement_id).0).0).await
+    }
+
+    pub async fn get_vault(&self, agent_key: &Pubkey) -> Result<Option<AgentVault>, ClientError> {
+        let identity = find_agent_identity(agent_key).0;
+        self.fetch(&find_vault(&identity).0).await
+    }
+
+    /// Pending authority transfer of the identity registered with `agent_key`.
+    pub async fn get_authority_transfer(
+        &self,
+        agent_key: &Pubkey,
+    ) -> Result<Option<AuthorityTransfer>, ClientError> {
+        self.fetch(&find_authority_transfer(&find_agent_identity(agent_key).0).0).await
+    }
+
+    /// Freeze set on `target`: an identity PDA, or an authority key for an
+    /// authority-wide freeze.
+    pub async fn get_freeze(&self, target: &Pubkey) -> Result<Option<Freeze>, ClientError> {
+        self.fetch(&find_freeze(target).0).await
+    }
+
+    /// Authority and parent of the identity registered with `agent_key`, for
+    /// the freeze accounts of `propose_agreement`, `add_party` and `sign_agreement`.
+    pub async fn freeze_scope(&self, agent_key: &Pubkey) -> Result<FreezeScope, ClientError> {
+        let address = find_agent_identity(agent_key).0;
+        let identity: AgentIdentity =
+            self.fetch(&address).await?.ok_or(ClientError::AccountNotFound(address))?;
+        Ok(FreezeScope::from(&identity))
+    }
+
+    pub async fn get_authority_set(
+        &self,
+        set_id: &[u8; 16],
+    ) -> Result<Option<AuthoritySet>, ClientError> {
+        self.fetch(&find_authority_set(set_id).0).await
+    }
+
+    /// Pending action `index` of the authority set `set_id`.
+    pub async fn get_authority_action(
+        &self,
+        set_id: &[u8; 16],
+        index: u64,
+    ) -> Result<Option<AuthorityAction>, ClientError> {
+        self.fetch(&find_authority_action(&find_authority_set(set_id).0, index).0).await
+    }
+
+    pub async fn get_config(&self) -> Result<Option<ProtocolConfig>, ClientError> {
+        self.fetch(&find_config().0).await
+    }
+
+    /// Fee recipient `propose_agreement` must pass.
+    pub async fn treasury(&self) -> Result<Pubkey, ClientError> {
+        let address = find_config().0;
+        let config = self.get_config().await?.ok_or(ClientError::AccountNotFound(address))?;
+        Ok(config.treasury)
+    }
+
+    /// Tombstone left by `migrate_*` for the V1 PDA `original`.
+    pub async fn get_tombstone(
+        &self,
+        original: &Pubkey,
+    ) -> Result<Option<MigrationTombstone>, ClientError> {
+        self.fetch(&find_tombstone(original).0).await
+    }
+
+    // ============================================================
+    // Identity
+    // ============================================================
+
+    pub async fn register_agent(
+        &self,
+        authority: &Keypair,
+        agent_key: &Pubkey,
+        metadata_hash: [u8; 32],
+        scope: DelegationScope,
+    ) -> Result<Signature, ClientError> {
+        let ix = instructions::register_agent(&authority.pubkey(), agent_key, metadata_hash, scope);
+        self.rpc.send(&[ix], authority, &[]).await
+    }
+
+    pub async fn update_delegation(
+        &self,
+        authority: &Keypair,
+        agent_key: &Pubkey,
+        new_scope: DelegationScope,
+    ) -> Result<Signature, ClientError> {
+        let ix = instructions::update_delegation(&authority.pubkey(), agent_key, new_scope);
+        self.rpc.send(&[ix], authority, &[]).await
+    }
+
+    pub async fn register_sub_agent(
+        &self,
+        parent_agent: &Keypair,
+        sub_agent_key: &Pubkey,
+        metadata_hash: [u8; 32],
+        scope: DelegationScope,
+    ) -> Result<Signature, ClientError> {
+        let ix = instructions::register_sub_agent(
+            &parent_agent.pubkey(),
+            sub_agent_key,
+            metadata_hash,
+            scope,
+        );
+        self.rpc.send(&[ix], parent_agent, &[]).await
+    }
+
+    pub async fn revoke_agent(
+        &self,
+        authority: &Keypair,
+        agent_key: &Pubkey,
+    ) -> Result<Signature, ClientError> {
+        let ix = instructions::revoke_agent(&authority.pubkey(), agent_key);
+        self.rpc.send(&[ix], authority, &[]).await
+    }
+
+    /// Move the identity registered with `registered_agent_key` to `new_agent`'s
+    /// key, co-signed by `new_agent`.
+    pub async fn rotate_agent_key(
+        &self,
+        authority: &Keypair,
+        registered_agent_key: &Pubkey,
+        new_agent: &Keypair,
+    ) -> Result<Signature, ClientError> {
+        let address = find_agent_identity(registered_agent_key).0;
+        let identity: AgentIdentity = self
+            .fetch(&address)
+            .await?
+            .ok_or(ClientError::AccountNotFound(address))?;
+        let ix = instructions::rotate_agent_key(
+            &authority.pubkey(),
+            registered_agent_key,
+            &identity.agent_key,
+            &new_agent.pubkey(),
+            true,
+        );
+        self.rpc.send(&[ix], authority, &[new_agent]).await
+    }
+
+    pub async fn propose_authority_transfer(
+        &self,
+        authority: &Keypair,
+        agent_key: &Pubkey,
+        new_authority: &Pubkey,
+    ) -> Result<Signature, ClientError> {
+        let ix = instructions::propose_authority_transfer(&authority.pubkey(), agent_key, new_authority);
+        self.rpc.send(&[ix], authority, &[]).await
+    }
+
+    /// Accept a pending transfer, moving the listed sub-agents along. Sub-agents
+    /// left out keep the previous authority.
+    pub async fn accept_authority_transfer(
+        &self,
+        new_authority: &Keypair,
+        agent_key: &Pubkey,
+        sub_agent_keys: &[Pubkey],
+    ) -> Result<Signature, ClientError> {
+        let address = find_authority_transfer(&find_agent_identity(agent_key).0).0;
+        let transfer: AuthorityTransfer =
+            self.fetch(&address).await?.ok_or(ClientError::AccountNotFound(address))?;
+        let ix = instructions::accept_authority_transfer(
+            &new_authority.pubkey(),
+            &transfer.authority,
+            agent_key,
+            sub_agent_keys,
+        );
+        self.rpc.send(&[ix], new_authority, &[]).await
+    }
+
+    pub async fn cancel_authority_transfer(
+        &self,
+        authority: &Keypair,
+        agent_key: &Pubkey,
+    ) -> Result<Signature, ClientError> {
+        let ix = instructions::cancel_authority_transfer(&authority.pubkey(), agent_key);
+        self.rpc.send(&[ix], authority, &[]).await
+    }
+
+    // ============================================================
+    // Freeze
+    // ============================================================
+
+    pub async fn freeze_agent(
+        &self,
+        authority: &Keypair,
+        agent_key: &Pubkey,
+    ) -> Result<Signature, ClientError> {
+        let ix = instructions::freeze_agent(&authority.pubkey(), agent_key);
+        self.rpc.send(&[ix], authority, &[]).await
+    }
+
+    pub async fn unfreeze_agent(
+        &self,
+        authority: &Keypair,
+        agent_key: &Pubkey,
+    ) -> Result<Signature, ClientError> {
+        let ix = instructions::unfreeze_agent(&authority.pubkey(), agent_key);
+        self.rpc.send(&[ix], authority, &[]).await
+    }
+
+    pub async fn freeze_authority(&self, authority: &Keypair) -> Result<Signature, ClientError> {
+        let ix = instructions::freeze_authority(&authority.pubkey());
+        self.rpc.send(&[ix], authority, &[]).await
+    }
+
+    pub async fn unfreeze_authority(&self, authority: &Keypair) -> Result<Signature, ClientError> {
+        let ix = instructions::unfreeze_authority(&authority.pubkey());
+        self.rpc.send(&[ix], authority, &[]).await
+    }
+
+    // ============================================================
+    // Threshold authorities
+    // ============================================================
+
+    pub async fn create_authority_set(
+        &self,
+        payer: &Keypair,
+        set_id: [u8; 16],
+        members: &[Pubkey],
+        threshold: u8,
+    ) -> Result<Signature, ClientError> {
+        let ix = instructions::create_authority_set(&payer.pubkey(), set_id, members, threshold);
+        self.rpc.send(&[ix], payer, &[]).await
+    }
+
+    /// Propose `inner` (built with the set's signer PDA as authority) as the
+    /// set's next action; returns the action's index.
+    pub async fn propose_authority_action(
+        &self,
+        member: &Keypair,
+        set_id: [u8; 16],
+        inner: &Instruction,
+    ) -> Result<u64, ClientError> {
+        let address = find_authority_set(&set_id).0;
+        let set: AuthoritySet =
+            self.fetch(&address).await?.ok_or(ClientError::AccountNotFound(address))?;
+        let ix = instructions::propose_authority_action(
+            &member.pubkey(),
+            set_id,
+            set.action_count,
+            inner,
+        );
+        self.rpc.send(&[ix], member, &[]).await?;
+        Ok(set.action_count)
+    }
+
+    pub async fn approve_authority_action(
+        &self,
+        member: &Keypair,
+        set_id: [u8; 16],
+        index: u64,
+    ) -> Result<Signature, ClientError> {
+        let ix = instructions::approve_authority_action(&member.pubkey(), set_id, index);
+        self.rpc.send(&[ix], member, &[]).await
+    }
+
+    /// Run an approved action. `signers` are any keys besides the set's signer
+    /// PDA that the inner instruction needs.
+    pub async fn execute_authority_action(
+        &self,
+        member: &Keypair,
+        set_id: [u8; 16],
+        index: u64,
+        signers: &[&Keypair],
+    ) -> Result<Signature, ClientError> {
+        let address = find_authority_action(&find_authority_set(&set_id).0, index).0;
+        let action: AuthorityAction =
+            self.fetch(&address).await?.ok_or(ClientError::AccountNotFound(address))?;
+        let ix = instructions::execute_authority_action(
+            &member.pubkey(),
+            &action.proposer,
+            set_id,
+            index,
+            &action.accounts,
+        );
+        self.rpc.send(&[ix], member, signers).await
+    }
+
+    pub async fn cancel_authority_action(
+        &self,
+        proposer: &Keypair,
+        set_id: [u8; 16],
+        index: u64,
+    ) -> Result<Signature, ClientError> {
+        let ix = instructions::cancel_authority_action(&proposer.pubkey(), set_id, index);
+        self.rpc.send(&[ix], proposer, &[]).await
+    }
+
+    // ============================================================
+    // Agreement lifecycle
+    // ============================================================
+
+    pub async fn propose_agreement(
+        &self,
+        proposer: &Keypair,
+        args: ProposeAgreementArgs,
+    ) -> Result<Signature, ClientError> {
+        let freeze = self.freeze_scope(&proposer.pubkey()).await?;
+        let treasury = self.treasury().await?;
+        let ix = instructions::propose_agreement(&proposer.pubkey(), freeze, &treasury, args);
+        self.rpc.send(&[ix], proposer, &[]).await
+    }
+
+    /// Propose and add every counterparty in one transaction.
+    /// `parties` are `(agent_key, role)` pairs, proposer excluded.
+    pub async fn propose_with_parties(
+        &self,
+        proposer: &Keypair,
+        args: ProposeAgreementArgs,
+        parties: &[(Pubkey, u8)],
+    ) -> Result<Signature, ClientError> {
+        let agreement_id = args.agreement_id;
+        let freeze = self.freeze_scope(&proposer.pubkey()).await?;
+        let treasury = self.treasury().await?;
+        let mut ixs =
+            vec![instructions::propose_agreement(&proposer.pubkey(), freeze, &treasury, args)];
+        for (agent_key, role) in parties {
+            ixs.push(instructions::add_party(&proposer.pubkey(), freeze, agreement_id, agent_key, *role));
+        }
+        self.rpc.send(&ixs, proposer, &[]).await
+    }
+
+    pub async fn add_party(
+        &self,
+        proposer: &Keypair,
+        agreement_id: [u8; 16],
+        party_agent_key: &Pubkey,
+        role: u8,
+    ) -> Result<Signature, ClientError> {
+        let freeze = self.freeze_scope(&proposer.pubkey()).await?;
+        let ix =
+            instructions::add_party(&proposer.pubkey(), freeze, agreement_id, party_agent_key, role);
+        self.rpc.send(&[ix], proposer, &[]).await
+    }
+
+    /// `party` is the party's identity PDA, or its raw key for direct parties.
+    pub async fn remove_party(
+        &self,
+        proposer: &Keypair,
+        agreement_id: [u8; 16],
+        party: &Pubkey,
+    ) -> Result<Signature, ClientError> {
+        let freeze = self.freeze_scope(&proposer.pubkey()).await?;
+        let ix = instructions::remove_party(&proposer.pubkey(), freeze, agreement_id, party);
+        self.rpc.send(&[ix], proposer, &[]).await
+    }
+
+    pub async fn set_num_parties(
+        &self,
+        proposer: &Keypair,
+        agreement_id: [u8; 16],
+        num_parties: u8,
+    ) -> Result<Signature, ClientError> {
+        let freeze = self.freeze_scope(&proposer.pubkey()).await?;
+        let ix = instructions::set_num_parties(&proposer.pubkey(), freeze, agreement_id, num_parties);
+        self.rpc.send(&[ix], proposer, &[]).await
+    }
+
+    pub async fn sign_agreement(
+        &self,
+        agent: &Keypair,
+        agreement_id: [u8; 16],
+    ) -> Result<Signature, ClientError> {
+        let freeze = self.freeze_scope(&agent.pubkey()).await?;
+        let ix = instructions::sign_agreement(&agent.pubkey(), freeze, agreement_id);
+        self.rpc.send(&[ix], agent, &[]).await
+    }
+
+    /// `signer` is the proposer's agent key or its authority.
+    pub async fn cancel_agreement(
+        &self,
+        signer: &Keypair,
+        proposer_agent_key: &Pubkey,
+        agreement_id: [u8; 16],
+    ) -> Result<Signature, ClientError> {
+        let ix = instructions::cancel_agreement(&signer.pubkey(), proposer_agent_key, agreement_id);
+        self.rpc.send(&[ix], signer, &[]).await
+    }
+
+    /// `signer` is the party's agent key or its authority.
+    pub async fn decline_agreement(
+        &self,
+        signer: &Keypair,
+        party_agent_key: &Pubkey,
+        agreement_id: [u8; 16],
+    ) -> Result<Signature, ClientError> {
+        let ix = instructions::decline_agreement(&signer.pubkey(), party_agent_key, agreement_id);
+        self.rpc.send(&[ix], signer, &[]).await
+    }
+
+    /// `signer` is the party's agent key or its authority.
+    pub async fn fulfill_agreement(
+        &self,
+        signer: &Keypair,
+        party_agent_key: &Pubkey,
+        agreement_id: [u8; 16],
+    ) -> Result<Signature, ClientError> {
+        let ix = instructions::fulfill_agreement(&signer.pubkey(), party_agent_key, agreement_id);
+        self.rpc.send(&[ix], signer, &[]).await
+    }
+
+    pub async fn close_agreement(
+        &self,
+        authority: &Keypair,
+        party_agent_key: &Pubkey,
+        agreement_id: [u8; 16],
+    ) -> Result<Signature, ClientError> {
+        let ix = instructions::close_agreement(&authority.pubkey(), party_agent_key, agreement_id);
+        self.rpc.send(&[ix], authority, &[]).await
+    }
+
+    // ============================================================
+    // Amendments
+    // ============================================================
+
+    /// `signed_parties` as for [`instructions::propose_amendment`].
+    pub async fn propose_amendment(
+        &self,
+        proposer: &Keypair,
+        args: ProposeAmendmentArgs,
+        signed_parties: &[Pubkey],
+    ) -> Result<Signature, ClientError> {
+        let freeze = self.freeze_scope(&proposer.pubkey()).await?;
+        let ix = instructions::propose_amendment(&proposer.pubkey(), freeze, args, signed_parties);
+        self.rpc.send(&[ix], proposer, &[]).await
+    }
+
+    pub async fn sign_amendment(
+        &self,
+        agent: &Keypair,
+        agreement_id: [u8; 16],
+        version: u16,
+    ) -> Result<Signature, ClientError> {
+        let freeze = self.freeze_scope(&agent.pubkey()).await?;
+        let ix = instructions::sign_amendment(&agent.pubkey(), freeze, agreement_id, version);
+        self.rpc.send(&[ix], agent, &[]).await
+    }
+
+    /// `signer` is the proposer's agent key or its authority.
+    pub async fn withdraw_amendment(
+        &self,
+        signer: &Keypair,
+        proposer_agent_key: &Pubkey,
+        agreement_id: [u8; 16],
+        version: u16,
+    ) -> Result<Signature, ClientError> {
+        let ix = instructions::withdraw_amendment(&signer.pubkey(), proposer_agent_key, agreement_id, version);
+        self.rpc.send(&[ix], signer, &[]).await
+    }
+
+    // ============================================================
+    // Counter-proposals
+    // ============================================================
+
+    pub async fn counter_propose(
+        &self,
+        agent: &Keypair,
+        args: CounterProposeArgs,
+    ) -> Result<Signature, ClientError> {
+        let freeze = self.freeze_scope(&agent.pubkey()).await?;
+        let ix = instructions::counter_propose(&agent.pubkey(), freeze, args);
+        self.rpc.send(&[ix], agent, &[]).await
+    }
+
+    /// `signed_parties` as for [`instructions::propose_amendment`].
+    pub async fn accept_counter_proposal(
+        &self,
+        proposer: &Keypair,
+        agreement_id: [u8; 16],
+        party_agent_key: &Pubkey,
+        round: u16,
+        version: u16,
+        signed_parties: &[Pubkey],
+    ) -> Result<Signature, ClientError> {
+        let freeze = self.freeze_scope(&proposer.pubkey()).await?;
+        let ix = instructions::accept_counter_proposal(
+            &proposer.pubkey(),
+            freeze,
+            agreement_id,
+            party_agent_key,
+            round,
+            version,
+            signed_parties,
+        );
+        self.rpc.send(&[ix], proposer, &[]).await
+    }
+
+    /// `signer` is the proposer's agent key or its authority.
+    pub async fn reject_counter_proposal(
+        &self,
+        signer: &Keypair,
+        proposer_agent_key: &Pubkey,
+        agreement_id: [u8; 16],
+        party_agent_key: &Pubkey,
+        round: u16,
+    ) -> Result<Signature, ClientError> {
+        let ix = instructions::reject_counter_proposal(
+            &signer.pubkey(),
+            proposer_agent_key,
+            agreement_id,
+            party_agent_key,
+            round,
+        );
+        self.rpc.send(&[ix], signer, &[]).await
+    }
+
+    // ============================================================
+    // Escrow and termination
+    // ============================================================
+
+    pub async fn fund_escrow(
+        &self,
+        agent: &Keypair,
+        agreement_id: [u8; 16],
+        amount: u64,
+    ) -> Result<Signature, ClientError> {
+        let freeze = self.freeze_scope(&agent.pubkey()).await?;
+        let ix = instructions::fund_escrow(&agent.pubkey(), freeze, agreement_id, amount);
+        self.rpc.send(&[ix], agent, &[]).await
+    }
+
+    /// See [`instructions::release_escrow`]; any signer can pay for it.
+    pub async fn release_escrow(
+        &self,
+        signer: &Keypair,
+        agreement_id: [u8; 16],
+        party: &Pubkey,
+        recipient: &Pubkey,
+        terminated: bool,
+    ) -> Result<Signature, ClientError> {
+        let ix = instructions::release_escrow(&signer.pubkey(), agreement_id, party, recipient, terminated);
+        self.rpc.send(&[ix], signer, &[]).await
+    }
+
+    pub async fn propose_termination(
+        &self,
+        agent: &Keypair,
+        agreement_id: [u8; 16],
+        settlement: &[(Pubkey, u64)],
+    ) -> Result<Signature, ClientError> {
+        let freeze = self.freeze_scope(&agent.pubkey()).await?;
+        let ix = instructions::propose_termination(&agent.pubkey(), freeze, agreement_id, settlement);
+        self.rpc.send(&[ix], agent, &[]).await
+    }
+
+    pub async fn approve_termination(
+        &self,
+        agent: &Keypair,
+        agreement_id: [u8; 16],
+    ) -> Result<Signature, ClientError> {
+        let freeze = self.freeze_scope(&agent.pubkey()).await?;
+        let ix = instructions::approve_termination(&agent.pubkey(), freeze, agreement_id);
+        self.rpc.send(&[ix], agent, &[]).await
+    }
+
+    pub async fn approve_termination_direct(
+        &self,
+        signer: &Keypair,
+        agreement_id: [u8; 16],
+    ) -> Result<Signature, ClientError> {
+        let ix = instructions::approve_termination_direct(&signer.pubkey(), agreement_id);
+        self.rpc.send(&[ix], signer, &[]).await
+    }
+
+    /// `signer` is the proposing party's agent key or its authority.
+    pub async fn cancel_termination(
+        &self,
+        signer: &Keypair,
+        proposer_agent_key: &Pubkey,
+        agreement_id: [u8; 16],
+    ) -> Result<Signature, ClientError> {
+        let ix = instructions::cancel_termination(&signer.pubkey(), proposer_agent_key, agreement_id);
+        self.rpc.send(&[ix], signer, &[]).await
+    }
+
+    // ============================================================
+    // Milestones
+    // ============================================================
+
+    pub async fn set_milestones(
+        &self,
+        proposer: &Keypair,
+        agreement_id: [u8; 16],
+        milestones: Vec<MilestoneTerms>,
+    ) -> Result<Signature, ClientError> {
+        let freeze = self.freeze_scope(&proposer.pubkey()).await?;
+        let ix = instructions::set_milestones(&proposer.pubkey(), freeze, agreement_id, milestones);
+        self.rpc.send(&[ix], proposer, &[]).await
+    }
+
+    pub async fn submit_milestone(
+        &self,
+        agent: &Keypair,
+        agreement_id: [u8; 16],
+        index: u8,
+    ) -> Result<Signature, ClientError> {
+        let freeze = self.freeze_scope(&agent.pubkey()).await?;
+        let ix = instructions::submit_milestone(&agent.pubkey(), freeze, agreement_id, index);
+        self.rpc.send(&[ix], agent, &[]).await
+    }
+
+    pub async fn approve_milestone(
+        &self,
+        agent: &Keypair,
+        agreement_id: [u8; 16],
+        index: u8,
+    ) -> Result<Signature, ClientError> {
+        let freeze = self.freeze_scope(&agent.pubkey()).await?;
+        let ix = instructions::approve_milestone(&agent.pubkey(), freeze, agreement_id, index);
+        self.rpc.send(&[ix], agent, &[]).await
+    }
+
+    pub async fn dispute_milestone(
+        &self,
+        agent: &Keypair,
+        agreement_id: [u8; 16],
+        index: u8,
+    ) -> Result<Signature, ClientError> {
+        let freeze = self.freeze_scope(&agent.pubkey()).await?;
+        let ix = instructions::dispute_milestone(&agent.pubkey(), freeze, agreement_id, index);
+        self.rpc.send(&[ix], agent, &[]).await
+    }
+
+    /// See [`instructions::release_milestone`]; any signer can pay for it.
+    pub async fn release_milestone(
+        &self,
+        signer: &Keypair,
+        agreement_id: [u8; 16],
+        index: u8,
+        payer: &Pubkey,
+        payee: &Pubkey,
+        recipient: &Pubkey,
+    ) -> Result<Signature, ClientError> {
+        let ix = instructions::release_milestone(&signer.pubkey(), agreement_id, index, payer, payee, recipient);
+        self.rpc.send(&[ix], signer, &[]).await
+    }
+
+    // ============================================================
+    // Recurring payments
+    // ============================================================
+
+    pub async fn set_recurring_payment(
+        &self,
+        proposer: &Keypair,
+        agreement_id: [u8; 16],
+        payer_identity: &Pubkey,
+        terms: RecurringTerms,
+    ) -> Result<Signature, ClientError> {
+        let freeze = self.freeze_scope(&proposer.pubkey()).await?;
+        let ix = instructions::set_recurring_payment(&proposer.pubkey(), freeze, agreement_id, payer_identity, terms);
+        self.rpc.send(&[ix], proposer, &[]).await
+    }
+
+    /// See [`instructions::crank_payment`]; any signer can pay for it.
+    pub async fn crank_payment(
+        &self,
+        signer: &Keypair,
+        agreement_id: [u8; 16],
+        payer: &Pubkey,
+        payee: &Pubkey,
+        recipient: &Pubkey,
+    ) -> Result<Signature, ClientError> {
+        let identity: AgentIdentity = self.fetch(payer).await?.ok_or(ClientError::AccountNotFound(*payer))?;
+        let freeze = FreezeScope::from(&identity);
+        let ix = instructions::crank_payment(&signer.pubkey(), agreement_id, payer, freeze, payee, recipient);
+        self.rpc.send(&[ix], signer, &[]).await
+    }
+
+    /// `signer` is the payer's or payee's agent key or its authority.
+    pub async fn cancel_recurring_payment(
+        &self,
+        signer: &Keypair,
+        party_agent_key: &Pubkey,
+        agreement_id: [u8; 16],
+    ) -> Result<Signature, ClientError> {
+        let ix = instructions::cancel_recurring_payment(&signer.pubkey(), party_agent_key, agreement_id);
+        self.rpc.send(&[ix], signer, &[]).await
+    }
+
+    // ============================================================
+    // Escrow streams
+    // ============================================================
+
+    /// See [`instructions::fund_stream`].
+    pub async fn fund_stream(
+        &self,
+        agent: &Keypair,
+        agreement_id: [u8; 16],
+        payee: &Pubkey,
+        amount: u64,
+        parties: &[Pubkey],
+    ) -> Result<Signature, ClientError> {
+        let freeze = self.freeze_scope(&agent.pubkey()).await?;
+        let ix = instructions::fund_stream(&agent.pubkey(), freeze, agreement_id, payee, amount, parties);
+        self.rpc.send(&[ix], agent, &[]).await
+    }
+
+    /// See [`instructions::claim_vested`]; any signer can pay for it.
+    pub async fn claim_vested(
+        &self,
+        signer: &Keypair,
+        agreement_id: [u8; 16],
+        payee: &Pubkey,
+        recipient: &Pubkey,
+    ) -> Result<Signature, ClientError> {
+        let ix = instructions::claim_vested(&signer.pubkey(), agreement_id, payee, recipient);
+        self.rpc.send(&[ix], signer, &[]).await
+    }
+
+    /// `signer` is the payer's or payee's agent key or its authority.
+    pub async fn cancel_stream(
+        &self,
+        signer: &Keypair,
+        party_agent_key: &Pubkey,
+        agreement_id: [u8; 16],
+        ends: StreamEnds,
+    ) -> Result<Signature, ClientError> {
+        let ix = instructions::cancel_stream(&signer.pubkey(), party_agent_key, agreement_id, ends);
+        self.rpc.send(&[ix], signer, &[]).await
+    }
+
+    /// See [`instructions::settle_stream`]; any signer can pay for it.
+    pub async fn settle_stream(
+        &self,
+        signer: &Keypair,
+        agreement_id: [u8; 16],
+        ends: StreamEnds,
+        terminated: bool,
+    ) -> Result<Signature, ClientError> {
+        let ix = instructions::settle_stream(&signer.pubkey(), agreement_id, ends, terminated);
+        self.rpc.send(&[ix], signer, &[]).await
+    }
+
+    // ============================================================
+    // Revenue shares
+    // ============================================================
+
+    pub async fn set_revenue_share(
+        &self,
+        proposer: &Keypair,
+        agreement_id: [u8; 16],
+        splits: Vec<RevenueSplit>,
+    ) -> Result<Signature, ClientError> {
+        let freeze = self.freeze_scope(&proposer.pubkey()).await?;
+        let ix = instructions::set_revenue_share(&proposer.pubkey(), freeze, agreement_id, splits);
+        self.rpc.send(&[ix], proposer, &[]).await
+    }
+
+    /// See [`instructions::distribute`]; any signer can pay for it.
+    pub async fn distribute(
+        &self,
+        signer: &Keypair,
+        agreement_id: [u8; 16],
+        payouts: &[(Pubkey, Pubkey)],
+    ) -> Result<Signature, ClientError> {
+        let ix = instructions::distribute(&signer.pubkey(), agreement_id, payouts);
+        self.rpc.send(&[ix], signer, &[]).await
+    }
+
+    /// See [`instructions::distribute_tokens`]; any signer can pay for it.
+    pub async fn distribute_tokens(
+        &self,
+        signer: &Keypair,
+        agreement_id: [u8; 16],
+        receiver_tokens: &Pubkey,
+        payouts: &[(Pubkey, Pubkey)],
+    ) -> Result<Signature, ClientError> {
+        let ix = instructions::distribute_tokens(&signer.pubkey(), agreement_id, receiver_tokens, payouts);
+        self.rpc.send(&[ix], signer, &[]).await
+    }
+
+    // ============================================================
+    // SAFEs
+    // ============================================================
+
+    /// Reads the investor's identity from its AgreementParty.
+    pub async fn set_safe(
+        &self,
+        proposer: &Keypair,
+        agreement_id: [u8; 16],
+        terms: SafeTerms,
+    ) -> Result<Signature, ClientError> {
+        let freeze = self.freeze_scope(&proposer.pubkey()).await?;
+        let investor: AgreementParty =
+            self.fetch(&terms.investor).await?.ok_or(ClientError::AccountNotFound(terms.investor))?;
+        let ix = instructions::set_safe(&proposer.pubkey(), freeze, agreement_id, terms, &investor.agent_identity);
+        self.rpc.send(&[ix], proposer, &[]).await
+    }
+
+    pub async fn sign_safe(&self, investor: &Keypair, agreement_id: [u8; 16]) -> Result<Signature, ClientError> {
+        let freeze = self.freeze_scope(&investor.pubkey()).await?;
+        let ix = instructions::sign_safe(&investor.pubkey(), freeze, agreement_id);
+        self.rpc.send(&[ix], investor, &[]).await
+    }
+
+    /// `signer` is the company's agent key or its authority.
+    pub async fn convert_safe(
+        &self,
+        signer: &Keypair,
+        company_agent_key: &Pubkey,
+        agreement_id: [u8; 16],
+        investor: &Pubkey,
+        round_valuation: u64,
+    ) -> Result<Signature, ClientError> {
+        let ix = instructions::convert_safe(&signer.pubkey(), company_agent_key, agreement_id, investor, round_valuation);
+        self.rpc.send(&[ix], signer, &[]).await
+    }
+
+    /// `signer` is the company's or investor's agent key or its authority.
+    pub async fn refund_safe(
+        &self,
+        signer: &Keypair,
+        party_agent_key: &Pubkey,
+        agreement_id: [u8; 16],
+        investor: &Pubkey,
+    ) -> Result<Signature, ClientError> {
+        let ix = instructions::refund_safe(&signer.pubkey(), party_agent_key, agreement_id, investor);
+        self.rpc.send(&[ix], signer, &[]).await
+    }
+
+    // ============================================================
+    // Vault
+    // ============================================================
+
+    pub async fn deposit_to_vault(
+        &self,
+        authority: &Keypair,
+        agent_key: &Pubkey,
+        amount: u64,
+    ) -> Result<Signature, ClientError> {
+        let ix = instructions::deposit_to_vault(&authority.pubkey(), agent_key, amount);
+        self.rpc.send(&[ix], authority, &[]).await
+    }
+
+    pub async fn withdraw_from_vault(
+        &self,
+        authority: &Keypair,
+        agent_key: &Pubkey,
+        amount: u64,
+    ) -> Result<Signature, ClientError> {
+        let ix = instructions::withdraw_from_vault(&authority.pubkey(), agent_key, &authority.pubkey(), amount);
+        self.rpc.send(&[ix], authority, &[]).await
+    }
+
+    // ============================================================
+    // Protocol config
+    // ============================================================
+
+    pub async fn initialize_config(
+        &self,
+        upgrade_authority: &Keypair,
+        params: ConfigParams,
+    ) -> Result<Signature, ClientError> {
+        let ix = instructions::initialize_config(&upgrade_authority.pubkey(), params);
+        self.rpc.send(&[ix], upgrade_authority, &[]).await
+    }
+
+    pub async fn update_config(
+        &self,
+        admin: &Keypair,
+        params: ConfigParams,
+    ) -> Result<Signature, ClientError> {
+        let ix = instructions::update_config(&admin.pubkey(), params);
+        self.rpc.send(&[ix], admin, &[]).await
+    }
+
+    pub async fn set_paused(&self, admin: &Keypair, paused: bool) -> Result<Signature, ClientError> {
+        let ix = instructions::set_paused(&admin.pubkey(), paused);
+        self.rpc.send(&[ix], admin, &[]).await
+    }
+}
@@ -0,0 +1,51 @@
+use light_sdk::address::v2::derive_address;
+use solana_sdk::pubkey::Pubkey;
+
+use super::COMPRESSED_PROGRAM_ID;
+
+/// CompressedAgentIdentity: `["agent", agent_key]`
+pub fn agent_address(agent_key: &Pubkey, address_tree: &Pubkey) -> [u8; 32] {
+    derive_address(&[b"agent", agent_key.as_ref()], address_tree, &COMPRESSED_PROGRAM_ID).0
+}
+
+/// CompressedAgreement: `["agreement", agreement_id]`
+pub fn agreement_address(agreement_id: &[u8; 16], address_tree: &Pubkey) -> [u8; 32] {
+    derive_address(
+        &[b"agreement", agreement_id.as_ref()],
+        address_tree,
+        &COMPRESSED_PROGRAM_ID,
+    )
+    .0
+}
+
+/// CompressedAgreementParty: `["party", agreement_id, identity_address]`, keyed by
+/// the party's *compressed identity address*, not its agent key.
+pub fn party_address(
+    agreement_id: &[u8; 16],
+    identity_address: &[u8; 32],
+    address_tree: &Pubkey,
+) -> [u8; 32] {
+    derive_address(
+        &[b"party", agreement_id.as_ref(), identity_address.as_ref()],
+        address_tree,
+        &COMPRESSED_PROGRAM_ID,
+    )
+    .0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn party_is_keyed_by_identity_address() {
+        let tree = Pubkey::new_unique();
+        let agent_key = Pubkey::new_unique();
+        let id = [5u8; 16];
+        let identity = agent_address(&agent_key, &tree);
+
+        assert_ne!(party_address(&id, &identity, &tree), party_address(&id, &agent_key.to_bytes(), &tree));
+        assert_ne!(identity, agent_address(&agent_key, &Pubkey::new_unique()));
+        assert_eq!(agreement_address(&id, &tree), agreement_address(&id, &tree));
+    }
+}
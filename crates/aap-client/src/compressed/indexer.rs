@@ -0,0 +1,560 @@
+//! Proof + packing for every `aap-compressed` instruction.
+//!
+//! Each builder fetches the current compressed accounts it consumes by derived
+//! address, requests one validity proof covering those hashes and any new
+//! addresses, packs the tree accounts into remaining accounts and returns the
+//! finished `Instruction`. Sending is left to the caller (`Rpc`,
+//! `create_and_send_transaction`, …); the builders only need an `Indexer`.
+//!
+//! Proofs are bound to the state root at fetch time, so build and send back to
+//! back — an instruction built before another write to the same account fails
+//! on-chain.
+
+use std::fmt::Display;
+
+use aap_compressed::{accounts, instruction};
+use anchor_lang::system_program;
+use light_client::indexer::{AddressWithTree, CompressedAccount, Indexer, TreeInfo};
+use light_sdk::instruction::{
+    account_meta::CompressedAccountMeta, PackedAccounts, PackedAddressTreeInfo,
+    SystemAccountMetaConfig, ValidityProof,
+};
+use solana_sdk::instruction::Instruction;
+use solana_sdk::pubkey::Pubkey;
+
+use super::address::{agent_address, agreement_address, party_address};
+use super::{
+    build, decode, decompress_agreement_accounts, decompress_party_accounts,
+    CompressedAgentIdentity, CompressedAgreement, CompressedAgreementParty,
+    CompressedDelegationScope, COMPRESSED_PROGRAM_ID,
+};
+use crate::instructions::ProposeAgreementArgs;
+use crate::pda::find_config;
+use crate::ClientError;
+
+fn light_err(e: impl Display) -> ClientError {
+    ClientError::Compressed(e.to_string())
+}
+
+/// Proof and packed accounts for one instruction. `metas[i]` belongs to the
+/// i-th input account, `address_trees[i]` to the i-th new address.
+struct Packed {
+    proof: ValidityProof,
+    remaining: PackedAccounts,
+    metas: Vec<CompressedAccountMeta>,
+    address_trees: Vec<PackedAddressTreeInfo>,
+    output_state_tree_index: u8,
+}
+
+/// Builds compressed instructions against one address tree and output state tree
+/// (e.g. `rpc.get_address_tree_v2()` and `rpc.get_random_state_tree_info()?`).
+pub struct CompressedBuilder<'a, I: Indexer> {
+    indexer: &'a I,
+    address_tree: TreeInfo,
+    output_tree: TreeInfo,
+}
+
+impl<'a, I: Indexer> CompressedBuilder<'a, I> {
+    pub fn new(indexer: &'a I, address_tree: TreeInfo, output_tree: TreeInfo) -> Self {
+        Self {
+            indexer,
+            address_tree,
+            output_tree,
+        }
+    }
+
+    // ============================================================
+    // Addresses & reads
+    // ============================================================
+
+    pub fn agent_address(&self, agent_key: &Pubkey) -> [u8; 32] {
+        agent_address(agent_key, &self.address_tree.tree)
+    }
+
+    pub fn agreement_address(&self, agreement_id: &[u8; 16]) -> [u8; 32] {
+        agreement_address(agreement_id, &self.address_tree.tree)
+    }
+
+    /// Party of the agent whose identity lives at `agent_key`'s compressed address.
+    pub fn party_address(&self, agreement_id: &[u8; 16], agent_key: &Pubkey) -> [u8; 32] {
+        party_address(agreement_id, &self.agent_address(agent_key), &self.address_tree.tree)
+    }
+
+    pub async fn fetch(&self, address: [u8; 32]) -> Result<CompressedAccount, ClientError> {
+        self.indexer
+            .get_compressed_account(address, None)
+            .await
+            .map_err(light_err)?
+            .value
+            .ok_or(ClientError::AccountNotFound(Pubkey::new_from_array(address)))
+    }
+
+    pub async fn get_agent_identity(
+        &self,
+        agent_key: &Pubkey,
+    ) -> Result<CompressedAgentIdentity, ClientError> {
+        data_of(&self.fetch(self.agent_address(agent_key)).await?)
+    }
+
+    pub async fn get_agreement(
+        &self,
+        agreement_id: &[u8; 16],
+    ) -> Result<CompressedAgreement, ClientError> {
+        data_of(&self.fetch(self.agreement_address(agreement_id)).await?)
+    }
+
+    pub async fn get_agreement_party(
+        &self,
+        agreement_id: &[u8; 16],
+        agent_key: &Pubkey,
+    ) -> Result<CompressedAgreementParty, ClientError> {
+        data_of(&self.fetch(self.party_address(agreement_id, agent_key)).await?)
+    }
+
+    async fn pack(
+        &self,
+        inputs: &[&CompressedAccount],
+        new_addresses: &[[u8; 32]],
+    ) -> Result<Packed, ClientError> {
+        let mut remaining = PackedAccounts::default();
+        remaining
+            .add_system_accounts_v2(SystemAccountMetaConfig::new(COMPRESSED_PROGRAM_ID))
+            .map_err(light_err)?;
+
+        let proof = self
+            .indexer
+            .get_validity_proof(
+                inputs.iter().map(|a| a.hash).collect(),
+                new_addresses
+                    .iter()
+                    .map(|address| AddressWithTree {
+                        tree: self.address_tree.tree,
+                        address: *address,
+                    })
+                    .collect(),
+                None,
+            )
+            .await
+            .map_err(light_err)?
+            .value;
+
+        // Create-only instructions have no input state tree to write back to
+        let fresh_output_index = if inputs.is_empty() {
+            Some(
+                self.output_tree
+                    .pack_output_tree_index(&mut remaining)
+                    .map_err(light_err)?,
+            )
+        } else {
+            None
+        };
+
+        let packed_tree_accounts = proof.pack_tree_infos(&mut remaining).clone();
+        let (metas, output_state_tree_index) = match packed_tree_accounts.state_trees {
+            Some(state_trees) => {
+                let metas = inputs
+                    .iter()
+                    .zip(state_trees.packed_tree_infos)
+                    .map(|(account, tree_info)| {
+                        Ok(CompressedAccountMeta {
+                            tree_info,
+                            address: address_of(account)?,
+                            output_state_tree_index: state_trees.output_tree_index,
+                        })
+                    })
+                    .collect::<Result<Vec<_>, ClientError>>()?;
+                (metas, state_trees.output_tree_index)
+            }
+            None => (
+                vec![],
+                fresh_output_index
+                    .ok_or_else(|| light_err("proof returned no state trees for input accounts"))?,
+            ),
+        };
+
+        Ok(Packed {
+            proof: proof.proof,
+            remaining,
+            metas,
+            address_trees: packed_tree_accounts.address_trees,
+            output_state_tree_index,
+        })
+    }
+
+    // ============================================================
+    // Identity
+    // ============================================================
+
+    /// `signer` becomes the identity's authority; pass `agent_key == signer` for
+    /// human signer mode.
+    pub async fn register_agent(
+        &self,
+        signer: &Pubkey,
+        agent_key: &Pubkey,
+        metadata_hash: [u8; 32],
+        scope: CompressedDelegationScope,
+    ) -> Result<Instruction, ClientError> {
+        let p = self.pack(&[], &[self.agent_address(agent_key)]).await?;
+        Ok(build(
+            accounts::RegisterAgent {
+                signer: *signer,
+                config: find_config().0,
+            },
+            instruction::RegisterAgent {
+                proof: p.proof,
+                address_tree_info: p.address_trees[0],
+                output_state_tree_index: p.output_state_tree_index,
+                agent_key: agent_key.to_bytes(),
+                metadata_hash,
+                scope,
+            },
+            &p.remaining,
+        ))
+    }
+
+    pub async fn update_delegation(
+        &self,
+        signer: &Pubkey,
+        agent_key: &Pubkey,
+        new_scope: CompressedDelegationScope,
+    ) -> Result<Instruction, ClientError> {
+        let identity = self.fetch(self.agent_address(agent_key)).await?;
+        let p = self.pack(&[&identity], &[]).await?;
+        Ok(build(
+            accounts::UpdateDelegation {
+                signer: *signer,
+                config: find_config().0,
+            },
+            instruction::UpdateDelegation {
+                proof: p.proof,
+                account_meta: p.metas[0],
+                current_identity: data_of(&identity)?,
+                new_scope,
+            },
+            &p.remaining,
+        ))
+    }
+
+    /// Signed by the parent agent's key (or the parent's authority).
+    pub async fn register_sub_agent(
+        &self,
+        signer: &Pubkey,
+        parent_agent_key: &Pubkey,
+        sub_agent_key: &Pubkey,
+        metadata_hash: [u8; 32],
+        scope: CompressedDelegationScope,
+    ) -> Result<Instruction, ClientError> {
+        let parent = self.fetch(self.agent_address(parent_agent_key)).await?;
+        let p = self
+            .pack(&[&parent], &[self.agent_address(sub_agent_key)])
+            .await?;
+        Ok(build(
+            accounts::RegisterSubAgent {
+                signer: *signer,
+                config: find_config().0,
+            },
+            instruction::RegisterSubAgent {
+                proof: p.proof,
+                parent_account_meta: p.metas[0],
+                parent_identity: data_of(&parent)?,
+                address_tree_info: p.address_trees[0],
+                output_state_tree_index: p.output_state_tree_index,
+                sub_agent_key: sub_agent_key.to_bytes(),
+                metadata_hash,
+                scope,
+            },
+            &p.remaining,
+        ))
+    }
+
+    pub async fn revoke_agent(
+        &self,
+        signer: &Pubkey,
+        agent_key: &Pubkey,
+    ) -> Result<Instruction, ClientError> {
+        let identity = self.fetch(self.agent_address(agent_key)).await?;
+        let p = self.pack(&[&identity], &[]).await?;
+        Ok(build(
+            accounts::RevokeAgent {
+                signer: *signer,
+                config: find_config().0,
+            },
+            instruction::RevokeAgent {
+                proof: p.proof,
+                account_meta: p.metas[0],
+                current_identity: data_of(&identity)?,
+            },
+            &p.remaining,
+        ))
+    }
+
+    // ============================================================
+    // Agreements
+    // ============================================================
+
+    /// Creates the agreement and the proposer's (auto-signed) party in one proof.
+    /// `signer` pays the protocol fee to `treasury` (the V1 config's `treasury`).
+    pub async fn propose_agreement(
+        &self,
+        signer: &Pubkey,
+        proposer_agent_key: &Pubkey,
+        treasury: &Pubkey,
+        args: ProposeAgreementArgs,
+    ) -> Result<Instruction, ClientError> {
+        let proposer = self.fetch(self.agent_address(proposer_agent_key)).await?;
+        let p = self
+            .pack(
+                &[&proposer],
+                &[
+                    self.agreement_address(&args.agreement_id),
+                    self.party_address(&args.agreement_id, proposer_agent_key),
+                ],
+            )
+            .await?;
+        Ok(build(
+            accounts::ProposeAgreement {
+                signer: *signer,
+                config: find_config().0,
+                treasury: *treasury,
+                system_program: system_program::ID,
+            },
+            instruction::ProposeAgreement {
+                proof: p.proof,
+                proposer_account_meta: p.metas[0],
+                proposer_identity: data_of(&proposer)?,
+                agreement_address_tree_info: p.address_trees[0],
+                party_address_tree_info: p.address_trees[1],
+                output_state_tree_index: p.output_state_tree_index,
+                agreement_id: args.agreement_id,
+                agreement_type: args.agreement_type,
+                visibility: args.visibility,
+                terms_hash: args.terms_hash,
+                terms_uri: args.terms_uri,
+                num_parties: args.num_parties,
+                expires_at: args.expires_at,
+            },
+            &p.remaining,
+        ))
+    }
+
+    pub async fn add_party(
+        &self,
+        signer: &Pubkey,
+        proposer_agent_key: &Pubkey,
+        agreement_id: [u8; 16],
+        party_agent_key: &Pubkey,
+        role: u8,
+    ) -> Result<Instruction, ClientError> {
+        let proposer = self.fetch(self.agent_address(proposer_agent_key)).await?;
+        let agreement = self.fetch(self.agreement_address(&agreement_id)).await?;
+        let party_identity = self.fetch(self.agent_address(party_agent_key)).await?;
+        let p = self
+            .pack(
+                &[&proposer, &agreement, &party_identity],
+                &[self.party_address(&agreement_id, party_agent_key)],
+            )
+            .await?;
+        Ok(build(
+            accounts::AddParty {
+                signer: *signer,
+                config: find_config().0,
+            },
+            instruction::AddParty {
+                proof: p.proof,
+                proposer_account_meta: p.metas[0],
+                proposer_identity: data_of(&proposer)?,
+                agreement_account_meta: p.metas[1],
+                current_agreement: data_of(&agreement)?,
+                party_identity_account_meta: p.metas[2],
+                party_identity: data_of(&party_identity)?,
+                party_address_tree_info: p.address_trees[0],
+                output_state_tree_index: p.output_state_tree_index,
+                role,
+            },
+            &p.remaining,
+        ))
+    }
+
+    pub async fn sign_agreement(
+        &self,
+        signer: &Pubkey,
+        agent_key: &Pubkey,
+        agreement_id: [u8; 16],
+    ) -> Result<Instruction, ClientError> {
+        let identity = self.fetch(self.agent_address(agent_key)).await?;
+        let agreement = self.fetch(self.agreement_address(&agreement_id)).await?;
+        let party = self.fetch(self.party_address(&agreement_id, agent_key)).await?;
+        let p = self.pack(&[&identity, &agreement, &party], &[]).await?;
+        Ok(build(
+            accounts::SignAgreement {
+                signer: *signer,
+                config: find_config().0,
+            },
+            instruction::SignAgreement {
+                proof: p.proof,
+                signer_identity_meta: p.metas[0],
+                signer_identity: data_of(&identity)?,
+                agreement_meta: p.metas[1],
+                current_agreement: data_of(&agreement)?,
+                party_meta: p.metas[2],
+                current_party: data_of(&party)?,
+            },
+            &p.remaining,
+        ))
+    }
+
+    pub async fn cancel_agreement(
+        &self,
+        signer: &Pubkey,
+        proposer_agent_key: &Pubkey,
+        agreement_id: [u8; 16],
+    ) -> Result<Instruction, ClientError> {
+        let proposer = self.fetch(self.agent_address(proposer_agent_key)).await?;
+        let agreement = self.fetch(self.agreement_address(&agreement_id)).await?;
+        let p = self.pack(&[&proposer, &agreement], &[]).await?;
+        Ok(build(
+            accounts::CancelAgreement {
+                signer: *signer,
+                config: find_config().0,
+            },
+            instruction::CancelAgreement {
+                proof: p.proof,
+                proposer_identity_meta: p.metas[0],
+                proposer_identity: data_of(&proposer)?,
+                agreement_meta: p.metas[1],
+                current_agreement: data_of(&agreement)?,
+            },
+            &p.remaining,
+        ))
+    }
+
+    /// Any party to the agreement may fulfill it.
+    pub async fn fulfill_agreement(
+        &self,
+        signer: &Pubkey,
+        agent_key: &Pubkey,
+        agreement_id: [u8; 16],
+    ) -> Result<Instruction, ClientError> {
+        let identity = self.fetch(self.agent_address(agent_key)).await?;
+        let party = self.fetch(self.party_address(&agreement_id, agent_key)).await?;
+        let agreement = self.fetch(self.agreement_address(&agreement_id)).await?;
+        let p = self.pack(&[&identity, &party, &agreement], &[]).await?;
+        Ok(build(
+            accounts::FulfillAgreement {
+                signer: *signer,
+                config: find_config().0,
+            },
+            instruction::FulfillAgreement {
+                proof: p.proof,
+                signer_identity_meta: p.metas[0],
+                signer_identity: data_of(&identity)?,
+                signer_party_meta: p.metas[1],
+                signer_party: data_of(&party)?,
+                agreement_meta: p.metas[2],
+                current_agreement: data_of(&agreement)?,
+            },
+            &p.remaining,
+        ))
+    }
+
+    /// Closes a terminal agreement; signed by a party (or its authority).
+    pub async fn close_agreement(
+        &self,
+        signer: &Pubkey,
+        agent_key: &Pubkey,
+        agreement_id: [u8; 16],
+    ) -> Result<Instruction, ClientError> {
+        let identity = self.fetch(self.agent_address(agent_key)).await?;
+        let party = self.fetch(self.party_address(&agreement_id, agent_key)).await?;
+        let agreement = self.fetch(self.agreement_address(&agreement_id)).await?;
+        let p = self.pack(&[&identity, &party, &agreement], &[]).await?;
+        Ok(build(
+            accounts::CloseAgreement {
+                signer: *signer,
+                config: find_config().0,
+            },
+            instruction::CloseAgreement {
+                proof: p.proof,
+                signer_identity_meta: p.metas[0],
+                signer_identity: data_of(&identity)?,
+                signer_party_meta: p.metas[1],
+                signer_party: data_of(&party)?,
+                agreement_meta: p.metas[2],
+                current_agreement: data_of(&agreement)?,
+            },
+            &p.remaining,
+        ))
+    }
+
+    // ============================================================
+    // Decompression
+    // ============================================================
+
+    /// Moves the agreement back to a V1 `Agreement` PDA. `proposer_agent_key` is
+    /// the key the proposer's identity was registered with. Signed by the
+    /// proposer's agent key or authority.
+    pub async fn decompress_agreement(
+        &self,
+        signer: &Pubkey,
+        proposer_agent_key: &Pubkey,
+        agreement_id: [u8; 16],
+    ) -> Result<Instruction, ClientError> {
+        let proposer = self.fetch(self.agent_address(proposer_agent_key)).await?;
+        let agreement = self.fetch(self.agreement_address(&agreement_id)).await?;
+        let p = self.pack(&[&proposer, &agreement], &[]).await?;
+        Ok(build(
+            decompress_agreement_accounts(signer, proposer_agent_key, &agreement_id),
+            instruction::DecompressAgreement {
+                proof: p.proof,
+                proposer_identity_meta: p.metas[0],
+                proposer_identity: data_of(&proposer)?,
+                agreement_meta: p.metas[1],
+                current_agreement: data_of(&agreement)?,
+                proposer_agent_key: *proposer_agent_key,
+                address_tree_pubkey: self.address_tree.tree,
+            },
+            &p.remaining,
+        ))
+    }
+
+    /// Moves one party to a V1 `AgreementParty` PDA once the agreement has been
+    /// decompressed. Signed by the proposer's agent key or authority.
+    pub async fn decompress_party(
+        &self,
+        signer: &Pubkey,
+        proposer_agent_key: &Pubkey,
+        agreement_id: [u8; 16],
+        party_agent_key: &Pubkey,
+    ) -> Result<Instruction, ClientError> {
+        let party = self.fetch(self.party_address(&agreement_id, party_agent_key)).await?;
+        let p = self.pack(&[&party], &[]).await?;
+        Ok(build(
+            decompress_party_accounts(signer, proposer_agent_key, &agreement_id, party_agent_key),
+            instruction::DecompressParty {
+                proof: p.proof,
+                party_meta: p.metas[0],
+                current_party: data_of(&party)?,
+                agreement_id,
+                agent_key: *party_agent_key,
+                address_tree_pubkey: self.address_tree.tree,
+            },
+            &p.remaining,
+        ))
+    }
+}
+
+fn address_of(account: &CompressedAccount) -> Result<[u8; 32], ClientError> {
+    account
+        .address
+        .ok_or_else(|| ClientError::Deserialize("compressed account has no address".into()))
+}
+
+fn data_of<T: anchor_lang_031::AnchorDeserialize>(
+    account: &CompressedAccount,
+) -> Result<T, ClientError> {
+    let data = account
+        .data
+        .as_ref()
+        .ok_or_else(|| ClientError::Deserialize("compressed account has no data".into()))?;
+    decode(&data.data)
+}
@@ -0,0 +1,209 @@
+//! Client support for the `aap-compressed` (V2) program.
+//!
+//! - [`address`] — compressed address derivation (`["agent", agent_key]`, …) on a
+//!   V2 address tree
+//! - [`indexer`] (feature `compressed-indexer`) — [`indexer::CompressedBuilder`]:
+//!   fetches current accounts from a Light `Indexer`, requests validity proofs,
+//!   packs tree accounts and returns ready-to-send instructions
+//!
+//! Compressed instructions take a fixed account list (the signer and the V1
+//! `ProtocolConfig`, plus the fee treasury for `propose_agreement`) followed by
+//! the Light system accounts and packed tree accounts as remaining accounts;
+//! [`build`] assembles both halves in that order. `decompress_*` also name the
+//! V1 PDAs they materialize; [`decompress_agreement_accounts`] and
+//! [`decompress_party_accounts`] derive them.
+
+pub mod address;
+#[cfg(feature = "compressed-indexer")]
+pub mod indexer;
+
+use anchor_lang_031::{AnchorDeserialize, InstructionData, ToAccountMetas};
+use light_sdk::instruction::PackedAccounts;
+use anchor_lang::system_program;
+use solana_sdk::instruction::Instruction;
+use solana_sdk::pubkey::Pubkey;
+
+use crate::pda::{
+    find_agent_identity, find_agreement, find_agreement_party, find_compressed_migration_authority,
+    find_config, find_decompression_marker,
+};
+use crate::ClientError;
+
+pub use aap_compressed::{
+    CompressedAgentIdentity, CompressedAgreement, CompressedAgreementParty,
+    CompressedDelegationScope, ID as COMPRESSED_PROGRAM_ID,
+};
+
+/// Fixed accounts first, then the packed Light system/tree accounts.
+pub fn build(
+    accounts: impl ToAccountMetas,
+    data: impl InstructionData,
+    remaining: &PackedAccounts,
+) -> Instruction {
+    let (remaining_metas, _, _) = remaining.to_account_metas();
+    Instruction {
+        program_id: COMPRESSED_PROGRAM_ID,
+        accounts: [accounts.to_account_metas(Some(true)), remaining_metas].concat(),
+        data: data.data(),
+    }
+}
+
+/// Fixed accounts of `decompress_agreement`: the V1 PDAs that `materialize_agreement`
+/// creates or reads, derived from the proposer's registered key.
+pub fn decompress_agreement_accounts(
+    signer: &Pubkey,
+    proposer_agent_key: &Pubkey,
+    agreement_id: &[u8; 16],
+) -> aap_compressed::accounts::DecompressAgreement {
+    let v1_agreement = find_agreement(agreement_id).0;
+    aap_compressed::accounts::DecompressAgreement {
+        signer: *signer,
+        migration_authority: find_compressed_migration_authority().0,
+        v1_proposer_identity: find_agent_identity(proposer_agent_key).0,
+        v1_agreement,
+        v1_program: agent_agreement_protocol::ID,
+        system_program: system_program::ID,
+        v1_marker: find_decompression_marker(&v1_agreement).0,
+        config: find_config().0,
+    }
+}
+
+/// Fixed accounts of `decompress_party`. The agreement must already be
+/// decompressed; its proposer's V1 identity and marker are passed for V1's checks.
+pub fn decompress_party_accounts(
+    signer: &Pubkey,
+    proposer_agent_key: &Pubkey,
+    agreement_id: &[u8; 16],
+    party_agent_key: &Pubkey,
+) -> aap_compressed::accounts::DecompressParty {
+    let v1_agreement = find_agreement(agreement_id).0;
+    let v1_party_identity = find_agent_identity(party_agent_key).0;
+    aap_compressed::accounts::DecompressParty {
+        signer: *signer,
+        migration_authority: find_compressed_migration_authority().0,
+        v1_agreement,
+        v1_party_identity,
+        v1_party: find_agreement_party(agreement_id, &v1_party_identity).0,
+        v1_program: agent_agreement_protocol::ID,
+        system_program: system_program::ID,
+        v1_proposer_identity: find_agent_identity(proposer_agent_key).0,
+        v1_marker: find_decompression_marker(&v1_agreement).0,
+        config: find_config().0,
+    }
+}
+
+/// Decode compressed account data. The Light discriminator is stored beside the
+/// data, not inside it, so there is no prefix to skip.
+pub fn decode<T: AnchorDeserialize>(mut data: &[u8]) -> Result<T, ClientError> {
+    T::deserialize(&mut data).map_err(|e| ClientError::Deserialize(e.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use light_sdk::instruction::SystemAccountMetaConfig;
+    use solana_sdk::hash::hash;
+    use solana_sdk::instruction::AccountMeta;
+
+    #[test]
+    fn build_appends_packed_accounts_after_fixed_accounts() {
+        let signer = Pubkey::new_unique();
+        let tree = Pubkey::new_unique();
+        let mut remaining = PackedAccounts::default();
+        remaining
+            .add_system_accounts_v2(SystemAccountMetaConfig::new(COMPRESSED_PROGRAM_ID))
+            .unwrap();
+        let tree_index = remaining.insert_or_get(tree);
+
+        let ix = build(
+            aap_compressed::accounts::RevokeAgent { signer, config: find_config().0 },
+            aap_compressed::instruction::RevokeAgent {
+                proof: Default::default(),
+                account_meta: Default::default(),
+                current_identity: identity(),
+            },
+            &remaining,
+        );
+
+        let expected: [u8; 8] = hash(b"global:revoke_agent").to_bytes()[..8]
+            .try_into()
+            .unwrap();
+        assert_eq!(ix.program_id, COMPRESSED_PROGRAM_ID);
+        assert_eq!(ix.data[..8], expected);
+        assert_eq!(ix.accounts[0], AccountMeta::new(signer, true));
+        let (packed, _, packed_offset) = remaining.to_account_metas();
+        assert_eq!(ix.accounts.len(), 2 + packed.len());
+        assert_eq!(ix.accounts[2 + packed_offset + tree_index as usize].pubkey, tree);
+    }
+
+    #[test]
+    fn decompress_accounts_follow_the_program_order() {
+        let signer = Pubkey::new_unique();
+        let proposer = Pubkey::new_unique();
+        let party_key = Pubkey::new_unique();
+        let id = [7u8; 16];
+        let migration_authority = find_compressed_migration_authority().0;
+        let v1_agreement = find_agreement(&id).0;
+        let v1_party_identity = find_agent_identity(&party_key).0;
+        let v1_marker = find_decompression_marker(&v1_agreement).0;
+
+        let agreement = decompress_agreement_accounts(&signer, &proposer, &id).to_account_metas(None);
+        assert_eq!(
+            agreement,
+            vec![
+                AccountMeta::new(signer, true),
+                AccountMeta::new_readonly(migration_authority, false),
+                AccountMeta::new_readonly(find_agent_identity(&proposer).0, false),
+                AccountMeta::new(v1_agreement, false),
+                AccountMeta::new_readonly(agent_agreement_protocol::ID, false),
+                AccountMeta::new_readonly(system_program::ID, false),
+                AccountMeta::new(v1_marker, false),
+                AccountMeta::new_readonly(find_config().0, false),
+            ]
+        );
+
+        let party = decompress_party_accounts(&signer, &proposer, &id, &party_key)
+            .to_account_metas(None);
+        assert_eq!(
+            party,
+            vec![
+                AccountMeta::new(signer, true),
+                AccountMeta::new_readonly(migration_authority, false),
+                AccountMeta::new_readonly(v1_agreement, false),
+                AccountMeta::new_readonly(v1_party_identity, false),
+                AccountMeta::new(find_agreement_party(&id, &v1_party_identity).0, false),
+                AccountMeta::new_readonly(agent_agreement_protocol::ID, false),
+                AccountMeta::new_readonly(system_program::ID, false),
+                AccountMeta::new_readonly(find_agent_identity(&proposer).0, false),
+                AccountMeta::new_readonly(v1_marker, false),
+                AccountMeta::new_readonly(find_config().0, false),
+            ]
+        );
+    }
+
+    #[test]
+    fn decode_round_trips_identity() {
+        let identity = identity();
+        let mut data = Vec::new();
+        anchor_lang_031::AnchorSerialize::serialize(&identity, &mut data).unwrap();
+        let decoded: CompressedAgentIdentity = decode(&data).unwrap();
+        assert_eq!(decoded.agent_key, identity.agent_key);
+        assert!(decode::<CompressedAgreement>(&data[..4]).is_err());
+    }
+
+    fn identity() -> CompressedAgentIdentity {
+        CompressedAgentIdentity {
+            authority: Pubkey::new_unique(),
+            agent_key: Pubkey::new_unique(),
+            metadata_hash: [1u8; 32],
+            scope: CompressedDelegationScope {
+                can_sign_agreements: true,
+                can_commit_funds: false,
+                max_commit_lamports: 0,
+                expires_at: 0,
+            },
+            parent: Pubkey::default(),
+            created_at: 0,
+        }
+    }
+}
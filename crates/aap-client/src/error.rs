@@ -0,0 +1,28 @@
+use std::fmt;
+
+use solana_sdk::pubkey::Pubkey;
+
+#[derive(Debug)]
+pub enum ClientError {
+    /// No account exists at the address.
+    AccountNotFound(Pubkey),
+    /// Account exists but is not the expected type (wrong discriminator or layout).
+    Deserialize(String),
+    /// Transport / transaction failure reported by the backend.
+    Rpc(String),
+    /// Indexer, validity-proof or account-packing failure (compressed program).
+    Compressed(String),
+}
+
+impl fmt::Display for ClientError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ClientError::AccountNotFound(address) => write!(f, "account not found: {address}"),
+            ClientError::Deserialize(e) => write!(f, "failed to deserialize account: {e}"),
+            ClientError::Rpc(e) => write!(f, "rpc error: {e}"),
+            ClientError::Compressed(e) => write!(f, "compressed account error: {e}"),
+        }
+    }
+}
+
+impl std::error::Error for ClientError {}
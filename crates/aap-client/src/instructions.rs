@@ -0,0 +1,2431 @@
+//! One builder per `agent_agreement_protocol` instruction.
+//!
+//! Builders take the *agent keys* callers already hold and derive every PDA
+//! (identity, agreement, party, vault, tombstone) internally. Account order and
+//! data come from the program's Anchor-generated `accounts`/`instruction` modules,
+//! so they can't drift from the on-chain definitions.
+
+use agent_agreement_protocol::constants::AAP_COMPRESSED_PROGRAM_ID;
+use agent_agreement_protocol::{accounts, instruction};
+use anchor_lang::{InstructionData, ToAccountMetas};
+use solana_sdk::instruction::{AccountMeta, Instruction};
+use solana_sdk::pubkey::Pubkey;
+use anchor_lang::system_program;
+
+use crate::pda::*;
+use crate::{
+    ActionAccount, AgentIdentity, AgreementSnapshot, ConfigParams, DelegationScope, MilestoneTerms,
+    PartySnapshot, RecurringTerms, RevenueSplit, SafeTerms, PROGRAM_ID,
+};
+
+fn build(accounts: impl ToAccountMetas, data: impl InstructionData) -> Instruction {
+    Instruction {
+        program_id: PROGRAM_ID,
+        accounts: accounts.to_account_metas(None),
+        data: data.data(),
+    }
+}
+
+// ============================================================
+// Identity
+// ============================================================
+
+pub fn register_agent(
+    authority: &Pubkey,
+    agent_key: &Pubkey,
+    metadata_hash: [u8; 32],
+    scope: DelegationScope,
+) -> Instruction {
+    build(
+        accounts::RegisterAgent {
+            authority: *authority,
+            agent_identity: find_agent_identity(agent_key).0,
+            system_program: system_program::ID,
+            config: find_config().0,
+        },
+        instruction::RegisterAgent {
+            agent_key: *agent_key,
+            metadata_hash,
+            scope,
+        },
+    )
+}
+
+pub fn update_delegation(
+    authority: &Pubkey,
+    agent_key: &Pubkey,
+    new_scope: DelegationScope,
+) -> Instruction {
+    build(
+        accounts::UpdateDelegation {
+            authority: *authority,
+            agent_identity: find_agent_identity(agent_key).0,
+            config: find_config().0,
+        },
+        instruction::UpdateDelegation { new_scope },
+    )
+}
+
+/// Signed by the parent agent's key, which also pays for the sub-agent identity.
+pub fn register_sub_agent(
+    parent_agent_key: &Pubkey,
+    sub_agent_key: &Pubkey,
+    metadata_hash: [u8; 32],
+    scope: DelegationScope,
+) -> Instruction {
+    build(
+        accounts::RegisterSubAgent {
+            parent_agent_signer: *parent_agent_key,
+            parent_identity: find_agent_identity(parent_agent_key).0,
+            sub_agent_identity: find_agent_identity(sub_agent_key).0,
+            system_program: system_program::ID,
+            config: find_config().0,
+        },
+        instruction::RegisterSubAgent {
+            sub_agent_key: *sub_agent_key,
+            metadata_hash,
+            scope,
+        },
+    )
+}
+
+pub fn revoke_agent(authority: &Pubkey, agent_key: &Pubkey) -> Instruction {
+    revoke_rotated_agent(authority, agent_key, agent_key)
+}
+
+/// Revoke an identity registered with `registered_agent_key` that now holds
+/// `current_agent_key`; the current key's lookup is closed with it.
+pub fn revoke_rotated_agent(
+    authority: &Pubkey,
+    registered_agent_key: &Pubkey,
+    current_agent_key: &Pubkey,
+) -> Instruction {
+    build(
+        accounts::RevokeAgent {
+            authority: *authority,
+            agent_identity: find_agent_identity(registered_agent_key).0,
+            current_key_lookup: key_lookup(registered_agent_key, current_agent_key),
+            config: find_config().0,
+        },
+        instruction::RevokeAgent {},
+    )
+}
+
+/// Replace the agent key of the identity registered with `registered_agent_key`.
+/// The identity keeps that address; `current_agent_key` is the key it holds now.
+/// With `co_sign`, `new_agent_key` must also sign the transaction. Rotating back
+/// to `registered_agent_key` passes no new lookup.
+pub fn rotate_agent_key(
+    authority: &Pubkey,
+    registered_agent_key: &Pubkey,
+    current_agent_key: &Pubkey,
+    new_agent_key: &Pubkey,
+    co_sign: bool,
+) -> Instruction {
+    build(
+        accounts::RotateAgentKey {
+            authority: *authority,
+            agent_identity: find_agent_identity(registered_agent_key).0,
+            current_key_lookup: key_lookup(registered_agent_key, current_agent_key),
+            new_key_lookup: key_lookup(registered_agent_key, new_agent_key),
+            new_agent_signer: co_sign.then_some(*new_agent_key),
+            system_program: system_program::ID,
+            config: find_config().0,
+        },
+        instruction::RotateAgentKey {
+            new_agent_key: *new_agent_key,
+        },
+    )
+}
+
+/// Offer the identity registered with `agent_key` to `new_authority`.
+pub fn propose_authority_transfer(
+    authority: &Pubkey,
+    agent_key: &Pubkey,
+    new_authority: &Pubkey,
+) -> Instruction {
+    let agent_identity = find_agent_identity(agent_key).0;
+    build(
+        accounts::ProposeAuthorityTransfer {
+            authority: *authority,
+            agent_identity,
+            transfer: find_authority_transfer(&agent_identity).0,
+            system_program: system_program::ID,
+            config: find_config().0,
+        },
+        instruction::ProposeAuthorityTransfer {
+            new_authority: *new_authority,
+        },
+    )
+}
+
+/// Accept the transfer of the identity registered with `agent_key`, moving
+/// the sub-agents registered with `sub_agent_keys` (and their vaults) along.
+pub fn accept_authority_transfer(
+    new_authority: &Pubkey,
+    previous_authority: &Pubkey,
+    agent_key: &Pubkey,
+    sub_agent_keys: &[Pubkey],
+) -> Instruction {
+    let agent_identity = find_agent_identity(agent_key).0;
+    let mut ix = build(
+        accounts::AcceptAuthorityTransfer {
+            new_authority: *new_authority,
+            previous_authority: *previous_authority,
+            agent_identity,
+            vault: find_vault(&agent_identity).0,
+            transfer: find_authority_transfer(&agent_identity).0,
+            config: find_config().0,
+        },
+        instruction::AcceptAuthorityTransfer {},
+    );
+    for sub_agent_key in sub_agent_keys {
+        let sub_identity = find_agent_identity(sub_agent_key).0;
+        ix.accounts.push(AccountMeta::new(sub_identity, false));
+        ix.accounts.push(AccountMeta::new(find_vault(&sub_identity).0, false));
+    }
+    ix
+}
+
+pub fn cancel_authority_transfer(authority: &Pubkey, agent_key: &Pubkey) -> Instruction {
+    build(
+        accounts::CancelAuthorityTransfer {
+            authority: *authority,
+            transfer: find_authority_transfer(&find_agent_identity(agent_key).0).0,
+            config: find_config().0,
+        },
+        instruction::CancelAuthorityTransfer {},
+    )
+}
+
+// ============================================================
+// Threshold authorities
+// ============================================================
+
+pub fn create_authority_set(
+    payer: &Pubkey,
+    set_id: [u8; 16],
+    members: &[Pubkey],
+    threshold: u8,
+) -> Instruction {
+    build(
+        accounts::CreateAuthoritySet {
+            payer: *payer,
+            authority_set: find_authority_set(&set_id).0,
+            system_program: system_program::ID,
+            config: find_config().0,
+        },
+        instruction::CreateAuthoritySet {
+            set_id,
+            members: members.to_vec(),
+            threshold,
+        },
+    )
+}
+
+/// Propose `inner` — any builder in this module, called with the set's signer
+/// PDA as authority — as action `index` (the set's current `action_count`).
+pub fn propose_authority_action(
+    member: &Pubkey,
+    set_id: [u8; 16],
+    index: u64,
+    inner: &Instruction,
+) -> Instruction {
+    let authority_set = find_authority_set(&set_id).0;
+    build(
+        accounts::ProposeAuthorityAction {
+            member: *member,
+            authority_set,
+            action: find_authority_action(&authority_set, index).0,
+            system_program: system_program::ID,
+            config: find_config().0,
+        },
+        instruction::ProposeAuthorityAction {
+            accounts: inner
+                .accounts
+                .iter()
+                .map(|a| ActionAccount {
+                    pubkey: a.pubkey,
+                    is_signer: a.is_signer,
+                    is_writable: a.is_writable,
+                })
+                .collect(),
+            data: inner.data.clone(),
+        },
+    )
+}
+
+pub fn approve_authority_action(member: &Pubkey, set_id: [u8; 16], index: u64) -> Instruction {
+    let authority_set = find_authority_set(&set_id).0;
+    build(
+        accounts::ApproveAuthorityAction {
+            member: *member,
+            authority_set,
+            action: find_authority_action(&authority_set, index).0,
+            config: find_config().0,
+        },
+        instruction::ApproveAuthorityAction { index },
+    )
+}
+
+/// Execute an approved action. `action_accounts` are the stored
+/// `AuthorityAction.accounts`; the set's signer PDA among them is passed
+/// unsigned (the program signs for it), any other signer must sign the transaction.
+pub fn execute_authority_action(
+    member: &Pubkey,
+    proposer: &Pubkey,
+    set_id: [u8; 16],
+    index: u64,
+    action_accounts: &[ActionAccount],
+) -> Instruction {
+    let authority_set = find_authority_set(&set_id).0;
+    let signer = find_authority_signer(&authority_set).0;
+    let mut ix = build(
+        accounts::ExecuteAuthorityAction {
+            member: *member,
+            authority_set,
+            proposer: *proposer,
+            action: find_authority_action(&authority_set, index).0,
+        },
+        instruction::ExecuteAuthorityAction { index },
+    );
+    ix.accounts.extend(action_accounts.iter().map(|a| AccountMeta {
+        pubkey: a.pubkey,
+        is_signer: a.is_signer && a.pubkey != signer,
+        is_writable: a.is_writable,
+    }));
+    ix
+}
+
+pub fn cancel_authority_action(proposer: &Pubkey, set_id: [u8; 16], index: u64) -> Instruction {
+    build(
+        accounts::CancelAuthorityAction {
+            proposer: *proposer,
+            action: find_authority_action(&find_authority_set(&set_id).0, index).0,
+            config: find_config().0,
+        },
+        instruction::CancelAuthorityAction {},
+    )
+}
+
+// ============================================================
+// Freeze
+// ============================================================
+
+pub fn freeze_agent(authority: &Pubkey, agent_key: &Pubkey) -> Instruction {
+    let agent_identity = find_agent_identity(agent_key).0;
+    build(
+        accounts::FreezeAgent {
+            authority: *authority,
+            agent_identity,
+            freeze: find_freeze(&agent_identity).0,
+            system_program: system_program::ID,
+        },
+        instruction::FreezeAgent {},
+    )
+}
+
+pub fn unfreeze_agent(authority: &Pubkey, agent_key: &Pubkey) -> Instruction {
+    let agent_identity = find_agent_identity(agent_key).0;
+    build(
+        accounts::UnfreezeAgent {
+            authority: *authority,
+            agent_identity,
+            freeze: find_freeze(&agent_identity).0,
+        },
+        instruction::UnfreezeAgent {},
+    )
+}
+
+pub fn freeze_authority(authority: &Pubkey) -> Instruction {
+    build(
+        accounts::FreezeAuthority {
+            authority: *authority,
+            freeze: find_freeze(authority).0,
+            system_program: system_program::ID,
+        },
+        instruction::FreezeAuthority {},
+    )
+}
+
+pub fn unfreeze_authority(authority: &Pubkey) -> Instruction {
+    build(
+        accounts::UnfreezeAuthority {
+            authority: *authority,
+            freeze: find_freeze(authority).0,
+        },
+        instruction::UnfreezeAuthority {},
+    )
+}
+
+/// Whose freezes cover an acting agent besides its own: its authority and its
+/// parent identity (`Pubkey::default()` for top-level agents). Read from the
+/// agent's `AgentIdentity`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct FreezeScope {
+    pub authority: Pubkey,
+    pub parent: Pubkey,
+}
+
+impl From<&AgentIdentity> for FreezeScope {
+    fn from(identity: &AgentIdentity) -> Self {
+        FreezeScope {
+            authority: identity.authority,
+            parent: identity.parent,
+        }
+    }
+}
+
+/// `(agent_freeze, parent_freeze, authority_freeze)` for the agent at `agent_identity`.
+fn freeze_accounts(agent_identity: &Pubkey, scope: FreezeScope) -> (Pubkey, Pubkey, Pubkey) {
+    (
+        find_freeze(agent_identity).0,
+        find_freeze(&scope.parent).0,
+        find_freeze(&scope.authority).0,
+    )
+}
+
+/// A rotated identity's current key has a lookup; a never-rotated one doesn't.
+fn key_lookup(registered_agent_key: &Pubkey, current_agent_key: &Pubkey) -> Option<Pubkey> {
+    (registered_agent_key != current_agent_key).then(|| find_agent_key_lookup(current_agent_key).0)
+}
+
+// ============================================================
+// Agreement lifecycle
+// ============================================================
+
+/// Arguments of `propose_agreement` besides the proposer.
+#[derive(Clone, Debug)]
+pub struct ProposeAgreementArgs {
+    pub agreement_id: [u8; 16],
+    pub agreement_type: u8,
+    pub visibility: u8,
+    pub terms_hash: [u8; 32],
+    pub terms_uri: [u8; 64],
+    pub num_parties: u8,
+    pub expires_at: i64,
+}
+
+/// Signed (and paid) by the proposer's agent key, which also pays the
+/// protocol fee to `treasury` (the config's `treasury`).
+pub fn propose_agreement(
+    proposer_agent_key: &Pubkey,
+    freeze: FreezeScope,
+    treasury: &Pubkey,
+    args: ProposeAgreementArgs,
+) -> Instruction {
+    let proposer_identity = find_agent_identity(proposer_agent_key).0;
+    let (agent_freeze, parent_freeze, authority_freeze) = freeze_accounts(&proposer_identity, freeze);
+    build(
+        accounts::ProposeAgreement {
+            proposer_signer: *proposer_agent_key,
+            proposer_identity,
+            agreement: find_agreement(&args.agreement_id).0,
+            proposer_party: find_agreement_party(&args.agreement_id, &proposer_identity).0,
+            system_program: system_program::ID,
+            agent_freeze,
+            parent_freeze,
+            authority_freeze,
+            config: find_config().0,
+            treasury: *treasury,
+        },
+        instruction::ProposeAgreement {
+            agreement_id: args.agreement_id,
+            agreement_type: args.agreement_type,
+            visibility: args.visibility,
+            terms_hash: args.terms_hash,
+            terms_uri: args.terms_uri,
+            num_parties: args.num_parties,
+            expires_at: args.expires_at,
+        },
+    )
+}
+
+pub fn add_party(
+    proposer_agent_key: &Pubkey,
+    freeze: FreezeScope,
+    agreement_id: [u8; 16],
+    party_agent_key: &Pubkey,
+    role: u8,
+) -> Instruction {
+    let proposer_identity = find_agent_identity(proposer_agent_key).0;
+    let party_identity = find_agent_identity(party_agent_key).0;
+    let (agent_freeze, parent_freeze, authority_freeze) = freeze_accounts(&proposer_identity, freeze);
+    build(
+        accounts::AddParty {
+            proposer_signer: *proposer_agent_key,
+            proposer_identity,
+            agreement: find_agreement(&agreement_id).0,
+            party_identity,
+            party: find_agreement_party(&agreement_id, &party_identity).0,
+            system_program: system_program::ID,
+            agent_freeze,
+            parent_freeze,
+            authority_freeze,
+            config: find_config().0,
+        },
+        instruction::AddParty { agreement_id, role },
+    )
+}
+
+/// Add a party by raw pubkey (no AgentIdentity). Fails while the wallet is
+/// frozen (`["freeze", party_pubkey]`).
+pub fn add_party_direct(
+    proposer_agent_key: &Pubkey,
+    freeze: FreezeScope,
+    agreement_id: [u8; 16],
+    party_pubkey: &Pubkey,
+    role: u8,
+) -> Instruction {
+    let proposer_identity = find_agent_identity(proposer_agent_key).0;
+    let (agent_freeze, parent_freeze, authority_freeze) = freeze_accounts(&proposer_identity, freeze);
+    build(
+        accounts::AddPartyDirect {
+            proposer_signer: *proposer_agent_key,
+            proposer_identity,
+            agreement: find_agreement(&agreement_id).0,
+            party: find_agreement_party(&agreement_id, party_pubkey).0,
+            system_program: system_program::ID,
+            agent_freeze,
+            parent_freeze,
+            authority_freeze,
+            party_freeze: find_freeze(party_pubkey).0,
+            config: find_config().0,
+        },
+        instruction::AddPartyDirect {
+            agreement_id,
+            party_pubkey: *party_pubkey,
+            role,
+        },
+    )
+}
+
+/// `party` is the party's identity PDA, or its raw key for direct parties.
+pub fn remove_party(
+    proposer_agent_key: &Pubkey,
+    freeze: FreezeScope,
+    agreement_id: [u8; 16],
+    party: &Pubkey,
+) -> Instruction {
+    let proposer_identity = find_agent_identity(proposer_agent_key).0;
+    let (agent_freeze, parent_freeze, authority_freeze) = freeze_accounts(&proposer_identity, freeze);
+    let agreement = find_agreement(&agreement_id).0;
+    build(
+        accounts::RemoveParty {
+            proposer_signer: *proposer_agent_key,
+            proposer_identity,
+            agreement,
+            party: find_agreement_party(&agreement_id, party).0,
+            agent_freeze,
+            parent_freeze,
+            authority_freeze,
+            milestones: find_milestones(&agreement).0,
+            recurring: find_recurring_payment(&agreement).0,
+            revenue_share: find_revenue_share(&agreement).0,
+            safe: find_safe(&agreement).0,
+            config: find_config().0,
+        },
+        instruction::RemoveParty { agreement_id },
+    )
+}
+
+pub fn set_num_parties(
+    proposer_agent_key: &Pubkey,
+    freeze: FreezeScope,
+    agreement_id: [u8; 16],
+    num_parties: u8,
+) -> Instruction {
+    let proposer_identity = find_agent_identity(proposer_agent_key).0;
+    let (agent_freeze, parent_freeze, authority_freeze) = freeze_accounts(&proposer_identity, freeze);
+    build(
+        accounts::SetNumParties {
+            proposer_signer: *proposer_agent_key,
+            proposer_identity,
+            agreement: find_agreement(&agreement_id).0,
+            agent_freeze,
+            parent_freeze,
+            authority_freeze,
+            config: find_config().0,
+        },
+        instruction::SetNumParties { agreement_id, num_parties },
+    )
+}
+
+pub fn sign_agreement(agent_key: &Pubkey, freeze: FreezeScope, agreement_id: [u8; 16]) -> Instruction {
+    let signer_identity = find_agent_identity(agent_key).0;
+    let (agent_freeze, parent_freeze, authority_freeze) = freeze_accounts(&signer_identity, freeze);
+    let agreement = find_agreement(&agreement_id).0;
+    build(
+        accounts::SignAgreement {
+            signer: *agent_key,
+            signer_identity,
+            agreement,
+            party: find_agreement_party(&agreement_id, &signer_identity).0,
+            agent_freeze,
+            parent_freeze,
+            authority_freeze,
+            safe: find_safe(&agreement).0,
+            config: find_config().0,
+        },
+        instruction::SignAgreement { agreement_id },
+    )
+}
+
+/// Sign as a party added with `add_party_direct`.
+pub fn sign_agreement_direct(signer: &Pubkey, agreement_id: [u8; 16]) -> Instruction {
+    let agreement = find_agreement(&agreement_id).0;
+    build(
+        accounts::SignAgreementDirect {
+            signer: *signer,
+            agreement,
+            party: find_agreement_party(&agreement_id, signer).0,
+            signer_freeze: find_freeze(signer).0,
+            safe: find_safe(&agreement).0,
+            config: find_config().0,
+        },
+        instruction::SignAgreementDirect { agreement_id },
+    )
+}
+
+/// `signer` is the proposer's agent key or its authority.
+pub fn cancel_agreement(
+    signer: &Pubkey,
+    proposer_agent_key: &Pubkey,
+    agreement_id: [u8; 16],
+) -> Instruction {
+    build(
+        accounts::CancelAgreement {
+            signer: *signer,
+            proposer_identity: find_agent_identity(proposer_agent_key).0,
+            agreement: find_agreement(&agreement_id).0,
+            config: find_config().0,
+        },
+        instruction::CancelAgreement { agreement_id },
+    )
+}
+
+/// `signer` is the party's agent key or its authority.
+pub fn decline_agreement(
+    signer: &Pubkey,
+    party_agent_key: &Pubkey,
+    agreement_id: [u8; 16],
+) -> Instruction {
+    let party_identity = find_agent_identity(party_agent_key).0;
+    let party = find_agreement_party(&agreement_id, &party_identity).0;
+    build(
+        accounts::DeclineAgreement {
+            signer: *signer,
+            party_identity,
+            agreement: find_agreement(&agreement_id).0,
+            party,
+            decline: find_decline(&party).0,
+            system_program: system_program::ID,
+            config: find_config().0,
+        },
+        instruction::DeclineAgreement { agreement_id },
+    )
+}
+
+/// Decline as a party added with `add_party_direct`.
+pub fn decline_agreement_direct(signer: &Pubkey, agreement_id: [u8; 16]) -> Instruction {
+    let party = find_agreement_party(&agreement_id, signer).0;
+    build(
+        accounts::DeclineAgreementDirect {
+            signer: *signer,
+            agreement: find_agreement(&agreement_id).0,
+            party,
+            decline: find_decline(&party).0,
+            system_program: system_program::ID,
+            config: find_config().0,
+        },
+        instruction::DeclineAgreementDirect { agreement_id },
+    )
+}
+
+/// `signer` is the party's agent key or its authority.
+pub fn fulfill_agreement(
+    signer: &Pubkey,
+    party_agent_key: &Pubkey,
+    agreement_id: [u8; 16],
+) -> Instruction {
+    let signer_identity = find_agent_identity(party_agent_key).0;
+    let agreement = find_agreement(&agreement_id).0;
+    build(
+        accounts::FulfillAgreement {
+            signer: *signer,
+            signer_identity,
+            signer_party: find_agreement_party(&agreement_id, &signer_identity).0,
+            agreement,
+            milestones: find_milestones(&agreement).0,
+            stream: find_stream(&agreement).0,
+            config: find_config().0,
+        },
+        instruction::FulfillAgreement { agreement_id },
+    )
+}
+
+/// `authority` of a party's identity closes the agreement and that party's record.
+pub fn close_agreement(
+    authority: &Pubkey,
+    party_agent_key: &Pubkey,
+    agreement_id: [u8; 16],
+) -> Instruction {
+    let signer_identity = find_agent_identity(party_agent_key).0;
+    let agreement = find_agreement(&agreement_id).0;
+    build(
+        accounts::CloseAgreement {
+            signer: *authority,
+            signer_identity,
+            signer_party: find_agreement_party(&agreement_id, &signer_identity).0,
+            agreement,
+            stream: find_stream(&agreement).0,
+            config: find_config().0,
+        },
+        instruction::CloseAgreement { agreement_id },
+    )
+}
+
+// ============================================================
+// Amendments
+// ============================================================
+
+/// Arguments of `propose_amendment`: the new terms and expiry of `version`.
+#[derive(Clone, Debug)]
+pub struct ProposeAmendmentArgs {
+    pub agreement_id: [u8; 16],
+    pub version: u16,
+    pub terms_hash: [u8; 32],
+    pub terms_uri: [u8; 64],
+    pub expires_at: i64,
+}
+
+/// Signed by the proposer's agent key. `signed_parties` are the parties
+/// (identity PDA, or raw key for direct parties) other than the proposer that
+/// have signed so far; only needed while the agreement is Proposed, where the
+/// amendment applies at once and resets their signatures. A funded SAFE
+/// investor keeps its signature and is left out.
+pub fn propose_amendment(
+    proposer_agent_key: &Pubkey,
+    freeze: FreezeScope,
+    args: ProposeAmendmentArgs,
+    signed_parties: &[Pubkey],
+) -> Instruction {
+    let proposer_identity = find_agent_identity(proposer_agent_key).0;
+    let (agent_freeze, parent_freeze, authority_freeze) = freeze_accounts(&proposer_identity, freeze);
+    let agreement = find_agreement(&args.agreement_id).0;
+    let mut ix = build(
+        accounts::ProposeAmendment {
+            proposer_signer: *proposer_agent_key,
+            proposer_identity,
+            agreement,
+            proposer_party: find_agreement_party(&args.agreement_id, &proposer_identity).0,
+            previous_amendment: (args.version > 1)
+                .then(|| find_amendment(&agreement, args.version - 1).0),
+            amendment: find_amendment(&agreement, args.version).0,
+            system_program: system_program::ID,
+            agent_freeze,
+            parent_freeze,
+            authority_freeze,
+            safe: find_safe(&agreement).0,
+            config: find_config().0,
+        },
+        instruction::ProposeAmendment {
+            agreement_id: args.agreement_id,
+            version: args.version,
+            terms_hash: args.terms_hash,
+            terms_uri: args.terms_uri,
+            expires_at: args.expires_at,
+        },
+    );
+    for party in signed_parties {
+        ix.accounts.push(AccountMeta::new(find_agreement_party(&args.agreement_id, party).0, false));
+    }
+    ix
+}
+
+pub fn sign_amendment(
+    agent_key: &Pubkey,
+    freeze: FreezeScope,
+    agreement_id: [u8; 16],
+    version: u16,
+) -> Instruction {
+    let signer_identity = find_agent_identity(agent_key).0;
+    let (agent_freeze, parent_freeze, authority_freeze) = freeze_accounts(&signer_identity, freeze);
+    let agreement = find_agreement(&agreement_id).0;
+    build(
+        accounts::SignAmendment {
+            signer: *agent_key,
+            signer_identity,
+            agreement,
+            party: find_agreement_party(&agreement_id, &signer_identity).0,
+            amendment: find_amendment(&agreement, version).0,
+            agent_freeze,
+            parent_freeze,
+            authority_freeze,
+            config: find_config().0,
+        },
+        instruction::SignAmendment { agreement_id, version },
+    )
+}
+
+/// Sign as a party added with `add_party_direct`.
+pub fn sign_amendment_direct(signer: &Pubkey, agreement_id: [u8; 16], version: u16) -> Instruction {
+    let agreement = find_agreement(&agreement_id).0;
+    build(
+        accounts::SignAmendmentDirect {
+            signer: *signer,
+            agreement,
+            party: find_agreement_party(&agreement_id, signer).0,
+            amendment: find_amendment(&agreement, version).0,
+            config: find_config().0,
+        },
+        instruction::SignAmendmentDirect { agreement_id, version },
+    )
+}
+
+/// `signer` is the proposer's agent key or its authority.
+pub fn withdraw_amendment(
+    signer: &Pubkey,
+    proposer_agent_key: &Pubkey,
+    agreement_id: [u8; 16],
+    version: u16,
+) -> Instruction {
+    let agreement = find_agreement(&agreement_id).0;
+    build(
+        accounts::WithdrawAmendment {
+            signer: *signer,
+            proposer_identity: find_agent_identity(proposer_agent_key).0,
+            agreement,
+            amendment: find_amendment(&agreement, version).0,
+            config: find_config().0,
+        },
+        instruction::WithdrawAmendment { agreement_id, version },
+    )
+}
+
+// ============================================================
+// Counter-proposals
+// ============================================================
+
+/// Arguments of `counter_propose`: the countering party's round and terms.
+#[derive(Clone, Debug)]
+pub struct CounterProposeArgs {
+    pub agreement_id: [u8; 16],
+    pub round: u16,
+    pub terms_hash: [u8; 32],
+    pub terms_uri: [u8; 64],
+    pub expires_at: i64,
+}
+
+/// Signed (and paid) by the countering party's agent key.
+pub fn counter_propose(agent_key: &Pubkey, freeze: FreezeScope, args: CounterProposeArgs) -> Instruction {
+    let signer_identity = find_agent_identity(agent_key).0;
+    let (agent_freeze, parent_freeze, authority_freeze) = freeze_accounts(&signer_identity, freeze);
+    let party = find_agreement_party(&args.agreement_id, &signer_identity).0;
+    build(
+        accounts::CounterPropose {
+            signer: *agent_key,
+            signer_identity,
+            agreement: find_agreement(&args.agreement_id).0,
+            party,
+            previous_counter: (args.round > 1).then(|| find_counter_proposal(&party, args.round - 1).0),
+            counter: find_counter_proposal(&party, args.round).0,
+            system_program: system_program::ID,
+            agent_freeze,
+            parent_freeze,
+            authority_freeze,
+            config: find_config().0,
+        },
+        instruction::CounterPropose {
+            agreement_id: args.agreement_id,
+            round: args.round,
+            terms_hash: args.terms_hash,
+            terms_uri: args.terms_uri,
+            expires_at: args.expires_at,
+        },
+    )
+}
+
+/// Signed by the proposer's agent key. Applies round `round` of
+/// `party_agent_key` as amendment `version`; `signed_parties` as for
+/// [`propose_amendment`].
+pub fn accept_counter_proposal(
+    proposer_agent_key: &Pubkey,
+    freeze: FreezeScope,
+    agreement_id: [u8; 16],
+    party_agent_key: &Pubkey,
+    round: u16,
+    version: u16,
+    signed_parties: &[Pubkey],
+) -> Instruction {
+    let proposer_identity = find_agent_identity(proposer_agent_key).0;
+    let (agent_freeze, parent_freeze, authority_freeze) = freeze_accounts(&proposer_identity, freeze);
+    let agreement = find_agreement(&agreement_id).0;
+    let party = find_agreement_party(&agreement_id, &find_agent_identity(party_agent_key).0).0;
+    let mut ix = build(
+        accounts::AcceptCounterProposal {
+            proposer_signer: *proposer_agent_key,
+            proposer_identity,
+            agreement,
+            proposer_party: find_agreement_party(&agreement_id, &proposer_identity).0,
+            counter: find_counter_proposal(&party, round).0,
+            previous_amendment: (version > 1).then(|| find_amendment(&agreement, version - 1).0),
+            amendment: find_amendment(&agreement, version).0,
+            system_program: system_program::ID,
+            agent_freeze,
+            parent_freeze,
+            authority_freeze,
+            safe: find_safe(&agreement).0,
+            config: find_config().0,
+        },
+        instruction::AcceptCounterProposal { agreement_id, version },
+    );
+    for party in signed_parties {
+        ix.accounts.push(AccountMeta::new(find_agreement_party(&agreement_id, party).0, false));
+    }
+    ix
+}
+
+/// `signer` is the proposer's agent key or its authority.
+pub fn reject_counter_proposal(
+    signer: &Pubkey,
+    proposer_agent_key: &Pubkey,
+    agreement_id: [u8; 16],
+    party_agent_key: &Pubkey,
+    round: u16,
+) -> Instruction {
+    let party = find_agreement_party(&agreement_id, &find_agent_identity(party_agent_key).0).0;
+    build(
+        accounts::RejectCounterProposal {
+            signer: *signer,
+            proposer_identity: find_agent_identity(proposer_agent_key).0,
+            agreement: find_agreement(&agreement_id).0,
+            counter: find_counter_proposal(&party, round).0,
+            config: find_config().0,
+        },
+        instruction::RejectCounterProposal { agreement_id },
+    )
+}
+
+// ============================================================
+// Escrow and termination
+// ============================================================
+
+pub fn fund_escrow(
+    agent_key: &Pubkey,
+    freeze: FreezeScope,
+    agreement_id: [u8; 16],
+    amount: u64,
+) -> Instruction {
+    let signer_identity = find_agent_identity(agent_key).0;
+    let (agent_freeze, parent_freeze, authority_freeze) = freeze_accounts(&signer_identity, freeze);
+    build(
+        accounts::FundEscrow {
+            signer: *agent_key,
+            signer_identity,
+            agreement: find_agreement(&agreement_id).0,
+            party: find_agreement_party(&agreement_id, &signer_identity).0,
+            vault: find_vault(&signer_identity).0,
+            agent_freeze,
+            parent_freeze,
+            authority_freeze,
+            config: find_config().0,
+        },
+        instruction::FundEscrow { agreement_id, amount },
+    )
+}
+
+/// Pay out `party`'s escrow (identity PDA, or wallet of a direct party).
+/// `recipient` is that identity's vault, or the direct party's wallet;
+/// `terminated` passes the Termination holding the settlement.
+pub fn release_escrow(
+    signer: &Pubkey,
+    agreement_id: [u8; 16],
+    party: &Pubkey,
+    recipient: &Pubkey,
+    terminated: bool,
+) -> Instruction {
+    let agreement = find_agreement(&agreement_id).0;
+    build(
+        accounts::ReleaseEscrow {
+            signer: *signer,
+            agreement,
+            party: find_agreement_party(&agreement_id, party).0,
+            termination: terminated.then(|| find_termination(&agreement).0),
+            recipient: *recipient,
+            config: find_config().0,
+        },
+        instruction::ReleaseEscrow { agreement_id },
+    )
+}
+
+/// `settlement` lists every party (identity PDA, or wallet of a direct party)
+/// with the lamports it receives.
+pub fn propose_termination(
+    agent_key: &Pubkey,
+    freeze: FreezeScope,
+    agreement_id: [u8; 16],
+    settlement: &[(Pubkey, u64)],
+) -> Instruction {
+    let signer_identity = find_agent_identity(agent_key).0;
+    let (agent_freeze, parent_freeze, authority_freeze) = freeze_accounts(&signer_identity, freeze);
+    let agreement = find_agreement(&agreement_id).0;
+    let mut ix = build(
+        accounts::ProposeTermination {
+            signer: *agent_key,
+            signer_identity,
+            agreement,
+            party: find_agreement_party(&agreement_id, &signer_identity).0,
+            termination: find_termination(&agreement).0,
+            system_program: system_program::ID,
+            agent_freeze,
+            parent_freeze,
+            authority_freeze,
+            config: find_config().0,
+        },
+        instruction::ProposeTermination {
+            agreement_id,
+            amounts: settlement.iter().map(|(_, amount)| *amount).collect(),
+        },
+    );
+    for (party, _) in settlement {
+        ix.accounts.push(AccountMeta::new_readonly(find_agreement_party(&agreement_id, party).0, false));
+    }
+    ix
+}
+
+pub fn approve_termination(
+    agent_key: &Pubkey,
+    freeze: FreezeScope,
+    agreement_id: [u8; 16],
+) -> Instruction {
+    let signer_identity = find_agent_identity(agent_key).0;
+    let (agent_freeze, parent_freeze, authority_freeze) = freeze_accounts(&signer_identity, freeze);
+    let agreement = find_agreement(&agreement_id).0;
+    build(
+        accounts::ApproveTermination {
+            signer: *agent_key,
+            signer_identity,
+            agreement,
+            party: find_agreement_party(&agreement_id, &signer_identity).0,
+            termination: find_termination(&agreement).0,
+            agent_freeze,
+            parent_freeze,
+            authority_freeze,
+            config: find_config().0,
+        },
+        instruction::ApproveTermination { agreement_id },
+    )
+}
+
+/// Approve as a party added with `add_party_direct`.
+pub fn approve_termination_direct(signer: &Pubkey, agreement_id: [u8; 16]) -> Instruction {
+    let agreement = find_agreement(&agreement_id).0;
+    build(
+        accounts::ApproveTerminationDirect {
+            signer: *signer,
+            agreement,
+            party: find_agreement_party(&agreement_id, signer).0,
+            termination: find_termination(&agreement).0,
+            config: find_config().0,
+        },
+        instruction::ApproveTerminationDirect { agreement_id },
+    )
+}
+
+/// `signer` is the proposing party's agent key or its authority.
+pub fn cancel_termination(
+    signer: &Pubkey,
+    proposer_agent_key: &Pubkey,
+    agreement_id: [u8; 16],
+) -> Instruction {
+    let proposer_identity = find_agent_identity(proposer_agent_key).0;
+    let agreement = find_agreement(&agreement_id).0;
+    build(
+        accounts::CancelTermination {
+            signer: *signer,
+            proposer_identity,
+            agreement,
+            proposer_party: find_agreement_party(&agreement_id, &proposer_identity).0,
+            termination: find_termination(&agreement).0,
+            config: find_config().0,
+        },
+        instruction::CancelTermination { agreement_id },
+    )
+}
+
+// ============================================================
+// Milestones
+// ============================================================
+
+/// Payers and payees in `milestones` are AgreementParty PDAs; each is also
+/// passed once as a remaining account.
+pub fn set_milestones(
+    proposer_agent_key: &Pubkey,
+    freeze: FreezeScope,
+    agreement_id: [u8; 16],
+    milestones: Vec<MilestoneTerms>,
+) -> Instruction {
+    let proposer_identity = find_agent_identity(proposer_agent_key).0;
+    let (agent_freeze, parent_freeze, authority_freeze) = freeze_accounts(&proposer_identity, freeze);
+    let agreement = find_agreement(&agreement_id).0;
+    let mut parties: Vec<Pubkey> = Vec::new();
+    for party in milestones.iter().flat_map(|terms| [terms.payer, terms.payee]) {
+        if !parties.contains(&party) {
+            parties.push(party);
+        }
+    }
+    let mut ix = build(
+        accounts::SetMilestones {
+            proposer_signer: *proposer_agent_key,
+            proposer_identity,
+            agreement,
+            schedule: find_milestones(&agreement).0,
+            system_program: system_program::ID,
+            agent_freeze,
+            parent_freeze,
+            authority_freeze,
+            config: find_config().0,
+        },
+        instruction::SetMilestones { agreement_id, milestones },
+    );
+    for party in parties {
+        ix.accounts.push(AccountMeta::new_readonly(party, false));
+    }
+    ix
+}
+
+pub fn submit_milestone(
+    agent_key: &Pubkey,
+    freeze: FreezeScope,
+    agreement_id: [u8; 16],
+    index: u8,
+) -> Instruction {
+    let signer_identity = find_agent_identity(agent_key).0;
+    let (agent_freeze, parent_freeze, authority_freeze) = freeze_accounts(&signer_identity, freeze);
+    let agreement = find_agreement(&agreement_id).0;
+    build(
+        accounts::SubmitMilestone {
+            signer: *agent_key,
+            signer_identity,
+            agreement,
+            party: find_agreement_party(&agreement_id, &signer_identity).0,
+            schedule: find_milestones(&agreement).0,
+            agent_freeze,
+            parent_freeze,
+            authority_freeze,
+            config: find_config().0,
+        },
+        instruction::SubmitMilestone { agreement_id, index },
+    )
+}
+
+pub fn approve_milestone(
+    agent_key: &Pubkey,
+    freeze: FreezeScope,
+    agreement_id: [u8; 16],
+    index: u8,
+) -> Instruction {
+    let signer_identity = find_agent_identity(agent_key).0;
+    let (agent_freeze, parent_freeze, authority_freeze) = freeze_accounts(&signer_identity, freeze);
+    let agreement = find_agreement(&agreement_id).0;
+    build(
+        accounts::ApproveMilestone {
+            signer: *agent_key,
+            signer_identity,
+            agreement,
+            party: find_agreement_party(&agreement_id, &signer_identity).0,
+            schedule: find_milestones(&agreement).0,
+            agent_freeze,
+            parent_freeze,
+            authority_freeze,
+            config: find_config().0,
+        },
+        instruction::ApproveMilestone { agreement_id, index },
+    )
+}
+
+pub fn dispute_milestone(
+    agent_key: &Pubkey,
+    freeze: FreezeScope,
+    agreement_id: [u8; 16],
+    index: u8,
+) -> Instruction {
+    let signer_identity = find_agent_identity(agent_key).0;
+    let (agent_freeze, parent_freeze, authority_freeze) = freeze_accounts(&signer_identity, freeze);
+    let agreement = find_agreement(&agreement_id).0;
+    build(
+        accounts::DisputeMilestone {
+            signer: *agent_key,
+            signer_identity,
+            agreement,
+            party: find_agreement_party(&agreement_id, &signer_identity).0,
+            schedule: find_milestones(&agreement).0,
+            agent_freeze,
+            parent_freeze,
+            authority_freeze,
+            config: find_config().0,
+        },
+        instruction::DisputeMilestone { agreement_id, index },
+    )
+}
+
+/// `payer` and `payee` are identity PDAs (or wallets of direct parties);
+/// `recipient` is the payee identity's vault, or the direct payee's wallet.
+pub fn release_milestone(
+    signer: &Pubkey,
+    agreement_id: [u8; 16],
+    index: u8,
+    payer: &Pubkey,
+    payee: &Pubkey,
+    recipient: &Pubkey,
+) -> Instruction {
+    let agreement = find_agreement(&agreement_id).0;
+    build(
+        accounts::ReleaseMilestone {
+            signer: *signer,
+            agreement,
+            schedule: find_milestones(&agreement).0,
+            payer_party: find_agreement_party(&agreement_id, payer).0,
+            payee_party: find_agreement_party(&agreement_id, payee).0,
+            recipient: *recipient,
+            config: find_config().0,
+        },
+        instruction::ReleaseMilestone { agreement_id, index },
+    )
+}
+
+// ============================================================
+// Recurring payments
+// ============================================================
+
+/// Payer and payee in `terms` are AgreementParty PDAs; `payer_identity` is
+/// the payer's AgentIdentity PDA.
+pub fn set_recurring_payment(
+    proposer_agent_key: &Pubkey,
+    freeze: FreezeScope,
+    agreement_id: [u8; 16],
+    payer_identity: &Pubkey,
+    terms: RecurringTerms,
+) -> Instruction {
+    let proposer_identity = find_agent_identity(proposer_agent_key).0;
+    let (agent_freeze, parent_freeze, authority_freeze) = freeze_accounts(&proposer_identity, freeze);
+    let agreement = find_agreement(&agreement_id).0;
+    build(
+        accounts::SetRecurringPayment {
+            proposer_signer: *proposer_agent_key,
+            proposer_identity,
+            agreement,
+            recurring: find_recurring_payment(&agreement).0,
+            payer_party: terms.payer,
+            payer_identity: *payer_identity,
+            payee_party: terms.payee,
+            system_program: system_program::ID,
+            agent_freeze,
+            parent_freeze,
+            authority_freeze,
+            config: find_config().0,
+        },
+        instruction::SetRecurringPayment { agreement_id, terms },
+    )
+}
+
+/// `payer` is the paying identity PDA and `payer_freeze` its freeze scope;
+/// `payee` is an identity PDA (or wallet of a direct party) and `recipient`
+/// its vault (or that wallet).
+pub fn crank_payment(
+    signer: &Pubkey,
+    agreement_id: [u8; 16],
+    payer: &Pubkey,
+    payer_freeze: FreezeScope,
+    payee: &Pubkey,
+    recipient: &Pubkey,
+) -> Instruction {
+    let (agent_freeze, parent_freeze, authority_freeze) = freeze_accounts(payer, payer_freeze);
+    let agreement = find_agreement(&agreement_id).0;
+    build(
+        accounts::CrankPayment {
+            signer: *signer,
+            agreement,
+            recurring: find_recurring_payment(&agreement).0,
+            payer_party: find_agreement_party(&agreement_id, payer).0,
+            payer_identity: *payer,
+            payer_vault: find_vault(payer).0,
+            payee_party: find_agreement_party(&agreement_id, payee).0,
+            recipient: *recipient,
+            agent_freeze,
+            parent_freeze,
+            authority_freeze,
+            config: find_config().0,
+        },
+        instruction::CrankPayment { agreement_id },
+    )
+}
+
+/// `signer` is the payer's or payee's agent key or its authority.
+pub fn cancel_recurring_payment(
+    signer: &Pubkey,
+    party_agent_key: &Pubkey,
+    agreement_id: [u8; 16],
+) -> Instruction {
+    let party_identity = find_agent_identity(party_agent_key).0;
+    let agreement = find_agreement(&agreement_id).0;
+    build(
+        accounts::CancelRecurringPayment {
+            signer: *signer,
+            party_identity,
+            agreement,
+            party: find_agreement_party(&agreement_id, &party_identity).0,
+            recurring: find_recurring_payment(&agreement).0,
+            config: find_config().0,
+        },
+        instruction::CancelRecurringPayment { agreement_id },
+    )
+}
+
+// ============================================================
+// Escrow streams
+// ============================================================
+
+/// `payee` is an identity PDA, or the wallet of a direct party. `parties`
+/// lists every party the same way; vesting starts when the last one signed.
+pub fn fund_stream(
+    agent_key: &Pubkey,
+    freeze: FreezeScope,
+    agreement_id: [u8; 16],
+    payee: &Pubkey,
+    amount: u64,
+    parties: &[Pubkey],
+) -> Instruction {
+    let signer_identity = find_agent_identity(agent_key).0;
+    let (agent_freeze, parent_freeze, authority_freeze) = freeze_accounts(&signer_identity, freeze);
+    let agreement = find_agreement(&agreement_id).0;
+    let mut ix = build(
+        accounts::FundStream {
+            signer: *agent_key,
+            signer_identity,
+            agreement,
+            party: find_agreement_party(&agreement_id, &signer_identity).0,
+            payee_party: find_agreement_party(&agreement_id, payee).0,
+            stream: find_stream(&agreement).0,
+            vault: find_vault(&signer_identity).0,
+            system_program: system_program::ID,
+            agent_freeze,
+            parent_freeze,
+            authority_freeze,
+            config: find_config().0,
+        },
+        instruction::FundStream { agreement_id, amount },
+    );
+    for party in parties {
+        ix.accounts.push(AccountMeta::new_readonly(find_agreement_party(&agreement_id, party).0, false));
+    }
+    ix
+}
+
+/// `payee` is an identity PDA (or wallet of a direct party) and `recipient`
+/// its vault (or that wallet).
+pub fn claim_vested(signer: &Pubkey, agreement_id: [u8; 16], payee: &Pubkey, recipient: &Pubkey) -> Instruction {
+    let agreement = find_agreement(&agreement_id).0;
+    build(
+        accounts::ClaimVested {
+            signer: *signer,
+            agreement,
+            stream: find_stream(&agreement).0,
+            payee_party: find_agreement_party(&agreement_id, payee).0,
+            recipient: *recipient,
+            config: find_config().0,
+        },
+        instruction::ClaimVested { agreement_id },
+    )
+}
+
+/// Both ends of a stream: identity PDAs (or wallets of direct parties) with
+/// the vault (or wallet) each is paid into.
+#[derive(Clone, Copy, Debug)]
+pub struct StreamEnds {
+    pub payer: Pubkey,
+    pub payer_recipient: Pubkey,
+    pub payee: Pubkey,
+    pub payee_recipient: Pubkey,
+}
+
+/// `signer` is the payer's or payee's agent key or its authority.
+pub fn cancel_stream(
+    signer: &Pubkey,
+    party_agent_key: &Pubkey,
+    agreement_id: [u8; 16],
+    ends: StreamEnds,
+) -> Instruction {
+    let party_identity = find_agent_identity(party_agent_key).0;
+    let agreement = find_agreement(&agreement_id).0;
+    build(
+        accounts::CancelStream {
+            signer: *signer,
+            party_identity,
+            agreement,
+            party: find_agreement_party(&agreement_id, &party_identity).0,
+            stream: find_stream(&agreement).0,
+            payer_party: find_agreement_party(&agreement_id, &ends.payer).0,
+            payee_party: find_agreement_party(&agreement_id, &ends.payee).0,
+            payer_recipient: ends.payer_recipient,
+            payee_recipient: ends.payee_recipient,
+            config: find_config().0,
+        },
+        instruction::CancelStream { agreement_id },
+    )
+}
+
+/// `terminated` passes the Termination whose approval stopped the vesting.
+pub fn settle_stream(signer: &Pubkey, agreement_id: [u8; 16], ends: StreamEnds, terminated: bool) -> Instruction {
+    let agreement = find_agreement(&agreement_id).0;
+    build(
+        accounts::SettleStream {
+            signer: *signer,
+            agreement,
+            stream: find_stream(&agreement).0,
+            termination: terminated.then(|| find_termination(&agreement).0),
+            payer_party: find_agreement_party(&agreement_id, &ends.payer).0,
+            payee_party: find_agreement_party(&agreement_id, &ends.payee).0,
+            payer_recipient: ends.payer_recipient,
+            payee_recipient: ends.payee_recipient,
+            config: find_config().0,
+        },
+        instruction::SettleStream { agreement_id },
+    )
+}
+
+// ============================================================
+// Revenue shares
+// ============================================================
+
+/// Parties in `splits` are AgreementParty PDAs, also passed as remaining accounts.
+pub fn set_revenue_share(
+    proposer_agent_key: &Pubkey,
+    freeze: FreezeScope,
+    agreement_id: [u8; 16],
+    splits: Vec<RevenueSplit>,
+) -> Instruction {
+    let proposer_identity = find_agent_identity(proposer_agent_key).0;
+    let (agent_freeze, parent_freeze, authority_freeze) = freeze_accounts(&proposer_identity, freeze);
+    let agreement = find_agreement(&agreement_id).0;
+    let parties: Vec<Pubkey> = splits.iter().map(|split| split.party).collect();
+    let mut ix = build(
+        accounts::SetRevenueShare {
+            proposer_signer: *proposer_agent_key,
+            proposer_identity,
+            agreement,
+            revenue_share: find_revenue_share(&agreement).0,
+            system_program: system_program::ID,
+            agent_freeze,
+            parent_freeze,
+            authority_freeze,
+            config: find_config().0,
+        },
+        instruction::SetRevenueShare { agreement_id, splits },
+    );
+    for party in parties {
+        ix.accounts.push(AccountMeta::new_readonly(party, false));
+    }
+    ix
+}
+
+/// `payouts` follows the splits: each party (identity PDA, or wallet of a
+/// direct party) with its recipient, that identity's vault or the wallet.
+pub fn distribute(signer: &Pubkey, agreement_id: [u8; 16], payouts: &[(Pubkey, Pubkey)]) -> Instruction {
+    let agreement = find_agreement(&agreement_id).0;
+    let mut ix = build(
+        accounts::Distribute {
+            signer: *signer,
+            agreement,
+            revenue_share: find_revenue_share(&agreement).0,
+            receiver: find_revenue_receiver(&agreement).0,
+            system_program: system_program::ID,
+            config: find_config().0,
+        },
+        instruction::Distribute { agreement_id },
+    );
+    for (party, recipient) in payouts {
+        ix.accounts.push(AccountMeta::new_readonly(find_agreement_party(&agreement_id, party).0, false));
+        ix.accounts.push(AccountMeta::new(*recipient, false));
+    }
+    ix
+}
+
+/// `receiver_tokens` is a token account owned by the revenue receiver.
+/// `payouts` follows the splits: each party (identity PDA, or wallet of a
+/// direct party) with a token account of the same mint, owned by that
+/// identity's authority or the wallet.
+pub fn distribute_tokens(
+    signer: &Pubkey,
+    agreement_id: [u8; 16],
+    receiver_tokens: &Pubkey,
+    payouts: &[(Pubkey, Pubkey)],
+) -> Instruction {
+    let agreement = find_agreement(&agreement_id).0;
+    let mut ix = build(
+        accounts::DistributeTokens {
+            signer: *signer,
+            agreement,
+            revenue_share: find_revenue_share(&agreement).0,
+            receiver: find_revenue_receiver(&agreement).0,
+            receiver_tokens: *receiver_tokens,
+            token_program: anchor_spl::token::ID,
+            config: find_config().0,
+        },
+        instruction::DistributeTokens { agreement_id },
+    );
+    for (party, token_account) in payouts {
+        ix.accounts.push(AccountMeta::new_readonly(find_agreement_party(&agreement_id, party).0, false));
+        ix.accounts.push(AccountMeta::new_readonly(*party, false));
+        ix.accounts.push(AccountMeta::new(*token_account, false));
+    }
+    ix
+}
+
+// ============================================================
+// SAFEs
+// ============================================================
+
+/// Investor and company in `terms` are AgreementParty PDAs;
+/// `investor_identity` is the investor's AgentIdentity PDA.
+pub fn set_safe(
+    proposer_agent_key: &Pubkey,
+    freeze: FreezeScope,
+    agreement_id: [u8; 16],
+    terms: SafeTerms,
+    investor_identity: &Pubkey,
+) -> Instruction {
+    let proposer_identity = find_agent_identity(proposer_agent_key).0;
+    let (agent_freeze, parent_freeze, authority_freeze) = freeze_accounts(&proposer_identity, freeze);
+    let agreement = find_agreement(&agreement_id).0;
+    build(
+        accounts::SetSafe {
+            proposer_signer: *proposer_agent_key,
+            proposer_identity,
+            agreement,
+            safe: find_safe(&agreement).0,
+            investor_party: terms.investor,
+            investor_identity: *investor_identity,
+            company_party: terms.company,
+            system_program: system_program::ID,
+            agent_freeze,
+            parent_freeze,
+            authority_freeze,
+            config: find_config().0,
+        },
+        instruction::SetSafe { agreement_id, terms },
+    )
+}
+
+pub fn sign_safe(agent_key: &Pubkey, freeze: FreezeScope, agreement_id: [u8; 16]) -> Instruction {
+    let signer_identity = find_agent_identity(agent_key).0;
+    let (agent_freeze, parent_freeze, authority_freeze) = freeze_accounts(&signer_identity, freeze);
+    let agreement = find_agreement(&agreement_id).0;
+    build(
+        accounts::SignSafe {
+            signer: *agent_key,
+            signer_identity,
+            agreement,
+            party: find_agreement_party(&agreement_id, &signer_identity).0,
+            safe: find_safe(&agreement).0,
+            vault: find_vault(&signer_identity).0,
+            agent_freeze,
+            parent_freeze,
+            authority_freeze,
+            config: find_config().0,
+        },
+        instruction::SignSafe { agreement_id },
+    )
+}
+
+/// `signer` is the company's agent key or its authority; the investment is
+/// paid into the company identity's vault. `investor` is an identity PDA.
+pub fn convert_safe(
+    signer: &Pubkey,
+    company_agent_key: &Pubkey,
+    agreement_id: [u8; 16],
+    investor: &Pubkey,
+    round_valuation: u64,
+) -> Instruction {
+    let company_identity = find_agent_identity(company_agent_key).0;
+    let agreement = find_agreement(&agreement_id).0;
+    build(
+        accounts::ConvertSafe {
+            signer: *signer,
+            company_identity,
+            agreement,
+            company_party: find_agreement_party(&agreement_id, &company_identity).0,
+            safe: find_safe(&agreement).0,
+            investor_party: find_agreement_party(&agreement_id, investor).0,
+            recipient: find_vault(&company_identity).0,
+            config: find_config().0,
+        },
+        instruction::ConvertSafe { agreement_id, round_valuation },
+    )
+}
+
+/// `signer` is the company's or investor's agent key or its authority; the
+/// investment goes back to the vault of `investor` (an identity PDA).
+pub fn refund_safe(
+    signer: &Pubkey,
+    party_agent_key: &Pubkey,
+    agreement_id: [u8; 16],
+    investor: &Pubkey,
+) -> Instruction {
+    let party_identity = find_agent_identity(party_agent_key).0;
+    let agreement = find_agreement(&agreement_id).0;
+    build(
+        accounts::RefundSafe {
+            signer: *signer,
+            party_identity,
+            agreement,
+            party: find_agreement_party(&agreement_id, &party_identity).0,
+            safe: find_safe(&agreement).0,
+            investor_party: find_agreement_party(&agreement_id, investor).0,
+            recipient: find_vault(investor).0,
+            config: find_config().0,
+        },
+        instruction::RefundSafe { agreement_id },
+    )
+}
+
+// ============================================================
+// Vault
+// ============================================================
+
+pub fn deposit_to_vault(authority: &Pubkey, agent_key: &Pubkey, amount: u64) -> Instruction {
+    let agent_identity = find_agent_identity(agent_key).0;
+    build(
+        accounts::DepositToVault {
+            authority: *authority,
+            agent_identity,
+            vault: find_vault(&agent_identity).0,
+            system_program: system_program::ID,
+            config: find_config().0,
+        },
+        instruction::DepositToVault { amount },
+    )
+}
+
+/// `destination` receives the lamports; a council names a member's wallet.
+pub fn withdraw_from_vault(authority: &Pubkey, agent_key: &Pubkey, destination: &Pubkey, amount: u64) -> Instruction {
+    let agent_identity = find_agent_identity(agent_key).0;
+    build(
+        accounts::WithdrawFromVault {
+            authority: *authority,
+            agent_identity,
+            vault: find_vault(&agent_identity).0,
+            system_program: system_program::ID,
+            config: find_config().0,
+            destination: Some(*destination),
+        },
+        instruction::WithdrawFromVault { amount },
+    )
+}
+
+// ============================================================
+// Migration (V1 → aap-compressed)
+//
+// `light_args` is the borsh-encoded
+// `(ValidityProof, PackedAddressTreeInfo, output_state_tree_index)` and
+// `light_accounts` the packed Light system accounts, appended as remaining accounts.
+// ============================================================
+
+/// `parent_agent_key` is required for sub-agents (the parent must be migrated first).
+pub fn migrate_agent(
+    authority: &Pubkey,
+    agent_key: &Pubkey,
+    parent_agent_key: Option<&Pubkey>,
+    light_args: Vec<u8>,
+    light_accounts: Vec<AccountMeta>,
+) -> Instruction {
+    migrate_rotated_agent(authority, agent_key, agent_key, parent_agent_key, light_args, light_accounts)
+}
+
+/// Migrate an identity registered with `registered_agent_key` that now holds
+/// `current_agent_key`; the current key's lookup is closed with it.
+pub fn migrate_rotated_agent(
+    authority: &Pubkey,
+    registered_agent_key: &Pubkey,
+    current_agent_key: &Pubkey,
+    parent_agent_key: Option<&Pubkey>,
+    light_args: Vec<u8>,
+    light_accounts: Vec<AccountMeta>,
+) -> Instruction {
+    let agent_identity = find_agent_identity(registered_agent_key).0;
+    let mut ix = build(
+        accounts::MigrateAgent {
+            authority: *authority,
+            agent_identity,
+            parent_tombstone: parent_agent_key
+                .map(|parent| find_tombstone(&find_agent_identity(parent).0).0),
+            vault: find_vault(&agent_identity).0,
+            tombstone: find_tombstone(&agent_identity).0,
+            migration_authority: find_migration_authority().0,
+            compressed_program: AAP_COMPRESSED_PROGRAM_ID,
+            system_program: system_program::ID,
+            current_key_lookup: key_lookup(registered_agent_key, current_agent_key),
+            config: find_config().0,
+        },
+        instruction::MigrateAgent {
+            registered_agent_key: *registered_agent_key,
+            light_args,
+        },
+    );
+    ix.accounts.extend(light_accounts);
+    ix
+}
+
+/// `amendment_version` is the agreement's latest amendment, 0 if it has none.
+pub fn migrate_agreement(
+    signer: &Pubkey,
+    proposer_agent_key: &Pubkey,
+    agreement_id: [u8; 16],
+    amendment_version: u16,
+    light_args: Vec<u8>,
+    light_accounts: Vec<AccountMeta>,
+) -> Instruction {
+    let agreement = find_agreement(&agreement_id).0;
+    let mut ix = build(
+        accounts::MigrateAgreement {
+            signer: *signer,
+            proposer_tombstone: find_tombstone(&find_agent_identity(proposer_agent_key).0).0,
+            agreement,
+            tombstone: find_tombstone(&agreement).0,
+            migration_authority: find_migration_authority().0,
+            compressed_program: AAP_COMPRESSED_PROGRAM_ID,
+            system_program: system_program::ID,
+            termination: find_termination(&agreement).0,
+            milestones: find_milestones(&agreement).0,
+            recurring: find_recurring_payment(&agreement).0,
+            revenue_share: find_revenue_share(&agreement).0,
+            safe: find_safe(&agreement).0,
+            stream: find_stream(&agreement).0,
+            latest_amendment: find_amendment(&agreement, amendment_version).0,
+            next_amendment: find_amendment(&agreement, amendment_version.wrapping_add(1)).0,
+            config: find_config().0,
+        },
+        instruction::MigrateAgreement {
+            agreement_id,
+            amendment_version,
+            light_args,
+        },
+    );
+    ix.accounts.extend(light_accounts);
+    ix
+}
+
+pub fn migrate_party(
+    signer: &Pubkey,
+    party_agent_key: &Pubkey,
+    agreement_id: [u8; 16],
+    light_args: Vec<u8>,
+    light_accounts: Vec<AccountMeta>,
+) -> Instruction {
+    let party_identity = find_agent_identity(party_agent_key).0;
+    let party = find_agreement_party(&agreement_id, &party_identity).0;
+    let mut ix = build(
+        accounts::MigrateParty {
+            signer: *signer,
+            agreement_tombstone: find_tombstone(&find_agreement(&agreement_id).0).0,
+            identity_tombstone: find_tombstone(&party_identity).0,
+            party,
+            tombstone: find_tombstone(&party).0,
+            migration_authority: find_migration_authority().0,
+            compressed_program: AAP_COMPRESSED_PROGRAM_ID,
+            system_program: system_program::ID,
+            config: find_config().0,
+        },
+        instruction::MigrateParty {
+            agreement_id,
+            light_args,
+        },
+    );
+    ix.accounts.extend(light_accounts);
+    ix
+}
+
+// ============================================================
+// Materialize (aap-compressed → V1)
+//
+// Only succeed as a CPI from aap-compressed `decompress_*`, which signs with its
+// migration PDA. Exposed so callers can build the inner instruction for
+// simulation and account-list inspection.
+// ============================================================
+
+pub fn materialize_agreement(payer: &Pubkey, snapshot: AgreementSnapshot) -> Instruction {
+    let agreement = find_agreement(&snapshot.agreement_id).0;
+    build(
+        accounts::MaterializeAgreement {
+            payer: *payer,
+            migration_authority: find_compressed_migration_authority().0,
+            proposer_identity: find_agent_identity(&snapshot.proposer_agent_key).0,
+            agreement,
+            system_program: system_program::ID,
+            marker: find_decompression_marker(&agreement).0,
+        },
+        instruction::MaterializeAgreement { snapshot },
+    )
+}
+
+/// `payer` must be the agent key or authority of the agreement's proposer,
+/// registered as `proposer_agent_key`.
+pub fn materialize_party(payer: &Pubkey, proposer_agent_key: &Pubkey, snapshot: PartySnapshot) -> Instruction {
+    let agreement = find_agreement(&snapshot.agreement_id).0;
+    let agent_identity = find_agent_identity(&snapshot.agent_key).0;
+    build(
+        accounts::MaterializeParty {
+            payer: *payer,
+            migration_authority: find_compressed_migration_authority().0,
+            agreement,
+            agent_identity,
+            party: find_agreement_party(&snapshot.agreement_id, &agent_identity).0,
+            system_program: system_program::ID,
+            proposer_identity: find_agent_identity(proposer_agent_key).0,
+            marker: find_decompression_marker(&agreement).0,
+        },
+        instruction::MaterializeParty { snapshot },
+    )
+}
+
+// ============================================================
+// Protocol config
+// ============================================================
+
+/// Signed by the V1 program's upgrade authority.
+pub fn initialize_config(upgrade_authority: &Pubkey, params: ConfigParams) -> Instruction {
+    build(
+        accounts::InitializeConfig {
+            upgrade_authority: *upgrade_authority,
+            config: find_config().0,
+            program: PROGRAM_ID,
+            program_data: find_program_data().0,
+            system_program: system_program::ID,
+        },
+        instruction::InitializeConfig { params },
+    )
+}
+
+pub fn update_config(admin: &Pubkey, params: ConfigParams) -> Instruction {
+    build(
+        accounts::UpdateConfig {
+            admin: *admin,
+            config: find_config().0,
+        },
+        instruction::UpdateConfig { params },
+    )
+}
+
+pub fn set_paused(admin: &Pubkey, paused: bool) -> Instruction {
+    build(
+        accounts::SetPaused {
+            admin: *admin,
+            config: find_config().0,
+        },
+        instruction::SetPaused { paused },
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anchor_lang::AnchorDeserialize;
+    use solana_sdk::hash::hash;
+
+    fn discriminator(name: &str) -> [u8; 8] {
+        hash(format!("global:{name}").as_bytes()).to_bytes()[..8]
+            .try_into()
+            .unwrap()
+    }
+
+    fn scope() -> DelegationScope {
+        DelegationScope {
+            can_sign_agreements: true,
+            can_commit_funds: false,
+            max_commit_lamports: 0,
+            expires_at: 0,
+        }
+    }
+
+    #[test]
+    fn register_agent_layout() {
+        let authority = Pubkey::new_unique();
+        let agent_key = Pubkey::new_unique();
+        let ix = register_agent(&authority, &agent_key, [9u8; 32], scope());
+
+        assert_eq!(ix.program_id, PROGRAM_ID);
+        assert_eq!(ix.data[..8], discriminator("register_agent"));
+        assert_eq!(ix.data[8..40], agent_key.to_bytes());
+
+        assert_eq!(ix.accounts.len(), 4);
+        assert_eq!(ix.accounts[0], AccountMeta::new(authority, true));
+        assert_eq!(ix.accounts[1], AccountMeta::new(find_agent_identity(&agent_key).0, false));
+        assert_eq!(ix.accounts[2], AccountMeta::new_readonly(system_program::ID, false));
+        assert_eq!(ix.accounts[3], AccountMeta::new_readonly(find_config().0, false));
+    }
+
+    #[test]
+    fn sign_agreement_derives_party_from_identity() {
+        let agent_key = Pubkey::new_unique();
+        let id = [3u8; 16];
+        let authority = Pubkey::new_unique();
+        let freeze = FreezeScope { authority, parent: Pubkey::default() };
+        let ix = sign_agreement(&agent_key, freeze, id);
+        let identity = find_agent_identity(&agent_key).0;
+
+        assert_eq!(ix.data[..8], discriminator("sign_agreement"));
+        assert_eq!(ix.data[8..], id);
+        assert_eq!(ix.accounts[0], AccountMeta::new_readonly(agent_key, true));
+        assert_eq!(ix.accounts[1].pubkey, identity);
+        assert_eq!(ix.accounts[2], AccountMeta::new(find_agreement(&id).0, false));
+        assert_eq!(ix.accounts[3], AccountMeta::new(find_agreement_party(&id, &identity).0, false));
+        // Own, parent's and authority's freeze PDAs
+        assert_eq!(ix.accounts[4], AccountMeta::new_readonly(find_freeze(&identity).0, false));
+        assert_eq!(ix.accounts[5].pubkey, find_freeze(&Pubkey::default()).0);
+        assert_eq!(ix.accounts[6].pubkey, find_freeze(&authority).0);
+
+        // A direct party is covered by a freeze on its own key
+        let wallet = Pubkey::new_unique();
+        let direct = add_party_direct(&agent_key, freeze, id, &wallet, 1);
+        assert_eq!(direct.accounts[5].pubkey, find_freeze(&identity).0);
+        assert_eq!(direct.accounts[8], AccountMeta::new_readonly(find_freeze(&wallet).0, false));
+        // and so is its signature
+        let sign = sign_agreement_direct(&wallet, id);
+        assert_eq!(sign.accounts[3], AccountMeta::new_readonly(find_freeze(&wallet).0, false));
+    }
+
+    #[test]
+    fn rotation_keeps_identity_and_tracks_lookups() {
+        let (authority, registered, current, new) =
+            (Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique());
+        let identity = find_agent_identity(&registered).0;
+
+        let first = rotate_agent_key(&authority, &registered, &registered, &new, true);
+        assert_eq!(first.data[8..], new.to_bytes());
+        assert_eq!(first.accounts[1].pubkey, identity);
+        assert_eq!(first.accounts[2].pubkey, PROGRAM_ID);
+        assert_eq!(first.accounts[3], AccountMeta::new(find_agent_key_lookup(&new).0, false));
+        assert_eq!(first.accounts[4], AccountMeta::new_readonly(new, true));
+
+        let again = rotate_agent_key(&authority, &registered, &current, &new, false);
+        assert_eq!(again.accounts[1].pubkey, identity);
+        assert_eq!(again.accounts[2], AccountMeta::new(find_agent_key_lookup(&current).0, false));
+        assert_eq!(again.accounts[4].pubkey, PROGRAM_ID);
+
+        // The identity itself is at the registered key's address, so going back needs no lookup
+        let back = rotate_agent_key(&authority, &registered, &current, &registered, false);
+        assert_eq!(back.accounts[2], AccountMeta::new(find_agent_key_lookup(&current).0, false));
+        assert_eq!(back.accounts[3].pubkey, PROGRAM_ID);
+
+        let revoke = revoke_rotated_agent(&authority, &registered, &current);
+        assert_eq!(revoke.accounts[1].pubkey, identity);
+        assert_eq!(revoke.accounts[2].pubkey, find_agent_key_lookup(&current).0);
+    }
+
+    #[test]
+    fn accept_transfer_appends_sub_agent_pairs() {
+        let (new_authority, previous, agent_key) =
+            (Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique());
+        let subs = [Pubkey::new_unique(), Pubkey::new_unique()];
+        let identity = find_agent_identity(&agent_key).0;
+
+        let ix = accept_authority_transfer(&new_authority, &previous, &agent_key, &subs);
+        assert_eq!(ix.accounts[0], AccountMeta::new_readonly(new_authority, true));
+        assert_eq!(ix.accounts[1], AccountMeta::new(previous, false));
+        assert_eq!(ix.accounts[3], AccountMeta::new(find_vault(&identity).0, false));
+        assert_eq!(ix.accounts[4], AccountMeta::new(find_authority_transfer(&identity).0, false));
+
+        assert_eq!(ix.accounts.len(), 6 + 2 * subs.len());
+        for (pair, sub) in ix.accounts[6..].chunks(2).zip(&subs) {
+            let sub_identity = find_agent_identity(sub).0;
+            assert_eq!(pair[0], AccountMeta::new(sub_identity, false));
+            assert_eq!(pair[1], AccountMeta::new(find_vault(&sub_identity).0, false));
+        }
+    }
+
+    #[test]
+    fn authority_actions_wrap_and_unwrap_the_inner_instruction() {
+        let (member, agent_key) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let set_id = [9u8; 16];
+        let set = find_authority_set(&set_id).0;
+        let signer = find_authority_signer(&set).0;
+        let inner = update_delegation(&signer, &agent_key, scope());
+
+        let propose = propose_authority_action(&member, set_id, 3, &inner);
+        assert_eq!(propose.accounts[2].pubkey, find_authority_action(&set, 3).0);
+        let args = instruction::ProposeAuthorityAction::deserialize(&mut &propose.data[8..]).unwrap();
+        assert_eq!(args.data, inner.data);
+        assert_eq!(args.accounts[0], ActionAccount { pubkey: signer, is_signer: true, is_writable: false });
+
+        // The signer PDA is signed for by the program, not the transaction
+        let execute = execute_authority_action(&member, &member, set_id, 3, &args.accounts);
+        assert_eq!(execute.accounts.len(), 4 + inner.accounts.len());
+        assert_eq!(execute.accounts[4], AccountMeta::new_readonly(signer, false));
+        assert_eq!(execute.accounts[5], inner.accounts[1]);
+    }
+
+    #[test]
+    fn propose_pays_the_treasury_and_admin_targets_config() {
+        let (agent_key, treasury, admin) =
+            (Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique());
+        let args = ProposeAgreementArgs {
+            agreement_id: [4u8; 16],
+            agreement_type: 1,
+            visibility: 0,
+            terms_hash: [0u8; 32],
+            terms_uri: [0u8; 64],
+            num_parties: 2,
+            expires_at: 0,
+        };
+        let ix = propose_agreement(&agent_key, FreezeScope::default(), &treasury, args);
+        let n = ix.accounts.len();
+        assert_eq!(ix.accounts[n - 2], AccountMeta::new_readonly(find_config().0, false));
+        assert_eq!(ix.accounts[n - 1], AccountMeta::new(treasury, false));
+
+        let init = initialize_config(&admin, ConfigParams { admin, max_parties: 4, fee_lamports: 0, treasury });
+        assert_eq!(init.accounts[1], AccountMeta::new(find_config().0, false));
+        assert_eq!(init.accounts[3].pubkey, find_program_data().0);
+        assert_eq!(set_paused(&admin, true).accounts[1], AccountMeta::new(find_config().0, false));
+    }
+
+    #[test]
+    fn amendments_chain_versions_and_append_reset_parties() {
+        let (agent_key, other) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let id = [5u8; 16];
+        let agreement = find_agreement(&id).0;
+        let args = |version| ProposeAmendmentArgs {
+            agreement_id: id,
+            version,
+            terms_hash: [1u8; 32],
+            terms_uri: [0u8; 64],
+            expires_at: 0,
+        };
+
+        let first = propose_amendment(&agent_key, FreezeScope::default(), args(1), &[other]);
+        assert_eq!(first.accounts[4].pubkey, PROGRAM_ID);
+        assert_eq!(first.accounts[5], AccountMeta::new(find_amendment(&agreement, 1).0, false));
+        assert_eq!(
+            first.accounts.last(),
+            Some(&AccountMeta::new(find_agreement_party(&id, &other).0, false))
+        );
+
+        let second = propose_amendment(&agent_key, FreezeScope::default(), args(2), &[]);
+        assert_eq!(second.accounts[4].pubkey, find_amendment(&agreement, 1).0);
+        assert_eq!(second.accounts[5].pubkey, find_amendment(&agreement, 2).0);
+        assert_ne!(find_amendment(&agreement, 2).0, find_amendment(&find_agreement(&[6u8; 16]).0, 2).0);
+    }
+
+    #[test]
+    fn counter_rounds_chain_per_party() {
+        let (proposer, party_key) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let id = [8u8; 16];
+        let party = find_agreement_party(&id, &find_agent_identity(&party_key).0).0;
+        let args = CounterProposeArgs {
+            agreement_id: id,
+            round: 2,
+            terms_hash: [1u8; 32],
+            terms_uri: [0u8; 64],
+            expires_at: 0,
+        };
+
+        let ix = counter_propose(&party_key, FreezeScope::default(), args);
+        assert_eq!(ix.accounts[0], AccountMeta::new(party_key, true));
+        assert_eq!(ix.accounts[4].pubkey, find_counter_proposal(&party, 1).0);
+        assert_eq!(ix.accounts[5], AccountMeta::new(find_counter_proposal(&party, 2).0, false));
+
+        let accept = accept_counter_proposal(&proposer, FreezeScope::default(), id, &party_key, 2, 1, &[]);
+        assert_eq!(accept.accounts[4], AccountMeta::new(find_counter_proposal(&party, 2).0, false));
+        assert_eq!(accept.accounts[5].pubkey, PROGRAM_ID);
+        let reject = reject_counter_proposal(&proposer, &proposer, id, &party_key, 2);
+        assert_eq!(reject.accounts[3].pubkey, find_counter_proposal(&party, 2).0);
+    }
+
+    #[test]
+    fn termination_lists_every_party_and_release_reads_it() {
+        let (agent_key, direct) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let id = [7u8; 16];
+        let identity = find_agent_identity(&agent_key).0;
+        let agreement = find_agreement(&id).0;
+
+        let propose = propose_termination(&agent_key, FreezeScope::default(), id, &[(identity, 60), (direct, 40)]);
+        let args = instruction::ProposeTermination::deserialize(&mut &propose.data[8..]).unwrap();
+        assert_eq!(args.amounts, vec![60, 40]);
+        assert_eq!(propose.accounts[4], AccountMeta::new(find_termination(&agreement).0, false));
+        let n = propose.accounts.len();
+        assert_eq!(propose.accounts[n - 2], AccountMeta::new_readonly(find_agreement_party(&id, &identity).0, false));
+        assert_eq!(propose.accounts[n - 1], AccountMeta::new_readonly(find_agreement_party(&id, &direct).0, false));
+
+        let vault = find_vault(&identity).0;
+        let refund = release_escrow(&direct, id, &identity, &vault, false);
+        assert_eq!(refund.accounts[2], AccountMeta::new(find_agreement_party(&id, &identity).0, false));
+        // Absent optional account is encoded as the program ID
+        assert_eq!(refund.accounts[3].pubkey, PROGRAM_ID);
+        assert_eq!(refund.accounts[4], AccountMeta::new(vault, false));
+
+        let settle = release_escrow(&direct, id, &direct, &direct, true);
+        assert_eq!(settle.accounts[3], AccountMeta::new(find_termination(&agreement).0, false));
+    }
+
+    #[test]
+    fn milestones_share_one_schedule_and_guard_fulfill() {
+        let (agent_key, payee) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let id = [8u8; 16];
+        let identity = find_agent_identity(&agent_key).0;
+        let schedule = find_milestones(&find_agreement(&id).0).0;
+
+        let terms = MilestoneTerms {
+            payer: find_agreement_party(&id, &identity).0,
+            payee: find_agreement_party(&id, &payee).0,
+            description_hash: [1u8; 32],
+            amount: 5,
+            due_at: 0,
+        };
+        let set = set_milestones(&agent_key, FreezeScope::default(), id, vec![terms, terms]);
+        assert_eq!(set.accounts[3], AccountMeta::new(schedule, false));
+        // Each party once, for the program to check it belongs to the agreement
+        assert_eq!(set.accounts.len(), 9 + 2);
+        assert_eq!(set.accounts[9], AccountMeta::new_readonly(terms.payer, false));
+        assert_eq!(set.accounts[10], AccountMeta::new_readonly(terms.payee, false));
+        let args = instruction::SetMilestones::deserialize(&mut &set.data[8..]).unwrap();
+        assert_eq!(args.milestones, vec![terms, terms]);
+
+        let approve = approve_milestone(&agent_key, FreezeScope::default(), id, 2);
+        assert_eq!(approve.data[8 + 16], 2);
+        assert_eq!(approve.accounts[4], AccountMeta::new(schedule, false));
+
+        let release = release_milestone(&payee, id, 2, &identity, &payee, &payee);
+        assert_eq!(release.accounts[3], AccountMeta::new(terms.payer, false));
+        assert_eq!(release.accounts[4], AccountMeta::new_readonly(terms.payee, false));
+
+        // The schedule PDA is passed so the program can refuse a manual fulfill
+        let fulfill = fulfill_agreement(&agent_key, &agent_key, id);
+        assert_eq!(fulfill.accounts[4], AccountMeta::new_readonly(schedule, false));
+        // and so it can refuse to remove a party the milestones name
+        let remove = remove_party(&agent_key, FreezeScope::default(), id, &payee);
+        assert_eq!(remove.accounts[7], AccountMeta::new_readonly(schedule, false));
+    }
+
+    #[test]
+    fn crank_pulls_from_the_payer_identity_vault() {
+        let (payer, payee) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let id = [9u8; 16];
+        let recurring = find_recurring_payment(&find_agreement(&id).0).0;
+
+        let ix = crank_payment(&payee, id, &payer, FreezeScope::default(), &payee, &payee);
+        assert_eq!(ix.accounts[2], AccountMeta::new(recurring, false));
+        assert_eq!(ix.accounts[3].pubkey, find_agreement_party(&id, &payer).0);
+        assert_eq!(ix.accounts[4].pubkey, payer);
+        assert_eq!(ix.accounts[5], AccountMeta::new(find_vault(&payer).0, false));
+        assert_eq!(ix.accounts[7], AccountMeta::new(payee, false));
+
+        let cancel = cancel_recurring_payment(&payee, &payee, id);
+        assert_eq!(cancel.accounts[3].pubkey, find_agreement_party(&id, &find_agent_identity(&payee).0).0);
+        assert_eq!(cancel.accounts[4], AccountMeta::new(recurring, false));
+    }
+
+    #[test]
+    fn stream_ends_pay_each_party_recipient() {
+        let (payer, payee) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let id = [9u8; 16];
+        let stream = find_stream(&find_agreement(&id).0).0;
+        let ends = StreamEnds {
+            payer,
+            payer_recipient: find_vault(&payer).0,
+            payee,
+            payee_recipient: payee,
+        };
+
+        let cancel = cancel_stream(&payee, &payee, id, ends);
+        assert_eq!(cancel.accounts[4], AccountMeta::new(stream, false));
+        assert_eq!(cancel.accounts[5].pubkey, find_agreement_party(&id, &payer).0);
+        assert_eq!(cancel.accounts[7], AccountMeta::new(find_vault(&payer).0, false));
+        assert_eq!(cancel.accounts[8], AccountMeta::new(payee, false));
+
+        let settle = settle_stream(&payee, id, ends, true);
+        assert_eq!(settle.accounts[3].pubkey, find_termination(&find_agreement(&id).0).0);
+        assert_eq!(close_agreement(&payer, &payer, id).accounts[4].pubkey, stream);
+        // A manual fulfill can't vest an active stream early
+        assert_eq!(fulfill_agreement(&payee, &payee, id).accounts[5].pubkey, stream);
+
+        // Every party, so the program can read when the agreement activated
+        let fund = fund_stream(&payer, FreezeScope::default(), id, &payee, 1, &[payer, payee]);
+        assert_eq!(fund.accounts.len(), 12 + 2);
+        assert_eq!(fund.accounts[13], AccountMeta::new_readonly(find_agreement_party(&id, &payee).0, false));
+    }
+
+    #[test]
+    fn distributions_append_one_group_per_split() {
+        let (identity, direct, tokens) = (Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique());
+        let id = [9u8; 16];
+        let agreement = find_agreement(&id).0;
+        let receiver = find_revenue_receiver(&agreement).0;
+
+        // The split parties follow so the program can check they belong to the agreement
+        let party = find_agreement_party(&id, &identity).0;
+        let set = set_revenue_share(&direct, FreezeScope::default(), id, vec![RevenueSplit { party, bps: 10_000 }]);
+        assert_eq!(set.accounts.len(), 9 + 1);
+        assert_eq!(set.accounts[9], AccountMeta::new_readonly(party, false));
+
+        let sol = distribute(&direct, id, &[(identity, find_vault(&identity).0), (direct, direct)]);
+        assert_eq!(sol.accounts[3], AccountMeta::new(receiver, false));
+        assert_eq!(sol.accounts.len(), 6 + 4);
+        assert_eq!(sol.accounts[6], AccountMeta::new_readonly(find_agreement_party(&id, &identity).0, false));
+        assert_eq!(sol.accounts[7], AccountMeta::new(find_vault(&identity).0, false));
+        assert_eq!(sol.accounts[9], AccountMeta::new(direct, false));
+
+        let spl = distribute_tokens(&direct, id, &tokens, &[(identity, tokens), (direct, tokens)]);
+        assert_eq!(spl.accounts[3], AccountMeta::new_readonly(receiver, false));
+        assert_eq!(spl.accounts[5].pubkey, anchor_spl::token::ID);
+        assert_eq!(spl.accounts.len(), 7 + 6);
+        assert_eq!(spl.accounts[8], AccountMeta::new_readonly(identity, false));
+    }
+
+    #[test]
+    fn safe_payouts_go_to_identity_vaults() {
+        let (company, investor) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let id = [9u8; 16];
+        let safe = find_safe(&find_agreement(&id).0).0;
+        let (company_identity, investor_identity) = (find_agent_identity(&company).0, find_agent_identity(&investor).0);
+
+        // Plain signatures carry the Safe PDA so the investor can be refused
+        assert_eq!(sign_agreement(&company, FreezeScope::default(), id).accounts[7].pubkey, safe);
+        assert_eq!(sign_agreement_direct(&company, id).accounts[4].pubkey, safe);
+
+        // Terms changes carry it so a funded investor keeps its signature
+        let counter = accept_counter_proposal(&company, FreezeScope::default(), id, &investor, 1, 1, &[]);
+        assert!(counter.accounts.iter().any(|meta| meta.pubkey == safe));
+
+        let terms = SafeTerms {
+            investor: find_agreement_party(&id, &investor_identity).0,
+            company: find_agreement_party(&id, &company_identity).0,
+            investment_amount: 1,
+            valuation_cap: 0,
+            discount_bps: 0,
+            mint: Pubkey::default(),
+        };
+        let set = set_safe(&company, FreezeScope::default(), id, terms, &investor_identity);
+        assert_eq!(set.accounts[4].pubkey, terms.investor);
+        assert_eq!(set.accounts[5].pubkey, investor_identity);
+        assert_eq!(set.accounts[6].pubkey, terms.company);
+
+        let sign = sign_safe(&investor, FreezeScope::default(), id);
+        assert_eq!(sign.accounts[4], AccountMeta::new(safe, false));
+        assert_eq!(sign.accounts[5], AccountMeta::new(find_vault(&investor_identity).0, false));
+
+        let convert = convert_safe(&company, &company, id, &investor_identity, 1);
+        assert_eq!(convert.accounts[5], AccountMeta::new(find_agreement_party(&id, &investor_identity).0, false));
+        assert_eq!(convert.accounts[6], AccountMeta::new(find_vault(&company_identity).0, false));
+
+        let refund = refund_safe(&investor, &investor, id, &investor_identity);
+        assert_eq!(refund.accounts[6], AccountMeta::new(find_vault(&investor_identity).0, false));
+    }
+
+    #[test]
+    fn migrate_agent_appends_light_accounts() {
+        let authority = Pubkey::new_unique();
+        let light = AccountMeta::new(Pubkey::new_unique(), false);
+
+        let root = migrate_agent(&authority, &Pubkey::new_unique(), None, vec![1, 2], vec![light.clone()]);
+        assert_eq!(root.data[..8], discriminator("migrate_agent"));
+        // Absent optional account is encoded as the program ID
+        assert_eq!(root.accounts[2].pubkey, PROGRAM_ID);
+        assert_eq!(root.accounts.last(), Some(&light));
+
+        let parent = Pubkey::new_unique();
+        let sub = migrate_agent(&authority, &Pubkey::new_unique(), Some(&parent), vec![], vec![]);
+        assert_eq!(sub.accounts[2].pubkey, find_tombstone(&find_agent_identity(&parent).0).0);
+
+        // A rotated identity is found by its registered key and closes its current lookup
+        let (registered, current) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let rotated = migrate_rotated_agent(&authority, &registered, &current, None, vec![], vec![]);
+        assert_eq!(rotated.accounts[1].pubkey, find_agent_identity(&registered).0);
+        assert_eq!(rotated.accounts[8], AccountMeta::new(find_agent_key_lookup(&current).0, false));
+        assert_eq!(root.accounts[8].pubkey, PROGRAM_ID);
+
+        // Agreements pass their extension PDAs so V1-only state isn't orphaned
+        let id = [5u8; 16];
+        let agreement = find_agreement(&id).0;
+        let ix = migrate_agreement(&authority, &parent, id, 2, vec![], vec![light.clone()]);
+        assert_eq!(ix.accounts[7].pubkey, find_termination(&agreement).0);
+        // and the latest amendment, so a pending one isn't left behind
+        assert_eq!(ix.accounts[13].pubkey, find_amendment(&agreement, 2).0);
+        assert_eq!(ix.accounts[14].pubkey, find_amendment(&agreement, 3).0);
+        assert_eq!(ix.accounts[15].pubkey, find_config().0);
+        assert_eq!(ix.accounts.last(), Some(&light));
+    }
+
+    #[test]
+    fn vault_withdrawals_name_the_destination_last() {
+        let (authority, destination) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let ix = withdraw_from_vault(&authority, &Pubkey::new_unique(), &destination, 1);
+        assert_eq!(ix.accounts[4].pubkey, find_config().0);
+        assert_eq!(ix.accounts.last(), Some(&AccountMeta::new(destination, false)));
+    }
+
+    #[test]
+    fn materialized_parties_need_the_marker_and_proposer() {
+        let (proposer, party) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let id = [6u8; 16];
+        let marker = find_decompression_marker(&find_agreement(&id).0).0;
+        let snapshot = PartySnapshot { agreement_id: id, agent_key: party, role: 1, signed: false, signed_at: 0 };
+
+        let ix = materialize_party(&proposer, &proposer, snapshot);
+        assert_eq!(ix.accounts[6], AccountMeta::new_readonly(find_agent_identity(&proposer).0, false));
+        assert_eq!(ix.accounts[7], AccountMeta::new_readonly(marker, false));
+    }
+
+    #[test]
+    fn every_instruction_has_its_discriminator() {
+        let k = Pubkey::new_unique();
+        let id = [1u8; 16];
+        let propose = ProposeAgreementArgs {
+            agreement_id: id,
+            agreement_type: 1,
+            visibility: 0,
+            terms_hash: [0u8; 32],
+            terms_uri: [0u8; 64],
+            num_parties: 2,
+            expires_at: 0,
+        };
+        let amendment = ProposeAmendmentArgs {
+            agreement_id: id,
+            version: 1,
+            terms_hash: [0u8; 32],
+            terms_uri: [0u8; 64],
+            expires_at: 0,
+        };
+        let counter = CounterProposeArgs {
+            agreement_id: id,
+            round: 1,
+            terms_hash: [0u8; 32],
+            terms_uri: [0u8; 64],
+            expires_at: 0,
+        };
+        let agreement_snapshot = AgreementSnapshot {
+            agreement_id: id,
+            agreement_type: 1,
+            status: 0,
+            visibility: 0,
+            proposer_agent_key: k,
+            terms_hash: [0u8; 32],
+            terms_uri: [0u8; 64],
+            num_parties: 2,
+            num_signed: 1,
+            parties_added: 1,
+            created_at: 0,
+            expires_at: 0,
+        };
+        let party_snapshot = PartySnapshot {
+            agreement_id: id,
+            agent_key: k,
+            role: 1,
+            signed: false,
+            signed_at: 0,
+        };
+        let config = ConfigParams {
+            admin: k,
+            max_parties: 8,
+            fee_lamports: 0,
+            treasury: k,
+        };
+        let recurring = RecurringTerms {
+            payer: k,
+            payee: k,
+            amount: 1,
+            period: 1,
+            num_periods: 1,
+            start_at: 0,
+        };
+        let safe = SafeTerms {
+            investor: k,
+            company: k,
+            investment_amount: 1,
+            valuation_cap: 0,
+            discount_bps: 0,
+            mint: k,
+        };
+        let ends = StreamEnds {
+            payer: k,
+            payer_recipient: k,
+            payee: k,
+            payee_recipient: k,
+        };
+        let cases = [
+            ("register_agent", register_agent(&k, &k, [0u8; 32], scope())),
+            ("update_delegation", update_delegation(&k, &k, scope())),
+            ("register_sub_agent", register_sub_agent(&k, &k, [0u8; 32], scope())),
+            ("revoke_agent", revoke_agent(&k, &k)),
+            ("rotate_agent_key", rotate_agent_key(&k, &k, &k, &k, false)),
+            ("propose_authority_transfer", propose_authority_transfer(&k, &k, &k)),
+            ("accept_authority_transfer", accept_authority_transfer(&k, &k, &k, &[])),
+            ("cancel_authority_transfer", cancel_authority_transfer(&k, &k)),
+            ("create_authority_set", create_authority_set(&k, id, &[k], 1)),
+            ("propose_authority_action", propose_authority_action(&k, id, 0, &revoke_agent(&k, &k))),
+            ("approve_authority_action", approve_authority_action(&k, id, 0)),
+            ("execute_authority_action", execute_authority_action(&k, &k, id, 0, &[])),
+            ("cancel_authority_action", cancel_authority_action(&k, id, 0)),
+            ("freeze_agent", freeze_agent(&k, &k)),
+            ("unfreeze_agent", unfreeze_agent(&k, &k)),
+            ("freeze_authority", freeze_authority(&k)),
+            ("unfreeze_authority", unfreeze_authority(&k)),
+            ("propose_agreement", propose_agreement(&k, FreezeScope::default(), &k, propose)),
+            ("add_party", add_party(&k, FreezeScope::default(), id, &k, 1)),
+            ("add_party_direct", add_party_direct(&k, FreezeScope::default(), id, &k, 1)),
+            ("remove_party", remove_party(&k, FreezeScope::default(), id, &k)),
+            ("set_num_parties", set_num_parties(&k, FreezeScope::default(), id, 3)),
+            ("sign_agreement", sign_agreement(&k, FreezeScope::default(), id)),
+            ("sign_agreement_direct", sign_agreement_direct(&k, id)),
+            ("cancel_agreement", cancel_agreement(&k, &k, id)),
+            ("decline_agreement", decline_agreement(&k, &k, id)),
+            ("decline_agreement_direct", decline_agreement_direct(&k, id)),
+            ("fulfill_agreement", fulfill_agreement(&k, &k, id)),
+            ("close_agreement", close_agreement(&k, &k, id)),
+            ("propose_amendment", propose_amendment(&k, FreezeScope::default(), amendment, &[])),
+            ("sign_amendment", sign_amendment(&k, FreezeScope::default(), id, 1)),
+            ("sign_amendment_direct", sign_amendment_direct(&k, id, 1)),
+            ("withdraw_amendment", withdraw_amendment(&k, &k, id, 1)),
+            ("counter_propose", counter_propose(&k, FreezeScope::default(), counter)),
+            ("accept_counter_proposal", accept_counter_proposal(&k, FreezeScope::default(), id, &k, 1, 1, &[])),
+            ("reject_counter_proposal", reject_counter_proposal(&k, &k, id, &k, 1)),
+            ("fund_escrow", fund_escrow(&k, FreezeScope::default(), id, 1)),
+            ("release_escrow", release_escrow(&k, id, &k, &k, false)),
+            ("propose_termination", propose_termination(&k, FreezeScope::default(), id, &[(k, 0)])),
+            ("approve_termination", approve_termination(&k, FreezeScope::default(), id)),
+            ("approve_termination_direct", approve_termination_direct(&k, id)),
+            ("cancel_termination", cancel_termination(&k, &k, id)),
+            ("set_milestones", set_milestones(&k, FreezeScope::default(), id, vec![])),
+            ("submit_milestone", submit_milestone(&k, FreezeScope::default(), id, 0)),
+            ("approve_milestone", approve_milestone(&k, FreezeScope::default(), id, 0)),
+            ("dispute_milestone", dispute_milestone(&k, FreezeScope::default(), id, 0)),
+            ("release_milestone", release_milestone(&k, id, 0, &k, &k, &k)),
+            ("set_recurring_payment", set_recurring_payment(&k, FreezeScope::default(), id, &k, recurring)),
+            ("crank_payment", crank_payment(&k, id, &k, FreezeScope::default(), &k, &k)),
+            ("cancel_recurring_payment", cancel_recurring_payment(&k, &k, id)),
+            ("fund_stream", fund_stream(&k, FreezeScope::default(), id, &k, 1, &[k])),
+            ("claim_vested", claim_vested(&k, id, &k, &k)),
+            ("cancel_stream", cancel_stream(&k, &k, id, ends)),
+            ("settle_stream", settle_stream(&k, id, ends, false)),
+            ("set_revenue_share", set_revenue_share(&k, FreezeScope::default(), id, vec![])),
+            ("distribute", distribute(&k, id, &[])),
+            ("distribute_tokens", distribute_tokens(&k, id, &k, &[])),
+            ("set_safe", set_safe(&k, FreezeScope::default(), id, safe, &k)),
+            ("sign_safe", sign_safe(&k, FreezeScope::default(), id)),
+            ("convert_safe", convert_safe(&k, &k, id, &k, 1)),
+            ("refund_safe", refund_safe(&k, &k, id, &k)),
+            ("deposit_to_vault", deposit_to_vault(&k, &k, 1)),
+            ("withdraw_from_vault", withdraw_from_vault(&k, &k, &k, 1)),
+            ("migrate_agent", migrate_agent(&k, &k, None, vec![], vec![])),
+            ("migrate_agreement", migrate_agreement(&k, &k, id, 0, vec![], vec![])),
+            ("migrate_party", migrate_party(&k, &k, id, vec![], vec![])),
+            ("materialize_agreement", materialize_agreement(&k, agreement_snapshot)),
+            ("materialize_party", materialize_party(&k, &k, party_snapshot)),
+            ("initialize_config", initialize_config(&k, config)),
+            ("update_config", update_config(&k, config)),
+            ("set_paused", set_paused(&k, true)),
+        ];
+        for (name, ix) in cases {
+            assert_eq!(ix.data[..8], discriminator(name), "{name}");
+        }
+    }
+}
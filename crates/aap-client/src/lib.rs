@@ -0,0 +1,33 @@
+//! Native Rust client for the Agent Agreement Protocol V1 program.
+//!
+//! - [`pda`] — PDA derivation (`["agent", agent_key]`, `["agreement", id]`, …)
+//! - [`instructions`] — one typed builder per `#[program]` entry, returning `Instruction`
+//! - [`accounts`] — decode raw account data into the program's state structs
+//! - [`client`] — async V1 flows over any [`rpc::AapRpc`] backend
+//! - `compressed` (feature `compressed`) — `aap-compressed` address derivation and,
+//!   with `compressed-indexer`, proof/packing builders for all ten V2 instructions
+//!
+//! Backends: `RpcClient` (feature `rpc`) for a validator/devnet, `BanksClient`
+//! (feature `banks`) for an in-process bank via `solana-program-test`.
+
+pub mod accounts;
+pub mod client;
+#[cfg(feature = "compressed")]
+pub mod compressed;
+pub mod error;
+pub mod instructions;
+pub mod pda;
+pub mod rpc;
+
+pub use agent_agreement_protocol::constants;
+pub use agent_agreement_protocol::migration::{AgreementSnapshot, PartySnapshot};
+pub use agent_agreement_protocol::state::{
+    ActionAccount, AgentIdentity, AgentKeyLookup, AgentVault, Agreement, AgreementAmendment,
+    AgreementParty, AuthorityAction, AuthoritySet, AuthorityTransfer, ConfigParams, CounterProposal,
+    Decline, DelegationScope, EscrowStream, Freeze, MigrationTombstone, Milestone, MilestoneSchedule,
+    MilestoneTerms, ProtocolConfig, RecurringPayment, RecurringTerms, RevenueShare, RevenueSplit, Safe,
+    SafeTerms, SettlementShare, Termination,
+};
+pub use agent_agreement_protocol::ID as PROGRAM_ID;
+pub use client::AapClient;
+pub use error::ClientError;
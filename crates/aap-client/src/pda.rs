@@ -0,0 +1,170 @@
+use agent_agreement_protocol::constants::{AAP_COMPRESSED_PROGRAM_ID, CONFIG_SEED, MIGRATION_SEED};
+use agent_agreement_protocol::ID;
+use anchor_lang::solana_program::bpf_loader_upgradeable;
+use solana_sdk::pubkey::Pubkey;
+
+/// AgentIdentity: `["agent", agent_key]`
+pub fn find_agent_identity(agent_key: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"agent", agent_key.as_ref()], &ID)
+}
+
+/// AgentKeyLookup for a key an identity was rotated to: `["agent", agent_key]`,
+/// the address a fresh identity for that key would have.
+pub fn find_agent_key_lookup(agent_key: &Pubkey) -> (Pubkey, u8) {
+    find_agent_identity(agent_key)
+}
+
+/// Agreement: `["agreement", agreement_id]`
+pub fn find_agreement(agreement_id: &[u8; 16]) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"agreement", agreement_id.as_ref()], &ID)
+}
+
+/// AgreementParty: `["party", agreement_id, agent_identity]`.
+/// For `add_party_direct` parties, `agent_identity` is the raw party pubkey.
+pub fn find_agreement_party(agreement_id: &[u8; 16], agent_identity: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[b"party", agreement_id.as_ref(), agent_identity.as_ref()],
+        &ID,
+    )
+}
+
+/// Decline record of an AgreementParty: `["decline", agreement_party]`
+pub fn find_decline(agreement_party: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"decline", agreement_party.as_ref()], &ID)
+}
+
+/// AgreementAmendment: `["amendment", agreement, version (u16 LE)]`
+pub fn find_amendment(agreement: &Pubkey, version: u16) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"amendment", agreement.as_ref(), &version.to_le_bytes()], &ID)
+}
+
+/// CounterProposal: `["counter", agreement_party, round (u16 LE)]`
+pub fn find_counter_proposal(agreement_party: &Pubkey, round: u16) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"counter", agreement_party.as_ref(), &round.to_le_bytes()], &ID)
+}
+
+/// Termination: `["termination", agreement]`
+pub fn find_termination(agreement: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"termination", agreement.as_ref()], &ID)
+}
+
+/// MilestoneSchedule: `["milestones", agreement]`
+pub fn find_milestones(agreement: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"milestones", agreement.as_ref()], &ID)
+}
+
+/// RecurringPayment: `["recurring", agreement]`
+pub fn find_recurring_payment(agreement: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"recurring", agreement.as_ref()], &ID)
+}
+
+/// EscrowStream: `["stream", agreement]`
+pub fn find_stream(agreement: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"stream", agreement.as_ref()], &ID)
+}
+
+/// RevenueShare: `["revenue_share", agreement]`
+pub fn find_revenue_share(agreement: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"revenue_share", agreement.as_ref()], &ID)
+}
+
+/// Revenue receiver (system-owned, no data): `["revenue", agreement]`
+pub fn find_revenue_receiver(agreement: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"revenue", agreement.as_ref()], &ID)
+}
+
+/// Safe: `["safe", agreement]`
+pub fn find_safe(agreement: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"safe", agreement.as_ref()], &ID)
+}
+
+/// AgentVault: `["vault", agent_identity]`
+pub fn find_vault(agent_identity: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"vault", agent_identity.as_ref()], &ID)
+}
+
+/// AuthorityTransfer: `["authority_transfer", agent_identity]`
+pub fn find_authority_transfer(agent_identity: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"authority_transfer", agent_identity.as_ref()], &ID)
+}
+
+/// AuthoritySet: `["authority_set", set_id]`
+pub fn find_authority_set(set_id: &[u8; 16]) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"authority_set", set_id.as_ref()], &ID)
+}
+
+/// Signer PDA an authority set acts as — the `authority` of identities it
+/// holds: `["authority_signer", authority_set]`
+pub fn find_authority_signer(authority_set: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"authority_signer", authority_set.as_ref()], &ID)
+}
+
+/// AuthorityAction: `["authority_action", authority_set, index (u64 LE)]`
+pub fn find_authority_action(authority_set: &Pubkey, index: u64) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[b"authority_action", authority_set.as_ref(), &index.to_le_bytes()],
+        &ID,
+    )
+}
+
+/// Freeze: `["freeze", target]`, target an AgentIdentity PDA or an authority key
+pub fn find_freeze(target: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"freeze", target.as_ref()], &ID)
+}
+
+/// ProtocolConfig singleton: `["config"]`
+pub fn find_config() -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[CONFIG_SEED], &ID)
+}
+
+/// The V1 program's ProgramData account, whose upgrade authority may
+/// `initialize_config`
+pub fn find_program_data() -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[ID.as_ref()], &bpf_loader_upgradeable::ID)
+}
+
+/// MigrationTombstone: `["tombstone", original_pda]`
+pub fn find_tombstone(original: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"tombstone", original.as_ref()], &ID)
+}
+
+/// DecompressionMarker of a materialized agreement: `["decompressed", agreement]`
+pub fn find_decompression_marker(agreement: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"decompressed", agreement.as_ref()], &ID)
+}
+
+/// V1 migration signer used for `migrate_*` CPIs: `["migration"]`
+pub fn find_migration_authority() -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[MIGRATION_SEED], &ID)
+}
+
+/// aap-compressed migration signer used for `materialize_*` CPIs: `["migration"]`
+pub fn find_compressed_migration_authority() -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[MIGRATION_SEED], &AAP_COMPRESSED_PROGRAM_ID)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn party_and_vault_are_keyed_by_identity_pda() {
+        let agent_key = Pubkey::new_unique();
+        let id = [7u8; 16];
+        let (identity, _) = find_agent_identity(&agent_key);
+
+        let (party, _) = find_agreement_party(&id, &identity);
+        let (expected, _) = Pubkey::find_program_address(
+            &[b"party", id.as_ref(), identity.as_ref()],
+            &ID,
+        );
+        assert_eq!(party, expected);
+        assert_ne!(party, find_agreement_party(&id, &agent_key).0);
+        assert_ne!(find_vault(&identity).0, find_vault(&agent_key).0);
+    }
+
+    #[test]
+    fn migration_authorities_differ_per_program() {
+        assert_ne!(find_migration_authority().0, find_compressed_migration_authority().0);
+    }
+}
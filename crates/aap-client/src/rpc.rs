@@ -0,0 +1,113 @@
+use solana_sdk::instruction::Instruction;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::{Keypair, Signature, Signer};
+use solana_sdk::transaction::Transaction;
+
+#[cfg(any(feature = "rpc", feature = "banks"))]
+use crate::error::ClientError;
+
+/// Minimal async backend the client needs: read an account, send a transaction.
+#[allow(async_fn_in_trait)]
+pub trait AapRpc {
+    /// Raw account data, or `None` if the account doesn't exist.
+    async fn get_account_data(
+        &self,
+        address: &Pubkey,
+    ) -> Result<Option<Vec<u8>>, crate::ClientError>;
+
+    /// Sign with `payer` (fee payer) plus `signers`, send and confirm.
+    async fn send(
+        &self,
+        instructions: &[Instruction],
+        payer: &Keypair,
+        signers: &[&Keypair],
+    ) -> Result<Signature, crate::ClientError>;
+}
+
+/// Build and sign a transaction: fee payer first, then every other distinct signer.
+pub fn sign_transaction(
+    instructions: &[Instruction],
+    payer: &Keypair,
+    signers: &[&Keypair],
+    blockhash: solana_sdk::hash::Hash,
+) -> Transaction {
+    let mut all = vec![payer];
+    for signer in signers {
+        if !all.iter().any(|s| s.pubkey() == signer.pubkey()) {
+            all.push(signer);
+        }
+    }
+    Transaction::new_signed_with_payer(instructions, Some(&payer.pubkey()), &all, blockhash)
+}
+
+#[cfg(any(feature = "rpc", feature = "banks"))]
+fn rpc_err(e: impl std::fmt::Display) -> ClientError {
+    ClientError::Rpc(e.to_string())
+}
+
+#[cfg(feature = "rpc")]
+impl AapRpc for solana_rpc_client::nonblocking::rpc_client::RpcClient {
+    async fn get_account_data(&self, address: &Pubkey) -> Result<Option<Vec<u8>>, ClientError> {
+        let response = self
+            .get_account_with_commitment(address, self.commitment())
+            .await
+            .map_err(rpc_err)?;
+        Ok(response.value.map(|account| account.data))
+    }
+
+    async fn send(
+        &self,
+        instructions: &[Instruction],
+        payer: &Keypair,
+        signers: &[&Keypair],
+    ) -> Result<Signature, ClientError> {
+        let blockhash = self.get_latest_blockhash().await.map_err(rpc_err)?;
+        let tx = sign_transaction(instructions, payer, signers, blockhash);
+        self.send_and_confirm_transaction(&tx).await.map_err(rpc_err)
+    }
+}
+
+#[cfg(feature = "banks")]
+impl AapRpc for solana_banks_client::BanksClient {
+    async fn get_account_data(&self, address: &Pubkey) -> Result<Option<Vec<u8>>, ClientError> {
+        let account = self.get_account(*address).await.map_err(rpc_err)?;
+        Ok(account.map(|account| account.data))
+    }
+
+    async fn send(
+        &self,
+        instructions: &[Instruction],
+        payer: &Keypair,
+        signers: &[&Keypair],
+    ) -> Result<Signature, ClientError> {
+        let blockhash = self.get_latest_blockhash().await.map_err(rpc_err)?;
+        let tx = sign_transaction(instructions, payer, signers, blockhash);
+        let signature = tx.signatures[0];
+        self.process_transaction(tx).await.map_err(rpc_err)?;
+        Ok(signature)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use solana_sdk::hash::Hash;
+
+    #[test]
+    fn payer_is_not_signed_twice() {
+        let payer = Keypair::new();
+        let other = Keypair::new();
+        let ix = Instruction::new_with_bytes(
+            Pubkey::new_unique(),
+            &[],
+            vec![
+                solana_sdk::instruction::AccountMeta::new(payer.pubkey(), true),
+                solana_sdk::instruction::AccountMeta::new_readonly(other.pubkey(), true),
+            ],
+        );
+        let tx = sign_transaction(&[ix], &payer, &[&payer, &other], Hash::default());
+        assert_eq!(tx.signatures.len(), 2);
+        assert_eq!(tx.message.account_keys[0], payer.pubkey());
+        tx.verify().unwrap();
+    }
+}
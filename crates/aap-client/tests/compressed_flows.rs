@@ -0,0 +1,147 @@
+#![cfg(all(feature = "test-sbf", feature = "compressed-indexer"))]
+
+//! aap-compressed flows through `CompressedBuilder` against `LightProgramTest`.
+//! Requires `anchor build` first so `target/deploy/aap_compressed.so` exists.
+
+use aap_client::compressed::indexer::CompressedBuilder;
+use aap_client::compressed::{CompressedDelegationScope, COMPRESSED_PROGRAM_ID};
+use aap_client::constants::*;
+use aap_client::instructions::ProposeAgreementArgs;
+use aap_client::pda::find_config;
+use aap_client::{ProtocolConfig, PROGRAM_ID};
+use anchor_lang::AccountSerialize;
+use light_program_test::program_test::{LightProgramTest, TestRpc};
+use light_program_test::{ProgramTestConfig, Rpc};
+use solana_sdk::account::Account;
+use solana_sdk::instruction::Instruction;
+use solana_sdk::native_token::LAMPORTS_PER_SOL;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::{Keypair, Signer};
+
+fn scope() -> CompressedDelegationScope {
+    CompressedDelegationScope {
+        can_sign_agreements: true,
+        can_commit_funds: false,
+        max_commit_lamports: 0,
+        expires_at: 0,
+    }
+}
+
+fn builder(rpc: &LightProgramTest) -> CompressedBuilder<'_, LightProgramTest> {
+    CompressedBuilder::new(
+        rpc,
+        rpc.get_address_tree_v2(),
+        rpc.get_random_state_tree_info().unwrap(),
+    )
+}
+
+/// Stand-in for V1 `initialize_config`, which aap-compressed reads. `treasury`
+/// doubles as admin; no fee.
+fn install_protocol_config(rpc: &mut LightProgramTest, treasury: &Pubkey) {
+    let config = ProtocolConfig {
+        admin: *treasury,
+        paused: false,
+        max_parties: MAX_PARTIES,
+        fee_lamports: 0,
+        treasury: *treasury,
+        bump: find_config().1,
+    };
+    let mut data = Vec::new();
+    config.try_serialize(&mut data).unwrap();
+    rpc.set_account(
+        find_config().0,
+        Account { lamports: LAMPORTS_PER_SOL, data, owner: PROGRAM_ID, executable: false, rent_epoch: 0 },
+    );
+}
+
+async fn send(rpc: &mut LightProgramTest, ix: Instruction, payer: &Keypair, signers: &[&Keypair]) {
+    let mut all = vec![payer];
+    all.extend_from_slice(signers);
+    rpc.create_and_send_transaction(&[ix], &payer.pubkey(), &all)
+        .await
+        .unwrap();
+}
+
+// ============================================================
+// Agreement lifecycle
+// ============================================================
+
+#[tokio::test]
+async fn test_compressed_agreement_lifecycle() {
+    let config = ProgramTestConfig::new(true, Some(vec![("aap_compressed", COMPRESSED_PROGRAM_ID)]));
+    let mut rpc = LightProgramTest::new(config).await.unwrap();
+    let human = rpc.get_payer().insecure_clone();
+    install_protocol_config(&mut rpc, &human.pubkey());
+    let counterparty = Keypair::new();
+    rpc.airdrop_lamports(&counterparty.pubkey(), 1_000_000_000)
+        .await
+        .unwrap();
+
+    // Human signer mode for the proposer, delegated agent for the counterparty
+    let ix = builder(&rpc)
+        .register_agent(&human.pubkey(), &human.pubkey(), [1u8; 32], scope())
+        .await
+        .unwrap();
+    send(&mut rpc, ix, &human, &[]).await;
+    let ix = builder(&rpc)
+        .register_agent(&human.pubkey(), &counterparty.pubkey(), [2u8; 32], scope())
+        .await
+        .unwrap();
+    send(&mut rpc, ix, &human, &[]).await;
+
+    let id = [11u8; 16];
+    let ix = builder(&rpc)
+        .propose_agreement(
+            &human.pubkey(),
+            &human.pubkey(),
+            &human.pubkey(),
+            ProposeAgreementArgs {
+                agreement_id: id,
+                agreement_type: AGREEMENT_TYPE_SERVICE,
+                visibility: VISIBILITY_PUBLIC,
+                terms_hash: [3u8; 32],
+                terms_uri: [0u8; 64],
+                num_parties: 2,
+                expires_at: 0,
+            },
+        )
+        .await
+        .unwrap();
+    send(&mut rpc, ix, &human, &[]).await;
+
+    let ix = builder(&rpc)
+        .add_party(&human.pubkey(), &human.pubkey(), id, &counterparty.pubkey(), ROLE_COUNTERPARTY)
+        .await
+        .unwrap();
+    send(&mut rpc, ix, &human, &[]).await;
+
+    let ix = builder(&rpc)
+        .sign_agreement(&counterparty.pubkey(), &counterparty.pubkey(), id)
+        .await
+        .unwrap();
+    send(&mut rpc, ix, &human, &[&counterparty]).await;
+    let agreement = builder(&rpc).get_agreement(&id).await.unwrap();
+    assert_eq!(agreement.status, STATUS_ACTIVE);
+    assert_eq!(agreement.num_signed, 2);
+
+    let ix = builder(&rpc)
+        .fulfill_agreement(&human.pubkey(), &human.pubkey(), id)
+        .await
+        .unwrap();
+    send(&mut rpc, ix, &human, &[]).await;
+    assert_eq!(builder(&rpc).get_agreement(&id).await.unwrap().status, STATUS_FULFILLED);
+
+    let ix = builder(&rpc)
+        .close_agreement(&human.pubkey(), &human.pubkey(), id)
+        .await
+        .unwrap();
+    send(&mut rpc, ix, &human, &[]).await;
+    assert!(builder(&rpc).get_agreement(&id).await.is_err());
+
+    let ix = builder(&rpc)
+        .revoke_agent(&human.pubkey(), &counterparty.pubkey())
+        .await
+        .unwrap();
+    send(&mut rpc, ix, &human, &[]).await;
+    assert!(builder(&rpc).get_agent_identity(&counterparty.pubkey()).await.is_err());
+}
@@ -0,0 +1,1071 @@
+#![cfg(feature = "test-sbf")]
+
+//! V1 flows against an in-process bank. Requires `anchor build` first so
+//! `target/deploy/agent_agreement_protocol.so` exists.
+
+use aap_client::constants::*;
+use aap_client::instructions::{
+    self, CounterProposeArgs, ProposeAgreementArgs, ProposeAmendmentArgs, StreamEnds,
+};
+use aap_client::pda::{
+    find_agent_identity, find_agreement, find_agreement_party, find_authority_set, find_authority_signer,
+    find_config, find_revenue_receiver, find_vault,
+};
+use aap_client::{
+    AapClient, ConfigParams, DelegationScope, MilestoneTerms, ProtocolConfig, RecurringTerms, RevenueSplit,
+    SafeTerms, PROGRAM_ID,
+};
+use anchor_lang::AccountSerialize;
+use solana_banks_client::BanksClient;
+use solana_program_test::ProgramTest;
+use solana_sdk::account::Account;
+use solana_sdk::native_token::LAMPORTS_PER_SOL;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::{Keypair, Signer};
+#[allow(deprecated)]
+use solana_sdk::system_instruction;
+
+use aap_client::rpc::AapRpc;
+
+async fn setup() -> (AapClient<BanksClient>, Keypair) {
+    let (client, payer, _) = setup_with_admin().await;
+    (client, payer)
+}
+
+/// Also returns the `ProtocolConfig` admin, which starts as its treasury too.
+async fn setup_with_admin() -> (AapClient<BanksClient>, Keypair, Keypair) {
+    let mut program_test = ProgramTest::new("agent_agreement_protocol", PROGRAM_ID, None);
+    program_test.prefer_bpf(true);
+
+    // Genesis programs have no upgrade authority to run `initialize_config`,
+    // so install the account it would create.
+    let admin = Keypair::new();
+    let (config, bump) = find_config();
+    let mut data = Vec::new();
+    ProtocolConfig {
+        admin: admin.pubkey(),
+        paused: false,
+        max_parties: MAX_PARTIES,
+        fee_lamports: 0,
+        treasury: admin.pubkey(),
+        bump,
+    }
+    .try_serialize(&mut data)
+    .unwrap();
+    program_test.add_account(
+        config,
+        Account { lamports: LAMPORTS_PER_SOL, data, owner: PROGRAM_ID, ..Account::default() },
+    );
+    program_test.add_account(
+        admin.pubkey(),
+        Account { lamports: LAMPORTS_PER_SOL, ..Account::default() },
+    );
+
+    let (banks, payer, _) = program_test.start().await;
+    (AapClient::new(banks), payer, admin)
+}
+
+fn scope() -> DelegationScope {
+    DelegationScope {
+        can_sign_agreements: true,
+        can_commit_funds: false,
+        max_commit_lamports: 0,
+        expires_at: 0,
+    }
+}
+
+async fn fund(client: &AapClient<BanksClient>, payer: &Keypair, to: &Keypair) {
+    let ix = system_instruction::transfer(&payer.pubkey(), &to.pubkey(), LAMPORTS_PER_SOL);
+    client.rpc().send(&[ix], payer, &[]).await.unwrap();
+}
+
+// ============================================================
+// Agreement lifecycle
+// ============================================================
+
+#[tokio::test]
+async fn test_agreement_lifecycle() {
+    let (client, human) = setup().await;
+    let counterparty = Keypair::new();
+    fund(&client, &human, &counterparty).await;
+
+    // Human signer mode for the proposer, delegated agent for the counterparty
+    client.register_agent(&human, &human.pubkey(), [1u8; 32], scope()).await.unwrap();
+    client.register_agent(&human, &counterparty.pubkey(), [2u8; 32], scope()).await.unwrap();
+
+    let agreement_id = [42u8; 16];
+    client
+        .propose_with_parties(
+            &human,
+            ProposeAgreementArgs {
+                agreement_id,
+                agreement_type: AGREEMENT_TYPE_SERVICE,
+                visibility: VISIBILITY_PUBLIC,
+                terms_hash: [3u8; 32],
+                terms_uri: [0u8; 64],
+                num_parties: 2,
+                expires_at: 0,
+            },
+            &[(counterparty.pubkey(), ROLE_COUNTERPARTY)],
+        )
+        .await
+        .unwrap();
+
+    let agreement = client.get_agreement(&agreement_id).await.unwrap().unwrap();
+    assert_eq!(agreement.status, STATUS_PROPOSED);
+    assert_eq!(agreement.parties_added, 2);
+
+    client.sign_agreement(&counterparty, agreement_id).await.unwrap();
+    let agreement = client.get_agreement(&agreement_id).await.unwrap().unwrap();
+    assert_eq!(agreement.status, STATUS_ACTIVE);
+    let party = client
+        .get_agreement_party(&agreement_id, &counterparty.pubkey())
+        .await
+        .unwrap()
+        .unwrap();
+    assert!(party.signed);
+
+    client.fulfill_agreement(&human, &human.pubkey(), agreement_id).await.unwrap();
+    client.close_agreement(&human, &human.pubkey(), agreement_id).await.unwrap();
+    assert!(client.get_agreement(&agreement_id).await.unwrap().is_none());
+}
+
+#[tokio::test]
+async fn test_remove_party_and_resize_before_activation() {
+    let (client, human) = setup().await;
+    let (a, typo, b) = (Keypair::new(), Keypair::new(), Keypair::new());
+    fund(&client, &human, &a).await;
+    for (key, seed) in [(human.pubkey(), 1u8), (a.pubkey(), 2), (typo.pubkey(), 3), (b.pubkey(), 4)] {
+        client.register_agent(&human, &key, [seed; 32], scope()).await.unwrap();
+    }
+
+    let agreement_id = [46u8; 16];
+    client
+        .propose_with_parties(
+            &human,
+            ProposeAgreementArgs {
+                agreement_id,
+                agreement_type: AGREEMENT_TYPE_SERVICE,
+                visibility: VISIBILITY_PUBLIC,
+                terms_hash: [3u8; 32],
+                terms_uri: [0u8; 64],
+                num_parties: 3,
+                expires_at: 0,
+            },
+            &[(a.pubkey(), ROLE_COUNTERPARTY), (typo.pubkey(), ROLE_COUNTERPARTY)],
+        )
+        .await
+        .unwrap();
+    client.sign_agreement(&a, agreement_id).await.unwrap();
+
+    // Signed parties and the proposer stay
+    let identity = |key: &Keypair| find_agent_identity(&key.pubkey()).0;
+    assert!(client.remove_party(&human, agreement_id, &identity(&a)).await.is_err());
+    assert!(client.remove_party(&human, agreement_id, &identity(&human)).await.is_err());
+    assert!(client.set_num_parties(&human, agreement_id, 2).await.is_err());
+
+    client.remove_party(&human, agreement_id, &identity(&typo)).await.unwrap();
+    assert!(client.get_agreement_party(&agreement_id, &typo.pubkey()).await.unwrap().is_none());
+    let agreement = client.get_agreement(&agreement_id).await.unwrap().unwrap();
+    assert_eq!(agreement.parties_added, 2);
+
+    // The freed slot takes the right party; more slots can be opened too
+    client.add_party(&human, agreement_id, &b.pubkey(), ROLE_COUNTERPARTY).await.unwrap();
+    assert!(client.set_num_parties(&human, agreement_id, MAX_PARTIES + 1).await.is_err());
+    client.set_num_parties(&human, agreement_id, 4).await.unwrap();
+    client.remove_party(&human, agreement_id, &identity(&b)).await.unwrap();
+
+    // Shrinking to the signed parties activates
+    client.set_num_parties(&human, agreement_id, 2).await.unwrap();
+    let agreement = client.get_agreement(&agreement_id).await.unwrap().unwrap();
+    assert_eq!((agreement.num_parties, agreement.status), (2, STATUS_ACTIVE));
+}
+
+#[tokio::test]
+async fn test_decline_rejects_agreement() {
+    let (client, human) = setup().await;
+    let (a, b) = (Keypair::new(), Keypair::new());
+    fund(&client, &human, &a).await;
+    fund(&client, &human, &b).await;
+    for (key, seed) in [(human.pubkey(), 1u8), (a.pubkey(), 2), (b.pubkey(), 3)] {
+        client.register_agent(&human, &key, [seed; 32], scope()).await.unwrap();
+    }
+
+    let agreement_id = [45u8; 16];
+    client
+        .propose_with_parties(
+            &human,
+            ProposeAgreementArgs {
+                agreement_id,
+                agreement_type: AGREEMENT_TYPE_SERVICE,
+                visibility: VISIBILITY_PUBLIC,
+                terms_hash: [3u8; 32],
+                terms_uri: [0u8; 64],
+                num_parties: 3,
+                expires_at: 0,
+            },
+            &[(a.pubkey(), ROLE_COUNTERPARTY), (b.pubkey(), ROLE_COUNTERPARTY)],
+        )
+        .await
+        .unwrap();
+    client.sign_agreement(&b, agreement_id).await.unwrap();
+
+    // The proposer cancels instead, and a signed party is committed
+    assert!(client.decline_agreement(&human, &human.pubkey(), agreement_id).await.is_err());
+    assert!(client.decline_agreement(&b, &b.pubkey(), agreement_id).await.is_err());
+
+    client.decline_agreement(&a, &a.pubkey(), agreement_id).await.unwrap();
+    let agreement = client.get_agreement(&agreement_id).await.unwrap().unwrap();
+    assert_eq!(agreement.status, STATUS_REJECTED);
+    let identity = find_agent_identity(&a.pubkey()).0;
+    let decline = client.get_decline(&agreement_id, &identity).await.unwrap().unwrap();
+    assert_eq!(decline.party, find_agreement_party(&agreement_id, &identity).0);
+    assert!(decline.declined_at > 0);
+    assert!(client.sign_agreement(&a, agreement_id).await.is_err());
+
+    client.close_agreement(&human, &human.pubkey(), agreement_id).await.unwrap();
+    assert!(client.get_agreement(&agreement_id).await.unwrap().is_none());
+}
+
+#[tokio::test]
+async fn test_amendments_reset_signatures_then_need_every_party() {
+    let (client, human) = setup().await;
+    let (a, b) = (Keypair::new(), Keypair::new());
+    fund(&client, &human, &a).await;
+    fund(&client, &human, &b).await;
+    for (key, seed) in [(human.pubkey(), 1u8), (a.pubkey(), 2), (b.pubkey(), 3)] {
+        client.register_agent(&human, &key, [seed; 32], scope()).await.unwrap();
+    }
+
+    let agreement_id = [43u8; 16];
+    client
+        .propose_with_parties(
+            &human,
+            ProposeAgreementArgs {
+                agreement_id,
+                agreement_type: AGREEMENT_TYPE_SERVICE,
+                visibility: VISIBILITY_PUBLIC,
+                terms_hash: [3u8; 32],
+                terms_uri: [0u8; 64],
+                num_parties: 3,
+                expires_at: 0,
+            },
+            &[(a.pubkey(), ROLE_COUNTERPARTY), (b.pubkey(), ROLE_COUNTERPARTY)],
+        )
+        .await
+        .unwrap();
+    client.sign_agreement(&a, agreement_id).await.unwrap();
+
+    let amend = |version, terms: u8| ProposeAmendmentArgs {
+        agreement_id,
+        version,
+        terms_hash: [terms; 32],
+        terms_uri: [0u8; 64],
+        expires_at: 0,
+    };
+
+    // Proposed: applies at once, but only with every other signature reset
+    assert!(client.propose_amendment(&human, amend(1, 4), &[]).await.is_err());
+    let a_identity = find_agent_identity(&a.pubkey()).0;
+    client.propose_amendment(&human, amend(1, 4), &[a_identity]).await.unwrap();
+    let agreement = client.get_agreement(&agreement_id).await.unwrap().unwrap();
+    assert_eq!((agreement.terms_hash, agreement.num_signed), ([4u8; 32], 1));
+    let party = client.get_agreement_party(&agreement_id, &a.pubkey()).await.unwrap().unwrap();
+    assert!(!party.signed);
+    let first = client.get_amendment(&agreement_id, 1).await.unwrap().unwrap();
+    assert_eq!((first.status, first.previous_terms_hash), (AMENDMENT_APPLIED, [3u8; 32]));
+
+    client.sign_agreement(&a, agreement_id).await.unwrap();
+    client.sign_agreement(&b, agreement_id).await.unwrap();
+
+    // Active: pending until every party signs; versions can't be skipped
+    assert!(client.propose_amendment(&a, amend(2, 5), &[]).await.is_err());
+    assert!(client.propose_amendment(&human, amend(3, 5), &[]).await.is_err());
+    client.propose_amendment(&human, amend(2, 5), &[]).await.unwrap();
+    assert!(client.propose_amendment(&human, amend(3, 6), &[]).await.is_err());
+    client.sign_amendment(&a, agreement_id, 2).await.unwrap();
+    assert!(client.sign_amendment(&a, agreement_id, 2).await.is_err());
+    let agreement = client.get_agreement(&agreement_id).await.unwrap().unwrap();
+    assert_eq!(agreement.terms_hash, [4u8; 32]);
+
+    client.sign_amendment(&b, agreement_id, 2).await.unwrap();
+    let agreement = client.get_agreement(&agreement_id).await.unwrap().unwrap();
+    assert_eq!(agreement.terms_hash, [5u8; 32]);
+    assert_eq!(agreement.status, STATUS_ACTIVE);
+
+    // A withdrawn version still lets the next one follow
+    client.propose_amendment(&human, amend(3, 6), &[]).await.unwrap();
+    client.withdraw_amendment(&human, &human.pubkey(), agreement_id, 3).await.unwrap();
+    assert!(client.sign_amendment(&a, agreement_id, 3).await.is_err());
+    client.propose_amendment(&human, amend(4, 7), &[]).await.unwrap();
+    let third = client.get_amendment(&agreement_id, 3).await.unwrap().unwrap();
+    assert_eq!(third.status, AMENDMENT_WITHDRAWN);
+}
+
+#[tokio::test]
+async fn test_counter_proposals_are_rejected_or_become_amendments() {
+    let (client, human) = setup().await;
+    let (a, b) = (Keypair::new(), Keypair::new());
+    fund(&client, &human, &a).await;
+    fund(&client, &human, &b).await;
+    for (key, seed) in [(human.pubkey(), 1u8), (a.pubkey(), 2), (b.pubkey(), 3)] {
+        client.register_agent(&human, &key, [seed; 32], scope()).await.unwrap();
+    }
+
+    let agreement_id = [44u8; 16];
+    client
+        .propose_with_parties(
+            &human,
+            ProposeAgreementArgs {
+                agreement_id,
+                agreement_type: AGREEMENT_TYPE_SERVICE,
+                visibility: VISIBILITY_PUBLIC,
+                terms_hash: [3u8; 32],
+                terms_uri: [0u8; 64],
+                num_parties: 3,
+                expires_at: 0,
+            },
+            &[(a.pubkey(), ROLE_COUNTERPARTY), (b.pubkey(), ROLE_COUNTERPARTY)],
+        )
+        .await
+        .unwrap();
+    client.sign_agreement(&b, agreement_id).await.unwrap();
+
+    let counter = |round, terms: u8| CounterProposeArgs {
+        agreement_id,
+        round,
+        terms_hash: [terms; 32],
+        terms_uri: [0u8; 64],
+        expires_at: 0,
+    };
+
+    // Signed parties and the proposer can't counter; rounds wait for an answer
+    assert!(client.counter_propose(&b, counter(1, 4)).await.is_err());
+    assert!(client.counter_propose(&human, counter(1, 4)).await.is_err());
+    client.counter_propose(&a, counter(1, 4)).await.unwrap();
+    assert!(client.counter_propose(&a, counter(2, 5)).await.is_err());
+
+    client
+        .reject_counter_proposal(&human, &human.pubkey(), agreement_id, &a.pubkey(), 1)
+        .await
+        .unwrap();
+    let first = client.get_counter_proposal(&agreement_id, &a.pubkey(), 1).await.unwrap().unwrap();
+    assert_eq!(first.status, COUNTER_REJECTED);
+
+    // Accepting applies round 2 as amendment 1 and resets b's signature
+    client.counter_propose(&a, counter(2, 5)).await.unwrap();
+    let b_identity = find_agent_identity(&b.pubkey()).0;
+    client
+        .accept_counter_proposal(&human, agreement_id, &a.pubkey(), 2, 1, &[b_identity])
+        .await
+        .unwrap();
+    let agreement = client.get_agreement(&agreement_id).await.unwrap().unwrap();
+    assert_eq!((agreement.terms_hash, agreement.num_signed), ([5u8; 32], 1));
+    let second = client.get_counter_proposal(&agreement_id, &a.pubkey(), 2).await.unwrap().unwrap();
+    assert_eq!((second.status, second.amendment_version), (COUNTER_ACCEPTED, 1));
+    let amendment = client.get_amendment(&agreement_id, 1).await.unwrap().unwrap();
+    assert_eq!((amendment.status, amendment.previous_terms_hash), (AMENDMENT_APPLIED, [3u8; 32]));
+    // Only the proposer approved; the countering party still signs the new terms
+    let proposer_party = find_agreement_party(&agreement_id, &find_agent_identity(&human.pubkey()).0).0;
+    assert_eq!(amendment.approvals, vec![proposer_party]);
+    let party = client.get_agreement_party(&agreement_id, &a.pubkey()).await.unwrap().unwrap();
+    assert!(!party.signed);
+
+    // A counter against terms that have since changed can't be accepted
+    client.counter_propose(&a, counter(3, 6)).await.unwrap();
+    client
+        .propose_amendment(
+            &human,
+            ProposeAmendmentArgs {
+                agreement_id,
+                version: 2,
+                terms_hash: [7u8; 32],
+                terms_uri: [0u8; 64],
+                expires_at: 0,
+            },
+            &[],
+        )
+        .await
+        .unwrap();
+    assert!(client
+        .accept_counter_proposal(&human, agreement_id, &a.pubkey(), 3, 3, &[])
+        .await
+        .is_err());
+
+    client.sign_agreement(&a, agreement_id).await.unwrap();
+    client.sign_agreement(&b, agreement_id).await.unwrap();
+    let agreement = client.get_agreement(&agreement_id).await.unwrap().unwrap();
+    assert_eq!((agreement.status, agreement.terms_hash), (STATUS_ACTIVE, [7u8; 32]));
+}
+
+// ============================================================
+// Key rotation
+// ============================================================
+
+#[tokio::test]
+async fn test_termination_splits_escrow_by_settlement() {
+    let (client, human) = setup().await;
+    let agent = Keypair::new();
+    fund(&client, &human, &agent).await;
+    let commit = DelegationScope { can_commit_funds: true, max_commit_lamports: LAMPORTS_PER_SOL, ..scope() };
+    for (key, seed) in [(human.pubkey(), 1u8), (agent.pubkey(), 2)] {
+        client.register_agent(&human, &key, [seed; 32], commit).await.unwrap();
+        client.deposit_to_vault(&human, &key, LAMPORTS_PER_SOL).await.unwrap();
+    }
+    let (proposer, party) = (find_agent_identity(&human.pubkey()).0, find_agent_identity(&agent.pubkey()).0);
+
+    let agreement_id = [47u8; 16];
+    client
+        .propose_with_parties(
+            &human,
+            ProposeAgreementArgs {
+                agreement_id,
+                agreement_type: AGREEMENT_TYPE_SERVICE,
+                visibility: VISIBILITY_PUBLIC,
+                terms_hash: [3u8; 32],
+                terms_uri: [0u8; 64],
+                num_parties: 2,
+                expires_at: 0,
+            },
+            &[(agent.pubkey(), ROLE_COUNTERPARTY)],
+        )
+        .await
+        .unwrap();
+
+    // Each side bonds 0.2 SOL from its vault, within max_commit_lamports
+    let bond = LAMPORTS_PER_SOL / 5;
+    client.fund_escrow(&human, agreement_id, bond).await.unwrap();
+    client.fund_escrow(&agent, agreement_id, bond).await.unwrap();
+    assert!(client.fund_escrow(&agent, agreement_id, LAMPORTS_PER_SOL).await.is_err());
+    client.sign_agreement(&agent, agreement_id).await.unwrap();
+
+    // The settlement must cover exactly what is held
+    let settlement = [(proposer, bond + bond / 2), (party, bond / 2)];
+    assert!(client.propose_termination(&human, agreement_id, &[(proposer, bond), (party, bond / 2)]).await.is_err());
+    client.propose_termination(&human, agreement_id, &settlement).await.unwrap();
+    assert!(client.approve_termination(&human, agreement_id).await.is_err());
+
+    // Funding while it is pending leaves the split short, so it can't apply
+    client.fund_escrow(&agent, agreement_id, bond / 2).await.unwrap();
+    assert!(client.approve_termination(&agent, agreement_id).await.is_err());
+    client.cancel_termination(&human, &human.pubkey(), agreement_id).await.unwrap();
+    let settlement = [(proposer, bond + bond / 2), (party, bond)];
+    client.propose_termination(&human, agreement_id, &settlement).await.unwrap();
+
+    client.approve_termination(&agent, agreement_id).await.unwrap();
+    let agreement = client.get_agreement(&agreement_id).await.unwrap().unwrap();
+    assert_eq!(agreement.status, STATUS_TERMINATED);
+    assert_eq!(agreement.escrow_total, 2 * bond + bond / 2);
+
+    // Anyone releases, but only into each party's own vault and only once
+    let vault = find_vault(&party).0;
+    let before = client.rpc().get_balance(vault).await.unwrap();
+    assert!(client.release_escrow(&human, agreement_id, &party, &find_vault(&proposer).0, true).await.is_err());
+    client.release_escrow(&human, agreement_id, &party, &vault, true).await.unwrap();
+    assert_eq!(client.rpc().get_balance(vault).await.unwrap(), before + bond);
+    assert!(client.release_escrow(&human, agreement_id, &party, &vault, true).await.is_err());
+    client.release_escrow(&agent, agreement_id, &proposer, &find_vault(&proposer).0, true).await.unwrap();
+
+    let termination = client.get_termination(&agreement_id).await.unwrap().unwrap();
+    assert!(termination.shares.iter().all(|share| share.released));
+    client.close_agreement(&human, &human.pubkey(), agreement_id).await.unwrap();
+}
+
+#[tokio::test]
+async fn test_milestones_release_escrow_and_fulfill() {
+    let (client, human) = setup().await;
+    let agent = Keypair::new();
+    fund(&client, &human, &agent).await;
+    let commit = DelegationScope { can_commit_funds: true, max_commit_lamports: LAMPORTS_PER_SOL, ..scope() };
+    for (key, seed) in [(human.pubkey(), 1u8), (agent.pubkey(), 2)] {
+        client.register_agent(&human, &key, [seed; 32], commit).await.unwrap();
+    }
+    client.deposit_to_vault(&human, &human.pubkey(), LAMPORTS_PER_SOL).await.unwrap();
+    // Releases are paid into the payee's vault, so it must exist
+    client.deposit_to_vault(&human, &agent.pubkey(), LAMPORTS_PER_SOL / 100).await.unwrap();
+    let (payer, payee) = (find_agent_identity(&human.pubkey()).0, find_agent_identity(&agent.pubkey()).0);
+
+    let agreement_id = [48u8; 16];
+    client
+        .propose_with_parties(
+            &human,
+            ProposeAgreementArgs {
+                agreement_id,
+                agreement_type: AGREEMENT_TYPE_SERVICE,
+                visibility: VISIBILITY_PUBLIC,
+                terms_hash: [3u8; 32],
+                terms_uri: [0u8; 64],
+                num_parties: 2,
+                expires_at: 0,
+            },
+            &[(agent.pubkey(), ROLE_COUNTERPARTY)],
+        )
+        .await
+        .unwrap();
+
+    let step = LAMPORTS_PER_SOL / 10;
+    let milestone = |n: u8| MilestoneTerms {
+        payer: find_agreement_party(&agreement_id, &payer).0,
+        payee: find_agreement_party(&agreement_id, &payee).0,
+        description_hash: [n; 32],
+        amount: step,
+        due_at: 0,
+    };
+    client.set_milestones(&human, agreement_id, vec![milestone(1), milestone(2)]).await.unwrap();
+    client.fund_escrow(&human, agreement_id, 3 * step).await.unwrap();
+    client.sign_agreement(&agent, agreement_id).await.unwrap();
+
+    // A schedule is fulfilled by its milestones, not by hand
+    assert!(client.fulfill_agreement(&human, &human.pubkey(), agreement_id).await.is_err());
+
+    let vault = find_vault(&payee).0;
+    let before = client.rpc().get_balance(vault).await.unwrap();
+    client.submit_milestone(&agent, agreement_id, 0).await.unwrap();
+    assert!(client.approve_milestone(&agent, agreement_id, 0).await.is_err());
+    client.approve_milestone(&human, agreement_id, 0).await.unwrap();
+    client.release_milestone(&agent, agreement_id, 0, &payer, &payee, &vault).await.unwrap();
+    assert!(client.release_milestone(&agent, agreement_id, 0, &payer, &payee, &vault).await.is_err());
+
+    // Not submitted and no due date: nothing to dispute yet
+    assert!(client.dispute_milestone(&human, agreement_id, 1).await.is_err());
+    client.submit_milestone(&agent, agreement_id, 1).await.unwrap();
+    client.dispute_milestone(&human, agreement_id, 1).await.unwrap();
+    assert!(client.release_milestone(&agent, agreement_id, 1, &payer, &payee, &vault).await.is_err());
+    client.submit_milestone(&agent, agreement_id, 1).await.unwrap();
+    client.approve_milestone(&human, agreement_id, 1).await.unwrap();
+    client.release_milestone(&agent, agreement_id, 1, &payer, &payee, &vault).await.unwrap();
+
+    assert_eq!(client.rpc().get_balance(vault).await.unwrap(), before + 2 * step);
+    let agreement = client.get_agreement(&agreement_id).await.unwrap().unwrap();
+    assert_eq!(agreement.status, STATUS_FULFILLED);
+    assert_eq!(agreement.escrow_total, step);
+
+    // The unspent deposit goes back to the payer
+    client.release_escrow(&agent, agreement_id, &payer, &find_vault(&payer).0, false).await.unwrap();
+    client.close_agreement(&human, &human.pubkey(), agreement_id).await.unwrap();
+}
+
+#[tokio::test]
+async fn test_recurring_payment_pays_misses_and_cancels() {
+    let (client, human) = setup().await;
+    let agent = Keypair::new();
+    fund(&client, &human, &agent).await;
+    let commit = DelegationScope { can_commit_funds: true, max_commit_lamports: LAMPORTS_PER_SOL, ..scope() };
+    for (key, seed) in [(human.pubkey(), 1u8), (agent.pubkey(), 2)] {
+        client.register_agent(&human, &key, [seed; 32], commit).await.unwrap();
+    }
+    let (payer, payee) = (find_agent_identity(&human.pubkey()).0, find_agent_identity(&agent.pubkey()).0);
+
+    let agreement_id = [49u8; 16];
+    client
+        .propose_with_parties(
+            &human,
+            ProposeAgreementArgs {
+                agreement_id,
+                agreement_type: AGREEMENT_TYPE_SERVICE,
+                visibility: VISIBILITY_PUBLIC,
+                terms_hash: [3u8; 32],
+                terms_uri: [0u8; 64],
+                num_parties: 2,
+                expires_at: 0,
+            },
+            &[(agent.pubkey(), ROLE_COUNTERPARTY)],
+        )
+        .await
+        .unwrap();
+
+    // Period 0 lapsed long ago, period 1 is open now, period 2 is decades away
+    let amount = LAMPORTS_PER_SOL / 10;
+    let terms = RecurringTerms {
+        payer: find_agreement_party(&agreement_id, &payer).0,
+        payee: find_agreement_party(&agreement_id, &payee).0,
+        amount,
+        period: 1_000_000_000,
+        num_periods: 3,
+        start_at: 1,
+    };
+    // The whole schedule must fit the payer's max_commit_lamports
+    let over_cap = RecurringTerms { num_periods: 11, ..terms };
+    assert!(client.set_recurring_payment(&human, agreement_id, &payer, over_cap).await.is_err());
+    client.set_recurring_payment(&human, agreement_id, &payer, terms).await.unwrap();
+    client.sign_agreement(&agent, agreement_id).await.unwrap();
+
+    // A short vault misses the lapsed period but can still pay the open one
+    client.deposit_to_vault(&human, &human.pubkey(), amount / 2).await.unwrap();
+    client.deposit_to_vault(&human, &agent.pubkey(), amount / 2).await.unwrap();
+    let vault = find_vault(&payee).0;
+    client.crank_payment(&agent, agreement_id, &payer, &payee, &vault).await.unwrap();
+    assert!(client.crank_payment(&agent, agreement_id, &payer, &payee, &vault).await.is_err());
+    client.deposit_to_vault(&human, &human.pubkey(), LAMPORTS_PER_SOL).await.unwrap();
+    // Nothing is pulled from a frozen payer
+    client.freeze_agent(&human, &human.pubkey()).await.unwrap();
+    assert!(client.crank_payment(&agent, agreement_id, &payer, &payee, &vault).await.is_err());
+    client.unfreeze_agent(&human, &human.pubkey()).await.unwrap();
+    let before = client.rpc().get_balance(vault).await.unwrap();
+    client.crank_payment(&agent, agreement_id, &payer, &payee, &vault).await.unwrap();
+    assert_eq!(client.rpc().get_balance(vault).await.unwrap(), before + amount);
+    assert!(client.crank_payment(&agent, agreement_id, &payer, &payee, &vault).await.is_err());
+
+    let recurring = client.get_recurring_payment(&agreement_id).await.unwrap().unwrap();
+    assert_eq!((recurring.periods_paid, recurring.periods_missed), (1, 1));
+    assert_eq!(recurring.status, RECURRING_ACTIVE);
+
+    client.cancel_recurring_payment(&agent, &agent.pubkey(), agreement_id).await.unwrap();
+    let recurring = client.get_recurring_payment(&agreement_id).await.unwrap().unwrap();
+    assert_eq!(recurring.status, RECURRING_CANCELLED);
+    assert!(client.cancel_recurring_payment(&human, &human.pubkey(), agreement_id).await.is_err());
+}
+
+#[tokio::test]
+async fn test_stream_cancel_returns_unvested_escrow() {
+    let (client, human) = setup().await;
+    let agent = Keypair::new();
+    fund(&client, &human, &agent).await;
+    let commit = DelegationScope { can_commit_funds: true, max_commit_lamports: LAMPORTS_PER_SOL, ..scope() };
+    for (key, seed) in [(human.pubkey(), 1u8), (agent.pubkey(), 2)] {
+        client.register_agent(&human, &key, [seed; 32], commit).await.unwrap();
+    }
+    let (payer, payee) = (find_agent_identity(&human.pubkey()).0, find_agent_identity(&agent.pubkey()).0);
+
+    // Vests until 2096, so almost nothing has vested during the test
+    let agreement_id = [50u8; 16];
+    client
+        .propose_with_parties(
+            &human,
+            ProposeAgreementArgs {
+                agreement_id,
+                agreement_type: AGREEMENT_TYPE_SERVICE,
+                visibility: VISIBILITY_PUBLIC,
+                terms_hash: [3u8; 32],
+                terms_uri: [0u8; 64],
+                num_parties: 2,
+                expires_at: 4_000_000_000,
+            },
+            &[(agent.pubkey(), ROLE_COUNTERPARTY)],
+        )
+        .await
+        .unwrap();
+
+    let amount = LAMPORTS_PER_SOL / 10;
+    client.deposit_to_vault(&human, &human.pubkey(), LAMPORTS_PER_SOL / 2).await.unwrap();
+    client.deposit_to_vault(&human, &agent.pubkey(), LAMPORTS_PER_SOL / 100).await.unwrap();
+    // Streams start once the agreement is Active
+    let parties = [payer, payee];
+    assert!(client.fund_stream(&human, agreement_id, &payee, amount, &parties).await.is_err());
+    client.sign_agreement(&agent, agreement_id).await.unwrap();
+    assert!(client.fund_stream(&human, agreement_id, &payer, amount, &parties).await.is_err());
+    // Vesting starts at activation, so every party is needed
+    assert!(client.fund_stream(&human, agreement_id, &payee, amount, &[payer]).await.is_err());
+    client.fund_stream(&human, agreement_id, &payee, amount, &parties).await.unwrap();
+
+    let stream = client.get_stream(&agreement_id).await.unwrap().unwrap();
+    let signed = client.get_agreement_party(&agreement_id, &payee).await.unwrap().unwrap();
+    assert_eq!(stream.amount, amount);
+    assert_eq!((stream.start_at, stream.end_at), (signed.signed_at, 4_000_000_000));
+    assert_eq!(stream.status, STREAM_ACTIVE);
+    // A party can't fulfill and vest the whole stream to the payee early
+    assert!(client.fulfill_agreement(&agent, &agent.pubkey(), agreement_id).await.is_err());
+
+    let ends = StreamEnds {
+        payer,
+        payer_recipient: find_vault(&payer).0,
+        payee,
+        payee_recipient: find_vault(&payee).0,
+    };
+    let payer_before = client.rpc().get_balance(ends.payer_recipient).await.unwrap();
+    client.cancel_stream(&agent, &agent.pubkey(), agreement_id, ends).await.unwrap();
+
+    let stream = client.get_stream(&agreement_id).await.unwrap().unwrap();
+    assert_eq!(stream.status, STREAM_CANCELLED);
+    assert_eq!(stream.claimed + stream.refunded, amount);
+    assert_eq!(client.rpc().get_balance(ends.payer_recipient).await.unwrap(), payer_before + stream.refunded);
+    assert!(client.cancel_stream(&human, &human.pubkey(), agreement_id, ends).await.is_err());
+    assert!(client.claim_vested(&agent, agreement_id, &payee, &ends.payee_recipient).await.is_err());
+}
+
+#[tokio::test]
+async fn test_revenue_share_distributes_by_bps() {
+    let (client, human) = setup().await;
+    let agent = Keypair::new();
+    fund(&client, &human, &agent).await;
+    for (key, seed) in [(human.pubkey(), 1u8), (agent.pubkey(), 2)] {
+        client.register_agent(&human, &key, [seed; 32], scope()).await.unwrap();
+    }
+    let (first, second) = (find_agent_identity(&human.pubkey()).0, find_agent_identity(&agent.pubkey()).0);
+
+    let agreement_id = [51u8; 16];
+    client
+        .propose_with_parties(
+            &human,
+            ProposeAgreementArgs {
+                agreement_id,
+                agreement_type: AGREEMENT_TYPE_REVENUE_SHARE,
+                visibility: VISIBILITY_PUBLIC,
+                terms_hash: [3u8; 32],
+                terms_uri: [0u8; 64],
+                num_parties: 2,
+                expires_at: 0,
+            },
+            &[(agent.pubkey(), ROLE_COUNTERPARTY)],
+        )
+        .await
+        .unwrap();
+
+    let split = |identity: Pubkey, bps| RevenueSplit { party: find_agreement_party(&agreement_id, &identity).0, bps };
+    let uneven = vec![split(first, 7_000), split(second, 2_000)];
+    assert!(client.set_revenue_share(&human, agreement_id, uneven).await.is_err());
+    client
+        .set_revenue_share(&human, agreement_id, vec![split(first, 7_000), split(second, 3_000)])
+        .await
+        .unwrap();
+
+    // Anyone pays revenue straight to the receiver
+    let receiver = find_revenue_receiver(&find_agreement(&agreement_id).0).0;
+    let revenue = LAMPORTS_PER_SOL / 10;
+    let pay = system_instruction::transfer(&human.pubkey(), &receiver, revenue);
+    client.rpc().send(&[pay], &human, &[]).await.unwrap();
+
+    // Payouts go to the vaults, so they must exist
+    for key in [human.pubkey(), agent.pubkey()] {
+        client.deposit_to_vault(&human, &key, LAMPORTS_PER_SOL / 100).await.unwrap();
+    }
+    let payouts = [(first, find_vault(&first).0), (second, find_vault(&second).0)];
+    assert!(client.distribute(&agent, agreement_id, &payouts).await.is_err());
+    client.sign_agreement(&agent, agreement_id).await.unwrap();
+    assert!(client.distribute(&agent, agreement_id, &payouts[..1]).await.is_err());
+
+    let before = [
+        client.rpc().get_balance(payouts[0].1).await.unwrap(),
+        client.rpc().get_balance(payouts[1].1).await.unwrap(),
+    ];
+    client.distribute(&agent, agreement_id, &payouts).await.unwrap();
+    assert_eq!(client.rpc().get_balance(payouts[0].1).await.unwrap(), before[0] + revenue * 7 / 10);
+    assert_eq!(client.rpc().get_balance(payouts[1].1).await.unwrap(), before[1] + revenue * 3 / 10);
+    assert_eq!(client.rpc().get_balance(receiver).await.unwrap(), 0);
+
+    let revenue_share = client.get_revenue_share(&agreement_id).await.unwrap().unwrap();
+    assert_eq!((revenue_share.distributed_lamports, revenue_share.distributions), (revenue, 1));
+    // Nothing left to distribute
+    assert!(client.distribute(&agent, agreement_id, &payouts).await.is_err());
+}
+
+#[tokio::test]
+async fn test_safe_funds_escrow_at_signing_and_converts() {
+    let (client, human) = setup().await;
+    let agent = Keypair::new();
+    fund(&client, &human, &agent).await;
+    let commit = DelegationScope { can_commit_funds: true, max_commit_lamports: LAMPORTS_PER_SOL, ..scope() };
+    for (key, seed) in [(human.pubkey(), 1u8), (agent.pubkey(), 2)] {
+        client.register_agent(&human, &key, [seed; 32], commit).await.unwrap();
+    }
+    let (company, investor) = (find_agent_identity(&human.pubkey()).0, find_agent_identity(&agent.pubkey()).0);
+
+    let agreement_id = [52u8; 16];
+    client
+        .propose_with_parties(
+            &human,
+            ProposeAgreementArgs {
+                agreement_id,
+                agreement_type: AGREEMENT_TYPE_SAFE,
+                visibility: VISIBILITY_PUBLIC,
+                terms_hash: [3u8; 32],
+                terms_uri: [0u8; 64],
+                num_parties: 2,
+                expires_at: 0,
+            },
+            &[(agent.pubkey(), ROLE_COUNTERPARTY)],
+        )
+        .await
+        .unwrap();
+
+    let amount = LAMPORTS_PER_SOL / 10;
+    let terms = SafeTerms {
+        investor: find_agreement_party(&agreement_id, &investor).0,
+        company: find_agreement_party(&agreement_id, &company).0,
+        investment_amount: amount,
+        valuation_cap: 9_000_000,
+        discount_bps: 2_000,
+        mint: Pubkey::new_unique(),
+    };
+    client.set_safe(&human, agreement_id, terms).await.unwrap();
+
+    // The investor can only sign by funding the escrow
+    assert!(client.sign_agreement(&agent, agreement_id).await.is_err());
+    client.deposit_to_vault(&human, &agent.pubkey(), LAMPORTS_PER_SOL / 2).await.unwrap();
+    client.deposit_to_vault(&human, &human.pubkey(), LAMPORTS_PER_SOL / 100).await.unwrap();
+    client.sign_safe(&agent, agreement_id).await.unwrap();
+
+    let agreement = client.get_agreement(&agreement_id).await.unwrap().unwrap();
+    assert_eq!((agreement.status, agreement.escrow_total), (STATUS_ACTIVE, amount));
+    let safe = client.get_safe(&agreement_id).await.unwrap().unwrap();
+    assert_eq!(safe.status, SAFE_FUNDED);
+
+    // Only the company converts; the investor can't refund before expiry
+    assert!(client.convert_safe(&agent, &agent.pubkey(), agreement_id, &investor, 10_000_000).await.is_err());
+    assert!(client.refund_safe(&agent, &agent.pubkey(), agreement_id, &investor).await.is_err());
+    let vault = find_vault(&company).0;
+    let before = client.rpc().get_balance(vault).await.unwrap();
+    client.convert_safe(&human, &human.pubkey(), agreement_id, &investor, 10_000_000).await.unwrap();
+    assert_eq!(client.rpc().get_balance(vault).await.unwrap(), before + amount);
+
+    let safe = client.get_safe(&agreement_id).await.unwrap().unwrap();
+    assert_eq!(safe.status, SAFE_CONVERTED);
+    // 20% off the 10M round beats the 9M cap
+    assert_eq!((safe.round_valuation, safe.conversion_valuation), (10_000_000, 8_000_000));
+    let agreement = client.get_agreement(&agreement_id).await.unwrap().unwrap();
+    assert_eq!((agreement.status, agreement.escrow_total), (STATUS_FULFILLED, 0));
+}
+
+#[tokio::test]
+async fn test_rotate_agent_key_keeps_identity() {
+    let (client, human) = setup().await;
+    let (old_key, new_key, newest_key) = (Keypair::new(), Keypair::new(), Keypair::new());
+    let identity = find_agent_identity(&old_key.pubkey()).0;
+
+    client.register_agent(&human, &old_key.pubkey(), [0u8; 32], scope()).await.unwrap();
+    client.deposit_to_vault(&human, &old_key.pubkey(), LAMPORTS_PER_SOL).await.unwrap();
+
+    client.rotate_agent_key(&human, &old_key.pubkey(), &new_key).await.unwrap();
+    assert_eq!(client.resolve_agent_identity(&new_key.pubkey()).await.unwrap(), Some(identity));
+    let rotated = client.get_agent_identity(&new_key.pubkey()).await.unwrap().unwrap();
+    assert_eq!(rotated.agent_key, new_key.pubkey());
+    // Vault is keyed by the identity address, so it carries over
+    assert!(client.get_vault(&old_key.pubkey()).await.unwrap().is_some());
+
+    // A key held by a rotated identity can't register a second identity
+    assert!(client.register_agent(&human, &new_key.pubkey(), [0u8; 32], scope()).await.is_err());
+
+    // Rotating again must close the intermediate key's lookup
+    let skip_lookup = instructions::rotate_agent_key(
+        &human.pubkey(),
+        &old_key.pubkey(),
+        &old_key.pubkey(),
+        &newest_key.pubkey(),
+        false,
+    );
+    assert!(client.rpc().send(&[skip_lookup], &human, &[]).await.is_err());
+    client.rotate_agent_key(&human, &old_key.pubkey(), &newest_key).await.unwrap();
+    assert_eq!(client.resolve_agent_identity(&new_key.pubkey()).await.unwrap(), None);
+
+    // Rotating back to the registered key closes the lookup and creates none
+    client.rotate_agent_key(&human, &old_key.pubkey(), &old_key).await.unwrap();
+    assert_eq!(client.resolve_agent_identity(&newest_key.pubkey()).await.unwrap(), None);
+    let restored = client.get_agent_identity(&old_key.pubkey()).await.unwrap().unwrap();
+    assert_eq!(restored.agent_key, old_key.pubkey());
+    client.rotate_agent_key(&human, &old_key.pubkey(), &newest_key).await.unwrap();
+
+    let revoke = instructions::revoke_rotated_agent(&human.pubkey(), &old_key.pubkey(), &newest_key.pubkey());
+    client.rpc().send(&[revoke], &human, &[]).await.unwrap();
+    assert_eq!(client.resolve_agent_identity(&newest_key.pubkey()).await.unwrap(), None);
+}
+
+// ============================================================
+// Authority transfer
+// ============================================================
+
+#[tokio::test]
+async fn test_authority_transfer_moves_sub_agents() {
+    let (client, human) = setup().await;
+    let (agent, sub_agent, new_owner) = (Keypair::new(), Keypair::new(), Keypair::new());
+    fund(&client, &human, &agent).await;
+    fund(&client, &human, &new_owner).await;
+
+    client.register_agent(&human, &agent.pubkey(), [0u8; 32], scope()).await.unwrap();
+    client.register_sub_agent(&agent, &sub_agent.pubkey(), [0u8; 32], scope()).await.unwrap();
+    client.deposit_to_vault(&human, &agent.pubkey(), LAMPORTS_PER_SOL).await.unwrap();
+
+    // Proposing changes nothing until accepted, and can be withdrawn
+    client.propose_authority_transfer(&human, &agent.pubkey(), &new_owner.pubkey()).await.unwrap();
+    client.cancel_authority_transfer(&human, &agent.pubkey()).await.unwrap();
+    assert!(client.get_authority_transfer(&agent.pubkey()).await.unwrap().is_none());
+    assert!(client.accept_authority_transfer(&new_owner, &agent.pubkey(), &[]).await.is_err());
+
+    client.propose_authority_transfer(&human, &agent.pubkey(), &new_owner.pubkey()).await.unwrap();
+    // Only the named new authority can accept
+    assert!(client.accept_authority_transfer(&agent, &agent.pubkey(), &[]).await.is_err());
+    client
+        .accept_authority_transfer(&new_owner, &agent.pubkey(), &[sub_agent.pubkey()])
+        .await
+        .unwrap();
+
+    let identity = client.get_agent_identity(&agent.pubkey()).await.unwrap().unwrap();
+    assert_eq!(identity.authority, new_owner.pubkey());
+    let sub = client.get_agent_identity(&sub_agent.pubkey()).await.unwrap().unwrap();
+    assert_eq!(sub.authority, new_owner.pubkey());
+    let vault = client.get_vault(&agent.pubkey()).await.unwrap().unwrap();
+    assert_eq!(vault.authority, new_owner.pubkey());
+
+    // The previous owner lost control
+    assert!(client.revoke_agent(&human, &sub_agent.pubkey()).await.is_err());
+    client.revoke_agent(&new_owner, &sub_agent.pubkey()).await.unwrap();
+}
+
+// ============================================================
+// Freeze
+// ============================================================
+
+#[tokio::test]
+async fn test_freeze_blocks_agent_and_sub_agents_until_lifted() {
+    let (client, human) = setup().await;
+    let (agent, sub_agent) = (Keypair::new(), Keypair::new());
+    fund(&client, &human, &agent).await;
+    fund(&client, &human, &sub_agent).await;
+
+    client.register_agent(&human, &agent.pubkey(), [0u8; 32], scope()).await.unwrap();
+    client.register_sub_agent(&agent, &sub_agent.pubkey(), [0u8; 32], scope()).await.unwrap();
+    let args = |id: u8| ProposeAgreementArgs {
+        agreement_id: [id; 16],
+        agreement_type: AGREEMENT_TYPE_SERVICE,
+        visibility: VISIBILITY_PUBLIC,
+        terms_hash: [0u8; 32],
+        terms_uri: [0u8; 64],
+        num_parties: 2,
+        expires_at: 0,
+    };
+
+    // Freezing the parent covers the sub-agent; only the authority can freeze
+    assert!(client.freeze_agent(&agent, &agent.pubkey()).await.is_err());
+    client.freeze_agent(&human, &agent.pubkey()).await.unwrap();
+    assert!(client.propose_agreement(&agent, args(1)).await.is_err());
+    assert!(client.propose_agreement(&sub_agent, args(1)).await.is_err());
+    client.unfreeze_agent(&human, &agent.pubkey()).await.unwrap();
+    client.propose_agreement(&sub_agent, args(1)).await.unwrap();
+
+    // Authority-wide freeze stops signing too, and nothing else changes
+    client.add_party(&sub_agent, [1u8; 16], &agent.pubkey(), ROLE_COUNTERPARTY).await.unwrap();
+    client.freeze_authority(&human).await.unwrap();
+    assert!(client.sign_agreement(&agent, [1u8; 16]).await.is_err());
+    assert!(client.propose_agreement(&agent, args(2)).await.is_err());
+    client.unfreeze_authority(&human).await.unwrap();
+    assert!(client.get_freeze(&human.pubkey()).await.unwrap().is_none());
+    client.sign_agreement(&agent, [1u8; 16]).await.unwrap();
+    let agreement = client.get_agreement(&[1u8; 16]).await.unwrap().unwrap();
+    assert_eq!(agreement.status, STATUS_ACTIVE);
+}
+
+// ============================================================
+// Protocol config
+// ============================================================
+
+#[tokio::test]
+async fn test_config_pauses_limits_parties_and_charges_fee() {
+    let (client, human, admin) = setup_with_admin().await;
+    client.register_agent(&human, &human.pubkey(), [0u8; 32], scope()).await.unwrap();
+    let args = |id: u8, num_parties: u8| ProposeAgreementArgs {
+        agreement_id: [id; 16],
+        agreement_type: AGREEMENT_TYPE_SERVICE,
+        visibility: VISIBILITY_PUBLIC,
+        terms_hash: [0u8; 32],
+        terms_uri: [0u8; 64],
+        num_parties,
+        expires_at: 0,
+    };
+
+    // Pause stops user instructions but not freezes or exits; only the admin can pause
+    client.deposit_to_vault(&human, &human.pubkey(), LAMPORTS_PER_SOL).await.unwrap();
+    assert!(client.set_paused(&human, true).await.is_err());
+    client.set_paused(&admin, true).await.unwrap();
+    assert!(client.propose_agreement(&human, args(1, 2)).await.is_err());
+    assert!(client.deposit_to_vault(&human, &human.pubkey(), 1).await.is_err());
+    client.withdraw_from_vault(&human, &human.pubkey(), LAMPORTS_PER_SOL / 2).await.unwrap();
+    client.freeze_authority(&human).await.unwrap();
+    client.unfreeze_authority(&human).await.unwrap();
+    client.set_paused(&admin, false).await.unwrap();
+
+    // Tighter party limit and a fee to a fresh treasury
+    let treasury = Keypair::new().pubkey();
+    let params = ConfigParams {
+        admin: admin.pubkey(),
+        max_parties: 3,
+        fee_lamports: LAMPORTS_PER_SOL / 100,
+        treasury,
+    };
+    assert!(client.update_config(&admin, ConfigParams { max_parties: 1, ..params }).await.is_err());
+    client.update_config(&admin, params).await.unwrap();
+    assert!(client.propose_agreement(&human, args(1, 4)).await.is_err());
+    client.propose_agreement(&human, args(1, 3)).await.unwrap();
+    assert_eq!(client.rpc().get_balance(treasury).await.unwrap(), LAMPORTS_PER_SOL / 100);
+}
+
+// ============================================================
+// Threshold authorities
+// ============================================================
+
+#[tokio::test]
+async fn test_threshold_authority_controls_identity_and_vault() {
+    let (client, human) = setup().await;
+    let (agent, alice, bob) = (Keypair::new(), Keypair::new(), Keypair::new());
+    fund(&client, &human, &alice).await;
+    fund(&client, &human, &bob).await;
+    fund(&client, &human, &agent).await;
+
+    let set_id = [5u8; 16];
+    let members = [human.pubkey(), alice.pubkey(), bob.pubkey()];
+    client.create_authority_set(&human, set_id, &members, 2).await.unwrap();
+    let council = find_authority_signer(&find_authority_set(&set_id).0).0;
+
+    client.register_agent(&human, &agent.pubkey(), [0u8; 32], scope()).await.unwrap();
+    client.deposit_to_vault(&human, &agent.pubkey(), LAMPORTS_PER_SOL).await.unwrap();
+
+    // Hand the identity to the council; the council accepts through an action
+    client.propose_authority_transfer(&human, &agent.pubkey(), &council).await.unwrap();
+    let accept = instructions::accept_authority_transfer(&council, &human.pubkey(), &agent.pubkey(), &[]);
+    let index = client.propose_authority_action(&alice, set_id, &accept).await.unwrap();
+    assert!(client.execute_authority_action(&alice, set_id, index, &[]).await.is_err());
+    client.approve_authority_action(&bob, set_id, index).await.unwrap();
+    assert!(client.approve_authority_action(&bob, set_id, index).await.is_err());
+    client.execute_authority_action(&bob, set_id, index, &[]).await.unwrap();
+    assert!(client.get_authority_action(&set_id, index).await.unwrap().is_none());
+
+    let identity = client.get_agent_identity(&agent.pubkey()).await.unwrap().unwrap();
+    assert_eq!(identity.authority, council);
+    // A single member no longer controls it
+    assert!(client.update_delegation(&human, &agent.pubkey(), scope()).await.is_err());
+
+    let mut narrowed = scope();
+    narrowed.can_sign_agreements = false;
+    let update = instructions::update_delegation(&council, &agent.pubkey(), narrowed);
+    let index = client.propose_authority_action(&human, set_id, &update).await.unwrap();
+    client.approve_authority_action(&alice, set_id, index).await.unwrap();
+    client.execute_authority_action(&human, set_id, index, &[]).await.unwrap();
+    let identity = client.get_agent_identity(&agent.pubkey()).await.unwrap().unwrap();
+    assert!(!identity.scope.can_sign_agreements);
+
+    // Withdrawals go to a destination the council names
+    let withdraw = instructions::withdraw_from_vault(&council, &agent.pubkey(), &bob.pubkey(), LAMPORTS_PER_SOL / 2);
+    let index = client.propose_authority_action(&bob, set_id, &withdraw).await.unwrap();
+    client.approve_authority_action(&human, set_id, index).await.unwrap();
+    let before = client.rpc().get_balance(bob.pubkey()).await.unwrap();
+    client.execute_authority_action(&alice, set_id, index, &[]).await.unwrap();
+    assert_eq!(client.rpc().get_balance(bob.pubkey()).await.unwrap(), before + LAMPORTS_PER_SOL / 2);
+    let vault = client.get_vault(&agent.pubkey()).await.unwrap().unwrap();
+    assert_eq!(vault.total_withdrawn, LAMPORTS_PER_SOL / 2);
+
+    // Non-members can't approve; the proposer can withdraw an action
+    let revoke = instructions::revoke_agent(&council, &agent.pubkey());
+    let index = client.propose_authority_action(&alice, set_id, &revoke).await.unwrap();
+    assert!(client.approve_authority_action(&agent, set_id, index).await.is_err());
+    client.cancel_authority_action(&alice, set_id, index).await.unwrap();
+    assert!(client.get_authority_action(&set_id, index).await.unwrap().is_none());
+}
+
+// ============================================================
+// Vault
+// ============================================================
+
+#[tokio::test]
+async fn test_vault_deposit_withdraw() {
+    let (client, human) = setup().await;
+    let agent = Keypair::new();
+
+    client.register_agent(&human, &agent.pubkey(), [0u8; 32], scope()).await.unwrap();
+    client.deposit_to_vault(&human, &agent.pubkey(), LAMPORTS_PER_SOL).await.unwrap();
+    client.withdraw_from_vault(&human, &agent.pubkey(), LAMPORTS_PER_SOL / 2).await.unwrap();
+
+    let vault = client.get_vault(&agent.pubkey()).await.unwrap().unwrap();
+    assert_eq!(vault.total_deposited, LAMPORTS_PER_SOL);
+    assert_eq!(vault.total_withdrawn, LAMPORTS_PER_SOL / 2);
+
+    client.revoke_agent(&human, &agent.pubkey()).await.unwrap();
+    assert!(client.get_agent_identity(&agent.pubkey()).await.unwrap().is_none());
+}
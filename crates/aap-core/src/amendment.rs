@@ -0,0 +1,58 @@
+use crate::constants::*;
+use crate::error::RuleError;
+
+/// Terms can be amended while the agreement is Proposed (applied at once,
+/// collected signatures reset) or Active (applied once every party signs).
+pub fn require_amendable(status: u8) -> Result<(), RuleError> {
+    if status != STATUS_PROPOSED && status != STATUS_ACTIVE {
+        return Err(RuleError::InvalidStatus);
+    }
+    Ok(())
+}
+
+/// An amendment proposed in `status` needs every party's signature before it applies.
+pub fn requires_all_parties(status: u8) -> bool {
+    status == STATUS_ACTIVE
+}
+
+/// Versions start at 1 (0 is the proposed terms) and form a chain: `version`
+/// must directly follow `previous_status`, the status of amendment `version - 1`
+/// (`None` for the first), and only one amendment may be pending at a time.
+pub fn require_next_version(version: u16, previous_status: Option<u8>) -> Result<(), RuleError> {
+    let follows = match (version, previous_status) {
+        (1, None) => true,
+        (2.., Some(status)) => status == AMENDMENT_APPLIED || status == AMENDMENT_WITHDRAWN,
+        _ => false,
+    };
+    if !follows {
+        return Err(RuleError::InvalidAmendment);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn amendable_phases() {
+        assert!(require_amendable(STATUS_PROPOSED).is_ok());
+        assert!(require_amendable(STATUS_ACTIVE).is_ok());
+        assert_eq!(require_amendable(STATUS_FULFILLED), Err(RuleError::InvalidStatus));
+        assert_eq!(require_amendable(STATUS_CANCELLED), Err(RuleError::InvalidStatus));
+        assert!(!requires_all_parties(STATUS_PROPOSED));
+        assert!(requires_all_parties(STATUS_ACTIVE));
+    }
+
+    #[test]
+    fn version_chain() {
+        assert!(require_next_version(1, None).is_ok());
+        assert!(require_next_version(2, Some(AMENDMENT_APPLIED)).is_ok());
+        assert!(require_next_version(3, Some(AMENDMENT_WITHDRAWN)).is_ok());
+
+        assert_eq!(require_next_version(0, None), Err(RuleError::InvalidAmendment));
+        assert_eq!(require_next_version(2, None), Err(RuleError::InvalidAmendment));
+        assert_eq!(require_next_version(1, Some(AMENDMENT_APPLIED)), Err(RuleError::InvalidAmendment));
+        assert_eq!(require_next_version(2, Some(AMENDMENT_PENDING)), Err(RuleError::InvalidAmendment));
+    }
+}
@@ -0,0 +1,74 @@
+// AgreementType
+pub const AGREEMENT_TYPE_SAFE: u8 = 0;
+pub const AGREEMENT_TYPE_SERVICE: u8 = 1;
+pub const AGREEMENT_TYPE_REVENUE_SHARE: u8 = 2;
+pub const AGREEMENT_TYPE_JOINT_VENTURE: u8 = 3;
+pub const AGREEMENT_TYPE_CUSTOM: u8 = 4;
+
+// AgreementStatus
+pub const STATUS_PROPOSED: u8 = 0;
+pub const STATUS_ACTIVE: u8 = 1;
+pub const STATUS_FULFILLED: u8 = 2;
+pub const STATUS_BREACHED: u8 = 3;
+pub const STATUS_DISPUTED: u8 = 4;
+pub const STATUS_CANCELLED: u8 = 5;
+pub const STATUS_REJECTED: u8 = 6;
+pub const STATUS_TERMINATED: u8 = 7;
+
+// AmendmentStatus (V1 AgreementAmendment)
+pub const AMENDMENT_PENDING: u8 = 0;
+pub const AMENDMENT_APPLIED: u8 = 1;
+pub const AMENDMENT_WITHDRAWN: u8 = 2;
+
+// CounterProposalStatus (V1 CounterProposal)
+pub const COUNTER_PENDING: u8 = 0;
+pub const COUNTER_ACCEPTED: u8 = 1;
+pub const COUNTER_REJECTED: u8 = 2;
+
+// TerminationStatus (V1 Termination)
+pub const TERMINATION_PENDING: u8 = 0;
+pub const TERMINATION_APPLIED: u8 = 1;
+
+// MilestoneStatus (V1 MilestoneSchedule)
+pub const MILESTONE_PENDING: u8 = 0;
+pub const MILESTONE_SUBMITTED: u8 = 1;
+pub const MILESTONE_APPROVED: u8 = 2;
+pub const MILESTONE_DISPUTED: u8 = 3;
+pub const MILESTONE_RELEASED: u8 = 4;
+
+// RecurringPaymentStatus (V1 RecurringPayment)
+pub const RECURRING_ACTIVE: u8 = 0;
+pub const RECURRING_COMPLETED: u8 = 1;
+pub const RECURRING_CANCELLED: u8 = 2;
+
+// EscrowStreamStatus (V1 EscrowStream)
+pub const STREAM_ACTIVE: u8 = 0;
+pub const STREAM_CANCELLED: u8 = 1;
+pub const STREAM_SETTLED: u8 = 2;
+
+// SafeStatus (V1 Safe)
+pub const SAFE_UNFUNDED: u8 = 0;
+pub const SAFE_FUNDED: u8 = 1;
+pub const SAFE_CONVERTED: u8 = 2;
+pub const SAFE_REFUNDED: u8 = 3;
+
+// Visibility
+pub const VISIBILITY_PUBLIC: u8 = 0;
+pub const VISIBILITY_PRIVATE: u8 = 1;
+
+// PartyRole
+pub const ROLE_PROPOSER: u8 = 0;
+pub const ROLE_COUNTERPARTY: u8 = 1;
+pub const ROLE_WITNESS: u8 = 2;
+pub const ROLE_ARBITRATOR: u8 = 3;
+
+// Limits
+pub const MAX_PARTIES: u8 = 8; // default ProtocolConfig.max_parties
+pub const MIN_PARTIES: u8 = 2;
+pub const MAX_AGREEMENT_TYPE: u8 = 4;
+pub const MAX_ROLE: u8 = 3;
+pub const MAX_MILESTONES: usize = 16;
+pub const MAX_REVENUE_SPLITS: usize = 16;
+
+// Revenue share splits
+pub const BPS_DENOMINATOR: u16 = 10_000;
@@ -0,0 +1,63 @@
+use crate::constants::*;
+use crate::error::RuleError;
+
+/// Only an invited party (not the proposer) that hasn't signed can counter,
+/// and only while the agreement is Proposed.
+pub fn require_counterable(status: u8, role: u8, signed: bool) -> Result<(), RuleError> {
+    if status != STATUS_PROPOSED {
+        return Err(RuleError::InvalidStatus);
+    }
+    if role == ROLE_PROPOSER || signed {
+        return Err(RuleError::InvalidCounterProposal);
+    }
+    Ok(())
+}
+
+/// Each party's counter-proposals are numbered from 1: `round` must directly
+/// follow `previous_status`, the status of that party's round `round - 1`
+/// (`None` for the first), which the proposer must have answered.
+pub fn require_next_round(round: u16, previous_status: Option<u8>) -> Result<(), RuleError> {
+    let follows = match (round, previous_status) {
+        (1, None) => true,
+        (2.., Some(status)) => status == COUNTER_ACCEPTED || status == COUNTER_REJECTED,
+        _ => false,
+    };
+    if !follows {
+        return Err(RuleError::InvalidCounterProposal);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn only_unsigned_invitees_counter_while_proposed() {
+        assert!(require_counterable(STATUS_PROPOSED, ROLE_COUNTERPARTY, false).is_ok());
+        assert!(require_counterable(STATUS_PROPOSED, ROLE_WITNESS, false).is_ok());
+        assert_eq!(
+            require_counterable(STATUS_PROPOSED, ROLE_PROPOSER, false),
+            Err(RuleError::InvalidCounterProposal)
+        );
+        assert_eq!(
+            require_counterable(STATUS_PROPOSED, ROLE_COUNTERPARTY, true),
+            Err(RuleError::InvalidCounterProposal)
+        );
+        assert_eq!(
+            require_counterable(STATUS_ACTIVE, ROLE_COUNTERPARTY, false),
+            Err(RuleError::InvalidStatus)
+        );
+    }
+
+    #[test]
+    fn round_chain() {
+        assert!(require_next_round(1, None).is_ok());
+        assert!(require_next_round(2, Some(COUNTER_REJECTED)).is_ok());
+        assert!(require_next_round(3, Some(COUNTER_ACCEPTED)).is_ok());
+
+        assert_eq!(require_next_round(0, None), Err(RuleError::InvalidCounterProposal));
+        assert_eq!(require_next_round(2, None), Err(RuleError::InvalidCounterProposal));
+        assert_eq!(require_next_round(2, Some(COUNTER_PENDING)), Err(RuleError::InvalidCounterProposal));
+    }
+}
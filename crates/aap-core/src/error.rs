@@ -0,0 +1,81 @@
+use core::fmt;
+
+/// A protocol rule violation. Variant names match the programs' `AapError`
+/// variants they map to.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RuleError {
+    ScopeExpired,
+    DelegationExpired,
+    CannotSignAgreements,
+    CannotCommitFunds,
+    EscrowExceedsLimit,
+    SubAgentScopeExceedsParent,
+    MaxDelegationDepth,
+    InvalidAgreementType,
+    InvalidVisibility,
+    InvalidRole,
+    InvalidPartyCount,
+    InvalidStatus,
+    AgreementExpired,
+    MaxPartiesExceeded,
+    InvalidAmendment,
+    InvalidCounterProposal,
+    EscrowDistributionMismatch,
+    InvalidMilestone,
+    InvalidMilestoneStatus,
+    InvalidRecurringPayment,
+    PaymentNotDue,
+    InvalidStream,
+    InvalidRevenueShare,
+    InvalidSafe,
+}
+
+impl fmt::Display for RuleError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let msg = match self {
+            RuleError::ScopeExpired => "Delegation scope has already expired",
+            RuleError::DelegationExpired => "Agent delegation has expired",
+            RuleError::CannotSignAgreements => "Agent does not have permission to sign agreements",
+            RuleError::CannotCommitFunds => "Agent does not have permission to commit funds",
+            RuleError::EscrowExceedsLimit => "Escrow amount exceeds delegation max_commit_lamports",
+            RuleError::SubAgentScopeExceedsParent => "Sub-agent scope cannot exceed parent scope",
+            RuleError::MaxDelegationDepth => {
+                "Maximum delegation depth is 2 levels (human -> agent -> sub-agent)"
+            }
+            RuleError::InvalidAgreementType => "Invalid agreement type",
+            RuleError::InvalidVisibility => "Invalid visibility value",
+            RuleError::InvalidRole => "Invalid party role",
+            RuleError::InvalidPartyCount => "Number of parties must be between 2 and the configured maximum",
+            RuleError::InvalidStatus => "Agreement is not in the expected status",
+            RuleError::AgreementExpired => "Agreement has expired",
+            RuleError::MaxPartiesExceeded => "Maximum number of parties already added",
+            RuleError::InvalidAmendment => {
+                "Amendment version must follow the latest one, which must be applied or withdrawn"
+            }
+            RuleError::InvalidCounterProposal => {
+                "Only an unsigned invited party can counter, after its previous round was answered"
+            }
+            RuleError::EscrowDistributionMismatch => "Escrow distribution does not sum to total",
+            RuleError::InvalidMilestone => {
+                "Milestone schedule must have 1 to 16 milestones, each paying a positive amount to another party"
+            }
+            RuleError::InvalidMilestoneStatus => "Milestone is not in a status that allows this",
+            RuleError::InvalidRecurringPayment => {
+                "Recurring payment needs a positive amount, period and number of periods between two parties"
+            }
+            RuleError::PaymentNotDue => "No payment period is due",
+            RuleError::InvalidStream => {
+                "Stream needs a positive amount and an agreement expiry after it starts"
+            }
+            RuleError::InvalidRevenueShare => {
+                "Revenue share needs 1 to 16 distinct parties with positive basis points summing to 10000"
+            }
+            RuleError::InvalidSafe => {
+                "SAFE needs a positive investment and round valuation, a discount below 100% and two different parties"
+            }
+        };
+        f.write_str(msg)
+    }
+}
+
+impl std::error::Error for RuleError {}
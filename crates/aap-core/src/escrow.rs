@@ -0,0 +1,58 @@
+use crate::constants::*;
+use crate::error::RuleError;
+
+/// Parties can add SOL escrow until the agreement ends.
+pub fn require_fundable(status: u8) -> Result<(), RuleError> {
+    if status != STATUS_PROPOSED && status != STATUS_ACTIVE {
+        return Err(RuleError::InvalidStatus);
+    }
+    Ok(())
+}
+
+/// Escrow is paid out once the agreement ends without a dispute: deposits go
+/// back to their parties, or follow the settlement of a termination.
+pub fn is_releasable(status: u8) -> bool {
+    matches!(
+        status,
+        STATUS_FULFILLED | STATUS_CANCELLED | STATUS_REJECTED | STATUS_TERMINATED
+    )
+}
+
+/// A settlement splits the whole escrow: `amounts` must add up to `escrow_total`.
+pub fn validate_settlement(amounts: &[u64], escrow_total: u64) -> Result<(), RuleError> {
+    let sum = amounts
+        .iter()
+        .try_fold(0u64, |sum, amount| sum.checked_add(*amount))
+        .ok_or(RuleError::EscrowDistributionMismatch)?;
+    if sum != escrow_total {
+        return Err(RuleError::EscrowDistributionMismatch);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn escrow_phases() {
+        assert!(require_fundable(STATUS_PROPOSED).is_ok());
+        assert!(require_fundable(STATUS_ACTIVE).is_ok());
+        assert_eq!(require_fundable(STATUS_TERMINATED), Err(RuleError::InvalidStatus));
+
+        assert!(is_releasable(STATUS_FULFILLED));
+        assert!(is_releasable(STATUS_TERMINATED));
+        assert!(is_releasable(STATUS_REJECTED));
+        assert!(!is_releasable(STATUS_ACTIVE));
+        assert!(!is_releasable(STATUS_DISPUTED));
+        assert!(!is_releasable(STATUS_BREACHED));
+    }
+
+    #[test]
+    fn settlement_must_cover_escrow() {
+        assert!(validate_settlement(&[70, 30], 100).is_ok());
+        assert!(validate_settlement(&[0, 0], 0).is_ok());
+        assert_eq!(validate_settlement(&[70, 20], 100), Err(RuleError::EscrowDistributionMismatch));
+        assert_eq!(validate_settlement(&[u64::MAX, 2], 1), Err(RuleError::EscrowDistributionMismatch));
+    }
+}
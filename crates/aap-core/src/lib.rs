@@ -0,0 +1,23 @@
+//! Protocol rules shared by `agent-agreement-protocol` (V1) and `aap-compressed` (V2).
+//!
+//! Everything here is a pure function over plain values — no Anchor, no Light SDK —
+//! so both programs (pinned to different Anchor versions) and off-chain clients can
+//! depend on it. Each program maps [`RuleError`] into its own `AapError`.
+
+pub mod amendment;
+pub mod constants;
+pub mod counter;
+pub mod error;
+pub mod escrow;
+pub mod milestone;
+pub mod parties;
+pub mod recurring;
+pub mod revenue;
+pub mod safe;
+pub mod scope;
+pub mod status;
+pub mod stream;
+
+pub use error::RuleError;
+pub use scope::Scope;
+pub use status::{AgreementStatus, AgreementType, PartyRole, Visibility};
@@ -0,0 +1,78 @@
+use crate::constants::*;
+use crate::error::RuleError;
+
+/// A schedule has 1 to `MAX_MILESTONES` milestones.
+pub fn validate_schedule(count: usize) -> Result<(), RuleError> {
+    if count == 0 || count > MAX_MILESTONES {
+        return Err(RuleError::InvalidMilestone);
+    }
+    Ok(())
+}
+
+/// The payee submits work that is pending, or again after a dispute.
+pub fn require_submittable(status: u8) -> Result<(), RuleError> {
+    if status != MILESTONE_PENDING && status != MILESTONE_DISPUTED {
+        return Err(RuleError::InvalidMilestoneStatus);
+    }
+    Ok(())
+}
+
+/// The payer can approve any milestone not yet approved, submitted or not:
+/// a payee without an identity can't submit.
+pub fn require_approvable(status: u8) -> Result<(), RuleError> {
+    if !matches!(status, MILESTONE_PENDING | MILESTONE_SUBMITTED | MILESTONE_DISPUTED) {
+        return Err(RuleError::InvalidMilestoneStatus);
+    }
+    Ok(())
+}
+
+/// The payer disputes submitted work, or a pending milestone past its due
+/// date (`due_at` 0 = none).
+pub fn require_disputable(status: u8, due_at: i64, now: i64) -> Result<(), RuleError> {
+    let overdue = status == MILESTONE_PENDING && due_at != 0 && now > due_at;
+    if status != MILESTONE_SUBMITTED && !overdue {
+        return Err(RuleError::InvalidMilestoneStatus);
+    }
+    Ok(())
+}
+
+/// Approved milestones are paid out once.
+pub fn require_releasable(status: u8) -> Result<(), RuleError> {
+    if status != MILESTONE_APPROVED {
+        return Err(RuleError::InvalidMilestoneStatus);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn schedule_size() {
+        assert!(validate_schedule(1).is_ok());
+        assert!(validate_schedule(MAX_MILESTONES).is_ok());
+        assert_eq!(validate_schedule(0), Err(RuleError::InvalidMilestone));
+        assert_eq!(validate_schedule(MAX_MILESTONES + 1), Err(RuleError::InvalidMilestone));
+    }
+
+    #[test]
+    fn milestone_lifecycle() {
+        assert!(require_submittable(MILESTONE_PENDING).is_ok());
+        assert!(require_submittable(MILESTONE_DISPUTED).is_ok());
+        assert_eq!(require_submittable(MILESTONE_APPROVED), Err(RuleError::InvalidMilestoneStatus));
+
+        assert!(require_approvable(MILESTONE_PENDING).is_ok());
+        assert!(require_approvable(MILESTONE_SUBMITTED).is_ok());
+        assert_eq!(require_approvable(MILESTONE_RELEASED), Err(RuleError::InvalidMilestoneStatus));
+
+        assert!(require_disputable(MILESTONE_SUBMITTED, 0, 10).is_ok());
+        assert!(require_disputable(MILESTONE_PENDING, 5, 10).is_ok());
+        assert_eq!(require_disputable(MILESTONE_PENDING, 0, 10), Err(RuleError::InvalidMilestoneStatus));
+        assert_eq!(require_disputable(MILESTONE_PENDING, 20, 10), Err(RuleError::InvalidMilestoneStatus));
+        assert_eq!(require_disputable(MILESTONE_APPROVED, 5, 10), Err(RuleError::InvalidMilestoneStatus));
+
+        assert!(require_releasable(MILESTONE_APPROVED).is_ok());
+        assert_eq!(require_releasable(MILESTONE_RELEASED), Err(RuleError::InvalidMilestoneStatus));
+    }
+}
@@ -0,0 +1,94 @@
+use crate::constants::*;
+use crate::error::RuleError;
+
+/// `num_parties` at proposal time, proposer included. `max_parties` is the
+/// protocol config's limit (`MAX_PARTIES` by default).
+pub fn validate_party_count(num_parties: u8, max_parties: u8) -> Result<(), RuleError> {
+    if !(MIN_PARTIES..=max_parties).contains(&num_parties) {
+        return Err(RuleError::InvalidPartyCount);
+    }
+    Ok(())
+}
+
+/// A configured `max_parties` must leave room for a two-party agreement.
+pub fn validate_party_limit(max_parties: u8) -> Result<(), RuleError> {
+    if max_parties < MIN_PARTIES {
+        return Err(RuleError::InvalidPartyCount);
+    }
+    Ok(())
+}
+
+/// A Proposed agreement may be resized to any valid count that still holds
+/// the `parties_added` parties already on it.
+pub fn validate_party_resize(num_parties: u8, parties_added: u8, max_parties: u8) -> Result<(), RuleError> {
+    validate_party_count(num_parties, max_parties)?;
+    if num_parties < parties_added {
+        return Err(RuleError::InvalidPartyCount);
+    }
+    Ok(())
+}
+
+/// Another party may be added while `parties_added < num_parties`.
+pub fn require_open_slot(parties_added: u8, num_parties: u8) -> Result<(), RuleError> {
+    if parties_added >= num_parties {
+        return Err(RuleError::MaxPartiesExceeded);
+    }
+    Ok(())
+}
+
+/// Roles assignable through `add_party`.
+pub fn validate_role(role: u8) -> Result<(), RuleError> {
+    if role > MAX_ROLE {
+        return Err(RuleError::InvalidRole);
+    }
+    Ok(())
+}
+
+/// Agreement becomes Active once every declared party has signed.
+pub fn is_fully_signed(num_signed: u8, num_parties: u8) -> bool {
+    num_signed == num_parties
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn party_count_bounds() {
+        assert_eq!(validate_party_count(MIN_PARTIES - 1, MAX_PARTIES), Err(RuleError::InvalidPartyCount));
+        assert!(validate_party_count(MIN_PARTIES, MAX_PARTIES).is_ok());
+        assert!(validate_party_count(MAX_PARTIES, MAX_PARTIES).is_ok());
+        assert_eq!(validate_party_count(MAX_PARTIES + 1, MAX_PARTIES), Err(RuleError::InvalidPartyCount));
+        // A raised limit admits more parties
+        assert!(validate_party_count(MAX_PARTIES + 1, MAX_PARTIES + 1).is_ok());
+    }
+
+    #[test]
+    fn party_resize() {
+        assert!(validate_party_resize(3, 3, MAX_PARTIES).is_ok());
+        assert!(validate_party_resize(MAX_PARTIES, 2, MAX_PARTIES).is_ok());
+        assert_eq!(validate_party_resize(2, 3, MAX_PARTIES), Err(RuleError::InvalidPartyCount));
+        assert_eq!(validate_party_resize(MIN_PARTIES - 1, 1, MAX_PARTIES), Err(RuleError::InvalidPartyCount));
+        assert_eq!(validate_party_resize(MAX_PARTIES + 1, 2, MAX_PARTIES), Err(RuleError::InvalidPartyCount));
+    }
+
+    #[test]
+    fn party_limit() {
+        assert_eq!(validate_party_limit(MIN_PARTIES - 1), Err(RuleError::InvalidPartyCount));
+        assert!(validate_party_limit(MIN_PARTIES).is_ok());
+    }
+
+    #[test]
+    fn slots_and_signatures() {
+        assert!(require_open_slot(1, 2).is_ok());
+        assert_eq!(require_open_slot(2, 2), Err(RuleError::MaxPartiesExceeded));
+        assert!(!is_fully_signed(1, 2));
+        assert!(is_fully_signed(2, 2));
+    }
+
+    #[test]
+    fn roles() {
+        assert!(validate_role(ROLE_ARBITRATOR).is_ok());
+        assert_eq!(validate_role(MAX_ROLE + 1), Err(RuleError::InvalidRole));
+    }
+}
@@ -0,0 +1,88 @@
+use crate::error::RuleError;
+
+/// Terms of a recurring payment: `amount` lamports every `period` seconds,
+/// `num_periods` times.
+pub fn validate_terms(amount: u64, period: i64, num_periods: u16) -> Result<(), RuleError> {
+    if amount == 0 || period <= 0 || num_periods == 0 {
+        return Err(RuleError::InvalidRecurringPayment);
+    }
+    Ok(())
+}
+
+/// What the payer has paid once `periods` periods are paid, which must stay
+/// within its `max_commit_lamports` like an escrow deposit.
+pub fn total_paid(amount: u64, periods: u16) -> Result<u64, RuleError> {
+    amount.checked_mul(periods as u64).ok_or(RuleError::InvalidRecurringPayment)
+}
+
+/// The next unprocessed period of a schedule.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct DuePeriod {
+    /// Zero-based period number
+    pub index: u16,
+    pub due_at: i64,
+    /// The following period has started, so this one can no longer be paid
+    /// late and is recorded as missed instead
+    pub lapsed: bool,
+}
+
+/// Period `processed` (paid + missed so far) falls due at
+/// `start_at + processed * period` and can be paid until the next one starts.
+pub fn next_due_period(
+    start_at: i64,
+    period: i64,
+    processed: u16,
+    num_periods: u16,
+    now: i64,
+) -> Result<DuePeriod, RuleError> {
+    if processed >= num_periods {
+        return Err(RuleError::PaymentNotDue);
+    }
+    let due_at = (processed as i64)
+        .checked_mul(period)
+        .and_then(|offset| start_at.checked_add(offset))
+        .ok_or(RuleError::InvalidRecurringPayment)?;
+    if now < due_at {
+        return Err(RuleError::PaymentNotDue);
+    }
+    Ok(DuePeriod {
+        index: processed,
+        due_at,
+        lapsed: now >= due_at.saturating_add(period),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn totals_count_every_paid_period() {
+        assert_eq!(total_paid(5, 0), Ok(0));
+        assert_eq!(total_paid(5, 3), Ok(15));
+        assert_eq!(total_paid(u64::MAX, 2), Err(RuleError::InvalidRecurringPayment));
+    }
+
+    #[test]
+    fn terms_must_be_positive() {
+        assert!(validate_terms(1, 30, 12).is_ok());
+        assert_eq!(validate_terms(0, 30, 12), Err(RuleError::InvalidRecurringPayment));
+        assert_eq!(validate_terms(1, 0, 12), Err(RuleError::InvalidRecurringPayment));
+        assert_eq!(validate_terms(1, 30, 0), Err(RuleError::InvalidRecurringPayment));
+    }
+
+    #[test]
+    fn periods_fall_due_then_lapse() {
+        assert_eq!(next_due_period(100, 30, 0, 3, 99), Err(RuleError::PaymentNotDue));
+        assert_eq!(
+            next_due_period(100, 30, 0, 3, 100),
+            Ok(DuePeriod { index: 0, due_at: 100, lapsed: false })
+        );
+        assert_eq!(
+            next_due_period(100, 30, 1, 3, 170),
+            Ok(DuePeriod { index: 1, due_at: 130, lapsed: true })
+        );
+        assert_eq!(next_due_period(100, 30, 2, 3, 159), Err(RuleError::PaymentNotDue));
+        assert_eq!(next_due_period(100, 30, 3, 3, 1_000), Err(RuleError::PaymentNotDue));
+    }
+}
@@ -0,0 +1,53 @@
+use crate::constants::{BPS_DENOMINATOR, MAX_REVENUE_SPLITS};
+use crate::error::RuleError;
+
+/// A revenue share splits every distribution among 1 to 16 parties, each with
+/// a positive share in basis points, adding up to 100%.
+pub fn validate_splits(bps: &[u16]) -> Result<(), RuleError> {
+    if bps.is_empty() || bps.len() > MAX_REVENUE_SPLITS || bps.contains(&0) {
+        return Err(RuleError::InvalidRevenueShare);
+    }
+    let total: u32 = bps.iter().map(|bps| *bps as u32).sum();
+    if total != BPS_DENOMINATOR as u32 {
+        return Err(RuleError::InvalidRevenueShare);
+    }
+    Ok(())
+}
+
+/// Each split's part of `amount`, rounded down; the last one also gets the
+/// rounding dust so the whole amount is paid out.
+pub fn split_amount(amount: u64, bps: &[u16]) -> Vec<u64> {
+    let mut parts: Vec<u64> = bps
+        .iter()
+        .map(|bps| (amount as u128 * *bps as u128 / BPS_DENOMINATOR as u128) as u64)
+        .collect();
+    let paid: u64 = parts.iter().sum();
+    if let Some(last) = parts.last_mut() {
+        *last += amount - paid;
+    }
+    parts
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn splits_cover_everything() {
+        assert!(validate_splits(&[10_000]).is_ok());
+        assert!(validate_splits(&[7_000, 2_500, 500]).is_ok());
+        assert_eq!(validate_splits(&[]), Err(RuleError::InvalidRevenueShare));
+        assert_eq!(validate_splits(&[10_000, 0]), Err(RuleError::InvalidRevenueShare));
+        assert_eq!(validate_splits(&[5_000, 4_999]), Err(RuleError::InvalidRevenueShare));
+        assert_eq!(validate_splits(&[u16::MAX, 1]), Err(RuleError::InvalidRevenueShare));
+        assert_eq!(validate_splits(&[625; 17]), Err(RuleError::InvalidRevenueShare));
+    }
+
+    #[test]
+    fn dust_goes_to_the_last_split() {
+        assert_eq!(split_amount(1_000, &[7_000, 3_000]), vec![700, 300]);
+        assert_eq!(split_amount(100, &[3_333, 3_333, 3_334]), vec![33, 33, 34]);
+        assert_eq!(split_amount(1, &[5_000, 5_000]), vec![0, 1]);
+        assert_eq!(split_amount(u64::MAX, &[10_000]), vec![u64::MAX]);
+    }
+}
@@ -0,0 +1,51 @@
+use crate::constants::BPS_DENOMINATOR;
+use crate::error::RuleError;
+
+/// A SAFE invests a positive amount; `valuation_cap` 0 means uncapped, and
+/// the discount must stay below 100%.
+pub fn validate_terms(investment_amount: u64, discount_bps: u16) -> Result<(), RuleError> {
+    if investment_amount == 0 || discount_bps >= BPS_DENOMINATOR {
+        return Err(RuleError::InvalidSafe);
+    }
+    Ok(())
+}
+
+/// Valuation the investment converts at in a priced round: the round's
+/// valuation less the discount, capped at `valuation_cap` if there is one.
+pub fn conversion_valuation(round_valuation: u64, valuation_cap: u64, discount_bps: u16) -> Result<u64, RuleError> {
+    if round_valuation == 0 || discount_bps >= BPS_DENOMINATOR {
+        return Err(RuleError::InvalidSafe);
+    }
+    let discounted = (round_valuation as u128 * (BPS_DENOMINATOR - discount_bps) as u128
+        / BPS_DENOMINATOR as u128) as u64;
+    if valuation_cap == 0 {
+        Ok(discounted)
+    } else {
+        Ok(discounted.min(valuation_cap))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn terms_need_an_investment() {
+        assert!(validate_terms(1, 0).is_ok());
+        assert!(validate_terms(1, 9_999).is_ok());
+        assert_eq!(validate_terms(0, 2_000), Err(RuleError::InvalidSafe));
+        assert_eq!(validate_terms(1, 10_000), Err(RuleError::InvalidSafe));
+    }
+
+    #[test]
+    fn converts_at_the_better_of_cap_and_discount() {
+        // 20% discount on 10M beats a 9M cap
+        assert_eq!(conversion_valuation(10_000_000, 9_000_000, 2_000), Ok(8_000_000));
+        // The cap beats the discount on 20M
+        assert_eq!(conversion_valuation(20_000_000, 9_000_000, 2_000), Ok(9_000_000));
+        assert_eq!(conversion_valuation(20_000_000, 0, 2_000), Ok(16_000_000));
+        assert_eq!(conversion_valuation(20_000_000, 0, 0), Ok(20_000_000));
+        assert_eq!(conversion_valuation(0, 9_000_000, 0), Err(RuleError::InvalidSafe));
+        assert_eq!(conversion_valuation(u64::MAX, 0, 0), Ok(u64::MAX));
+    }
+}
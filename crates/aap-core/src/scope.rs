@@ -0,0 +1,183 @@
+use crate::error::RuleError;
+
+/// Delegation scope as plain values. Both programs' `DelegationScope` structs
+/// convert into this; the field layout is identical.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Scope {
+    pub can_sign_agreements: bool,
+    pub can_commit_funds: bool,
+    pub max_commit_lamports: u64, // 0 = unlimited
+    pub expires_at: i64,          // 0 = never expires
+}
+
+impl Scope {
+    pub fn is_expired(&self, now: i64) -> bool {
+        self.expires_at != 0 && self.expires_at <= now
+    }
+}
+
+/// A scope being granted (register / update / sub-agent) must not already be expired.
+pub fn validate_new_scope(scope: &Scope, now: i64) -> Result<(), RuleError> {
+    if scope.is_expired(now) {
+        return Err(RuleError::ScopeExpired);
+    }
+    Ok(())
+}
+
+/// An existing delegation must still be live.
+pub fn require_active(scope: &Scope, now: i64) -> Result<(), RuleError> {
+    if scope.is_expired(now) {
+        return Err(RuleError::DelegationExpired);
+    }
+    Ok(())
+}
+
+/// Live delegation with `can_sign_agreements` (propose / sign).
+pub fn require_can_sign(scope: &Scope, now: i64) -> Result<(), RuleError> {
+    require_active(scope, now)?;
+    if !scope.can_sign_agreements {
+        return Err(RuleError::CannotSignAgreements);
+    }
+    Ok(())
+}
+
+/// Live delegation with `can_commit_funds`, and `amount` within `max_commit_lamports`.
+pub fn require_can_commit(scope: &Scope, now: i64, amount: u64) -> Result<(), RuleError> {
+    require_active(scope, now)?;
+    if !scope.can_commit_funds {
+        return Err(RuleError::CannotCommitFunds);
+    }
+    if scope.max_commit_lamports > 0 && amount > scope.max_commit_lamports {
+        return Err(RuleError::EscrowExceedsLimit);
+    }
+    Ok(())
+}
+
+/// A sub-agent may only receive permissions its parent holds.
+pub fn require_within_parent(parent: &Scope, child: &Scope) -> Result<(), RuleError> {
+    if !parent.can_sign_agreements && child.can_sign_agreements {
+        return Err(RuleError::SubAgentScopeExceedsParent);
+    }
+    if parent.can_commit_funds {
+        if parent.max_commit_lamports > 0 && child.max_commit_lamports > parent.max_commit_lamports {
+            return Err(RuleError::SubAgentScopeExceedsParent);
+        }
+    } else if child.can_commit_funds {
+        return Err(RuleError::SubAgentScopeExceedsParent);
+    }
+    Ok(())
+}
+
+/// Full `register_sub_agent` check: depth (human -> agent -> sub-agent),
+/// live parent, child within parent, child not already expired.
+pub fn validate_sub_agent(
+    parent: &Scope,
+    parent_is_sub_agent: bool,
+    child: &Scope,
+    now: i64,
+) -> Result<(), RuleError> {
+    if parent_is_sub_agent {
+        return Err(RuleError::MaxDelegationDepth);
+    }
+    require_active(parent, now)?;
+    require_within_parent(parent, child)?;
+    validate_new_scope(child, now)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const NOW: i64 = 1_000;
+
+    fn scope(sign: bool, commit: bool, max: u64, expires_at: i64) -> Scope {
+        Scope {
+            can_sign_agreements: sign,
+            can_commit_funds: commit,
+            max_commit_lamports: max,
+            expires_at,
+        }
+    }
+
+    #[test]
+    fn expiry() {
+        assert!(!scope(true, false, 0, 0).is_expired(NOW));
+        assert!(!scope(true, false, 0, NOW + 1).is_expired(NOW));
+        assert!(scope(true, false, 0, NOW).is_expired(NOW));
+
+        assert_eq!(validate_new_scope(&scope(true, false, 0, NOW), NOW), Err(RuleError::ScopeExpired));
+        assert_eq!(require_active(&scope(true, false, 0, NOW), NOW), Err(RuleError::DelegationExpired));
+    }
+
+    #[test]
+    fn sign_permission() {
+        assert!(require_can_sign(&scope(true, false, 0, 0), NOW).is_ok());
+        assert_eq!(
+            require_can_sign(&scope(false, false, 0, 0), NOW),
+            Err(RuleError::CannotSignAgreements)
+        );
+        // Expiry is reported before the missing permission
+        assert_eq!(
+            require_can_sign(&scope(false, false, 0, NOW - 1), NOW),
+            Err(RuleError::DelegationExpired)
+        );
+    }
+
+    #[test]
+    fn commit_permission_and_limit() {
+        assert_eq!(
+            require_can_commit(&scope(true, false, 0, 0), NOW, 1),
+            Err(RuleError::CannotCommitFunds)
+        );
+        assert!(require_can_commit(&scope(true, true, 0, 0), NOW, u64::MAX).is_ok());
+        assert!(require_can_commit(&scope(true, true, 500, 0), NOW, 500).is_ok());
+        assert_eq!(
+            require_can_commit(&scope(true, true, 500, 0), NOW, 501),
+            Err(RuleError::EscrowExceedsLimit)
+        );
+    }
+
+    #[test]
+    fn sub_agent_scope_cannot_exceed_parent() {
+        let parent = scope(true, true, 1_000, 0);
+        assert!(require_within_parent(&parent, &scope(true, true, 1_000, 0)).is_ok());
+        assert!(require_within_parent(&parent, &scope(false, false, 0, 0)).is_ok());
+        assert_eq!(
+            require_within_parent(&parent, &scope(true, true, 1_001, 0)),
+            Err(RuleError::SubAgentScopeExceedsParent)
+        );
+
+        let no_sign = scope(false, true, 0, 0);
+        assert_eq!(
+            require_within_parent(&no_sign, &scope(true, false, 0, 0)),
+            Err(RuleError::SubAgentScopeExceedsParent)
+        );
+        // Unlimited parent allows any child limit
+        assert!(require_within_parent(&no_sign, &scope(false, true, u64::MAX, 0)).is_ok());
+
+        let no_commit = scope(true, false, 0, 0);
+        assert_eq!(
+            require_within_parent(&no_commit, &scope(true, true, 0, 0)),
+            Err(RuleError::SubAgentScopeExceedsParent)
+        );
+    }
+
+    #[test]
+    fn sub_agent_registration() {
+        let parent = scope(true, false, 0, 0);
+        let child = scope(true, false, 0, 0);
+        assert!(validate_sub_agent(&parent, false, &child, NOW).is_ok());
+        assert_eq!(
+            validate_sub_agent(&parent, true, &child, NOW),
+            Err(RuleError::MaxDelegationDepth)
+        );
+        assert_eq!(
+            validate_sub_agent(&scope(true, false, 0, NOW), false, &child, NOW),
+            Err(RuleError::DelegationExpired)
+        );
+        assert_eq!(
+            validate_sub_agent(&parent, false, &scope(true, false, 0, NOW), NOW),
+            Err(RuleError::ScopeExpired)
+        );
+    }
+}
@@ -0,0 +1,203 @@
+use crate::constants::*;
+use crate::error::RuleError;
+
+/// Declares a `#[repr(u8)]` enum over the on-chain u8 constants,
+/// with `TryFrom<u8>` (unknown values → `$err`) and `From<Enum> for u8`.
+macro_rules! u8_enum {
+    ($(#[$meta:meta])* $name:ident, $err:expr, { $($variant:ident = $value:expr),+ $(,)? }) => {
+        $(#[$meta])*
+        #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+        #[repr(u8)]
+        pub enum $name {
+            $($variant = $value),+
+        }
+
+        impl TryFrom<u8> for $name {
+            type Error = RuleError;
+
+            fn try_from(value: u8) -> Result<Self, RuleError> {
+                match value {
+                    $(v if v == $value => Ok($name::$variant),)+
+                    _ => Err($err),
+                }
+            }
+        }
+
+        impl From<$name> for u8 {
+            fn from(value: $name) -> u8 {
+                value as u8
+            }
+        }
+    };
+}
+
+u8_enum!(
+    /// Agreement lifecycle state, stored on-chain as `Agreement.status`.
+    AgreementStatus, RuleError::InvalidStatus, {
+        Proposed = STATUS_PROPOSED,
+        Active = STATUS_ACTIVE,
+        Fulfilled = STATUS_FULFILLED,
+        Breached = STATUS_BREACHED,
+        Disputed = STATUS_DISPUTED,
+        Cancelled = STATUS_CANCELLED,
+        Rejected = STATUS_REJECTED,
+        Terminated = STATUS_TERMINATED,
+    }
+);
+
+u8_enum!(
+    /// Role of a party within an agreement, stored as `AgreementParty.role`.
+    PartyRole, RuleError::InvalidRole, {
+        Proposer = ROLE_PROPOSER,
+        Counterparty = ROLE_COUNTERPARTY,
+        Witness = ROLE_WITNESS,
+        Arbitrator = ROLE_ARBITRATOR,
+    }
+);
+
+u8_enum!(
+    AgreementType, RuleError::InvalidAgreementType, {
+        Safe = AGREEMENT_TYPE_SAFE,
+        Service = AGREEMENT_TYPE_SERVICE,
+        RevenueShare = AGREEMENT_TYPE_REVENUE_SHARE,
+        JointVenture = AGREEMENT_TYPE_JOINT_VENTURE,
+        Custom = AGREEMENT_TYPE_CUSTOM,
+    }
+);
+
+u8_enum!(
+    Visibility, RuleError::InvalidVisibility, {
+        Public = VISIBILITY_PUBLIC,
+        Private = VISIBILITY_PRIVATE,
+    }
+);
+
+/// Allowed status transitions:
+///
+/// ```text
+/// Proposed ── sign (all parties) ──→ Active ── fulfill ──→ Fulfilled
+///    │                                 ├──────────────────→ Breached  (external)
+///    ├── cancel ──→ Cancelled          ├──────────────────→ Disputed  (external)
+///    └── decline (any invited party)   └── terminate (all parties) ──→ Terminated
+///           ──→ Rejected
+/// ```
+const TRANSITIONS: &[(AgreementStatus, AgreementStatus)] = &[
+    (AgreementStatus::Proposed, AgreementStatus::Active),
+    (AgreementStatus::Proposed, AgreementStatus::Cancelled),
+    (AgreementStatus::Proposed, AgreementStatus::Rejected),
+    (AgreementStatus::Active, AgreementStatus::Fulfilled),
+    (AgreementStatus::Active, AgreementStatus::Breached),
+    (AgreementStatus::Active, AgreementStatus::Disputed),
+    (AgreementStatus::Active, AgreementStatus::Terminated),
+];
+
+impl AgreementStatus {
+    pub fn can_transition_to(self, to: AgreementStatus) -> bool {
+        TRANSITIONS.contains(&(self, to))
+    }
+
+    /// Terminal states whose accounts may be closed to reclaim rent.
+    pub fn is_closable(self) -> bool {
+        matches!(
+            self,
+            AgreementStatus::Fulfilled
+                | AgreementStatus::Cancelled
+                | AgreementStatus::Breached
+                | AgreementStatus::Rejected
+                | AgreementStatus::Terminated
+        )
+    }
+}
+
+/// `true` if the raw on-chain status `from` may move to `to`.
+pub fn can_transition(from: u8, to: u8) -> bool {
+    match (AgreementStatus::try_from(from), AgreementStatus::try_from(to)) {
+        (Ok(from), Ok(to)) => from.can_transition_to(to),
+        _ => false,
+    }
+}
+
+pub fn require_transition(from: u8, to: u8) -> Result<(), RuleError> {
+    if can_transition(from, to) {
+        Ok(())
+    } else {
+        Err(RuleError::InvalidStatus)
+    }
+}
+
+pub fn is_closable(status: u8) -> bool {
+    AgreementStatus::try_from(status).is_ok_and(AgreementStatus::is_closable)
+}
+
+/// Agreement `expires_at` (0 = never) must still be in the future.
+pub fn require_agreement_not_expired(expires_at: i64, now: i64) -> Result<(), RuleError> {
+    if expires_at != 0 && expires_at <= now {
+        return Err(RuleError::AgreementExpired);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn enums_roundtrip_through_u8() {
+        for v in 0..=STATUS_TERMINATED {
+            assert_eq!(u8::from(AgreementStatus::try_from(v).unwrap()), v);
+        }
+        for v in 0..=MAX_ROLE {
+            assert_eq!(u8::from(PartyRole::try_from(v).unwrap()), v);
+        }
+        for v in 0..=MAX_AGREEMENT_TYPE {
+            assert_eq!(u8::from(AgreementType::try_from(v).unwrap()), v);
+        }
+        assert_eq!(AgreementStatus::try_from(STATUS_TERMINATED + 1), Err(RuleError::InvalidStatus));
+        assert_eq!(PartyRole::try_from(MAX_ROLE + 1), Err(RuleError::InvalidRole));
+        assert_eq!(
+            AgreementType::try_from(MAX_AGREEMENT_TYPE + 1),
+            Err(RuleError::InvalidAgreementType)
+        );
+        assert_eq!(Visibility::try_from(2), Err(RuleError::InvalidVisibility));
+    }
+
+    #[test]
+    fn transition_table() {
+        assert!(can_transition(STATUS_PROPOSED, STATUS_ACTIVE));
+        assert!(can_transition(STATUS_PROPOSED, STATUS_CANCELLED));
+        assert!(can_transition(STATUS_PROPOSED, STATUS_REJECTED));
+        assert!(can_transition(STATUS_ACTIVE, STATUS_FULFILLED));
+
+        assert!(!can_transition(STATUS_PROPOSED, STATUS_FULFILLED));
+        assert!(!can_transition(STATUS_ACTIVE, STATUS_CANCELLED));
+        assert!(!can_transition(STATUS_FULFILLED, STATUS_ACTIVE));
+        assert!(!can_transition(STATUS_CANCELLED, STATUS_PROPOSED));
+        assert!(!can_transition(STATUS_ACTIVE, STATUS_REJECTED));
+        assert!(can_transition(STATUS_ACTIVE, STATUS_TERMINATED));
+        assert!(!can_transition(STATUS_PROPOSED, STATUS_TERMINATED));
+        assert!(!can_transition(STATUS_DISPUTED, STATUS_FULFILLED));
+        assert!(!can_transition(42, STATUS_ACTIVE));
+
+        assert_eq!(require_transition(STATUS_FULFILLED, STATUS_FULFILLED), Err(RuleError::InvalidStatus));
+    }
+
+    #[test]
+    fn only_terminal_states_are_closable() {
+        assert!(is_closable(STATUS_FULFILLED));
+        assert!(is_closable(STATUS_CANCELLED));
+        assert!(is_closable(STATUS_BREACHED));
+        assert!(is_closable(STATUS_REJECTED));
+        assert!(is_closable(STATUS_TERMINATED));
+        assert!(!is_closable(STATUS_PROPOSED));
+        assert!(!is_closable(STATUS_ACTIVE));
+        assert!(!is_closable(STATUS_DISPUTED));
+        assert!(!is_closable(42));
+    }
+
+    #[test]
+    fn agreement_expiry() {
+        assert!(require_agreement_not_expired(0, i64::MAX).is_ok());
+        assert!(require_agreement_not_expired(101, 100).is_ok());
+        assert_eq!(require_agreement_not_expired(100, 100), Err(RuleError::AgreementExpired));
+    }
+}
@@ -0,0 +1,63 @@
+use crate::error::RuleError;
+
+/// A stream vests `amount` from `start_at` until `end_at`, the agreement's
+/// expiry, so the agreement must expire and not before the stream starts.
+pub fn validate_stream(amount: u64, start_at: i64, end_at: i64) -> Result<(), RuleError> {
+    if amount == 0 || end_at <= start_at {
+        return Err(RuleError::InvalidStream);
+    }
+    Ok(())
+}
+
+/// Lamports of `amount` vested at `at`, linearly between `start_at` and
+/// `end_at`.
+pub fn vested_amount(amount: u64, start_at: i64, end_at: i64, at: i64) -> u64 {
+    if at <= start_at {
+        return 0;
+    }
+    if at >= end_at {
+        return amount;
+    }
+    let elapsed = (at - start_at) as u128;
+    let duration = (end_at - start_at) as u128;
+    (amount as u128 * elapsed / duration) as u64
+}
+
+/// Splits what is left of a stream when it stops vesting at `cutoff`:
+/// `(to_payee, to_payer)` — the vested part not yet claimed, and the unvested
+/// remainder.
+pub fn split_at(amount: u64, claimed: u64, start_at: i64, end_at: i64, cutoff: i64) -> (u64, u64) {
+    let vested = vested_amount(amount, start_at, end_at, cutoff).max(claimed);
+    (vested - claimed, amount - vested)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn stream_needs_amount_and_window() {
+        assert!(validate_stream(1, 100, 200).is_ok());
+        assert_eq!(validate_stream(0, 100, 200), Err(RuleError::InvalidStream));
+        assert_eq!(validate_stream(1, 100, 100), Err(RuleError::InvalidStream));
+        assert_eq!(validate_stream(1, 100, 0), Err(RuleError::InvalidStream));
+    }
+
+    #[test]
+    fn vests_linearly() {
+        assert_eq!(vested_amount(1_000, 100, 200, 50), 0);
+        assert_eq!(vested_amount(1_000, 100, 200, 100), 0);
+        assert_eq!(vested_amount(1_000, 100, 200, 125), 250);
+        assert_eq!(vested_amount(1_000, 100, 200, 199), 990);
+        assert_eq!(vested_amount(1_000, 100, 200, 500), 1_000);
+        assert_eq!(vested_amount(u64::MAX, 0, 4, 2), u64::MAX / 2);
+    }
+
+    #[test]
+    fn split_returns_the_unvested_part() {
+        assert_eq!(split_at(1_000, 0, 100, 200, 140), (400, 600));
+        assert_eq!(split_at(1_000, 300, 100, 200, 140), (100, 600));
+        assert_eq!(split_at(1_000, 300, 100, 200, 300), (700, 0));
+        assert_eq!(split_at(1_000, 0, 100, 200, 100), (0, 1_000));
+    }
+}
@@ -0,0 +1,704 @@
+//! Transaction → [`Event`]s.
+//!
+//! V1 events are Anchor `emit!` logs (`Program data: <base64>`) attributed to the
+//! V1 program by tracking the invoke stack. aap-compressed only logs a message,
+//! so its state changes are decoded from top-level instruction data, which
+//! carries the current account state the program was handed.
+
+use aap_compressed::instruction as cix;
+use agent_agreement_protocol::constants::EVENT_VERSION;
+use agent_agreement_protocol::events::*;
+use agent_agreement_protocol::state::{DelegationScope, SettlementShare};
+use anchor_lang::{AnchorDeserialize, Discriminator};
+use base64::Engine;
+use serde_json::{json, Value};
+use solana_sdk::pubkey::Pubkey;
+
+use crate::record::TxRecord;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Program {
+    V1,
+    Compressed,
+}
+
+impl Program {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Program::V1 => "v1",
+            Program::Compressed => "compressed",
+        }
+    }
+}
+
+/// One protocol state change, normalized for storage.
+///
+/// `agent` is the agent the event is about, keyed the way each program keys
+/// agents: the AgentIdentity PDA for V1 (the raw pubkey for `add_party_direct`
+/// parties), the agent key for compressed.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Event {
+    pub program: Program,
+    pub kind: &'static str,
+    pub agreement_id: Option<[u8; 16]>,
+    pub agent: Option<Pubkey>,
+    pub role: Option<u8>,
+    pub amount: Option<u64>,
+    pub data: Value,
+}
+
+impl Event {
+    fn new(program: Program, kind: &'static str) -> Self {
+        Event {
+            program,
+            kind,
+            agreement_id: None,
+            agent: None,
+            role: None,
+            amount: None,
+            data: Value::Null,
+        }
+    }
+
+    fn agreement(mut self, id: [u8; 16]) -> Self {
+        self.agreement_id = Some(id);
+        self
+    }
+
+    fn agent(mut self, agent: Pubkey) -> Self {
+        self.agent = Some(agent);
+        self
+    }
+
+    fn data(mut self, data: Value) -> Self {
+        self.data = data;
+        self
+    }
+}
+
+/// All events in `tx`, in execution order. Failed transactions yield none.
+pub fn decode_transaction(tx: &TxRecord) -> Vec<Event> {
+    if tx.failed {
+        return vec![];
+    }
+    let v1 = agent_agreement_protocol::ID.to_string();
+    let compressed = aap_compressed::ID.to_string();
+
+    let mut events = vec![];
+    let mut stack: Vec<&str> = vec![];
+    let mut top_level = 0usize;
+    for line in &tx.logs {
+        if let Some(rest) = line.strip_prefix("Program ") {
+            if let Some((program, depth)) = rest.split_once(" invoke [") {
+                stack.push(program);
+                if depth == "1]" {
+                    // aap-compressed changes are attributed to the instruction's invoke
+                    if program == compressed {
+                        if let Some(ix) = tx.instructions.get(top_level) {
+                            events.extend(decode_compressed_instruction(&ix.data));
+                        }
+                    }
+                    top_level += 1;
+                }
+                continue;
+            }
+            if rest.ends_with(" success") || rest.contains(" failed: ") {
+                stack.pop();
+                continue;
+            }
+            if let Some(data) = rest.strip_prefix("data: ") {
+                if stack.last() == Some(&v1.as_str()) {
+                    if let Ok(bytes) = base64::engine::general_purpose::STANDARD.decode(data) {
+                        events.extend(decode_v1_event(&bytes));
+                    }
+                }
+            }
+        }
+    }
+    events
+}
+
+// ============================================================
+// V1
+// ============================================================
+
+macro_rules! match_events {
+    ($bytes:expr, $( $ty:ident => |$e:ident| $body:expr ),+ $(,)?) => {{
+        $(
+            if $bytes.starts_with($ty::DISCRIMINATOR) {
+                // Other layouts (and pre-versioning events) are skipped, not misread
+                if $bytes.get($ty::DISCRIMINATOR.len()) != Some(&EVENT_VERSION) {
+                    return None;
+                }
+                let $e = $ty::deserialize(&mut &$bytes[$ty::DISCRIMINATOR.len()..]).ok()?;
+                return Some($body);
+            }
+        )+
+        None
+    }};
+}
+
+fn scope(s: &DelegationScope) -> Value {
+    json!({
+        "can_sign_agreements": s.can_sign_agreements,
+        "can_commit_funds": s.can_commit_funds,
+        "max_commit_lamports": s.max_commit_lamports,
+        "expires_at": s.expires_at,
+    })
+}
+
+fn shares(shares: &[SettlementShare]) -> Value {
+    shares
+        .iter()
+        .map(|s| json!({ "party": s.party.to_string(), "amount": s.amount }))
+        .collect()
+}
+
+/// Decode one `emit!` payload (discriminator + borsh). Every event carries its
+/// layout version, signer and timestamp; they lead the stored `data`.
+pub fn decode_v1_event(bytes: &[u8]) -> Option<Event> {
+    let ev = |kind| Event::new(Program::V1, kind);
+    macro_rules! data {
+        ($e:ident, { $($k:literal: $v:expr),* $(,)? }) => {
+            json!({
+                "version": $e.version,
+                "actor": $e.actor.to_string(),
+                "timestamp": $e.timestamp,
+                $($k: $v,)*
+            })
+        };
+    }
+    match_events!(bytes,
+        AgentRegistered => |e| ev("AgentRegistered").agent(e.agent_identity).data(data!(e, {
+            "authority": e.authority.to_string(),
+            "agent_key": e.agent_key.to_string(),
+            "parent": e.parent.to_string(),
+            "metadata_hash": hex::encode(e.metadata_hash),
+            "scope": scope(&e.scope),
+        })),
+        AgentRevoked => |e| ev("AgentRevoked").agent(e.agent_identity).data(data!(e, {
+            "authority": e.authority.to_string(),
+            "agent_key": e.agent_key.to_string(),
+        })),
+        AgentKeyRotated => |e| ev("AgentKeyRotated").agent(e.agent_identity).data(data!(e, {
+            "authority": e.authority.to_string(),
+            "previous_agent_key": e.previous_agent_key.to_string(),
+            "agent_key": e.agent_key.to_string(),
+            "co_signed": e.co_signed,
+        })),
+        AuthorityTransferProposed => |e| ev("AuthorityTransferProposed").agent(e.agent_identity).data(data!(e, {
+            "authority": e.authority.to_string(),
+            "new_authority": e.new_authority.to_string(),
+        })),
+        AuthorityTransferCancelled => |e| ev("AuthorityTransferCancelled").agent(e.agent_identity).data(data!(e, {
+            "authority": e.authority.to_string(),
+            "new_authority": e.new_authority.to_string(),
+        })),
+        AuthorityTransferred => |e| ev("AuthorityTransferred").agent(e.agent_identity).data(data!(e, {
+            "previous_authority": e.previous_authority.to_string(),
+            "authority": e.authority.to_string(),
+            "vault": e.vault,
+            "sub_agents": e.sub_agents,
+        })),
+        // Authority-wide freezes cover agents only through their authority
+        FreezeApplied => |e| {
+            let event = ev("FreezeApplied").data(data!(e, {
+                "target": e.target.to_string(),
+                "authority": e.authority.to_string(),
+                "authority_wide": e.authority_wide,
+            }));
+            if e.authority_wide { event } else { event.agent(e.target) }
+        },
+        FreezeLifted => |e| {
+            let event = ev("FreezeLifted").data(data!(e, {
+                "target": e.target.to_string(),
+                "authority": e.authority.to_string(),
+                "authority_wide": e.authority_wide,
+                "frozen_at": e.frozen_at,
+            }));
+            if e.authority_wide { event } else { event.agent(e.target) }
+        },
+        // Authority sets aren't agents; the identities they act on get their own events
+        AuthoritySetCreated => |e| ev("AuthoritySetCreated").data(data!(e, {
+            "authority_set": e.authority_set.to_string(),
+            "signer": e.signer.to_string(),
+            "set_id": hex::encode(e.set_id),
+            "members": e.members.iter().map(|m| m.to_string()).collect::<Vec<_>>(),
+            "threshold": e.threshold,
+        })),
+        AuthorityActionProposed => |e| ev("AuthorityActionProposed").data(data!(e, {
+            "authority_set": e.authority_set.to_string(),
+            "action": e.action.to_string(),
+            "index": e.index,
+            "discriminator": hex::encode(e.discriminator),
+            "approvals": e.approvals,
+        })),
+        AuthorityActionApproved => |e| ev("AuthorityActionApproved").data(data!(e, {
+            "authority_set": e.authority_set.to_string(),
+            "action": e.action.to_string(),
+            "index": e.index,
+            "approvals": e.approvals,
+            "threshold": e.threshold,
+        })),
+        AuthorityActionExecuted => |e| ev("AuthorityActionExecuted").data(data!(e, {
+            "authority_set": e.authority_set.to_string(),
+            "action": e.action.to_string(),
+            "index": e.index,
+            "approvals": e.approvals,
+        })),
+        AuthorityActionCancelled => |e| ev("AuthorityActionCancelled").data(data!(e, {
+            "authority_set": e.authority_set.to_string(),
+            "action": e.action.to_string(),
+            "index": e.index,
+        })),
+        ConfigUpdated => |e| ev("ConfigUpdated").data(data!(e, {
+            "admin": e.admin.to_string(),
+            "paused": e.paused,
+            "max_parties": e.max_parties,
+            "fee_lamports": e.fee_lamports,
+            "treasury": e.treasury.to_string(),
+        })),
+        DelegationUpdated => |e| ev("DelegationUpdated").agent(e.agent_identity).data(data!(e, {
+            "authority": e.authority.to_string(),
+            "agent_key": e.agent_key.to_string(),
+            "previous_scope": scope(&e.previous_scope),
+            "scope": scope(&e.scope),
+        })),
+        // The proposer is added and signed by `propose_agreement` itself
+        AgreementProposed => |e| Event {
+            role: Some(aap_core::constants::ROLE_PROPOSER),
+            ..ev("AgreementProposed").agreement(e.agreement_id).agent(e.proposer).data(data!(e, {
+                "agreement_pda": e.agreement_pda.to_string(),
+                "agreement_type": e.agreement_type,
+                "visibility": e.visibility,
+                "terms_hash": hex::encode(e.terms_hash),
+                "terms_uri": String::from_utf8_lossy(&e.terms_uri).trim_end_matches('\0'),
+                "num_parties": e.num_parties,
+                "expires_at": e.expires_at,
+                "status": e.status,
+            }))
+        },
+        PartyAdded => |e| Event {
+            role: Some(e.role),
+            ..ev("PartyAdded").agreement(e.agreement_id).agent(e.party).data(data!(e, {
+                "direct": e.direct,
+                "parties_added": e.parties_added,
+            }))
+        },
+        PartyRemoved => |e| Event {
+            role: Some(e.role),
+            ..ev("PartyRemoved").agreement(e.agreement_id).agent(e.party).data(data!(e, {
+                "parties_added": e.parties_added,
+            }))
+        },
+        PartyCountChanged => |e| ev("PartyCountChanged").agreement(e.agreement_id).data(data!(e, {
+            "previous_num_parties": e.previous_num_parties,
+            "num_parties": e.num_parties,
+            "parties_added": e.parties_added,
+            "num_signed": e.num_signed,
+        })),
+        AgreementSigned => |e| ev("AgreementSigned").agreement(e.agreement_id).agent(e.party).data(data!(e, {
+            "direct": e.direct,
+            "num_signed": e.num_signed,
+            "num_parties": e.num_parties,
+        })),
+        AgreementActivated => |e| ev("AgreementActivated").agreement(e.agreement_id).data(data!(e, {
+            "previous_status": e.previous_status,
+            "status": e.status,
+        })),
+        AgreementCancelled => |e| ev("AgreementCancelled").agreement(e.agreement_id).data(data!(e, {
+            "previous_status": e.previous_status,
+            "status": e.status,
+        })),
+        AgreementDeclined => |e| ev("AgreementDeclined").agreement(e.agreement_id).agent(e.party).data(data!(e, {
+            "direct": e.direct,
+            "previous_status": e.previous_status,
+            "status": e.status,
+        })),
+        AgreementFulfilled => |e| ev("AgreementFulfilled").agreement(e.agreement_id).data(data!(e, {
+            "previous_status": e.previous_status,
+            "status": e.status,
+        })),
+        AgreementClosed => |e| ev("AgreementClosed").agreement(e.agreement_id).agent(e.party).data(data!(e, {
+            "status": e.status,
+        })),
+        AmendmentProposed => |e| ev("AmendmentProposed").agreement(e.agreement_id).agent(e.proposer).data(data!(e, {
+            "amendment": e.amendment.to_string(),
+            "amendment_version": e.amendment_version,
+            "phase": e.phase,
+            "previous_terms_hash": hex::encode(e.previous_terms_hash),
+            "terms_hash": hex::encode(e.terms_hash),
+            "terms_uri": String::from_utf8_lossy(&e.terms_uri).trim_end_matches('\0'),
+            "expires_at": e.expires_at,
+            "required": e.required,
+        })),
+        AmendmentSigned => |e| ev("AmendmentSigned").agreement(e.agreement_id).agent(e.party).data(data!(e, {
+            "amendment": e.amendment.to_string(),
+            "amendment_version": e.amendment_version,
+            "direct": e.direct,
+            "approvals": e.approvals,
+            "required": e.required,
+        })),
+        AmendmentApplied => |e| ev("AmendmentApplied").agreement(e.agreement_id).data(data!(e, {
+            "amendment": e.amendment.to_string(),
+            "amendment_version": e.amendment_version,
+            "previous_terms_hash": hex::encode(e.previous_terms_hash),
+            "terms_hash": hex::encode(e.terms_hash),
+            "signatures_reset": e.signatures_reset,
+        })),
+        AmendmentWithdrawn => |e| ev("AmendmentWithdrawn").agreement(e.agreement_id).data(data!(e, {
+            "amendment": e.amendment.to_string(),
+            "amendment_version": e.amendment_version,
+        })),
+        CounterProposed => |e| ev("CounterProposed").agreement(e.agreement_id).agent(e.party).data(data!(e, {
+            "counter": e.counter.to_string(),
+            "round": e.round,
+            "base_terms_hash": hex::encode(e.base_terms_hash),
+            "terms_hash": hex::encode(e.terms_hash),
+            "terms_uri": String::from_utf8_lossy(&e.terms_uri).trim_end_matches('\0'),
+            "expires_at": e.expires_at,
+        })),
+        CounterProposalAccepted => |e| ev("CounterProposalAccepted").agreement(e.agreement_id).agent(e.party).data(data!(e, {
+            "counter": e.counter.to_string(),
+            "round": e.round,
+            "amendment": e.amendment.to_string(),
+            "amendment_version": e.amendment_version,
+        })),
+        CounterProposalRejected => |e| ev("CounterProposalRejected").agreement(e.agreement_id).agent(e.party).data(data!(e, {
+            "counter": e.counter.to_string(),
+            "round": e.round,
+        })),
+        EscrowFunded => |e| Event { amount: Some(e.amount), ..ev("EscrowFunded").agreement(e.agreement_id).agent(e.party).data(data!(e, {
+            "party_escrow": e.party_escrow,
+            "escrow_total": e.escrow_total,
+        })) },
+        EscrowReleased => |e| Event { amount: Some(e.amount), ..ev("EscrowReleased").agreement(e.agreement_id).agent(e.party).data(data!(e, {
+            "recipient": e.recipient.to_string(),
+            "escrow_total": e.escrow_total,
+            "status": e.status,
+        })) },
+        TerminationProposed => |e| ev("TerminationProposed").agreement(e.agreement_id).agent(e.party).data(data!(e, {
+            "termination": e.termination.to_string(),
+            "shares": shares(&e.shares),
+            "required": e.required,
+        })),
+        TerminationApproved => |e| ev("TerminationApproved").agreement(e.agreement_id).agent(e.party).data(data!(e, {
+            "termination": e.termination.to_string(),
+            "direct": e.direct,
+            "approvals": e.approvals,
+            "required": e.required,
+        })),
+        TerminationCancelled => |e| ev("TerminationCancelled").agreement(e.agreement_id).data(data!(e, {
+            "termination": e.termination.to_string(),
+        })),
+        AgreementTerminated => |e| ev("AgreementTerminated").agreement(e.agreement_id).data(data!(e, {
+            "termination": e.termination.to_string(),
+            "previous_status": e.previous_status,
+            "status": e.status,
+            "escrow_total": e.escrow_total,
+        })),
+        MilestonesSet => |e| ev("MilestonesSet").agreement(e.agreement_id).data(data!(e, {
+            "schedule": e.schedule.to_string(),
+            "milestones": e.milestones.iter().map(|m| json!({
+                "payer": m.payer.to_string(),
+                "payee": m.payee.to_string(),
+                "description_hash": hex::encode(m.description_hash),
+                "amount": m.amount,
+                "due_at": m.due_at,
+            })).collect::<Vec<_>>(),
+        })),
+        MilestoneSubmitted => |e| ev("MilestoneSubmitted").agreement(e.agreement_id).agent(e.party).data(data!(e, {
+            "index": e.index,
+            "previous_status": e.previous_status,
+        })),
+        MilestoneApproved => |e| ev("MilestoneApproved").agreement(e.agreement_id).agent(e.party).data(data!(e, {
+            "index": e.index,
+            "previous_status": e.previous_status,
+        })),
+        MilestoneDisputed => |e| ev("MilestoneDisputed").agreement(e.agreement_id).agent(e.party).data(data!(e, {
+            "index": e.index,
+            "previous_status": e.previous_status,
+            "overdue": e.overdue,
+        })),
+        MilestoneReleased => |e| Event { amount: Some(e.amount), ..ev("MilestoneReleased").agreement(e.agreement_id).agent(e.payee).data(data!(e, {
+            "index": e.index,
+            "payer": e.payer.to_string(),
+            "recipient": e.recipient.to_string(),
+            "released": e.released,
+            "escrow_total": e.escrow_total,
+        })) },
+        RecurringPaymentSet => |e| ev("RecurringPaymentSet").agreement(e.agreement_id).data(data!(e, {
+            "recurring": e.recurring.to_string(),
+            "payer": e.terms.payer.to_string(),
+            "payee": e.terms.payee.to_string(),
+            "amount": e.terms.amount,
+            "period": e.terms.period,
+            "num_periods": e.terms.num_periods,
+            "start_at": e.terms.start_at,
+        })),
+        RecurringPaymentCranked => |e| Event { amount: Some(e.amount), ..ev("RecurringPaymentCranked").agreement(e.agreement_id).agent(e.payer).data(data!(e, {
+            "recurring": e.recurring.to_string(),
+            "payee": e.payee.to_string(),
+            "recipient": e.recipient.to_string(),
+            "period_index": e.period_index,
+            "due_at": e.due_at,
+            "paid": e.paid,
+            "periods_paid": e.periods_paid,
+            "periods_missed": e.periods_missed,
+            "status": e.status,
+        })) },
+        RecurringPaymentCancelled => |e| ev("RecurringPaymentCancelled").agreement(e.agreement_id).agent(e.party).data(data!(e, {
+            "recurring": e.recurring.to_string(),
+            "periods_paid": e.periods_paid,
+            "periods_missed": e.periods_missed,
+        })),
+        StreamFunded => |e| Event { amount: Some(e.amount), ..ev("StreamFunded").agreement(e.agreement_id).agent(e.payer).data(data!(e, {
+            "stream": e.stream.to_string(),
+            "payee": e.payee.to_string(),
+            "start_at": e.start_at,
+            "end_at": e.end_at,
+        })) },
+        VestedClaimed => |e| Event { amount: Some(e.amount), ..ev("VestedClaimed").agreement(e.agreement_id).agent(e.payee).data(data!(e, {
+            "stream": e.stream.to_string(),
+            "recipient": e.recipient.to_string(),
+            "claimed": e.claimed,
+        })) },
+        StreamEnded => |e| ev("StreamEnded").agreement(e.agreement_id).data(data!(e, {
+            "stream": e.stream.to_string(),
+            "status": e.status,
+            "to_payee": e.to_payee,
+            "to_payer": e.to_payer,
+            "claimed": e.claimed,
+        })),
+        RevenueShareSet => |e| ev("RevenueShareSet").agreement(e.agreement_id).data(data!(e, {
+            "revenue_share": e.revenue_share.to_string(),
+            "receiver": e.receiver.to_string(),
+            "splits": e.splits.iter().map(|s| json!({
+                "party": s.party.to_string(),
+                "bps": s.bps,
+            })).collect::<Vec<_>>(),
+        })),
+        RevenueDistributed => |e| Event { amount: Some(e.amount), ..ev("RevenueDistributed").agreement(e.agreement_id).agent(e.party).data(data!(e, {
+            "receiver": e.receiver.to_string(),
+            "recipient": e.recipient.to_string(),
+            "mint": e.mint.to_string(),
+            "bps": e.bps,
+        })) },
+        SafeSet => |e| ev("SafeSet").agreement(e.agreement_id).data(data!(e, {
+            "safe": e.safe.to_string(),
+            "investor": e.terms.investor.to_string(),
+            "company": e.terms.company.to_string(),
+            "investment_amount": e.terms.investment_amount,
+            "valuation_cap": e.terms.valuation_cap,
+            "discount_bps": e.terms.discount_bps,
+            "mint": e.terms.mint.to_string(),
+        })),
+        SafeFunded => |e| Event { amount: Some(e.amount), ..ev("SafeFunded").agreement(e.agreement_id).agent(e.investor).data(data!(e, {
+            "safe": e.safe.to_string(),
+            "escrow_total": e.escrow_total,
+        })) },
+        SafeConverted => |e| Event { amount: Some(e.amount), ..ev("SafeConverted").agreement(e.agreement_id).agent(e.company).data(data!(e, {
+            "safe": e.safe.to_string(),
+            "recipient": e.recipient.to_string(),
+            "mint": e.mint.to_string(),
+            "round_valuation": e.round_valuation,
+            "conversion_valuation": e.conversion_valuation,
+        })) },
+        SafeRefunded => |e| Event { amount: Some(e.amount), ..ev("SafeRefunded").agreement(e.agreement_id).agent(e.party).data(data!(e, {
+            "safe": e.safe.to_string(),
+            "recipient": e.recipient.to_string(),
+        })) },
+        VaultDeposit => |e| Event { amount: Some(e.amount), ..ev("VaultDeposit").agent(e.agent_identity).data(data!(e, {
+            "authority": e.authority.to_string(),
+            "balance": e.new_balance,
+            "total_deposited": e.total_deposited,
+        })) },
+        VaultWithdraw => |e| Event { amount: Some(e.amount), ..ev("VaultWithdraw").agent(e.agent_identity).data(data!(e, {
+            "authority": e.authority.to_string(),
+            "balance": e.remaining_balance,
+            "total_withdrawn": e.total_withdrawn,
+        })) },
+        AgentMigrated => |e| ev("AgentMigrated").agent(e.agent_identity).data(data!(e, {
+            "authority": e.authority.to_string(),
+            "agent_key": e.agent_key.to_string(),
+        })),
+        AgreementMigrated => |e| ev("AgreementMigrated").agreement(e.agreement_id).data(data!(e, {
+            "agreement_pda": e.agreement_pda.to_string(),
+            "status": e.status,
+        })),
+        PartyMigrated => |e| Event {
+            role: Some(e.role),
+            ..ev("PartyMigrated").agreement(e.agreement_id).agent(e.party).data(data!(e, {
+                "signed": e.signed,
+            }))
+        },
+        AgreementMaterialized => |e| ev("AgreementMaterialized").agreement(e.agreement_id).data(data!(e, {
+            "agreement_pda": e.agreement_pda.to_string(),
+            "status": e.status,
+        })),
+        PartyMaterialized => |e| Event {
+            role: Some(e.role),
+            ..ev("PartyMaterialized").agreement(e.agreement_id).agent(e.party).data(data!(e, {
+                "signed": e.signed,
+            }))
+        },
+    )
+}
+
+// ============================================================
+// Compressed
+// ============================================================
+
+macro_rules! match_instructions {
+    ($bytes:expr, $( $ty:ident => |$i:ident| $body:expr ),+ $(,)?) => {{
+        use anchor_lang_031::{AnchorDeserialize as _, Discriminator as _};
+        $(
+            if $bytes.starts_with(cix::$ty::DISCRIMINATOR) {
+                let Ok($i) = cix::$ty::deserialize(&mut &$bytes[cix::$ty::DISCRIMINATOR.len()..]) else {
+                    return vec![];
+                };
+                return $body;
+            }
+        )+
+        vec![]
+    }};
+}
+
+/// Decode one base58 aap-compressed instruction into the changes it makes.
+pub fn decode_compressed_instruction(data_b58: &str) -> Vec<Event> {
+    let Ok(bytes) = bs58::decode(data_b58).into_vec() else {
+        return vec![];
+    };
+    let ev = |kind| Event::new(Program::Compressed, kind);
+    match_instructions!(bytes,
+        RegisterAgent => |i| vec![ev("AgentRegistered").agent(Pubkey::new_from_array(i.agent_key))],
+        RegisterSubAgent => |i| vec![ev("AgentRegistered")
+            .agent(Pubkey::new_from_array(i.sub_agent_key))
+            .data(json!({ "parent_agent_key": i.parent_identity.agent_key.to_string() }))],
+        UpdateDelegation => |i| vec![ev("DelegationUpdated").agent(i.current_identity.agent_key)],
+        RevokeAgent => |i| vec![ev("AgentRevoked").agent(i.current_identity.agent_key).data(json!({
+            "authority": i.current_identity.authority.to_string(),
+        }))],
+        ProposeAgreement => |i| vec![
+            ev("AgreementProposed").agreement(i.agreement_id).agent(i.proposer_identity.agent_key),
+            // The proposer's party is created signed in the same instruction
+            Event { role: Some(aap_core::constants::ROLE_PROPOSER), ..ev("PartyAdded").agreement(i.agreement_id).agent(i.proposer_identity.agent_key) },
+            ev("AgreementSigned").agreement(i.agreement_id).agent(i.proposer_identity.agent_key),
+        ],
+        AddParty => |i| vec![Event {
+            role: Some(i.role),
+            ..ev("PartyAdded").agreement(i.current_agreement.agreement_id).agent(i.party_identity.agent_key)
+        }],
+        SignAgreement => |i| {
+            let id = i.current_agreement.agreement_id;
+            let mut out = vec![ev("AgreementSigned").agreement(id).agent(i.signer_identity.agent_key)];
+            if aap_core::parties::is_fully_signed(
+                i.current_agreement.num_signed.saturating_add(1),
+                i.current_agreement.num_parties,
+            ) {
+                out.push(ev("AgreementActivated").agreement(id));
+            }
+            out
+        },
+        CancelAgreement => |i| vec![ev("AgreementCancelled").agreement(i.current_agreement.agreement_id)],
+        FulfillAgreement => |i| vec![ev("AgreementFulfilled")
+            .agreement(i.current_agreement.agreement_id)
+            .agent(i.signer_identity.agent_key)],
+        CloseAgreement => |i| vec![ev("AgreementClosed")
+            .agreement(i.current_agreement.agreement_id)
+            .agent(i.signer_identity.agent_key)],
+        DecompressAgreement => |i| vec![ev("AgreementDecompressed").agreement(i.current_agreement.agreement_id)],
+        DecompressParty => |i| vec![ev("PartyDecompressed").agreement(i.agreement_id).agent(i.agent_key)],
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anchor_lang::Event as _;
+
+    fn logs_with(program: &Pubkey, payloads: &[Vec<u8>]) -> Vec<String> {
+        let mut logs = vec![format!("Program {program} invoke [1]")];
+        for p in payloads {
+            logs.push(format!(
+                "Program data: {}",
+                base64::engine::general_purpose::STANDARD.encode(p)
+            ));
+        }
+        logs.push(format!("Program {program} success"));
+        logs
+    }
+
+    fn record(logs: Vec<String>) -> TxRecord {
+        TxRecord {
+            signature: "sig".into(),
+            slot: 1,
+            block_time: None,
+            failed: false,
+            logs,
+            instructions: vec![],
+        }
+    }
+
+    fn signed(party: Pubkey) -> AgreementSigned {
+        AgreementSigned {
+            version: EVENT_VERSION,
+            agreement_id: [1u8; 16],
+            party,
+            actor: party,
+            direct: true,
+            num_signed: 2,
+            num_parties: 2,
+            timestamp: 1_700_000_000,
+        }
+    }
+
+    #[test]
+    fn decodes_v1_events_only_inside_v1_invocations() {
+        let party = Pubkey::new_unique();
+        let signed = signed(party).data();
+        let activated = AgreementActivated {
+            version: EVENT_VERSION,
+            agreement_id: [1u8; 16],
+            actor: party,
+            previous_status: aap_core::constants::STATUS_PROPOSED,
+            status: aap_core::constants::STATUS_ACTIVE,
+            timestamp: 1_700_000_000,
+        }
+        .data();
+
+        let events = decode_transaction(&record(logs_with(
+            &agent_agreement_protocol::ID,
+            &[signed.clone(), activated],
+        )));
+        assert_eq!(events.len(), 2);
+        assert_eq!(events[0].kind, "AgreementSigned");
+        assert_eq!(events[0].agent, Some(party));
+        assert_eq!(events[0].data["direct"], true);
+        assert_eq!(events[0].data["timestamp"], 1_700_000_000);
+        assert_eq!(events[1].kind, "AgreementActivated");
+        assert_eq!(events[1].data["previous_status"], 0);
+        assert_eq!(events[1].data["status"], 1);
+
+        // Same bytes logged by another program are ignored
+        let other = decode_transaction(&record(logs_with(&Pubkey::new_unique(), &[signed])));
+        assert!(other.is_empty());
+    }
+
+    #[test]
+    fn unknown_event_versions_are_skipped() {
+        let mut event = signed(Pubkey::new_unique());
+        event.version = EVENT_VERSION + 1;
+        assert!(decode_v1_event(&event.data()).is_none());
+    }
+
+    #[test]
+    fn failed_transactions_yield_nothing() {
+        let mut tx = record(logs_with(
+            &agent_agreement_protocol::ID,
+            &[signed(Pubkey::new_unique()).data()],
+        ));
+        tx.failed = true;
+        assert!(decode_transaction(&tx).is_empty());
+    }
+}
@@ -0,0 +1,20 @@
+//! Event indexer for the Agent Agreement Protocol.
+//!
+//! - [`record`] — the per-transaction input (`TxRecord`), serializable as a fixture
+//! - [`decode`] — V1 `emit!` logs and aap-compressed instruction data → [`decode::Event`]
+//! - [`store`] — SQLite tables and views: agreement status, parties, signatures, vault flows
+//! - `rpc` (feature `rpc`) — fetch transactions from a node and poll with finality tracking
+//!
+//! Events read at `confirmed` are kept only provisionally: each poll deletes
+//! everything above the stored finalized slot and fetches it again, so a
+//! dropped fork never leaves rows behind.
+
+pub mod decode;
+pub mod record;
+#[cfg(feature = "rpc")]
+pub mod rpc;
+pub mod store;
+
+pub use decode::{decode_transaction, Event, Program};
+pub use record::{RecordedInstruction, TxRecord};
+pub use store::Store;
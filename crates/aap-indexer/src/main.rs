@@ -0,0 +1,130 @@
+//! `aap-indexer` — index both AAP programs into SQLite and query the result.
+//!
+//! `run` polls the node until interrupted; the other commands read the
+//! database and print one JSON value on stdout.
+
+use std::path::PathBuf;
+use std::process::ExitCode;
+use std::time::Duration;
+
+use aap_indexer::rpc::poll;
+use aap_indexer::{Program, Store};
+use clap::{Parser, Subcommand, ValueEnum};
+use serde_json::{json, Value};
+use solana_rpc_client::nonblocking::rpc_client::RpcClient;
+
+type Result<T> = std::result::Result<T, Box<dyn std::error::Error>>;
+
+#[derive(Parser)]
+#[command(name = "aap-indexer", version, about = "Agent Agreement Protocol event indexer")]
+struct Cli {
+    /// SQLite database file (created if missing)
+    #[arg(long, env = "AAP_INDEXER_DB", default_value = "aap-indexer.db")]
+    db: PathBuf,
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Poll the node and index new transactions
+    Run {
+        #[arg(long, env = "AAP_RPC_URL", default_value = "https://api.devnet.solana.com")]
+        url: String,
+        /// Start after this slot on an empty database instead of indexing all history
+        #[arg(long)]
+        from_slot: Option<u64>,
+        /// Seconds between polls
+        #[arg(long, default_value_t = 10)]
+        interval: u64,
+        /// Poll once and exit
+        #[arg(long)]
+        once: bool,
+    },
+    /// Forget everything from SLOT on; the next `run` re-indexes it
+    Replay { slot: u64 },
+    /// Status, parties/signatures and event history of one agreement
+    Agreement {
+        /// 32 hex characters or a dashed UUID
+        #[arg(value_parser = agreement_id)]
+        id: [u8; 16],
+        #[arg(long, value_enum, default_value = "v1")]
+        program: ProgramArg,
+    },
+    /// Active agreements the agent is a party to
+    Active {
+        /// V1 AgentIdentity PDA or compressed agent key (base58)
+        agent: String,
+    },
+    /// Vault deposits and withdrawals of a V1 AgentIdentity PDA
+    Vault { agent_identity: String },
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum ProgramArg {
+    V1,
+    Compressed,
+}
+
+impl From<ProgramArg> for Program {
+    fn from(p: ProgramArg) -> Self {
+        match p {
+            ProgramArg::V1 => Program::V1,
+            ProgramArg::Compressed => Program::Compressed,
+        }
+    }
+}
+
+fn agreement_id(s: &str) -> std::result::Result<[u8; 16], String> {
+    let bytes = hex::decode(s.replace('-', "")).map_err(|e| e.to_string())?;
+    bytes
+        .try_into()
+        .map_err(|b: Vec<u8>| format!("expected 16 bytes of hex, got {}", b.len()))
+}
+
+async fn run(cli: Cli) -> Result<Value> {
+    let mut store = Store::open(&cli.db)?;
+    match cli.command {
+        Command::Run { url, from_slot, interval, once } => {
+            if let (None, Some(slot)) = (store.finalized_slot()?, from_slot) {
+                store.set_finalized_slot(slot)?;
+            }
+            let rpc = RpcClient::new(url);
+            loop {
+                let report = poll(&mut store, &rpc).await?;
+                if once {
+                    return Ok(serde_json::to_value(report)?);
+                }
+                eprintln!("{}", serde_json::to_string(&report)?);
+                tokio::time::sleep(Duration::from_secs(interval)).await;
+            }
+        }
+        Command::Replay { slot } => Ok(json!({ "deleted": store.replay_from(slot)? })),
+        Command::Agreement { id, program } => {
+            let program = program.into();
+            Ok(json!({
+                "agreement": store.agreement(program, &id)?,
+                "parties": store.signatures(program, &id)?,
+                "history": store.agreement_history(program, &id)?,
+            }))
+        }
+        Command::Active { agent } => Ok(serde_json::to_value(store.active_agreements(&agent)?)?),
+        Command::Vault { agent_identity } => {
+            Ok(serde_json::to_value(store.vault_flows(&agent_identity)?)?)
+        }
+    }
+}
+
+#[tokio::main]
+async fn main() -> ExitCode {
+    match run(Cli::parse()).await {
+        Ok(out) => {
+            println!("{out:#}");
+            ExitCode::SUCCESS
+        }
+        Err(e) => {
+            println!("{:#}", json!({ "error": e.to_string() }));
+            ExitCode::FAILURE
+        }
+    }
+}
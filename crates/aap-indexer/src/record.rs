@@ -0,0 +1,27 @@
+use serde::{Deserialize, Serialize};
+
+/// What the indexer needs from one transaction. Serializable so fetched
+/// transactions can be recorded as JSON fixtures and replayed offline.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct TxRecord {
+    pub signature: String,
+    pub slot: u64,
+    #[serde(default)]
+    pub block_time: Option<i64>,
+    /// Failed transactions emit logs but change no state; they are skipped.
+    #[serde(default)]
+    pub failed: bool,
+    pub logs: Vec<String>,
+    /// Top-level instructions in order. Needed for aap-compressed, which logs
+    /// only a message, so its state changes are read from instruction data.
+    #[serde(default)]
+    pub instructions: Vec<RecordedInstruction>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct RecordedInstruction {
+    /// Base58 program ID
+    pub program_id: String,
+    /// Base58 instruction data
+    pub data: String,
+}
@@ -0,0 +1,187 @@
+//! Fetch program transactions from a JSON-RPC node and keep a [`Store`] in
+//! sync with finality tracking.
+
+use std::fmt;
+
+use solana_rpc_client::nonblocking::rpc_client::RpcClient;
+use solana_rpc_client::rpc_client::GetConfirmedSignaturesForAddress2Config;
+use solana_rpc_client_api::client_error::Error as ClientError;
+use solana_rpc_client_api::config::RpcTransactionConfig;
+use solana_sdk::commitment_config::CommitmentConfig;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::Signature;
+use solana_transaction_status::{EncodedConfirmedTransactionWithStatusMeta, UiTransactionEncoding};
+
+use crate::record::{RecordedInstruction, TxRecord};
+use crate::store::Store;
+
+/// `getSignaturesForAddress` page size (the RPC maximum)
+const PAGE: usize = 1000;
+
+#[derive(Debug)]
+pub enum PollError {
+    Rpc(ClientError),
+    Store(rusqlite::Error),
+}
+
+impl fmt::Display for PollError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PollError::Rpc(e) => write!(f, "rpc error: {e}"),
+            PollError::Store(e) => write!(f, "store error: {e}"),
+        }
+    }
+}
+
+impl std::error::Error for PollError {}
+
+impl From<ClientError> for PollError {
+    fn from(e: ClientError) -> Self {
+        PollError::Rpc(e)
+    }
+}
+
+impl From<rusqlite::Error> for PollError {
+    fn from(e: rusqlite::Error) -> Self {
+        PollError::Store(e)
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, serde::Serialize)]
+pub struct PollReport {
+    pub finalized_slot: u64,
+    /// Provisional rows dropped before re-fetching
+    pub rolled_back: usize,
+    pub transactions: usize,
+    pub inserted: usize,
+}
+
+/// Both AAP programs.
+pub fn program_ids() -> [Pubkey; 2] {
+    [agent_agreement_protocol::ID, aap_compressed::ID]
+}
+
+/// Successful transactions that invoked any of `programs` in a slot above
+/// `after` (all history if `None`), oldest first, read at `confirmed`.
+pub async fn fetch_after(
+    rpc: &RpcClient,
+    programs: &[Pubkey],
+    after: Option<u64>,
+) -> Result<Vec<TxRecord>, ClientError> {
+    let mut found: Vec<(u64, Signature)> = vec![];
+    for program in programs {
+        let mut before = None;
+        loop {
+            let page = rpc
+                .get_signatures_for_address_with_config(
+                    program,
+                    GetConfirmedSignaturesForAddress2Config {
+                        before,
+                        until: None,
+                        limit: Some(PAGE),
+                        commitment: Some(CommitmentConfig::confirmed()),
+                    },
+                )
+                .await?;
+            let full = page.len() == PAGE;
+            let mut reached_cursor = false;
+            for status in page {
+                if after.is_some_and(|after| status.slot <= after) {
+                    reached_cursor = true;
+                    break;
+                }
+                let Ok(signature) = status.signature.parse::<Signature>() else {
+                    continue;
+                };
+                before = Some(signature);
+                if status.err.is_none() {
+                    found.push((status.slot, signature));
+                }
+            }
+            if reached_cursor || !full {
+                break;
+            }
+        }
+    }
+
+    // Pages are newest first; a transaction touching both programs appears twice
+    found.reverse();
+    found.sort_by_key(|(slot, _)| *slot);
+    let mut seen = std::collections::HashSet::new();
+    found.retain(|(_, signature)| seen.insert(*signature));
+
+    let mut records = Vec::with_capacity(found.len());
+    for (_, signature) in found {
+        let tx = rpc
+            .get_transaction_with_config(
+                &signature,
+                RpcTransactionConfig {
+                    encoding: Some(UiTransactionEncoding::Base64),
+                    commitment: Some(CommitmentConfig::confirmed()),
+                    max_supported_transaction_version: Some(0),
+                },
+            )
+            .await?;
+        records.push(to_record(signature, tx));
+    }
+    Ok(records)
+}
+
+/// Keep the parts of an RPC transaction the decoder reads.
+pub fn to_record(signature: Signature, tx: EncodedConfirmedTransactionWithStatusMeta) -> TxRecord {
+    let (failed, logs) = match tx.transaction.meta {
+        Some(meta) => (meta.err.is_some(), Option::from(meta.log_messages).unwrap_or_default()),
+        None => (false, vec![]),
+    };
+    // Program IDs cannot come from lookup tables, so static keys suffice
+    let instructions = tx
+        .transaction
+        .transaction
+        .decode()
+        .map(|versioned| {
+            let keys = versioned.message.static_account_keys();
+            versioned
+                .message
+                .instructions()
+                .iter()
+                .map(|ix| RecordedInstruction {
+                    program_id: keys
+                        .get(ix.program_id_index as usize)
+                        .map(|k| k.to_string())
+                        .unwrap_or_default(),
+                    data: bs58::encode(&ix.data).into_string(),
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+    TxRecord {
+        signature: signature.to_string(),
+        slot: tx.slot,
+        block_time: tx.block_time,
+        failed,
+        logs,
+        instructions,
+    }
+}
+
+/// One indexing round.
+///
+/// Reads the cluster's finalized slot first, drops rows above the stored
+/// finalized slot, re-fetches everything after it at `confirmed`, and then
+/// advances the stored slot. Rows above the new finalized slot stay
+/// provisional until a later round confirms them.
+pub async fn poll(store: &mut Store, rpc: &RpcClient) -> Result<PollReport, PollError> {
+    let finalized = rpc.get_slot_with_commitment(CommitmentConfig::finalized()).await?;
+    let from = store.finalized_slot()?;
+    let rolled_back = store.rollback_unfinalized()?;
+    let txs = fetch_after(rpc, &program_ids(), from).await?;
+    let inserted = store.ingest(&txs)?;
+    let finalized = from.map_or(finalized, |from| from.max(finalized));
+    store.set_finalized_slot(finalized)?;
+    Ok(PollReport {
+        finalized_slot: finalized,
+        rolled_back,
+        transactions: txs.len(),
+        inserted,
+    })
+}
@@ -0,0 +1,337 @@
+//! SQLite store.
+//!
+//! Only decoded events are written; agreement status, parties and per-agent
+//! views are SQL views over the `events` table. Rolling back a range of slots
+//! is therefore a single `DELETE`, and reprocessing a transaction is a no-op
+//! because rows are keyed by `(signature, idx)`.
+
+use std::path::Path;
+
+use rusqlite::{params, Connection, OptionalExtension, Row};
+use serde::Serialize;
+use serde_json::Value;
+
+use crate::decode::{decode_transaction, Program};
+use crate::record::TxRecord;
+
+pub type Result<T> = rusqlite::Result<T>;
+
+const SCHEMA: &str = "
+CREATE TABLE IF NOT EXISTS events (
+    signature    TEXT    NOT NULL,
+    idx          INTEGER NOT NULL,
+    slot         INTEGER NOT NULL,
+    block_time   INTEGER,
+    program      TEXT    NOT NULL,
+    kind         TEXT    NOT NULL,
+    agreement_id TEXT,
+    agent        TEXT,
+    role         INTEGER,
+    amount       INTEGER,
+    data         TEXT    NOT NULL,
+    PRIMARY KEY (signature, idx)
+);
+CREATE INDEX IF NOT EXISTS events_slot ON events (slot);
+CREATE INDEX IF NOT EXISTS events_agreement ON events (program, agreement_id);
+CREATE INDEX IF NOT EXISTS events_agent ON events (agent);
+
+-- Highest slot whose events are final; everything above it is re-fetched
+CREATE TABLE IF NOT EXISTS cursor (
+    id             INTEGER PRIMARY KEY CHECK (id = 0),
+    finalized_slot INTEGER NOT NULL
+);
+
+-- Status is derived from which events exist rather than their order, so it
+-- does not depend on how transactions within one slot were fetched. Views
+-- hold no data, so they are recreated on open to pick up changes.
+DROP VIEW IF EXISTS agreements;
+CREATE VIEW agreements AS
+SELECT
+    program,
+    agreement_id,
+    MAX(CASE WHEN kind = 'AgreementProposed' THEN agent END) AS proposer,
+    MIN(slot) AS first_slot,
+    CASE
+        WHEN SUM(kind = 'AgreementFulfilled') > 0 THEN 'Fulfilled'
+        WHEN SUM(kind = 'AgreementCancelled') > 0 THEN 'Cancelled'
+        WHEN SUM(kind = 'AgreementTerminated') > 0 THEN 'Terminated'
+        WHEN SUM(kind = 'AgreementDeclined') > 0 THEN 'Rejected'
+        WHEN SUM(kind = 'AgreementActivated') > 0 THEN 'Active'
+        ELSE 'Proposed'
+    END AS status,
+    SUM(kind = 'AgreementClosed') > 0 AS closed
+FROM events
+WHERE agreement_id IS NOT NULL
+GROUP BY program, agreement_id;
+
+-- A party may be removed and added again; counting instead of ordering keeps
+-- this independent of fetch order too, since each removal follows an add
+DROP VIEW IF EXISTS parties;
+CREATE VIEW parties AS
+SELECT
+    program,
+    agreement_id,
+    agent,
+    MAX(role) AS role,
+    MIN(CASE WHEN kind IN ('AgreementProposed', 'AgreementSigned') THEN slot END) AS signed_slot,
+    MIN(CASE WHEN kind IN ('AgreementProposed', 'AgreementSigned') THEN block_time END) AS signed_at,
+    MIN(CASE WHEN kind IN ('AgreementProposed', 'AgreementSigned') THEN signature END) AS signed_in
+FROM events
+WHERE agreement_id IS NOT NULL
+  AND agent IS NOT NULL
+  AND kind IN ('AgreementProposed', 'PartyAdded', 'PartyRemoved', 'AgreementSigned')
+GROUP BY program, agreement_id, agent
+HAVING SUM(kind IN ('AgreementProposed', 'PartyAdded')) > SUM(kind = 'PartyRemoved');
+";
+
+/// One row of the `events` table.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct StoredEvent {
+    pub signature: String,
+    pub idx: u32,
+    pub slot: u64,
+    pub block_time: Option<i64>,
+    pub program: String,
+    pub kind: String,
+    pub agreement_id: Option<String>,
+    pub agent: Option<String>,
+    pub role: Option<u8>,
+    pub amount: Option<u64>,
+    pub data: Value,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct AgreementRow {
+    pub program: String,
+    pub agreement_id: String,
+    pub proposer: Option<String>,
+    pub status: String,
+    pub closed: bool,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct PartyRow {
+    pub agent: String,
+    pub role: Option<u8>,
+    /// `None` until the party signs
+    pub signed_slot: Option<u64>,
+    pub signed_at: Option<i64>,
+    pub signature: Option<String>,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct VaultFlow {
+    pub signature: String,
+    pub slot: u64,
+    pub block_time: Option<i64>,
+    /// `VaultDeposit` or `VaultWithdraw`
+    pub kind: String,
+    pub amount: u64,
+    /// Vault balance after the flow
+    pub balance: Option<u64>,
+}
+
+pub struct Store {
+    conn: Connection,
+}
+
+impl Store {
+    pub fn open(path: impl AsRef<Path>) -> Result<Self> {
+        Self::init(Connection::open(path)?)
+    }
+
+    pub fn open_in_memory() -> Result<Self> {
+        Self::init(Connection::open_in_memory()?)
+    }
+
+    fn init(conn: Connection) -> Result<Self> {
+        conn.execute_batch(SCHEMA)?;
+        Ok(Store { conn })
+    }
+
+    // ============================================================
+    // Ingestion & finality
+    // ============================================================
+
+    /// Decode and store `txs` in one SQLite transaction. Rows already present
+    /// are left untouched; returns the number of new rows.
+    pub fn ingest(&mut self, txs: &[TxRecord]) -> Result<usize> {
+        let db = self.conn.transaction()?;
+        let mut inserted = 0;
+        {
+            let mut insert = db.prepare(
+                "INSERT OR IGNORE INTO events
+                 (signature, idx, slot, block_time, program, kind, agreement_id, agent, role, amount, data)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11)",
+            )?;
+            for tx in txs {
+                for (idx, event) in decode_transaction(tx).into_iter().enumerate() {
+                    inserted += insert.execute(params![
+                        tx.signature,
+                        idx as i64,
+                        tx.slot as i64,
+                        tx.block_time,
+                        event.program.as_str(),
+                        event.kind,
+                        event.agreement_id.map(hex::encode),
+                        event.agent.map(|a| a.to_string()),
+                        event.role,
+                        event.amount.map(|a| a as i64),
+                        event.data.to_string(),
+                    ])?;
+                }
+            }
+        }
+        db.commit()?;
+        Ok(inserted)
+    }
+
+    /// Highest slot known to be finalized, or `None` before the first poll.
+    pub fn finalized_slot(&self) -> Result<Option<u64>> {
+        self.conn
+            .query_row("SELECT finalized_slot FROM cursor WHERE id = 0", [], |r| {
+                r.get::<_, i64>(0)
+            })
+            .optional()
+            .map(|s| s.map(|s| s as u64))
+    }
+
+    pub fn set_finalized_slot(&self, slot: u64) -> Result<()> {
+        self.conn.execute(
+            "INSERT INTO cursor (id, finalized_slot) VALUES (0, ?1)
+             ON CONFLICT (id) DO UPDATE SET finalized_slot = excluded.finalized_slot",
+            [slot as i64],
+        )?;
+        Ok(())
+    }
+
+    /// Drop events above the finalized slot. They were read at `confirmed` and
+    /// may belong to a fork; the next poll fetches them again.
+    pub fn rollback_unfinalized(&self) -> Result<usize> {
+        let finalized = self.finalized_slot()?.map_or(-1, |s| s as i64);
+        self.conn.execute("DELETE FROM events WHERE slot > ?1", [finalized])
+    }
+
+    /// Forget everything from `slot` on so the next poll re-indexes it.
+    pub fn replay_from(&self, slot: u64) -> Result<usize> {
+        let deleted = self.conn.execute("DELETE FROM events WHERE slot >= ?1", [slot as i64])?;
+        match slot.checked_sub(1) {
+            Some(prev) => self.set_finalized_slot(prev)?,
+            None => {
+                self.conn.execute("DELETE FROM cursor", [])?;
+            }
+        }
+        Ok(deleted)
+    }
+
+    // ============================================================
+    // Queries
+    // ============================================================
+
+    pub fn event_count(&self) -> Result<u64> {
+        self.conn
+            .query_row("SELECT COUNT(*) FROM events", [], |r| r.get::<_, i64>(0))
+            .map(|n| n as u64)
+    }
+
+    /// Every event touching the agreement, oldest first.
+    pub fn agreement_history(&self, program: Program, id: &[u8; 16]) -> Result<Vec<StoredEvent>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT * FROM events WHERE program = ?1 AND agreement_id = ?2 ORDER BY slot, rowid",
+        )?;
+        let rows = stmt.query_map(params![program.as_str(), hex::encode(id)], stored_event)?;
+        rows.collect()
+    }
+
+    pub fn agreement(&self, program: Program, id: &[u8; 16]) -> Result<Option<AgreementRow>> {
+        self.conn
+            .query_row(
+                "SELECT program, agreement_id, proposer, status, closed
+                 FROM agreements WHERE program = ?1 AND agreement_id = ?2",
+                params![program.as_str(), hex::encode(id)],
+                agreement_row,
+            )
+            .optional()
+    }
+
+    /// Parties of the agreement and when each signed.
+    pub fn signatures(&self, program: Program, id: &[u8; 16]) -> Result<Vec<PartyRow>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT agent, role, signed_slot, signed_at, signed_in FROM parties
+             WHERE program = ?1 AND agreement_id = ?2 ORDER BY role, agent",
+        )?;
+        let rows = stmt.query_map(params![program.as_str(), hex::encode(id)], |r| {
+            Ok(PartyRow {
+                agent: r.get(0)?,
+                role: r.get(1)?,
+                signed_slot: r.get::<_, Option<i64>>(2)?.map(|s| s as u64),
+                signed_at: r.get(3)?,
+                signature: r.get(4)?,
+            })
+        })?;
+        rows.collect()
+    }
+
+    /// Active, unclosed agreements the agent is a party to.
+    pub fn active_agreements(&self, agent: &str) -> Result<Vec<AgreementRow>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT a.program, a.agreement_id, a.proposer, a.status, a.closed
+             FROM agreements a
+             JOIN parties p ON p.program = a.program AND p.agreement_id = a.agreement_id
+             WHERE p.agent = ?1 AND a.status = 'Active' AND NOT a.closed
+             ORDER BY a.first_slot",
+        )?;
+        let rows = stmt.query_map([agent], agreement_row)?;
+        rows.collect()
+    }
+
+    /// V1 vault deposits and withdrawals for an AgentIdentity PDA, oldest first.
+    pub fn vault_flows(&self, agent_identity: &str) -> Result<Vec<VaultFlow>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT signature, slot, block_time, kind, amount, json_extract(data, '$.balance')
+             FROM events
+             WHERE agent = ?1 AND kind IN ('VaultDeposit', 'VaultWithdraw')
+             ORDER BY slot, rowid",
+        )?;
+        let rows = stmt.query_map([agent_identity], |r| {
+            Ok(VaultFlow {
+                signature: r.get(0)?,
+                slot: r.get::<_, i64>(1)? as u64,
+                block_time: r.get(2)?,
+                kind: r.get(3)?,
+                amount: r.get::<_, i64>(4)? as u64,
+                balance: r.get::<_, Option<i64>>(5)?.map(|b| b as u64),
+            })
+        })?;
+        rows.collect()
+    }
+}
+
+fn stored_event(r: &Row) -> Result<StoredEvent> {
+    let data: String = r.get("data")?;
+    Ok(StoredEvent {
+        signature: r.get("signature")?,
+        idx: r.get("idx")?,
+        slot: r.get::<_, i64>("slot")? as u64,
+        block_time: r.get("block_time")?,
+        program: r.get("program")?,
+        kind: r.get("kind")?,
+        agreement_id: r.get("agreement_id")?,
+        agent: r.get("agent")?,
+        role: r.get("role")?,
+        amount: r.get::<_, Option<i64>>("amount")?.map(|a| a as u64),
+        data: serde_json::from_str(&data).map_err(|e| {
+            rusqlite::Error::FromSqlConversionFailure(0, rusqlite::types::Type::Text, Box::new(e))
+        })?,
+    })
+}
+
+fn agreement_row(r: &Row) -> Result<AgreementRow> {
+    Ok(AgreementRow {
+        program: r.get(0)?,
+        agreement_id: r.get(1)?,
+        proposer: r.get(2)?,
+        status: r.get(3)?,
+        closed: r.get(4)?,
+    })
+}
@@ -0,0 +1,237 @@
+//! Offline indexing of recorded transaction fixtures.
+
+use aap_indexer::{Program, Store, TxRecord};
+use agent_agreement_protocol::constants::{
+    AGREEMENT_TYPE_SERVICE, EVENT_VERSION, ROLE_COUNTERPARTY, STATUS_PROPOSED, STATUS_REJECTED,
+};
+use agent_agreement_protocol::events::{AgreementDeclined, AgreementProposed, PartyAdded, PartyRemoved};
+use anchor_lang::prelude::Pubkey;
+use anchor_lang::Event;
+use base64::Engine;
+
+// Keys used by the fixtures
+const V1_IDENTITY_A: &str = "5xaLQQLJf1G83aeBELscYRJqyddyAL5SVmUQ116cXscG";
+const V1_IDENTITY_B: &str = "Cg9u3ZsVHfUmGEAVDu1Zwb3PsP3x6HtX3xvZB6sS9GAU";
+const COMPRESSED_AGENT_C: &str = "CktRuQ2mttgRGkXJtyksdKHjUdc2C4TgDzyB98oEzy8";
+const COMPRESSED_AGENT_D: &str = "GgBaCs3NCBuZN12kCJgAW63ydqohFkHEdfdEXBPzLHq";
+const AGREEMENT_X: [u8; 16] = [0x11; 16];
+const AGREEMENT_Y: [u8; 16] = [0x22; 16];
+const AGREEMENT_Z: [u8; 16] = [0x33; 16];
+
+fn fixture(json: &str) -> Vec<TxRecord> {
+    serde_json::from_str(json).unwrap()
+}
+
+fn v1() -> Vec<TxRecord> {
+    fixture(include_str!("fixtures/v1_lifecycle.json"))
+}
+
+fn compressed() -> Vec<TxRecord> {
+    fixture(include_str!("fixtures/compressed_lifecycle.json"))
+}
+
+/// A successful top-level V1 transaction that emits `events`
+fn v1_tx(signature: &str, slot: u64, events: &[Vec<u8>]) -> TxRecord {
+    let program = agent_agreement_protocol::ID;
+    let mut logs = vec![format!("Program {program} invoke [1]")];
+    for event in events {
+        logs.push(format!("Program data: {}", base64::engine::general_purpose::STANDARD.encode(event)));
+    }
+    logs.push(format!("Program {program} success"));
+    TxRecord {
+        signature: signature.to_string(),
+        slot,
+        block_time: Some(1_760_000_000 + slot as i64),
+        failed: false,
+        logs,
+        instructions: vec![],
+    }
+}
+
+/// `proposer` proposes `id` and invites `party`
+fn proposed(id: [u8; 16], proposer: Pubkey, party: Pubkey) -> Vec<TxRecord> {
+    let propose = AgreementProposed {
+        version: EVENT_VERSION,
+        agreement_id: id,
+        proposer,
+        agreement_pda: Pubkey::new_unique(),
+        actor: proposer,
+        agreement_type: AGREEMENT_TYPE_SERVICE,
+        visibility: 0,
+        terms_hash: [0; 32],
+        terms_uri: [0; 64],
+        num_parties: 2,
+        expires_at: 0,
+        status: STATUS_PROPOSED,
+        timestamp: 0,
+    };
+    let add = PartyAdded {
+        version: EVENT_VERSION,
+        agreement_id: id,
+        party,
+        role: ROLE_COUNTERPARTY,
+        actor: proposer,
+        direct: false,
+        parties_added: 2,
+        timestamp: 0,
+    };
+    vec![v1_tx("propose", 300, &[propose.data(), add.data()])]
+}
+
+fn status(store: &Store, program: Program, id: &[u8; 16]) -> String {
+    store.agreement(program, id).unwrap().unwrap().status
+}
+
+#[test]
+fn v1_fixture_builds_agreement_state() {
+    let mut store = Store::open_in_memory().unwrap();
+    store.ingest(&v1()).unwrap();
+
+    assert_eq!(status(&store, Program::V1, &AGREEMENT_X), "Active");
+    let parties = store.signatures(Program::V1, &AGREEMENT_X).unwrap();
+    assert_eq!(parties.len(), 2);
+    assert_eq!(parties[0].agent, V1_IDENTITY_A);
+    assert_eq!(parties[0].signed_slot, Some(102));
+    assert_eq!(parties[1].agent, V1_IDENTITY_B);
+    assert_eq!(parties[1].role, Some(1));
+    assert_eq!(parties[1].signed_slot, Some(103));
+
+    // Proposed via CPI; the other program's look-alike log is not attributed to V1
+    assert_eq!(status(&store, Program::V1, &AGREEMENT_Y), "Proposed");
+    let history = store.agreement_history(Program::V1, &AGREEMENT_X).unwrap();
+    assert!(history.iter().all(|e| e.kind != "AgreementCancelled"));
+
+    let active = store.active_agreements(V1_IDENTITY_B).unwrap();
+    assert_eq!(active.len(), 1);
+    assert_eq!(active[0].agreement_id, hex::encode(AGREEMENT_X));
+
+    // The failed 9 SOL withdrawal is not a flow
+    let flows = store.vault_flows(V1_IDENTITY_A).unwrap();
+    let flows: Vec<_> = flows.iter().map(|f| (f.kind.as_str(), f.amount, f.balance)).collect();
+    assert_eq!(
+        flows,
+        [
+            ("VaultDeposit", 5_000_000, Some(5_000_000)),
+            ("VaultWithdraw", 1_000_000, Some(4_000_000)),
+        ]
+    );
+}
+
+#[test]
+fn compressed_fixture_builds_agreement_state() {
+    let mut store = Store::open_in_memory().unwrap();
+    store.ingest(&compressed()).unwrap();
+
+    assert_eq!(status(&store, Program::Compressed, &AGREEMENT_Z), "Active");
+    let parties = store.signatures(Program::Compressed, &AGREEMENT_Z).unwrap();
+    let signed: Vec<_> = parties.iter().map(|p| (p.agent.as_str(), p.signed_slot)).collect();
+    assert_eq!(signed, [(COMPRESSED_AGENT_C, Some(201)), (COMPRESSED_AGENT_D, Some(203))]);
+    assert_eq!(store.active_agreements(COMPRESSED_AGENT_D).unwrap().len(), 1);
+
+    // Same ID under the other program is a different agreement
+    assert!(store.agreement(Program::V1, &AGREEMENT_Z).unwrap().is_none());
+}
+
+#[test]
+fn reprocessing_is_idempotent() {
+    let mut store = Store::open_in_memory().unwrap();
+    let inserted = store.ingest(&v1()).unwrap();
+    let count = store.event_count().unwrap();
+    assert_eq!(inserted as u64, count);
+
+    assert_eq!(store.ingest(&v1()).unwrap(), 0);
+    assert_eq!(store.event_count().unwrap(), count);
+}
+
+#[test]
+fn replay_from_slot_reindexes_the_tail() {
+    let mut store = Store::open_in_memory().unwrap();
+    store.ingest(&v1()).unwrap();
+    let count = store.event_count().unwrap();
+
+    assert!(store.replay_from(103).unwrap() > 0);
+    assert_eq!(store.finalized_slot().unwrap(), Some(102));
+    assert_eq!(status(&store, Program::V1, &AGREEMENT_X), "Proposed");
+    assert!(store.agreement(Program::V1, &AGREEMENT_Y).unwrap().is_none());
+
+    // Re-fetching only restores what was dropped
+    let tail: Vec<_> = v1().into_iter().filter(|tx| tx.slot >= 103).collect();
+    store.ingest(&tail).unwrap();
+    assert_eq!(store.event_count().unwrap(), count);
+    assert_eq!(status(&store, Program::V1, &AGREEMENT_X), "Active");
+}
+
+#[test]
+fn unfinalized_rows_are_rolled_back() {
+    let mut store = Store::open_in_memory().unwrap();
+    store.ingest(&v1()).unwrap();
+    store.set_finalized_slot(102).unwrap();
+
+    // A fork drops the slot-103 signature; everything above 102 is re-fetched
+    store.rollback_unfinalized().unwrap();
+    let refetched: Vec<_> = v1().into_iter().filter(|tx| tx.slot > 103).collect();
+    store.ingest(&refetched).unwrap();
+
+    assert_eq!(status(&store, Program::V1, &AGREEMENT_X), "Proposed");
+    assert!(store.active_agreements(V1_IDENTITY_B).unwrap().is_empty());
+    assert_eq!(store.vault_flows(V1_IDENTITY_A).unwrap().len(), 2);
+}
+
+#[test]
+fn declined_agreements_are_rejected() {
+    let (proposer, party) = (Pubkey::new_unique(), Pubkey::new_unique());
+    let id = [0x44; 16];
+    let mut store = Store::open_in_memory().unwrap();
+    store.ingest(&proposed(id, proposer, party)).unwrap();
+
+    let decline = AgreementDeclined {
+        version: EVENT_VERSION,
+        agreement_id: id,
+        party,
+        actor: party,
+        direct: false,
+        previous_status: STATUS_PROPOSED,
+        status: STATUS_REJECTED,
+        timestamp: 0,
+    };
+    store.ingest(&[v1_tx("decline", 301, &[decline.data()])]).unwrap();
+
+    assert_eq!(status(&store, Program::V1, &id), "Rejected");
+    assert!(store.active_agreements(&party.to_string()).unwrap().is_empty());
+}
+
+#[test]
+fn removed_parties_are_not_listed() {
+    let (proposer, party) = (Pubkey::new_unique(), Pubkey::new_unique());
+    let id = [0x55; 16];
+    let mut store = Store::open_in_memory().unwrap();
+    store.ingest(&proposed(id, proposer, party)).unwrap();
+
+    let remove = PartyRemoved {
+        version: EVENT_VERSION,
+        agreement_id: id,
+        party,
+        role: ROLE_COUNTERPARTY,
+        actor: proposer,
+        parties_added: 1,
+        timestamp: 0,
+    };
+    store.ingest(&[v1_tx("remove", 301, &[remove.data()])]).unwrap();
+    let parties = store.signatures(Program::V1, &id).unwrap();
+    let agents: Vec<_> = parties.iter().map(|p| p.agent.clone()).collect();
+    assert_eq!(agents, [proposer.to_string()]);
+
+    // Added again, it is listed again
+    let add = PartyAdded {
+        version: EVENT_VERSION,
+        agreement_id: id,
+        party,
+        role: ROLE_COUNTERPARTY,
+        actor: proposer,
+        direct: false,
+        parties_added: 2,
+        timestamp: 0,
+    };
+    store.ingest(&[v1_tx("re-add", 302, &[add.data()])]).unwrap();
+    assert_eq!(store.signatures(Program::V1, &id).unwrap().len(), 2);
+}
@@ -0,0 +1,41 @@
+//! Shared authorization checks for compressed instructions.
+//!
+//! V1 ties a party to its agreement and identity through the
+//! `["party", agreement_id, agent_identity]` PDA seeds. Compressed accounts are
+//! passed in as instruction data, so the same relationships are enforced here
+//! against the addresses in the (proof-verified) `CompressedAccountMeta`s.
+
+use anchor_lang::prelude::*;
+
+use crate::errors::AapError;
+use crate::state::{CompressedAgentIdentity, CompressedAgreementParty};
+
+/// Signer must be the identity's agent_key or its human authority.
+pub fn require_agent_or_authority(
+    identity: &CompressedAgentIdentity,
+    signer: &Pubkey,
+) -> Result<()> {
+    require!(
+        identity.agent_key == *signer || identity.authority == *signer,
+        AapError::Unauthorized
+    );
+    Ok(())
+}
+
+/// Party record must belong to the agreement at `agreement_address`
+/// and be held by the identity at `identity_address`.
+pub fn require_party_of(
+    party: &CompressedAgreementParty,
+    agreement_address: &[u8; 32],
+    identity_address: &[u8; 32],
+) -> Result<()> {
+    require!(
+        party.agreement_address == *agreement_address,
+        AapError::PartyAgreementMismatch
+    );
+    require!(
+        party.agent_identity_address == *identity_address,
+        AapError::PartyIdentityMismatch
+    );
+    Ok(())
+}
@@ -0,0 +1,40 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::*;
+use crate::errors::AapError;
+
+// Anchor discriminator of V1 `ProtocolConfig`: sha256("account:ProtocolConfig")[..8]
+pub const PROTOCOL_CONFIG_DISCRIMINATOR: [u8; 8] = [207, 91, 250, 28, 152, 179, 215, 209];
+
+/// Read-only mirror of V1 `ProtocolConfig`. The admin manages a single config
+/// through V1; this program only reads it.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct ProtocolConfig {
+    pub admin: Pubkey,
+    pub paused: bool,
+    pub max_parties: u8,
+    pub fee_lamports: u64,
+    pub treasury: Pubkey,
+    pub bump: u8,
+}
+
+impl ProtocolConfig {
+    /// The V1 config PDA: `["config"]` under `V1_PROGRAM_ID`.
+    pub fn address() -> Pubkey {
+        Pubkey::find_program_address(&[CONFIG_SEED], &V1_PROGRAM_ID).0
+    }
+
+    /// Deserialize the V1 config account (address checked by the caller's
+    /// seeds constraint) and reject the call if the protocol is paused.
+    pub fn load_live(info: &AccountInfo) -> Result<Self> {
+        require_keys_eq!(*info.owner, V1_PROGRAM_ID, AapError::InvalidConfig);
+        let data = info.try_borrow_data()?;
+        require!(
+            data.len() >= 8 && data[..8] == PROTOCOL_CONFIG_DISCRIMINATOR,
+            AapError::InvalidConfig
+        );
+        let config = Self::deserialize(&mut &data[8..]).map_err(|_| AapError::InvalidConfig)?;
+        require!(!config.paused, AapError::ProtocolPaused);
+        Ok(config)
+    }
+}
@@ -1,3 +1,4 @@
+use aap_core::RuleError;
 use anchor_lang::prelude::*;
 
 #[error_code]
@@ -23,7 +24,7 @@ pub enum AapError {
     #[msg("Sub-agent scope cannot exceed parent scope")]
     SubAgentScopeExceedsParent,
 
-    #[msg("Maximum delegation depth is 2 levels")]
+    #[msg("Maximum delegation depth is 2 levels (human -> agent -> sub-agent)")]
     MaxDelegationDepth,
 
     #[msg("Invalid agreement type")]
@@ -35,7 +36,7 @@ pub enum AapError {
     #[msg("Invalid party role")]
     InvalidRole,
 
-    #[msg("Number of parties must be between 2 and 8")]
+    #[msg("Number of parties must be between 2 and the configured maximum")]
     InvalidPartyCount,
 
     #[msg("Agreement is not in the expected status")]
@@ -52,4 +53,54 @@ pub enum AapError {
 
     #[msg("Invalid address tree")]
     InvalidAddressTree,
+
+    #[msg("Party record does not belong to this agreement")]
+    PartyAgreementMismatch,
+
+    #[msg("Party record does not belong to this identity")]
+    PartyIdentityMismatch,
+
+    #[msg("Protocol is paused")]
+    ProtocolPaused,
+
+    #[msg("Protocol config account is not agent-agreement-protocol's ProtocolConfig")]
+    InvalidConfig,
+
+    #[msg("Treasury does not match the protocol config")]
+    InvalidTreasury,
+}
+
+impl From<RuleError> for AapError {
+    fn from(e: RuleError) -> Self {
+        match e {
+            RuleError::ScopeExpired => AapError::ScopeExpired,
+            RuleError::DelegationExpired => AapError::DelegationExpired,
+            RuleError::CannotSignAgreements => AapError::CannotSignAgreements,
+            // No escrow in V2 — commit limits collapse into the permission error
+            RuleError::CannotCommitFunds | RuleError::EscrowExceedsLimit => AapError::CannotCommitFunds,
+            RuleError::SubAgentScopeExceedsParent => AapError::SubAgentScopeExceedsParent,
+            RuleError::MaxDelegationDepth => AapError::MaxDelegationDepth,
+            RuleError::InvalidAgreementType => AapError::InvalidAgreementType,
+            RuleError::InvalidVisibility => AapError::InvalidVisibility,
+            RuleError::InvalidRole => AapError::InvalidRole,
+            RuleError::InvalidPartyCount => AapError::InvalidPartyCount,
+            RuleError::InvalidStatus => AapError::InvalidStatus,
+            RuleError::AgreementExpired => AapError::AgreementExpired,
+            RuleError::MaxPartiesExceeded => AapError::MaxPartiesExceeded,
+            // No amendments or counter-proposals in V2
+            RuleError::InvalidAmendment | RuleError::InvalidCounterProposal => AapError::InvalidStatus,
+            // No escrow in V2
+            RuleError::EscrowDistributionMismatch => AapError::InvalidStatus,
+            // No milestones in V2
+            RuleError::InvalidMilestone | RuleError::InvalidMilestoneStatus => AapError::InvalidStatus,
+            // No recurring payments in V2
+            RuleError::InvalidRecurringPayment | RuleError::PaymentNotDue => AapError::InvalidStatus,
+            // No escrow streams in V2
+            RuleError::InvalidStream => AapError::InvalidStatus,
+            // No revenue shares in V2
+            RuleError::InvalidRevenueShare => AapError::InvalidStatus,
+            // No SAFE extension in V2
+            RuleError::InvalidSafe => AapError::InvalidStatus,
+        }
+    }
 }
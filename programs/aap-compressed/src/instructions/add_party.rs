@@ -9,6 +9,7 @@ use light_sdk::{
 };
 use light_sdk::cpi::{LightCpiInstruction, InvokeLightSystemProgram};
 
+use crate::config::ProtocolConfig;
 use crate::constants::*;
 use crate::errors::AapError;
 use crate::state::{
@@ -20,8 +21,13 @@ use crate::LIGHT_CPI_SIGNER;
 pub struct AddParty<'info> {
     #[account(mut)]
     pub signer: Signer<'info>,
+
+    /// CHECK: V1 ProtocolConfig, deserialized and pause-checked by the handler
+    #[account(seeds = [CONFIG_SEED], bump, seeds::program = V1_PROGRAM_ID)]
+    pub config: UncheckedAccount<'info>,
 }
 
+#[allow(clippy::too_many_arguments)]
 pub fn handler<'info>(
     ctx: Context<'_, '_, '_, 'info, AddParty<'info>>,
     proof: ValidityProof,
@@ -35,6 +41,8 @@ pub fn handler<'info>(
     output_state_tree_index: u8,
     role: u8,
 ) -> Result<()> {
+    ProtocolConfig::load_live(&ctx.accounts.config)?;
+
     let signer_key = ctx.accounts.signer.key();
 
     require!(proposer_identity.agent_key == signer_key, AapError::Unauthorized);
@@ -43,11 +51,12 @@ pub fn handler<'info>(
         current_agreement.proposer == Pubkey::from(proposer_account_meta.address),
         AapError::Unauthorized
     );
-    require!(
-        current_agreement.parties_added < current_agreement.num_parties,
-        AapError::MaxPartiesExceeded
-    );
-    require!(role <= MAX_ROLE, AapError::InvalidRole);
+    aap_core::parties::require_open_slot(
+        current_agreement.parties_added,
+        current_agreement.num_parties,
+    )
+    .map_err(AapError::from)?;
+    aap_core::parties::validate_role(role).map_err(AapError::from)?;
 
     let light_cpi_accounts = CpiAccounts::new(
         ctx.accounts.signer.as_ref(),
@@ -87,6 +96,7 @@ pub fn handler<'info>(
 }
 
 #[inline(never)]
+#[allow(clippy::too_many_arguments)]
 fn build_accounts_and_invoke<'info>(
     proof: ValidityProof,
     proposer_account_meta: CompressedAccountMeta,
@@ -145,6 +155,7 @@ fn build_accounts_and_invoke<'info>(
 }
 
 #[inline(never)]
+#[allow(clippy::too_many_arguments)]
 fn invoke_cpi<'info>(
     proof: ValidityProof,
     proposer: LightAccount<CompressedAgentIdentity>,
@@ -6,6 +6,8 @@ use light_sdk::{
 };
 use light_sdk::cpi::{LightCpiInstruction, InvokeLightSystemProgram};
 
+use crate::auth::require_agent_or_authority;
+use crate::config::ProtocolConfig;
 use crate::constants::*;
 use crate::errors::AapError;
 use crate::state::{CompressedAgentIdentity, CompressedAgreement};
@@ -15,6 +17,10 @@ use crate::LIGHT_CPI_SIGNER;
 pub struct CancelAgreement<'info> {
     #[account(mut)]
     pub signer: Signer<'info>,
+
+    /// CHECK: V1 ProtocolConfig, deserialized and pause-checked by the handler
+    #[account(seeds = [CONFIG_SEED], bump, seeds::program = V1_PROGRAM_ID)]
+    pub config: UncheckedAccount<'info>,
 }
 
 pub fn handler<'info>(
@@ -27,14 +33,12 @@ pub fn handler<'info>(
     agreement_meta: CompressedAccountMeta,
     current_agreement: CompressedAgreement,
 ) -> Result<()> {
+    ProtocolConfig::load_live(&ctx.accounts.config)?;
+
     let signer_key = ctx.accounts.signer.key();
 
     // Signer must be agent_key or authority of the proposer identity
-    require!(
-        proposer_identity.agent_key == signer_key
-            || proposer_identity.authority == signer_key,
-        AapError::Unauthorized
-    );
+    require_agent_or_authority(&proposer_identity, &signer_key)?;
 
     // Agreement's proposer must match the provided identity
     require!(
@@ -43,7 +47,8 @@ pub fn handler<'info>(
     );
 
     // Agreement must be in Proposed status
-    require!(current_agreement.status == STATUS_PROPOSED, AapError::InvalidStatus);
+    aap_core::status::require_transition(current_agreement.status, STATUS_CANCELLED)
+        .map_err(AapError::from)?;
 
     // Pass-through proposer identity
     let identity = LightAccount::<CompressedAgentIdentity>::new_mut(
@@ -6,7 +6,9 @@ use light_sdk::{
 };
 use light_sdk::cpi::{LightCpiInstruction, InvokeLightSystemProgram};
 
-use crate::constants::*;
+use crate::auth::require_party_of;
+use crate::config::ProtocolConfig;
+use crate::constants::{CONFIG_SEED, V1_PROGRAM_ID};
 use crate::errors::AapError;
 use crate::state::{CompressedAgentIdentity, CompressedAgreement, CompressedAgreementParty};
 use crate::LIGHT_CPI_SIGNER;
@@ -15,8 +17,13 @@ use crate::LIGHT_CPI_SIGNER;
 pub struct CloseAgreement<'info> {
     #[account(mut)]
     pub signer: Signer<'info>,
+
+    /// CHECK: V1 ProtocolConfig, deserialized and pause-checked by the handler
+    #[account(seeds = [CONFIG_SEED], bump, seeds::program = V1_PROGRAM_ID)]
+    pub config: UncheckedAccount<'info>,
 }
 
+#[allow(clippy::too_many_arguments)]
 pub fn handler<'info>(
     ctx: Context<'_, '_, '_, 'info, CloseAgreement<'info>>,
     proof: ValidityProof,
@@ -30,16 +37,23 @@ pub fn handler<'info>(
     agreement_meta: CompressedAccountMeta,
     current_agreement: CompressedAgreement,
 ) -> Result<()> {
+    ProtocolConfig::load_live(&ctx.accounts.config)?;
+
     let signer_key = ctx.accounts.signer.key();
 
     // Signer must be the authority of the identity
     require!(signer_identity.authority == signer_key, AapError::Unauthorized);
 
+    // Signer must be a party to the agreement
+    require_party_of(
+        &signer_party,
+        &agreement_meta.address,
+        &signer_identity_meta.address,
+    )?;
+
     // Agreement must be in a terminal state
     require!(
-        current_agreement.status == STATUS_FULFILLED
-            || current_agreement.status == STATUS_CANCELLED
-            || current_agreement.status == STATUS_BREACHED,
+        aap_core::status::is_closable(current_agreement.status),
         AapError::InvalidStatus
     );
 
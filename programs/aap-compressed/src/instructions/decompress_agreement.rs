@@ -0,0 +1,155 @@
+use anchor_lang::prelude::*;
+use light_sdk::{
+    account::LightAccount,
+    address::v2::derive_address,
+    cpi::v2::{CpiAccounts, LightSystemProgramCpi},
+    instruction::{account_meta::CompressedAccountMeta, ValidityProof},
+};
+use light_sdk::cpi::{LightCpiInstruction, InvokeLightSystemProgram};
+
+use crate::auth::require_agent_or_authority;
+use crate::config::ProtocolConfig;
+use crate::constants::*;
+use crate::errors::AapError;
+use crate::instructions::import_agreement::AgreementSnapshot;
+use crate::migration::{
+    invoke_materialize, MaterializeCpiAccounts, MATERIALIZE_AGREEMENT_DISCRIMINATOR,
+};
+use crate::state::{CompressedAgentIdentity, CompressedAgreement};
+use crate::LIGHT_CPI_SIGNER;
+
+/// Nullify a compressed agreement and recreate it as a regular V1 Agreement PDA
+/// (via CPI into V1 `materialize_agreement`) so other programs can read it.
+/// Parties follow via `decompress_party`.
+#[derive(Accounts)]
+pub struct DecompressAgreement<'info> {
+    #[account(mut)]
+    pub signer: Signer<'info>,
+
+    /// CHECK: PDA signer trusted by V1 materialize instructions
+    #[account(seeds = [MIGRATION_SEED], bump)]
+    pub migration_authority: UncheckedAccount<'info>,
+
+    /// CHECK: V1 AgentIdentity PDA of the proposer — seeds checked by V1
+    pub v1_proposer_identity: UncheckedAccount<'info>,
+
+    /// CHECK: V1 Agreement PDA — created and seeds-checked by V1
+    #[account(mut)]
+    pub v1_agreement: UncheckedAccount<'info>,
+
+    /// CHECK: Address-checked V1 program
+    #[account(address = V1_PROGRAM_ID)]
+    pub v1_program: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+
+    /// CHECK: V1 DecompressionMarker PDA — created and seeds-checked by V1
+    #[account(mut)]
+    pub v1_marker: UncheckedAccount<'info>,
+
+    /// CHECK: V1 ProtocolConfig, deserialized and pause-checked by the handler
+    #[account(seeds = [CONFIG_SEED], bump, seeds::program = V1_PROGRAM_ID)]
+    pub config: UncheckedAccount<'info>,
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn handler<'info>(
+    ctx: Context<'_, '_, '_, 'info, DecompressAgreement<'info>>,
+    proof: ValidityProof,
+    // Proposer's identity for auth
+    proposer_identity_meta: CompressedAccountMeta,
+    proposer_identity: CompressedAgentIdentity,
+    // The agreement to decompress (will be closed)
+    agreement_meta: CompressedAccountMeta,
+    current_agreement: CompressedAgreement,
+    // Preimage of the proposer identity's address: the key it was registered
+    // with, which differs from its agent_key if it was rotated on V1
+    proposer_agent_key: Pubkey,
+    address_tree_pubkey: Pubkey,
+) -> Result<()> {
+    ProtocolConfig::load_live(&ctx.accounts.config)?;
+
+    let signer_key = ctx.accounts.signer.key();
+
+    // Signer must be agent_key or authority of the proposer identity
+    require_agent_or_authority(&proposer_identity, &signer_key)?;
+
+    // Agreement's proposer must match the provided identity
+    require!(
+        current_agreement.proposer == Pubkey::from(proposer_identity_meta.address),
+        AapError::Unauthorized
+    );
+
+    // V1 derives the proposer's AgentIdentity PDA from the same key
+    let (identity_address, _) = derive_address(
+        &[b"agent", proposer_agent_key.as_ref()],
+        &address_tree_pubkey,
+        &crate::ID,
+    );
+    require!(
+        identity_address == proposer_identity_meta.address,
+        AapError::Unauthorized
+    );
+
+    let snapshot = AgreementSnapshot {
+        agreement_id: current_agreement.agreement_id,
+        agreement_type: current_agreement.agreement_type,
+        status: current_agreement.status,
+        visibility: current_agreement.visibility,
+        proposer_agent_key,
+        terms_hash: current_agreement.terms_hash,
+        terms_uri: current_agreement.terms_uri,
+        num_parties: current_agreement.num_parties,
+        num_signed: current_agreement.num_signed,
+        parties_added: current_agreement.parties_added,
+        created_at: current_agreement.created_at,
+        expires_at: current_agreement.expires_at,
+    };
+
+    // Pass-through proposer identity
+    let identity = LightAccount::<CompressedAgentIdentity>::new_mut(
+        &crate::ID,
+        &proposer_identity_meta,
+        proposer_identity,
+    )?;
+
+    // Nullify the compressed agreement
+    let agreement = LightAccount::<CompressedAgreement>::new_close(
+        &crate::ID,
+        &agreement_meta,
+        current_agreement,
+    )?;
+
+    let light_cpi_accounts = CpiAccounts::new(
+        ctx.accounts.signer.as_ref(),
+        ctx.remaining_accounts,
+        crate::LIGHT_CPI_SIGNER,
+    );
+
+    LightSystemProgramCpi::new_cpi(LIGHT_CPI_SIGNER, proof)
+        .with_light_account(identity)?
+        .with_light_account(agreement)?
+        .invoke(light_cpi_accounts)?;
+
+    // V1 account order after payer + migration_authority:
+    // proposer_identity, agreement, system_program, marker
+    invoke_materialize(
+        MATERIALIZE_AGREEMENT_DISCRIMINATOR,
+        &snapshot,
+        MaterializeCpiAccounts {
+            payer: &ctx.accounts.signer.to_account_info(),
+            migration_authority: &ctx.accounts.migration_authority.to_account_info(),
+            migration_authority_bump: ctx.bumps.migration_authority,
+            v1_program: &ctx.accounts.v1_program.to_account_info(),
+            accounts: &[
+                ctx.accounts.v1_proposer_identity.to_account_info(),
+                ctx.accounts.v1_agreement.to_account_info(),
+                ctx.accounts.system_program.to_account_info(),
+                ctx.accounts.v1_marker.to_account_info(),
+            ],
+        },
+    )?;
+
+    msg!("Compressed agreement decompressed to V1");
+    Ok(())
+}
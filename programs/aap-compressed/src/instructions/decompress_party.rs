@@ -0,0 +1,135 @@
+use anchor_lang::prelude::*;
+use light_sdk::{
+    account::LightAccount,
+    address::v2::derive_address,
+    cpi::v2::{CpiAccounts, LightSystemProgramCpi},
+    instruction::{account_meta::CompressedAccountMeta, ValidityProof},
+};
+use light_sdk::cpi::{LightCpiInstruction, InvokeLightSystemProgram};
+
+use crate::auth::require_party_of;
+use crate::config::ProtocolConfig;
+use crate::constants::*;
+use crate::instructions::import_party::PartySnapshot;
+use crate::migration::{
+    invoke_materialize, MaterializeCpiAccounts, MATERIALIZE_PARTY_DISCRIMINATOR,
+};
+use crate::state::CompressedAgreementParty;
+use crate::LIGHT_CPI_SIGNER;
+
+/// Nullify a compressed party and recreate it as a V1 AgreementParty PDA
+/// (via CPI into V1 `materialize_party`). The agreement must already be
+/// decompressed — V1 requires its Agreement PDA and DecompressionMarker to
+/// exist — and the signer must be the agreement proposer's agent key or
+/// authority, which V1 checks against the proposer's AgentIdentity.
+#[derive(Accounts)]
+pub struct DecompressParty<'info> {
+    #[account(mut)]
+    pub signer: Signer<'info>,
+
+    /// CHECK: PDA signer trusted by V1 materialize instructions
+    #[account(seeds = [MIGRATION_SEED], bump)]
+    pub migration_authority: UncheckedAccount<'info>,
+
+    /// CHECK: V1 Agreement PDA — seeds and ownership checked by V1
+    pub v1_agreement: UncheckedAccount<'info>,
+
+    /// CHECK: V1 AgentIdentity PDA of the party — seeds checked by V1
+    pub v1_party_identity: UncheckedAccount<'info>,
+
+    /// CHECK: V1 AgreementParty PDA — created and seeds-checked by V1
+    #[account(mut)]
+    pub v1_party: UncheckedAccount<'info>,
+
+    /// CHECK: Address-checked V1 program
+    #[account(address = V1_PROGRAM_ID)]
+    pub v1_program: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+
+    /// CHECK: V1 AgentIdentity PDA of the agreement's proposer — checked by V1
+    pub v1_proposer_identity: UncheckedAccount<'info>,
+
+    /// CHECK: V1 DecompressionMarker PDA — seeds checked by V1
+    pub v1_marker: UncheckedAccount<'info>,
+
+    /// CHECK: V1 ProtocolConfig, deserialized and pause-checked by the handler
+    #[account(seeds = [CONFIG_SEED], bump, seeds::program = V1_PROGRAM_ID)]
+    pub config: UncheckedAccount<'info>,
+}
+
+pub fn handler<'info>(
+    ctx: Context<'_, '_, '_, 'info, DecompressParty<'info>>,
+    proof: ValidityProof,
+    // The party to decompress (will be closed)
+    party_meta: CompressedAccountMeta,
+    current_party: CompressedAgreementParty,
+    // Preimages of the party's compressed addresses
+    agreement_id: [u8; 16],
+    agent_key: Pubkey,
+    address_tree_pubkey: Pubkey,
+) -> Result<()> {
+    ProtocolConfig::load_live(&ctx.accounts.config)?;
+
+    // Bind agreement_id and agent_key to the addresses stored on the party
+    let (agreement_address, _) = derive_address(
+        &[b"agreement", &agreement_id],
+        &address_tree_pubkey,
+        &crate::ID,
+    );
+    let (identity_address, _) = derive_address(
+        &[b"agent", agent_key.as_ref()],
+        &address_tree_pubkey,
+        &crate::ID,
+    );
+    require_party_of(&current_party, &agreement_address, &identity_address)?;
+
+    let snapshot = PartySnapshot {
+        agreement_id,
+        agent_key,
+        role: current_party.role,
+        signed: current_party.signed,
+        signed_at: current_party.signed_at,
+    };
+
+    // Nullify the compressed party
+    let party = LightAccount::<CompressedAgreementParty>::new_close(
+        &crate::ID,
+        &party_meta,
+        current_party,
+    )?;
+
+    let light_cpi_accounts = CpiAccounts::new(
+        ctx.accounts.signer.as_ref(),
+        ctx.remaining_accounts,
+        crate::LIGHT_CPI_SIGNER,
+    );
+
+    LightSystemProgramCpi::new_cpi(LIGHT_CPI_SIGNER, proof)
+        .with_light_account(party)?
+        .invoke(light_cpi_accounts)?;
+
+    // V1 account order after payer + migration_authority:
+    // agreement, agent_identity, party, system_program, proposer_identity, marker
+    invoke_materialize(
+        MATERIALIZE_PARTY_DISCRIMINATOR,
+        &snapshot,
+        MaterializeCpiAccounts {
+            payer: &ctx.accounts.signer.to_account_info(),
+            migration_authority: &ctx.accounts.migration_authority.to_account_info(),
+            migration_authority_bump: ctx.bumps.migration_authority,
+            v1_program: &ctx.accounts.v1_program.to_account_info(),
+            accounts: &[
+                ctx.accounts.v1_agreement.to_account_info(),
+                ctx.accounts.v1_party_identity.to_account_info(),
+                ctx.accounts.v1_party.to_account_info(),
+                ctx.accounts.system_program.to_account_info(),
+                ctx.accounts.v1_proposer_identity.to_account_info(),
+                ctx.accounts.v1_marker.to_account_info(),
+            ],
+        },
+    )?;
+
+    msg!("Compressed party decompressed to V1");
+    Ok(())
+}
@@ -6,6 +6,8 @@ use light_sdk::{
 };
 use light_sdk::cpi::{LightCpiInstruction, InvokeLightSystemProgram};
 
+use crate::auth::{require_agent_or_authority, require_party_of};
+use crate::config::ProtocolConfig;
 use crate::constants::*;
 use crate::errors::AapError;
 use crate::state::{CompressedAgentIdentity, CompressedAgreement, CompressedAgreementParty};
@@ -15,8 +17,13 @@ use crate::LIGHT_CPI_SIGNER;
 pub struct FulfillAgreement<'info> {
     #[account(mut)]
     pub signer: Signer<'info>,
+
+    /// CHECK: V1 ProtocolConfig, deserialized and pause-checked by the handler
+    #[account(seeds = [CONFIG_SEED], bump, seeds::program = V1_PROGRAM_ID)]
+    pub config: UncheckedAccount<'info>,
 }
 
+#[allow(clippy::too_many_arguments)]
 pub fn handler<'info>(
     ctx: Context<'_, '_, '_, 'info, FulfillAgreement<'info>>,
     proof: ValidityProof,
@@ -30,17 +37,23 @@ pub fn handler<'info>(
     agreement_meta: CompressedAccountMeta,
     current_agreement: CompressedAgreement,
 ) -> Result<()> {
+    ProtocolConfig::load_live(&ctx.accounts.config)?;
+
     let signer_key = ctx.accounts.signer.key();
 
     // Signer must be agent_key or authority
-    require!(
-        signer_identity.agent_key == signer_key
-            || signer_identity.authority == signer_key,
-        AapError::Unauthorized
-    );
+    require_agent_or_authority(&signer_identity, &signer_key)?;
+
+    // Signer must be a party to the agreement
+    require_party_of(
+        &signer_party,
+        &agreement_meta.address,
+        &signer_identity_meta.address,
+    )?;
 
     // Agreement must be Active
-    require!(current_agreement.status == STATUS_ACTIVE, AapError::InvalidStatus);
+    aap_core::status::require_transition(current_agreement.status, STATUS_FULFILLED)
+        .map_err(AapError::from)?;
 
     // Pass-through identity and party
     let identity = LightAccount::<CompressedAgentIdentity>::new_mut(
@@ -0,0 +1,101 @@
+use anchor_lang::prelude::*;
+use light_sdk::{
+    account::LightAccount,
+    address::v2::derive_address,
+    cpi::v2::{CpiAccounts, LightSystemProgramCpi},
+    instruction::{PackedAddressTreeInfo, ValidityProof},
+};
+use light_sdk::cpi::{LightCpiInstruction, InvokeLightSystemProgram};
+
+use crate::constants::*;
+use crate::state::{CompressedAgentIdentity, CompressedDelegationScope};
+use crate::LIGHT_CPI_SIGNER;
+
+/// Snapshot of a closed V1 AgentIdentity PDA.
+/// Built by V1 `migrate_agent` — layout must match the V1 side.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct AgentSnapshot {
+    pub authority: Pubkey,
+    pub agent_key: Pubkey,
+    pub metadata_hash: [u8; 32],
+    pub scope: CompressedDelegationScope,
+    pub parent_agent_key: Pubkey, // Pubkey::default() if no parent
+    pub created_at: i64,
+    pub registered_agent_key: Pubkey, // address preimage; differs from agent_key once rotated on V1
+}
+
+/// Only callable via CPI from V1 `migrate_agent`, which signs with its migration PDA.
+#[derive(Accounts)]
+pub struct ImportAgent<'info> {
+    #[account(mut)]
+    pub signer: Signer<'info>,
+
+    #[account(
+        seeds = [MIGRATION_SEED],
+        bump,
+        seeds::program = V1_PROGRAM_ID,
+    )]
+    pub migration_authority: Signer<'info>,
+}
+
+pub fn handler<'info>(
+    ctx: Context<'_, '_, '_, 'info, ImportAgent<'info>>,
+    proof: ValidityProof,
+    address_tree_info: PackedAddressTreeInfo,
+    output_state_tree_index: u8,
+    snapshot: AgentSnapshot,
+) -> Result<()> {
+    let light_cpi_accounts = CpiAccounts::new(
+        ctx.accounts.signer.as_ref(),
+        ctx.remaining_accounts,
+        crate::LIGHT_CPI_SIGNER,
+    );
+
+    // Resolve address tree pubkey (validated by Light system program CPI)
+    let address_tree_pubkey = address_tree_info
+        .get_tree_pubkey(&light_cpi_accounts)
+        .map_err(|_| ErrorCode::AccountNotEnoughKeys)?;
+
+    // Same address register_agent would have derived: seeds = ["agent", agent_key].
+    // A rotated identity keeps the address of the key it was registered with.
+    let (address, address_seed) = derive_address(
+        &[b"agent", snapshot.registered_agent_key.as_ref()],
+        &address_tree_pubkey,
+        &crate::ID,
+    );
+
+    // Parent is re-pointed at the parent's compressed address
+    let parent = if snapshot.parent_agent_key == Pubkey::default() {
+        Pubkey::default()
+    } else {
+        let (parent_address, _) = derive_address(
+            &[b"agent", snapshot.parent_agent_key.as_ref()],
+            &address_tree_pubkey,
+            &crate::ID,
+        );
+        Pubkey::from(parent_address)
+    };
+
+    let mut identity = LightAccount::<CompressedAgentIdentity>::new_init(
+        &crate::ID,
+        Some(address),
+        output_state_tree_index,
+    );
+
+    identity.authority = snapshot.authority;
+    identity.agent_key = snapshot.agent_key;
+    identity.metadata_hash = snapshot.metadata_hash;
+    identity.scope = snapshot.scope;
+    identity.parent = parent;
+    identity.created_at = snapshot.created_at;
+
+    LightSystemProgramCpi::new_cpi(LIGHT_CPI_SIGNER, proof)
+        .with_light_account(identity)?
+        .with_new_addresses(&[
+            address_tree_info.into_new_address_params_assigned_packed(address_seed, Some(0)),
+        ])
+        .invoke(light_cpi_accounts)?;
+
+    msg!("V1 agent imported as compressed account");
+    Ok(())
+}
@@ -0,0 +1,106 @@
+use anchor_lang::prelude::*;
+use light_sdk::{
+    account::LightAccount,
+    address::v2::derive_address,
+    cpi::v2::{CpiAccounts, LightSystemProgramCpi},
+    instruction::{PackedAddressTreeInfo, ValidityProof},
+};
+use light_sdk::cpi::{LightCpiInstruction, InvokeLightSystemProgram};
+
+use crate::constants::*;
+use crate::state::CompressedAgreement;
+use crate::LIGHT_CPI_SIGNER;
+
+/// Snapshot of a closed V1 Agreement PDA.
+/// Built by V1 `migrate_agreement` — layout must match the V1 side.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct AgreementSnapshot {
+    pub agreement_id: [u8; 16],
+    pub agreement_type: u8,
+    pub status: u8,
+    pub visibility: u8,
+    pub proposer_agent_key: Pubkey,
+    pub terms_hash: [u8; 32],
+    pub terms_uri: [u8; 64],
+    pub num_parties: u8,
+    pub num_signed: u8,
+    pub parties_added: u8,
+    pub created_at: i64,
+    pub expires_at: i64,
+}
+
+/// Only callable via CPI from V1 `migrate_agreement`, which signs with its migration PDA.
+#[derive(Accounts)]
+pub struct ImportAgreement<'info> {
+    #[account(mut)]
+    pub signer: Signer<'info>,
+
+    #[account(
+        seeds = [MIGRATION_SEED],
+        bump,
+        seeds::program = V1_PROGRAM_ID,
+    )]
+    pub migration_authority: Signer<'info>,
+}
+
+pub fn handler<'info>(
+    ctx: Context<'_, '_, '_, 'info, ImportAgreement<'info>>,
+    proof: ValidityProof,
+    address_tree_info: PackedAddressTreeInfo,
+    output_state_tree_index: u8,
+    snapshot: AgreementSnapshot,
+) -> Result<()> {
+    let light_cpi_accounts = CpiAccounts::new(
+        ctx.accounts.signer.as_ref(),
+        ctx.remaining_accounts,
+        crate::LIGHT_CPI_SIGNER,
+    );
+
+    // Resolve address tree pubkey (validated by Light system program CPI)
+    let address_tree_pubkey = address_tree_info
+        .get_tree_pubkey(&light_cpi_accounts)
+        .map_err(|_| ErrorCode::AccountNotEnoughKeys)?;
+
+    // Same address propose_agreement would have derived: seeds = ["agreement", agreement_id]
+    let (agreement_address, agreement_address_seed) = derive_address(
+        &[b"agreement", &snapshot.agreement_id],
+        &address_tree_pubkey,
+        &crate::ID,
+    );
+
+    // Proposer is re-pointed at the proposer's compressed identity address
+    let (proposer_address, _) = derive_address(
+        &[b"agent", snapshot.proposer_agent_key.as_ref()],
+        &address_tree_pubkey,
+        &crate::ID,
+    );
+
+    let mut agreement = LightAccount::<CompressedAgreement>::new_init(
+        &crate::ID,
+        Some(agreement_address),
+        output_state_tree_index,
+    );
+    agreement.agreement_id = snapshot.agreement_id;
+    agreement.agreement_type = snapshot.agreement_type;
+    agreement.status = snapshot.status;
+    agreement.visibility = snapshot.visibility;
+    agreement.proposer = Pubkey::from(proposer_address);
+    agreement.terms_hash = snapshot.terms_hash;
+    agreement.terms_uri = snapshot.terms_uri;
+    agreement.num_parties = snapshot.num_parties;
+    agreement.num_signed = snapshot.num_signed;
+    agreement.parties_added = snapshot.parties_added;
+    agreement.created_at = snapshot.created_at;
+    agreement.expires_at = snapshot.expires_at;
+
+    LightSystemProgramCpi::new_cpi(LIGHT_CPI_SIGNER, proof)
+        .with_light_account(agreement)?
+        .with_new_addresses(&[
+            address_tree_info
+                .into_new_address_params_assigned_packed(agreement_address_seed, Some(0)),
+        ])
+        .invoke(light_cpi_accounts)?;
+
+    msg!("V1 agreement imported as compressed account");
+    Ok(())
+}
@@ -0,0 +1,95 @@
+use anchor_lang::prelude::*;
+use light_sdk::{
+    account::LightAccount,
+    address::v2::derive_address,
+    cpi::v2::{CpiAccounts, LightSystemProgramCpi},
+    instruction::{PackedAddressTreeInfo, ValidityProof},
+};
+use light_sdk::cpi::{LightCpiInstruction, InvokeLightSystemProgram};
+
+use crate::constants::*;
+use crate::state::CompressedAgreementParty;
+use crate::LIGHT_CPI_SIGNER;
+
+/// Snapshot of a closed V1 AgreementParty PDA.
+/// Built by V1 `migrate_party` — layout must match the V1 side.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct PartySnapshot {
+    pub agreement_id: [u8; 16],
+    pub agent_key: Pubkey, // agent_key of the party's identity
+    pub role: u8,
+    pub signed: bool,
+    pub signed_at: i64,
+}
+
+/// Only callable via CPI from V1 `migrate_party`, which signs with its migration PDA.
+#[derive(Accounts)]
+pub struct ImportParty<'info> {
+    #[account(mut)]
+    pub signer: Signer<'info>,
+
+    #[account(
+        seeds = [MIGRATION_SEED],
+        bump,
+        seeds::program = V1_PROGRAM_ID,
+    )]
+    pub migration_authority: Signer<'info>,
+}
+
+pub fn handler<'info>(
+    ctx: Context<'_, '_, '_, 'info, ImportParty<'info>>,
+    proof: ValidityProof,
+    address_tree_info: PackedAddressTreeInfo,
+    output_state_tree_index: u8,
+    snapshot: PartySnapshot,
+) -> Result<()> {
+    let light_cpi_accounts = CpiAccounts::new(
+        ctx.accounts.signer.as_ref(),
+        ctx.remaining_accounts,
+        crate::LIGHT_CPI_SIGNER,
+    );
+
+    // Resolve address tree pubkey (validated by Light system program CPI)
+    let address_tree_pubkey = address_tree_info
+        .get_tree_pubkey(&light_cpi_accounts)
+        .map_err(|_| ErrorCode::AccountNotEnoughKeys)?;
+
+    let (agreement_address, _) = derive_address(
+        &[b"agreement", &snapshot.agreement_id],
+        &address_tree_pubkey,
+        &crate::ID,
+    );
+    let (identity_address, _) = derive_address(
+        &[b"agent", snapshot.agent_key.as_ref()],
+        &address_tree_pubkey,
+        &crate::ID,
+    );
+
+    // Same address add_party would have derived: seeds = ["party", agreement_id, identity_address]
+    let (party_address, party_address_seed) = derive_address(
+        &[b"party", &snapshot.agreement_id, &identity_address],
+        &address_tree_pubkey,
+        &crate::ID,
+    );
+
+    let mut party = LightAccount::<CompressedAgreementParty>::new_init(
+        &crate::ID,
+        Some(party_address),
+        output_state_tree_index,
+    );
+    party.agreement_address = agreement_address;
+    party.agent_identity_address = identity_address;
+    party.role = snapshot.role;
+    party.signed = snapshot.signed;
+    party.signed_at = snapshot.signed_at;
+
+    LightSystemProgramCpi::new_cpi(LIGHT_CPI_SIGNER, proof)
+        .with_light_account(party)?
+        .with_new_addresses(&[
+            address_tree_info.into_new_address_params_assigned_packed(party_address_seed, Some(0)),
+        ])
+        .invoke(light_cpi_accounts)?;
+
+    msg!("V1 party imported as compressed account");
+    Ok(())
+}
@@ -8,6 +8,11 @@ pub mod sign_agreement;
 pub mod cancel_agreement;
 pub mod fulfill_agreement;
 pub mod close_agreement;
+pub mod import_agent;
+pub mod import_agreement;
+pub mod import_party;
+pub mod decompress_agreement;
+pub mod decompress_party;
 
 pub use register_agent::RegisterAgent;
 pub use update_delegation::UpdateDelegation;
@@ -19,3 +24,8 @@ pub use sign_agreement::SignAgreement;
 pub use cancel_agreement::CancelAgreement;
 pub use fulfill_agreement::FulfillAgreement;
 pub use close_agreement::CloseAgreement;
+pub use import_agent::ImportAgent;
+pub use import_agreement::ImportAgreement;
+pub use import_party::ImportParty;
+pub use decompress_agreement::DecompressAgreement;
+pub use decompress_party::DecompressParty;
@@ -1,4 +1,5 @@
 use anchor_lang::prelude::*;
+use anchor_lang::system_program;
 use light_sdk::{
     account::LightAccount,
     address::v2::derive_address,
@@ -9,6 +10,8 @@ use light_sdk::{
 };
 use light_sdk::cpi::{LightCpiInstruction, InvokeLightSystemProgram};
 
+use aap_core::{AgreementType, Visibility};
+use crate::config::ProtocolConfig;
 use crate::constants::*;
 use crate::errors::AapError;
 use crate::state::{
@@ -20,6 +23,16 @@ use crate::LIGHT_CPI_SIGNER;
 pub struct ProposeAgreement<'info> {
     #[account(mut)]
     pub signer: Signer<'info>,
+
+    /// CHECK: V1 ProtocolConfig, deserialized and pause-checked by the handler
+    #[account(seeds = [CONFIG_SEED], bump, seeds::program = V1_PROGRAM_ID)]
+    pub config: UncheckedAccount<'info>,
+
+    /// CHECK: Fee recipient, checked against `config.treasury` by the handler
+    #[account(mut)]
+    pub treasury: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
 }
 
 #[allow(clippy::too_many_arguments)]
@@ -42,31 +55,38 @@ pub fn handler<'info>(
     num_parties: u8,
     expires_at: i64,
 ) -> Result<()> {
+    let config = ProtocolConfig::load_live(&ctx.accounts.config)?;
+
     let signer_key = ctx.accounts.signer.key();
 
     // Signer must be the agent_key of the proposer identity
     require!(proposer_identity.agent_key == signer_key, AapError::Unauthorized);
 
     // Validate delegation
-    if proposer_identity.scope.expires_at != 0 {
-        let clock = Clock::get()?;
-        require!(
-            proposer_identity.scope.expires_at > clock.unix_timestamp,
-            AapError::DelegationExpired
-        );
-    }
-    require!(
-        proposer_identity.scope.can_sign_agreements,
-        AapError::CannotSignAgreements
-    );
+    let now = Clock::get()?.unix_timestamp;
+    aap_core::scope::require_can_sign(&proposer_identity.scope.into(), now)
+        .map_err(AapError::from)?;
 
     // Validate params
-    require!(agreement_type <= MAX_AGREEMENT_TYPE, AapError::InvalidAgreementType);
-    require!(visibility <= VISIBILITY_PRIVATE, AapError::InvalidVisibility);
-    require!(
-        num_parties >= MIN_PARTIES && num_parties <= MAX_PARTIES,
-        AapError::InvalidPartyCount
-    );
+    AgreementType::try_from(agreement_type).map_err(AapError::from)?;
+    Visibility::try_from(visibility).map_err(AapError::from)?;
+    aap_core::parties::validate_party_count(num_parties, config.max_parties)
+        .map_err(AapError::from)?;
+
+    // Protocol fee, paid by the signer
+    require_keys_eq!(ctx.accounts.treasury.key(), config.treasury, AapError::InvalidTreasury);
+    if config.fee_lamports > 0 {
+        system_program::transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                system_program::Transfer {
+                    from: ctx.accounts.signer.to_account_info(),
+                    to: ctx.accounts.treasury.to_account_info(),
+                },
+            ),
+            config.fee_lamports,
+        )?;
+    }
 
     let light_cpi_accounts = CpiAccounts::new(
         ctx.accounts.signer.as_ref(),
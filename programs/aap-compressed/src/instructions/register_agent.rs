@@ -7,6 +7,8 @@ use light_sdk::{
 };
 use light_sdk::cpi::{LightCpiInstruction, InvokeLightSystemProgram};
 
+use crate::config::ProtocolConfig;
+use crate::constants::{CONFIG_SEED, V1_PROGRAM_ID};
 use crate::errors::AapError;
 use crate::state::{CompressedAgentIdentity, CompressedDelegationScope};
 use crate::LIGHT_CPI_SIGNER;
@@ -15,6 +17,10 @@ use crate::LIGHT_CPI_SIGNER;
 pub struct RegisterAgent<'info> {
     #[account(mut)]
     pub signer: Signer<'info>,
+
+    /// CHECK: V1 ProtocolConfig, deserialized and pause-checked by the handler
+    #[account(seeds = [CONFIG_SEED], bump, seeds::program = V1_PROGRAM_ID)]
+    pub config: UncheckedAccount<'info>,
 }
 
 pub fn handler<'info>(
@@ -26,17 +32,16 @@ pub fn handler<'info>(
     metadata_hash: [u8; 32],
     scope: CompressedDelegationScope,
 ) -> Result<()> {
+    ProtocolConfig::load_live(&ctx.accounts.config)?;
+
     let signer_key = ctx.accounts.signer.key();
     let agent_pubkey = Pubkey::from(agent_key);
 
-    // Agent key must differ from authority
-    require!(agent_pubkey != signer_key, AapError::AgentKeyEqualsAuthority);
+    // Agent key can equal authority (human signer mode) or differ (delegated agent mode)
 
     // Validate scope expiration
-    if scope.expires_at != 0 {
-        let clock = Clock::get()?;
-        require!(scope.expires_at > clock.unix_timestamp, AapError::ScopeExpired);
-    }
+    let now = Clock::get()?.unix_timestamp;
+    aap_core::scope::validate_new_scope(&scope.into(), now).map_err(AapError::from)?;
 
     let light_cpi_accounts = CpiAccounts::new(
         ctx.accounts.signer.as_ref(),
@@ -9,6 +9,8 @@ use light_sdk::{
 };
 use light_sdk::cpi::{LightCpiInstruction, InvokeLightSystemProgram};
 
+use crate::config::ProtocolConfig;
+use crate::constants::{CONFIG_SEED, V1_PROGRAM_ID};
 use crate::errors::AapError;
 use crate::state::{CompressedAgentIdentity, CompressedDelegationScope};
 use crate::LIGHT_CPI_SIGNER;
@@ -17,8 +19,13 @@ use crate::LIGHT_CPI_SIGNER;
 pub struct RegisterSubAgent<'info> {
     #[account(mut)]
     pub signer: Signer<'info>,
+
+    /// CHECK: V1 ProtocolConfig, deserialized and pause-checked by the handler
+    #[account(seeds = [CONFIG_SEED], bump, seeds::program = V1_PROGRAM_ID)]
+    pub config: UncheckedAccount<'info>,
 }
 
+#[allow(clippy::too_many_arguments)]
 pub fn handler<'info>(
     ctx: Context<'_, '_, '_, 'info, RegisterSubAgent<'info>>,
     proof: ValidityProof,
@@ -30,46 +37,22 @@ pub fn handler<'info>(
     metadata_hash: [u8; 32],
     scope: CompressedDelegationScope,
 ) -> Result<()> {
+    ProtocolConfig::load_live(&ctx.accounts.config)?;
+
     let signer_key = ctx.accounts.signer.key();
 
     // Signer must be the parent's agent_key
     require!(parent_identity.agent_key == signer_key, AapError::Unauthorized);
 
-    // Parent must not be a sub-agent itself (max 2 levels)
-    require!(
-        parent_identity.parent == Pubkey::default(),
-        AapError::MaxDelegationDepth
-    );
-
-    // Parent delegation must not be expired
-    if parent_identity.scope.expires_at != 0 {
-        let clock = Clock::get()?;
-        require!(
-            parent_identity.scope.expires_at > clock.unix_timestamp,
-            AapError::DelegationExpired
-        );
-    }
-
-    // Sub-agent scope cannot exceed parent scope
-    if !parent_identity.scope.can_sign_agreements {
-        require!(!scope.can_sign_agreements, AapError::SubAgentScopeExceedsParent);
-    }
-    if parent_identity.scope.can_commit_funds {
-        if parent_identity.scope.max_commit_lamports > 0 {
-            require!(
-                scope.max_commit_lamports <= parent_identity.scope.max_commit_lamports,
-                AapError::SubAgentScopeExceedsParent
-            );
-        }
-    } else {
-        require!(!scope.can_commit_funds, AapError::SubAgentScopeExceedsParent);
-    }
-
-    // Validate scope expiration
-    if scope.expires_at != 0 {
-        let clock = Clock::get()?;
-        require!(scope.expires_at > clock.unix_timestamp, AapError::ScopeExpired);
-    }
+    // Max 2 levels, parent not expired, sub-agent scope within parent, scope not expired
+    let now = Clock::get()?.unix_timestamp;
+    aap_core::scope::validate_sub_agent(
+        &parent_identity.scope.into(),
+        parent_identity.parent != Pubkey::default(),
+        &scope.into(),
+        now,
+    )
+    .map_err(AapError::from)?;
 
     let light_cpi_accounts = CpiAccounts::new(
         ctx.accounts.signer.as_ref(),
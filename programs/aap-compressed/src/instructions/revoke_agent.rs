@@ -6,6 +6,8 @@ use light_sdk::{
 };
 use light_sdk::cpi::{LightCpiInstruction, InvokeLightSystemProgram};
 
+use crate::config::ProtocolConfig;
+use crate::constants::{CONFIG_SEED, V1_PROGRAM_ID};
 use crate::errors::AapError;
 use crate::state::CompressedAgentIdentity;
 use crate::LIGHT_CPI_SIGNER;
@@ -14,6 +16,10 @@ use crate::LIGHT_CPI_SIGNER;
 pub struct RevokeAgent<'info> {
     #[account(mut)]
     pub signer: Signer<'info>,
+
+    /// CHECK: V1 ProtocolConfig, deserialized and pause-checked by the handler
+    #[account(seeds = [CONFIG_SEED], bump, seeds::program = V1_PROGRAM_ID)]
+    pub config: UncheckedAccount<'info>,
 }
 
 pub fn handler<'info>(
@@ -22,6 +28,8 @@ pub fn handler<'info>(
     account_meta: CompressedAccountMeta,
     current_identity: CompressedAgentIdentity,
 ) -> Result<()> {
+    ProtocolConfig::load_live(&ctx.accounts.config)?;
+
     let signer_key = ctx.accounts.signer.key();
 
     // Only authority can revoke
@@ -6,6 +6,8 @@ use light_sdk::{
 };
 use light_sdk::cpi::{LightCpiInstruction, InvokeLightSystemProgram};
 
+use crate::auth::require_party_of;
+use crate::config::ProtocolConfig;
 use crate::constants::*;
 use crate::errors::AapError;
 use crate::state::{CompressedAgentIdentity, CompressedAgreement, CompressedAgreementParty};
@@ -15,8 +17,13 @@ use crate::LIGHT_CPI_SIGNER;
 pub struct SignAgreement<'info> {
     #[account(mut)]
     pub signer: Signer<'info>,
+
+    /// CHECK: V1 ProtocolConfig, deserialized and pause-checked by the handler
+    #[account(seeds = [CONFIG_SEED], bump, seeds::program = V1_PROGRAM_ID)]
+    pub config: UncheckedAccount<'info>,
 }
 
+#[allow(clippy::too_many_arguments)]
 pub fn handler<'info>(
     ctx: Context<'_, '_, '_, 'info, SignAgreement<'info>>,
     proof: ValidityProof,
@@ -30,35 +37,31 @@ pub fn handler<'info>(
     party_meta: CompressedAccountMeta,
     current_party: CompressedAgreementParty,
 ) -> Result<()> {
+    ProtocolConfig::load_live(&ctx.accounts.config)?;
+
     let signer_key = ctx.accounts.signer.key();
 
     // Signer must be the identity's agent_key
     require!(signer_identity.agent_key == signer_key, AapError::Unauthorized);
 
     // Validate delegation
-    if signer_identity.scope.expires_at != 0 {
-        let clock = Clock::get()?;
-        require!(
-            signer_identity.scope.expires_at > clock.unix_timestamp,
-            AapError::DelegationExpired
-        );
-    }
-    require!(
-        signer_identity.scope.can_sign_agreements,
-        AapError::CannotSignAgreements
-    );
+    let now = Clock::get()?.unix_timestamp;
+    aap_core::scope::require_can_sign(&signer_identity.scope.into(), now)
+        .map_err(AapError::from)?;
 
     // Agreement must be in Proposed status
     require!(current_agreement.status == STATUS_PROPOSED, AapError::InvalidStatus);
 
     // Check agreement expiry
-    if current_agreement.expires_at != 0 {
-        let clock = Clock::get()?;
-        require!(
-            current_agreement.expires_at > clock.unix_timestamp,
-            AapError::AgreementExpired
-        );
-    }
+    aap_core::status::require_agreement_not_expired(current_agreement.expires_at, now)
+        .map_err(AapError::from)?;
+
+    // Party must belong to this agreement and to the signer's identity
+    require_party_of(
+        &current_party,
+        &agreement_meta.address,
+        &signer_identity_meta.address,
+    )?;
 
     // Party must not have already signed
     require!(!current_party.signed, AapError::AlreadySigned);
@@ -82,6 +85,7 @@ pub fn handler<'info>(
 }
 
 #[inline(never)]
+#[allow(clippy::too_many_arguments)]
 fn invoke_sign_cpi<'info>(
     proof: ValidityProof,
     signer_identity_meta: CompressedAccountMeta,
@@ -106,7 +110,7 @@ fn invoke_sign_cpi<'info>(
         current_agreement.clone(),
     )?;
     agreement.num_signed += 1;
-    if agreement.num_signed == agreement.num_parties {
+    if aap_core::parties::is_fully_signed(agreement.num_signed, agreement.num_parties) {
         agreement.status = STATUS_ACTIVE;
     }
 
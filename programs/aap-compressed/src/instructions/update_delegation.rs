@@ -6,6 +6,8 @@ use light_sdk::{
 };
 use light_sdk::cpi::{LightCpiInstruction, InvokeLightSystemProgram};
 
+use crate::config::ProtocolConfig;
+use crate::constants::{CONFIG_SEED, V1_PROGRAM_ID};
 use crate::errors::AapError;
 use crate::state::{CompressedAgentIdentity, CompressedDelegationScope};
 use crate::LIGHT_CPI_SIGNER;
@@ -14,6 +16,10 @@ use crate::LIGHT_CPI_SIGNER;
 pub struct UpdateDelegation<'info> {
     #[account(mut)]
     pub signer: Signer<'info>,
+
+    /// CHECK: V1 ProtocolConfig, deserialized and pause-checked by the handler
+    #[account(seeds = [CONFIG_SEED], bump, seeds::program = V1_PROGRAM_ID)]
+    pub config: UncheckedAccount<'info>,
 }
 
 pub fn handler<'info>(
@@ -23,16 +29,16 @@ pub fn handler<'info>(
     current_identity: CompressedAgentIdentity,
     new_scope: CompressedDelegationScope,
 ) -> Result<()> {
+    ProtocolConfig::load_live(&ctx.accounts.config)?;
+
     let signer_key = ctx.accounts.signer.key();
 
     // Only authority can update delegation
     require!(current_identity.authority == signer_key, AapError::Unauthorized);
 
     // Validate new scope expiration
-    if new_scope.expires_at != 0 {
-        let clock = Clock::get()?;
-        require!(new_scope.expires_at > clock.unix_timestamp, AapError::ScopeExpired);
-    }
+    let now = Clock::get()?.unix_timestamp;
+    aap_core::scope::validate_new_scope(&new_scope.into(), now).map_err(AapError::from)?;
 
     let mut identity = LightAccount::<CompressedAgentIdentity>::new_mut(
         &crate::ID,
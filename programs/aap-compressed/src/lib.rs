@@ -4,9 +4,12 @@
 use anchor_lang::prelude::*;
 use light_sdk::{cpi::CpiSigner, derive_light_cpi_signer};
 
+pub mod auth;
+pub mod config;
 pub mod constants;
 pub mod errors;
 pub mod instructions;
+pub mod migration;
 pub mod state;
 
 // Re-export instruction module contents at crate root (required by Anchor 0.31.x #[program] macro)
@@ -21,6 +24,11 @@ pub use instructions::sign_agreement::*;
 pub use instructions::cancel_agreement::*;
 pub use instructions::fulfill_agreement::*;
 pub use instructions::close_agreement::*;
+pub use instructions::import_agent::*;
+pub use instructions::import_agreement::*;
+pub use instructions::import_party::*;
+pub use instructions::decompress_agreement::*;
+pub use instructions::decompress_party::*;
 
 pub use state::{CompressedAgentIdentity, CompressedDelegationScope, CompressedAgreement, CompressedAgreementParty};
 
@@ -68,6 +76,7 @@ pub mod aap_compressed {
         )
     }
 
+    #[allow(clippy::too_many_arguments)]
     pub fn register_sub_agent<'info>(
         ctx: Context<'_, '_, '_, 'info, RegisterSubAgent<'info>>,
         proof: ValidityProof,
@@ -120,6 +129,7 @@ pub mod aap_compressed {
         )
     }
 
+    #[allow(clippy::too_many_arguments)]
     pub fn add_party<'info>(
         ctx: Context<'_, '_, '_, 'info, AddParty<'info>>,
         proof: ValidityProof,
@@ -141,6 +151,7 @@ pub mod aap_compressed {
         )
     }
 
+    #[allow(clippy::too_many_arguments)]
     pub fn sign_agreement<'info>(
         ctx: Context<'_, '_, '_, 'info, SignAgreement<'info>>,
         proof: ValidityProof,
@@ -171,6 +182,7 @@ pub mod aap_compressed {
         )
     }
 
+    #[allow(clippy::too_many_arguments)]
     pub fn fulfill_agreement<'info>(
         ctx: Context<'_, '_, '_, 'info, FulfillAgreement<'info>>,
         proof: ValidityProof,
@@ -187,6 +199,7 @@ pub mod aap_compressed {
         )
     }
 
+    #[allow(clippy::too_many_arguments)]
     pub fn close_agreement<'info>(
         ctx: Context<'_, '_, '_, 'info, CloseAgreement<'info>>,
         proof: ValidityProof,
@@ -202,4 +215,78 @@ pub mod aap_compressed {
             signer_party_meta, signer_party, agreement_meta, current_agreement,
         )
     }
+
+    /// Migration target for V1 `migrate_agent` (CPI only).
+    pub fn import_agent<'info>(
+        ctx: Context<'_, '_, '_, 'info, ImportAgent<'info>>,
+        proof: ValidityProof,
+        address_tree_info: PackedAddressTreeInfo,
+        output_state_tree_index: u8,
+        snapshot: AgentSnapshot,
+    ) -> Result<()> {
+        instructions::import_agent::handler(
+            ctx, proof, address_tree_info, output_state_tree_index, snapshot,
+        )
+    }
+
+    /// Migration target for V1 `migrate_agreement` (CPI only).
+    pub fn import_agreement<'info>(
+        ctx: Context<'_, '_, '_, 'info, ImportAgreement<'info>>,
+        proof: ValidityProof,
+        address_tree_info: PackedAddressTreeInfo,
+        output_state_tree_index: u8,
+        snapshot: AgreementSnapshot,
+    ) -> Result<()> {
+        instructions::import_agreement::handler(
+            ctx, proof, address_tree_info, output_state_tree_index, snapshot,
+        )
+    }
+
+    /// Migration target for V1 `migrate_party` (CPI only).
+    pub fn import_party<'info>(
+        ctx: Context<'_, '_, '_, 'info, ImportParty<'info>>,
+        proof: ValidityProof,
+        address_tree_info: PackedAddressTreeInfo,
+        output_state_tree_index: u8,
+        snapshot: PartySnapshot,
+    ) -> Result<()> {
+        instructions::import_party::handler(
+            ctx, proof, address_tree_info, output_state_tree_index, snapshot,
+        )
+    }
+
+    /// Move a compressed agreement back into a V1 Agreement PDA.
+    #[allow(clippy::too_many_arguments)]
+    pub fn decompress_agreement<'info>(
+        ctx: Context<'_, '_, '_, 'info, DecompressAgreement<'info>>,
+        proof: ValidityProof,
+        proposer_identity_meta: CompressedAccountMeta,
+        proposer_identity: CompressedAgentIdentity,
+        agreement_meta: CompressedAccountMeta,
+        current_agreement: CompressedAgreement,
+        proposer_agent_key: Pubkey,
+        address_tree_pubkey: Pubkey,
+    ) -> Result<()> {
+        instructions::decompress_agreement::handler(
+            ctx, proof, proposer_identity_meta, proposer_identity,
+            agreement_meta, current_agreement, proposer_agent_key, address_tree_pubkey,
+        )
+    }
+
+    /// Move a compressed party back into a V1 AgreementParty PDA.
+    #[allow(clippy::too_many_arguments)]
+    pub fn decompress_party<'info>(
+        ctx: Context<'_, '_, '_, 'info, DecompressParty<'info>>,
+        proof: ValidityProof,
+        party_meta: CompressedAccountMeta,
+        current_party: CompressedAgreementParty,
+        agreement_id: [u8; 16],
+        agent_key: Pubkey,
+        address_tree_pubkey: Pubkey,
+    ) -> Result<()> {
+        instructions::decompress_party::handler(
+            ctx, proof, party_meta, current_party,
+            agreement_id, agent_key, address_tree_pubkey,
+        )
+    }
 }
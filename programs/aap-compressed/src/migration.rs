@@ -0,0 +1,62 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::{instruction::Instruction, program::invoke_signed};
+
+use crate::constants::*;
+
+// Anchor discriminators of the V1 materialize instructions:
+// sha256("global:<name>")[..8]
+pub const MATERIALIZE_AGREEMENT_DISCRIMINATOR: [u8; 8] = [86, 56, 71, 85, 53, 135, 203, 150];
+pub const MATERIALIZE_PARTY_DISCRIMINATOR: [u8; 8] = [184, 55, 168, 65, 144, 108, 196, 45];
+
+/// Accounts shared by every CPI into a V1 `materialize_*` instruction.
+pub struct MaterializeCpiAccounts<'a, 'info> {
+    pub payer: &'a AccountInfo<'info>,
+    pub migration_authority: &'a AccountInfo<'info>,
+    pub migration_authority_bump: u8,
+    pub v1_program: &'a AccountInfo<'info>,
+    /// The V1 instruction's remaining accounts, in its declared order.
+    pub accounts: &'a [AccountInfo<'info>],
+}
+
+/// CPI into a V1 `materialize_*` instruction, signed by this program's migration PDA.
+/// The snapshot is built from proof-verified compressed state and is the
+/// instruction's only argument.
+pub fn invoke_materialize<T: AnchorSerialize>(
+    discriminator: [u8; 8],
+    snapshot: &T,
+    accounts: MaterializeCpiAccounts,
+) -> Result<()> {
+    let mut data = discriminator.to_vec();
+    snapshot.serialize(&mut data)?;
+
+    let mut metas = vec![
+        AccountMeta::new(accounts.payer.key(), true),
+        AccountMeta::new_readonly(accounts.migration_authority.key(), true),
+    ];
+    metas.extend(accounts.accounts.iter().map(|account| {
+        if account.is_writable {
+            AccountMeta::new(account.key(), account.is_signer)
+        } else {
+            AccountMeta::new_readonly(account.key(), account.is_signer)
+        }
+    }));
+
+    let mut infos = vec![
+        accounts.payer.clone(),
+        accounts.migration_authority.clone(),
+    ];
+    infos.extend_from_slice(accounts.accounts);
+    infos.push(accounts.v1_program.clone());
+
+    invoke_signed(
+        &Instruction {
+            program_id: V1_PROGRAM_ID,
+            accounts: metas,
+            data,
+        },
+        &infos,
+        &[&[MIGRATION_SEED, &[accounts.migration_authority_bump]]],
+    )?;
+
+    Ok(())
+}
@@ -20,3 +20,14 @@ pub struct CompressedDelegationScope {
     pub max_commit_lamports: u64,
     pub expires_at: i64,
 }
+
+impl From<CompressedDelegationScope> for aap_core::Scope {
+    fn from(s: CompressedDelegationScope) -> Self {
+        aap_core::Scope {
+            can_sign_agreements: s.can_sign_agreements,
+            can_commit_funds: s.can_commit_funds,
+            max_commit_lamports: s.max_commit_lamports,
+            expires_at: s.expires_at,
+        }
+    }
+}
@@ -1,13 +1,15 @@
 #![cfg(feature = "test-sbf")]
 
 use anchor_lang::{AnchorDeserialize, InstructionData, ToAccountMetas};
+use aap_compressed::config::{ProtocolConfig, PROTOCOL_CONFIG_DISCRIMINATOR};
+use aap_compressed::constants::{MAX_PARTIES, V1_PROGRAM_ID};
 use aap_compressed::{
     CompressedAgentIdentity, CompressedAgreement, CompressedAgreementParty,
     CompressedDelegationScope,
 };
 use light_client::indexer::CompressedAccount;
 use light_program_test::{
-    program_test::LightProgramTest, AddressWithTree, Indexer, ProgramTestConfig, Rpc, RpcError,
+    program_test::{LightProgramTest, TestRpc}, AddressWithTree, Indexer, ProgramTestConfig, Rpc, RpcError,
 };
 use light_sdk::{
     address::v2::derive_address,
@@ -16,7 +18,9 @@ use light_sdk::{
     },
 };
 use solana_sdk::{
+    account::Account,
     instruction::Instruction,
+    pubkey::Pubkey,
     signature::{Keypair, Signature, Signer},
 };
 
@@ -28,6 +32,7 @@ async fn test_register_update_revoke_agent() {
     let config = ProgramTestConfig::new(true, Some(vec![("aap_compressed", aap_compressed::ID)]));
     let mut rpc = LightProgramTest::new(config).await.unwrap();
     let payer = rpc.get_payer().insecure_clone();
+    install_protocol_config(&mut rpc, &payer.pubkey(), false);
     let agent_key = Keypair::new();
 
     let address_tree_info = rpc.get_address_tree_v2();
@@ -115,6 +120,7 @@ async fn test_agreement_lifecycle() {
     let config = ProgramTestConfig::new(true, Some(vec![("aap_compressed", aap_compressed::ID)]));
     let mut rpc = LightProgramTest::new(config).await.unwrap();
     let payer = rpc.get_payer().insecure_clone();
+    install_protocol_config(&mut rpc, &payer.pubkey(), false);
 
     let proposer_agent = Keypair::new();
     let counterparty_agent = Keypair::new();
@@ -434,6 +440,7 @@ async fn test_cancel_agreement() {
     let config = ProgramTestConfig::new(true, Some(vec![("aap_compressed", aap_compressed::ID)]));
     let mut rpc = LightProgramTest::new(config).await.unwrap();
     let payer = rpc.get_payer().insecure_clone();
+    install_protocol_config(&mut rpc, &payer.pubkey(), false);
 
     let proposer_agent = Keypair::new();
     rpc.airdrop_lamports(&proposer_agent.pubkey(), 1_000_000_000)
@@ -535,6 +542,366 @@ async fn test_cancel_agreement() {
     println!("Cancel agreement test passed: propose → cancel");
 }
 
+// =========================================================================
+// Test: Signing with a party record from another agreement is rejected
+// =========================================================================
+#[tokio::test]
+async fn test_sign_with_foreign_party_rejected() {
+    let config = ProgramTestConfig::new(true, Some(vec![("aap_compressed", aap_compressed::ID)]));
+    let mut rpc = LightProgramTest::new(config).await.unwrap();
+    let payer = rpc.get_payer().insecure_clone();
+    install_protocol_config(&mut rpc, &payer.pubkey(), false);
+
+    let proposer_agent = Keypair::new();
+    let counterparty_agent = Keypair::new();
+    rpc.airdrop_lamports(&proposer_agent.pubkey(), 1_000_000_000)
+        .await
+        .unwrap();
+    rpc.airdrop_lamports(&counterparty_agent.pubkey(), 1_000_000_000)
+        .await
+        .unwrap();
+
+    let address_tree_info = rpc.get_address_tree_v2();
+    let scope = CompressedDelegationScope {
+        can_sign_agreements: true,
+        can_commit_funds: false,
+        max_commit_lamports: 0,
+        expires_at: 0,
+    };
+
+    let (proposer_address, _) = derive_address(
+        &[b"agent", proposer_agent.pubkey().as_ref()],
+        &address_tree_info.tree,
+        &aap_compressed::ID,
+    );
+    register_agent(
+        &mut rpc,
+        &payer,
+        &proposer_address,
+        address_tree_info,
+        proposer_agent.pubkey().to_bytes(),
+        [1u8; 32],
+        scope,
+    )
+    .await
+    .unwrap();
+
+    let (counterparty_address, _) = derive_address(
+        &[b"agent", counterparty_agent.pubkey().as_ref()],
+        &address_tree_info.tree,
+        &aap_compressed::ID,
+    );
+    register_agent(
+        &mut rpc,
+        &payer,
+        &counterparty_address,
+        address_tree_info,
+        counterparty_agent.pubkey().to_bytes(),
+        [2u8; 32],
+        scope,
+    )
+    .await
+    .unwrap();
+
+    // Propose two agreements and add the counterparty to both
+    let agreement_ids: [[u8; 16]; 2] = [[7u8; 16], [8u8; 16]];
+    for agreement_id in agreement_ids {
+        let proposer_account = rpc
+            .get_compressed_account(proposer_address, None)
+            .await
+            .unwrap()
+            .value
+            .unwrap();
+        propose_agreement(
+            &mut rpc,
+            &payer,
+            &proposer_agent,
+            &proposer_account,
+            address_tree_info,
+            agreement_id,
+            [0u8; 32],
+            [0u8; 64],
+            2,
+        )
+        .await
+        .unwrap();
+
+        let (agreement_address, _) = derive_address(
+            &[b"agreement", &agreement_id],
+            &address_tree_info.tree,
+            &aap_compressed::ID,
+        );
+        let proposer_account = rpc
+            .get_compressed_account(proposer_address, None)
+            .await
+            .unwrap()
+            .value
+            .unwrap();
+        let agreement_account = rpc
+            .get_compressed_account(agreement_address, None)
+            .await
+            .unwrap()
+            .value
+            .unwrap();
+        let counterparty_account = rpc
+            .get_compressed_account(counterparty_address, None)
+            .await
+            .unwrap()
+            .value
+            .unwrap();
+        add_party(
+            &mut rpc,
+            &payer,
+            &proposer_agent,
+            &proposer_account,
+            &agreement_account,
+            &counterparty_account,
+            address_tree_info,
+            1, // ROLE_COUNTERPARTY
+        )
+        .await
+        .unwrap();
+    }
+
+    // Sign the first agreement using the party record of the second
+    let (first_agreement_address, _) = derive_address(
+        &[b"agreement", &agreement_ids[0]],
+        &address_tree_info.tree,
+        &aap_compressed::ID,
+    );
+    let (foreign_party_address, _) = derive_address(
+        &[b"party", &agreement_ids[1], &counterparty_address],
+        &address_tree_info.tree,
+        &aap_compressed::ID,
+    );
+    let counterparty_account = rpc
+        .get_compressed_account(counterparty_address, None)
+        .await
+        .unwrap()
+        .value
+        .unwrap();
+    let agreement_account = rpc
+        .get_compressed_account(first_agreement_address, None)
+        .await
+        .unwrap()
+        .value
+        .unwrap();
+    let foreign_party_account = rpc
+        .get_compressed_account(foreign_party_address, None)
+        .await
+        .unwrap()
+        .value
+        .unwrap();
+
+    let result = sign_agreement(
+        &mut rpc,
+        &payer,
+        &counterparty_agent,
+        &counterparty_account,
+        &agreement_account,
+        &foreign_party_account,
+    )
+    .await;
+    assert!(result.is_err(), "foreign party record must be rejected");
+
+    // Agreement is untouched
+    let agreement_account = rpc
+        .get_compressed_account(first_agreement_address, None)
+        .await
+        .unwrap()
+        .value
+        .unwrap();
+    let data = &agreement_account.data.as_ref().unwrap().data;
+    let agreement = CompressedAgreement::deserialize(&mut &data[..]).unwrap();
+    assert_eq!(agreement.status, 0); // STATUS_PROPOSED
+    assert_eq!(agreement.num_signed, 1);
+
+    println!("Foreign party record rejected on sign");
+}
+
+// =========================================================================
+// Test: Human signer mode (agent_key == authority) + sub-agent depth limit
+// =========================================================================
+#[tokio::test]
+async fn test_human_signer_and_sub_agent_depth() {
+    let config = ProgramTestConfig::new(true, Some(vec![("aap_compressed", aap_compressed::ID)]));
+    let mut rpc = LightProgramTest::new(config).await.unwrap();
+    let payer = rpc.get_payer().insecure_clone();
+    install_protocol_config(&mut rpc, &payer.pubkey(), false);
+    let sub_agent = Keypair::new();
+    rpc.airdrop_lamports(&sub_agent.pubkey(), 1_000_000_000)
+        .await
+        .unwrap();
+
+    let address_tree_info = rpc.get_address_tree_v2();
+    let scope = CompressedDelegationScope {
+        can_sign_agreements: true,
+        can_commit_funds: false,
+        max_commit_lamports: 0,
+        expires_at: 0,
+    };
+
+    // Register the human's own wallet as the agent key
+    let (human_address, _) = derive_address(
+        &[b"agent", payer.pubkey().as_ref()],
+        &address_tree_info.tree,
+        &aap_compressed::ID,
+    );
+    register_agent(
+        &mut rpc,
+        &payer,
+        &human_address,
+        address_tree_info,
+        payer.pubkey().to_bytes(),
+        [1u8; 32],
+        scope,
+    )
+    .await
+    .unwrap();
+
+    let human_account = rpc
+        .get_compressed_account(human_address, None)
+        .await
+        .unwrap()
+        .value
+        .unwrap();
+    let data = &human_account.data.as_ref().unwrap().data;
+    let identity = CompressedAgentIdentity::deserialize(&mut &data[..]).unwrap();
+    assert_eq!(identity.authority, payer.pubkey());
+    assert_eq!(identity.agent_key, payer.pubkey());
+    println!("  ✓ register_agent (human signer mode)");
+
+    // Human-signer identity registers a sub-agent
+    register_sub_agent(
+        &mut rpc,
+        &payer,
+        &payer,
+        &human_account,
+        address_tree_info,
+        sub_agent.pubkey().to_bytes(),
+        [2u8; 32],
+        scope,
+    )
+    .await
+    .unwrap();
+
+    let (sub_address, _) = derive_address(
+        &[b"agent", sub_agent.pubkey().as_ref()],
+        &address_tree_info.tree,
+        &aap_compressed::ID,
+    );
+    let sub_account = rpc
+        .get_compressed_account(sub_address, None)
+        .await
+        .unwrap()
+        .value
+        .unwrap();
+    let data = &sub_account.data.as_ref().unwrap().data;
+    let sub_identity = CompressedAgentIdentity::deserialize(&mut &data[..]).unwrap();
+    assert_eq!(sub_identity.authority, payer.pubkey());
+    assert_eq!(sub_identity.parent, Pubkey::from(human_address));
+    println!("  ✓ register_sub_agent");
+
+    // A sub-agent cannot register its own sub-agent (max 2 levels)
+    let result = register_sub_agent(
+        &mut rpc,
+        &payer,
+        &sub_agent,
+        &sub_account,
+        address_tree_info,
+        Keypair::new().pubkey().to_bytes(),
+        [3u8; 32],
+        scope,
+    )
+    .await;
+    assert!(result.is_err(), "third delegation level must be rejected");
+    println!("  ✓ register_sub_agent rejected at depth 3");
+}
+
+// =========================================================================
+// Test: a paused ProtocolConfig rejects instructions until resumed
+// =========================================================================
+#[tokio::test]
+async fn test_paused_protocol_rejects_instructions() {
+    let config = ProgramTestConfig::new(true, Some(vec![("aap_compressed", aap_compressed::ID)]));
+    let mut rpc = LightProgramTest::new(config).await.unwrap();
+    let payer = rpc.get_payer().insecure_clone();
+    install_protocol_config(&mut rpc, &payer.pubkey(), true);
+
+    let agent_key = Keypair::new();
+    let address_tree_info = rpc.get_address_tree_v2();
+    let (address, _) = derive_address(
+        &[b"agent", agent_key.pubkey().as_ref()],
+        &address_tree_info.tree,
+        &aap_compressed::ID,
+    );
+    let scope = CompressedDelegationScope {
+        can_sign_agreements: true,
+        can_commit_funds: false,
+        max_commit_lamports: 0,
+        expires_at: 0,
+    };
+
+    let result = register_agent(
+        &mut rpc,
+        &payer,
+        &address,
+        address_tree_info,
+        agent_key.pubkey().to_bytes(),
+        [1u8; 32],
+        scope,
+    )
+    .await;
+    assert!(result.is_err(), "register_agent must fail while paused");
+
+    install_protocol_config(&mut rpc, &payer.pubkey(), false);
+    register_agent(
+        &mut rpc,
+        &payer,
+        &address,
+        address_tree_info,
+        agent_key.pubkey().to_bytes(),
+        [1u8; 32],
+        scope,
+    )
+    .await
+    .unwrap();
+}
+
+// =========================================================================
+// Helper: V1 ProtocolConfig
+// =========================================================================
+/// Stand-in for V1 `initialize_config`: aap-compressed only reads the account.
+/// `treasury` doubles as admin; no fee.
+fn install_protocol_config(rpc: &mut LightProgramTest, treasury: &Pubkey, paused: bool) {
+    let (address, bump) = Pubkey::find_program_address(&[b"config"], &V1_PROGRAM_ID);
+    let config = ProtocolConfig {
+        admin: *treasury,
+        paused,
+        max_parties: MAX_PARTIES,
+        fee_lamports: 0,
+        treasury: *treasury,
+        bump,
+    };
+    let mut data = PROTOCOL_CONFIG_DISCRIMINATOR.to_vec();
+    anchor_lang::AnchorSerialize::serialize(&config, &mut data).unwrap();
+    rpc.set_account(
+        address,
+        Account {
+            lamports: 1_000_000_000,
+            data,
+            owner: V1_PROGRAM_ID,
+            executable: false,
+            rent_epoch: 0,
+        },
+    );
+}
+
+async fn treasury<R: Rpc>(rpc: &R) -> Pubkey {
+    let account = rpc.get_account(ProtocolConfig::address()).await.unwrap().unwrap();
+    ProtocolConfig::deserialize(&mut &account.data[8..]).unwrap().treasury
+}
+
 // =========================================================================
 // Helper: register_agent
 // =========================================================================
@@ -582,6 +949,7 @@ async fn register_agent<R: Rpc + Indexer>(
 
     let accounts = aap_compressed::accounts::RegisterAgent {
         signer: payer.pubkey(),
+        config: ProtocolConfig::address(),
     };
 
     let (remaining_accounts_metas, _, _) = remaining_accounts.to_account_metas();
@@ -600,6 +968,91 @@ async fn register_agent<R: Rpc + Indexer>(
         .await
 }
 
+// =========================================================================
+// Helper: register_sub_agent
+// =========================================================================
+#[allow(clippy::too_many_arguments)]
+async fn register_sub_agent<R: Rpc + Indexer>(
+    rpc: &mut R,
+    payer: &Keypair,
+    parent_signer: &Keypair,
+    parent_account: &CompressedAccount,
+    address_tree_info: light_client::indexer::TreeInfo,
+    sub_agent_key: [u8; 32],
+    metadata_hash: [u8; 32],
+    scope: CompressedDelegationScope,
+) -> Result<Signature, RpcError> {
+    let mut remaining_accounts = PackedAccounts::default();
+    let config = SystemAccountMetaConfig::new(aap_compressed::ID);
+    remaining_accounts.add_system_accounts_v2(config)?;
+
+    let (sub_agent_address, _) = derive_address(
+        &[b"agent", &sub_agent_key],
+        &address_tree_info.tree,
+        &aap_compressed::ID,
+    );
+
+    // Validity proof: 1 existing account + 1 new address
+    let rpc_result = rpc
+        .get_validity_proof(
+            vec![parent_account.hash],
+            vec![AddressWithTree {
+                tree: address_tree_info.tree,
+                address: sub_agent_address,
+            }],
+            None,
+        )
+        .await?
+        .value;
+
+    let packed_tree_accounts = rpc_result
+        .pack_tree_infos(&mut remaining_accounts)
+        .clone();
+    let state_trees = packed_tree_accounts.state_trees.unwrap();
+
+    let parent_identity = CompressedAgentIdentity::deserialize(
+        &mut parent_account.data.as_ref().unwrap().data.as_slice(),
+    )
+    .unwrap();
+
+    let parent_account_meta = CompressedAccountMeta {
+        tree_info: state_trees.packed_tree_infos[0],
+        address: parent_account.address.unwrap(),
+        output_state_tree_index: state_trees.output_tree_index,
+    };
+
+    let instruction_data = aap_compressed::instruction::RegisterSubAgent {
+        proof: rpc_result.proof,
+        parent_account_meta,
+        parent_identity,
+        address_tree_info: packed_tree_accounts.address_trees[0],
+        output_state_tree_index: state_trees.output_tree_index,
+        sub_agent_key,
+        metadata_hash,
+        scope,
+    };
+
+    let accounts = aap_compressed::accounts::RegisterSubAgent {
+        signer: parent_signer.pubkey(),
+        config: ProtocolConfig::address(),
+    };
+
+    let (remaining_accounts_metas, _, _) = remaining_accounts.to_account_metas();
+
+    let instruction = Instruction {
+        program_id: aap_compressed::ID,
+        accounts: [
+            accounts.to_account_metas(Some(true)),
+            remaining_accounts_metas,
+        ]
+        .concat(),
+        data: instruction_data.data(),
+    };
+
+    rpc.create_and_send_transaction(&[instruction], &payer.pubkey(), &[payer, parent_signer])
+        .await
+}
+
 // =========================================================================
 // Helper: update_delegation
 // =========================================================================
@@ -645,6 +1098,7 @@ async fn update_delegation<R: Rpc + Indexer>(
 
     let accounts = aap_compressed::accounts::UpdateDelegation {
         signer: payer.pubkey(),
+        config: ProtocolConfig::address(),
     };
 
     let (remaining_accounts_metas, _, _) = remaining_accounts.to_account_metas();
@@ -706,6 +1160,7 @@ async fn revoke_agent<R: Rpc + Indexer>(
 
     let accounts = aap_compressed::accounts::RevokeAgent {
         signer: payer.pubkey(),
+        config: ProtocolConfig::address(),
     };
 
     let (remaining_accounts_metas, _, _) = remaining_accounts.to_account_metas();
@@ -812,6 +1267,9 @@ async fn propose_agreement<R: Rpc + Indexer>(
 
     let accounts = aap_compressed::accounts::ProposeAgreement {
         signer: agent_signer.pubkey(),
+        config: ProtocolConfig::address(),
+        treasury: treasury(rpc).await,
+        system_program: solana_sdk::system_program::ID,
     };
 
     let (remaining_accounts_metas, _, _) = remaining_accounts.to_account_metas();
@@ -926,6 +1384,7 @@ async fn add_party<R: Rpc + Indexer>(
 
     let accounts = aap_compressed::accounts::AddParty {
         signer: proposer_signer.pubkey(),
+        config: ProtocolConfig::address(),
     };
 
     let (remaining_accounts_metas, _, _) = remaining_accounts.to_account_metas();
@@ -1019,6 +1478,7 @@ async fn sign_agreement<R: Rpc + Indexer>(
 
     let accounts = aap_compressed::accounts::SignAgreement {
         signer: signer_agent.pubkey(),
+        config: ProtocolConfig::address(),
     };
 
     let (remaining_accounts_metas, _, _) = remaining_accounts.to_account_metas();
@@ -1112,6 +1572,7 @@ async fn fulfill_agreement<R: Rpc + Indexer>(
 
     let accounts = aap_compressed::accounts::FulfillAgreement {
         signer: signer_agent.pubkey(),
+        config: ProtocolConfig::address(),
     };
 
     let (remaining_accounts_metas, _, _) = remaining_accounts.to_account_metas();
@@ -1205,6 +1666,7 @@ async fn close_agreement<R: Rpc + Indexer>(
     // close_agreement requires authority (payer), not agent_key
     let accounts = aap_compressed::accounts::CloseAgreement {
         signer: payer.pubkey(),
+        config: ProtocolConfig::address(),
     };
 
     let (remaining_accounts_metas, _, _) = remaining_accounts.to_account_metas();
@@ -1285,6 +1747,7 @@ async fn cancel_agreement<R: Rpc + Indexer>(
 
     let accounts = aap_compressed::accounts::CancelAgreement {
         signer: proposer_signer.pubkey(),
+        config: ProtocolConfig::address(),
     };
 
     let (remaining_accounts_metas, _, _) = remaining_accounts.to_account_metas();
@@ -1,30 +1,24 @@
-// AgreementType
-pub const AGREEMENT_TYPE_SAFE: u8 = 0;
-pub const AGREEMENT_TYPE_SERVICE: u8 = 1;
-pub const AGREEMENT_TYPE_REVENUE_SHARE: u8 = 2;
-pub const AGREEMENT_TYPE_JOINT_VENTURE: u8 = 3;
-pub const AGREEMENT_TYPE_CUSTOM: u8 = 4;
+use anchor_lang::prelude::*;
 
-// AgreementStatus
-pub const STATUS_PROPOSED: u8 = 0;
-pub const STATUS_ACTIVE: u8 = 1;
-pub const STATUS_FULFILLED: u8 = 2;
-pub const STATUS_BREACHED: u8 = 3;
-pub const STATUS_DISPUTED: u8 = 4;
-pub const STATUS_CANCELLED: u8 = 5;
+// AgreementType, AgreementStatus, Visibility, PartyRole and limits are shared with aap-compressed
+pub use aap_core::constants::*;
 
-// Visibility
-pub const VISIBILITY_PUBLIC: u8 = 0;
-pub const VISIBILITY_PRIVATE: u8 = 1;
+// Migration to aap-compressed
+pub const AAP_COMPRESSED_PROGRAM_ID: Pubkey = pubkey!("Ey56W7XXaeLm2kYNt5Ewp6TfgWgpVEZ2DD23ernmfuxY");
+pub const MIGRATION_SEED: &[u8] = b"migration"; // V1 PDA for migrate, aap-compressed PDA for decompress
 
-// PartyRole
-pub const ROLE_PROPOSER: u8 = 0;
-pub const ROLE_COUNTERPARTY: u8 = 1;
-pub const ROLE_WITNESS: u8 = 2;
-pub const ROLE_ARBITRATOR: u8 = 3;
+// ProtocolConfig PDA, read by aap-compressed too
+pub const CONFIG_SEED: &[u8] = b"config";
 
-// Limits
-pub const MAX_PARTIES: u8 = 8;
-pub const MIN_PARTIES: u8 = 2;
-pub const MAX_AGREEMENT_TYPE: u8 = 4;
-pub const MAX_ROLE: u8 = 3;
+// TombstoneKind
+pub const TOMBSTONE_KIND_AGENT: u8 = 0;
+pub const TOMBSTONE_KIND_AGREEMENT: u8 = 1;
+pub const TOMBSTONE_KIND_PARTY: u8 = 2;
+
+// Layout version written as the first field of every event; bump when any event changes
+pub const EVENT_VERSION: u8 = 1;
+
+// Threshold authorities
+pub const MAX_SET_MEMBERS: usize = 16; // approvals are a u16 bitmap over member indexes
+pub const MAX_ACTION_ACCOUNTS: usize = 24;
+pub const MAX_ACTION_DATA_LEN: usize = 512;
@@ -1,3 +1,4 @@
+use aap_core::RuleError;
 use anchor_lang::prelude::*;
 
 #[error_code]
@@ -38,7 +39,7 @@ pub enum AapError {
     #[msg("Invalid party role")]
     InvalidRole,
 
-    #[msg("Number of parties must be between 2 and 8")]
+    #[msg("Number of parties must be between 2 and the configured maximum")]
     InvalidPartyCount,
 
     #[msg("Agreement is not in the expected status")]
@@ -67,4 +68,176 @@ pub enum AapError {
 
     #[msg("Insufficient vault balance for withdrawal")]
     InsufficientVaultBalance,
+
+    #[msg("Tombstone does not match the migrated account")]
+    InvalidTombstone,
+
+    #[msg("Vault has funds committed to agreements")]
+    VaultHasCommitments,
+
+    #[msg("Identity's agent key was rotated: its key lookup account must be passed")]
+    KeyLookupRequired,
+
+    #[msg("New authority must differ from the current one")]
+    InvalidNewAuthority,
+
+    #[msg("Account is not a sub-agent of this identity under the same authority, or its vault")]
+    InvalidSubAgent,
+
+    #[msg("Authority set needs 1 to 16 distinct members and a threshold between 1 and the member count")]
+    InvalidAuthoritySet,
+
+    #[msg("Signer is not a member of the authority set")]
+    NotSetMember,
+
+    #[msg("Member already approved this action")]
+    AlreadyApproved,
+
+    #[msg("Action has fewer approvals than the set's threshold")]
+    ThresholdNotReached,
+
+    #[msg("Action exceeds the account or data limit, or references its own account")]
+    InvalidAction,
+
+    #[msg("Remaining accounts do not match the action's accounts")]
+    ActionAccountMismatch,
+
+    #[msg("Agent is frozen by its authority")]
+    AgentFrozen,
+
+    #[msg("Protocol is paused")]
+    ProtocolPaused,
+
+    #[msg("Treasury does not match the protocol config")]
+    InvalidTreasury,
+
+    #[msg("Amendment version must follow the latest one, which must be applied or withdrawn")]
+    InvalidAmendment,
+
+    #[msg("Amendment is not pending")]
+    AmendmentNotPending,
+
+    #[msg("Signed parties passed for reset do not match the agreement's signatures")]
+    InvalidPartyReset,
+
+    #[msg("Only an unsigned invited party can counter, after its previous round was answered")]
+    InvalidCounterProposal,
+
+    #[msg("Counter-proposal is not pending")]
+    CounterProposalNotPending,
+
+    #[msg("Agreement terms changed since the counter-proposal was made")]
+    StaleCounterProposal,
+
+    #[msg("Settlement must list every party of the agreement once")]
+    InvalidSettlement,
+
+    #[msg("Termination is not pending")]
+    TerminationNotPending,
+
+    #[msg("Escrow recipient must be the party's vault, or the wallet of a direct party")]
+    InvalidEscrowRecipient,
+
+    #[msg("Milestone schedule must have 1 to 16 milestones, each paying a positive amount to another party")]
+    InvalidMilestone,
+
+    #[msg("Milestone is not in a status that allows this")]
+    InvalidMilestoneStatus,
+
+    #[msg("Milestones can only be set before any other party signs")]
+    MilestonesLocked,
+
+    #[msg("Payer's escrow does not cover the milestone")]
+    MilestoneUnfunded,
+
+    #[msg("Agreement has milestones; it is fulfilled by releasing every milestone")]
+    MilestonesPending,
+
+    #[msg("Recurring payment needs a positive amount, period and number of periods between two parties")]
+    InvalidRecurringPayment,
+
+    #[msg("No payment period is due")]
+    PaymentNotDue,
+
+    #[msg("Recurring payment is not active")]
+    RecurringPaymentNotActive,
+
+    #[msg("Payment terms can only be set before any other party signs")]
+    PaymentTermsLocked,
+
+    #[msg("Stream needs a positive amount and an agreement expiry after it starts")]
+    InvalidStream,
+
+    #[msg("Escrow stream is not active")]
+    StreamNotActive,
+
+    #[msg("Revenue share needs 1 to 16 distinct parties with positive basis points summing to 10000")]
+    InvalidRevenueShare,
+
+    #[msg("Distribution accounts do not match the revenue share splits")]
+    InvalidDistribution,
+
+    #[msg("SAFE needs a positive investment and round valuation, a discount below 100% and two different parties")]
+    InvalidSafe,
+
+    #[msg("The SAFE's investor signs with sign_safe, which funds its escrow")]
+    SafeSignatureRequired,
+
+    #[msg("SAFE is not funded")]
+    SafeNotFunded,
+
+    #[msg("Party does not belong to this agreement")]
+    PartyNotInAgreement,
+
+    #[msg("Escrow changed since the termination was proposed; cancel it and propose a new split")]
+    StaleTermination,
+
+    #[msg("Agreement uses a V1-only extension and can't be migrated")]
+    AgreementHasExtensions,
+
+    #[msg("New agent key needs a key lookup account unless it is the identity's registered key")]
+    NewKeyLookupRequired,
+
+    #[msg("Agreement has an active escrow stream; cancel it first")]
+    StreamPending,
+
+    #[msg("Agreement was not decompressed from aap-compressed")]
+    AgreementNotDecompressed,
+
+    #[msg("Agreement has a pending amendment, counter-proposal or decline and can't be migrated")]
+    PendingNegotiation,
+
+    #[msg("Party is named in the agreement's payment terms")]
+    PartyInPaymentTerms,
+}
+
+impl From<RuleError> for AapError {
+    fn from(e: RuleError) -> Self {
+        match e {
+            RuleError::ScopeExpired => AapError::ScopeExpired,
+            RuleError::DelegationExpired => AapError::DelegationExpired,
+            RuleError::CannotSignAgreements => AapError::CannotSignAgreements,
+            RuleError::CannotCommitFunds => AapError::CannotCommitFunds,
+            RuleError::EscrowExceedsLimit => AapError::EscrowExceedsLimit,
+            RuleError::SubAgentScopeExceedsParent => AapError::SubAgentScopeExceedsParent,
+            RuleError::MaxDelegationDepth => AapError::MaxDelegationDepth,
+            RuleError::InvalidAgreementType => AapError::InvalidAgreementType,
+            RuleError::InvalidVisibility => AapError::InvalidVisibility,
+            RuleError::InvalidRole => AapError::InvalidRole,
+            RuleError::InvalidPartyCount => AapError::InvalidPartyCount,
+            RuleError::InvalidStatus => AapError::InvalidStatus,
+            RuleError::AgreementExpired => AapError::AgreementExpired,
+            RuleError::MaxPartiesExceeded => AapError::MaxPartiesExceeded,
+            RuleError::InvalidAmendment => AapError::InvalidAmendment,
+            RuleError::InvalidCounterProposal => AapError::InvalidCounterProposal,
+            RuleError::EscrowDistributionMismatch => AapError::EscrowDistributionMismatch,
+            RuleError::InvalidMilestone => AapError::InvalidMilestone,
+            RuleError::InvalidMilestoneStatus => AapError::InvalidMilestoneStatus,
+            RuleError::InvalidRecurringPayment => AapError::InvalidRecurringPayment,
+            RuleError::PaymentNotDue => AapError::PaymentNotDue,
+            RuleError::InvalidStream => AapError::InvalidStream,
+            RuleError::InvalidRevenueShare => AapError::InvalidRevenueShare,
+            RuleError::InvalidSafe => AapError::InvalidSafe,
+        }
+    }
 }
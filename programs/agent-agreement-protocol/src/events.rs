@@ -1,71 +1,746 @@
 use anchor_lang::prelude::*;
+use crate::state::{DelegationScope, MilestoneTerms, RecurringTerms, RevenueSplit, SafeTerms, SettlementShare};
+
+// Every event starts with `version` (EVENT_VERSION) and ends with the unix
+// `timestamp` it was emitted at. `actor` is the transaction signer that
+// authorized the change. Together the stream is enough to rebuild state
+// without fetching accounts, which may already be closed.
 
 #[event]
 pub struct AgentRegistered {
+    pub version: u8,
     pub authority: Pubkey,
     pub agent_key: Pubkey,
     pub agent_identity: Pubkey,
+    pub actor: Pubkey,
+    pub parent: Pubkey, // Pubkey::default() if no parent
+    pub metadata_hash: [u8; 32],
+    pub scope: DelegationScope,
+    pub timestamp: i64,
 }
 
 #[event]
 pub struct AgentRevoked {
+    pub version: u8,
+    pub authority: Pubkey,
+    pub agent_key: Pubkey,
+    pub agent_identity: Pubkey,
+    pub actor: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct AgentKeyRotated {
+    pub version: u8,
     pub authority: Pubkey,
+    pub agent_identity: Pubkey,
+    pub previous_agent_key: Pubkey,
     pub agent_key: Pubkey,
+    pub actor: Pubkey,
+    pub co_signed: bool, // new key signed the rotation
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct AuthorityTransferProposed {
+    pub version: u8,
+    pub agent_identity: Pubkey,
+    pub authority: Pubkey,
+    pub new_authority: Pubkey,
+    pub actor: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct AuthorityTransferCancelled {
+    pub version: u8,
+    pub agent_identity: Pubkey,
+    pub authority: Pubkey,
+    pub new_authority: Pubkey,
+    pub actor: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct AuthorityTransferred {
+    pub version: u8,
+    pub agent_identity: Pubkey,
+    pub previous_authority: Pubkey,
+    pub authority: Pubkey,
+    pub actor: Pubkey,
+    pub vault: bool,       // identity's vault existed and moved with it
+    pub sub_agents: u8,    // sub-agents moved along (with their vaults)
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct AuthoritySetCreated {
+    pub version: u8,
+    pub authority_set: Pubkey,
+    pub signer: Pubkey, // key to use as an identity's authority
+    pub set_id: [u8; 16],
+    pub members: Vec<Pubkey>,
+    pub threshold: u8,
+    pub actor: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct AuthorityActionProposed {
+    pub version: u8,
+    pub authority_set: Pubkey,
+    pub action: Pubkey,
+    pub index: u64,
+    pub actor: Pubkey,
+    pub discriminator: [u8; 8], // instruction the set will run
+    pub approvals: u8,          // the proposer's own approval
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct AuthorityActionApproved {
+    pub version: u8,
+    pub authority_set: Pubkey,
+    pub action: Pubkey,
+    pub index: u64,
+    pub actor: Pubkey,
+    pub approvals: u8,
+    pub threshold: u8,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct AuthorityActionExecuted {
+    pub version: u8,
+    pub authority_set: Pubkey,
+    pub action: Pubkey,
+    pub index: u64,
+    pub actor: Pubkey,
+    pub approvals: u8,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct AuthorityActionCancelled {
+    pub version: u8,
+    pub authority_set: Pubkey,
+    pub action: Pubkey,
+    pub index: u64,
+    pub actor: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct FreezeApplied {
+    pub version: u8,
+    pub target: Pubkey, // identity PDA, or the authority itself
+    pub authority: Pubkey,
+    pub actor: Pubkey,
+    pub authority_wide: bool,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct FreezeLifted {
+    pub version: u8,
+    pub target: Pubkey,
+    pub authority: Pubkey,
+    pub actor: Pubkey,
+    pub authority_wide: bool,
+    pub frozen_at: i64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ConfigUpdated {
+    pub version: u8,
+    pub admin: Pubkey,
+    pub actor: Pubkey,
+    pub paused: bool,
+    pub max_parties: u8,
+    pub fee_lamports: u64,
+    pub treasury: Pubkey,
+    pub timestamp: i64,
 }
 
 #[event]
 pub struct DelegationUpdated {
+    pub version: u8,
     pub authority: Pubkey,
     pub agent_identity: Pubkey,
+    pub agent_key: Pubkey,
+    pub actor: Pubkey,
+    pub previous_scope: DelegationScope,
+    pub scope: DelegationScope,
+    pub timestamp: i64,
 }
 
 #[event]
 pub struct AgreementProposed {
+    pub version: u8,
     pub agreement_id: [u8; 16],
     pub proposer: Pubkey,
     pub agreement_pda: Pubkey,
+    pub actor: Pubkey,
+    pub agreement_type: u8,
+    pub visibility: u8,
+    pub terms_hash: [u8; 32],
+    pub terms_uri: [u8; 64],
+    pub num_parties: u8,
+    pub expires_at: i64,
+    pub status: u8,
+    pub timestamp: i64,
 }
 
 #[event]
 pub struct PartyAdded {
+    pub version: u8,
     pub agreement_id: [u8; 16],
-    pub party: Pubkey,
+    pub party: Pubkey, // AgentIdentity PDA, or the raw pubkey when `direct`
     pub role: u8,
+    pub actor: Pubkey,
+    pub direct: bool,
+    pub parties_added: u8,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct PartyRemoved {
+    pub version: u8,
+    pub agreement_id: [u8; 16],
+    pub party: Pubkey, // AgentIdentity PDA, or the raw pubkey for direct parties
+    pub role: u8,
+    pub actor: Pubkey,
+    pub parties_added: u8,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct PartyCountChanged {
+    pub version: u8,
+    pub agreement_id: [u8; 16],
+    pub actor: Pubkey,
+    pub previous_num_parties: u8,
+    pub num_parties: u8,
+    pub parties_added: u8,
+    pub num_signed: u8,
+    pub timestamp: i64,
 }
 
 #[event]
 pub struct AgreementSigned {
+    pub version: u8,
     pub agreement_id: [u8; 16],
-    pub party: Pubkey,
+    pub party: Pubkey, // AgentIdentity PDA, or the raw pubkey when `direct`
+    pub actor: Pubkey,
+    pub direct: bool,
+    pub num_signed: u8,
+    pub num_parties: u8,
+    pub timestamp: i64,
 }
 
 #[event]
 pub struct AgreementActivated {
+    pub version: u8,
     pub agreement_id: [u8; 16],
+    pub actor: Pubkey,
+    pub previous_status: u8,
+    pub status: u8,
+    pub timestamp: i64,
 }
 
 #[event]
 pub struct AgreementCancelled {
+    pub version: u8,
+    pub agreement_id: [u8; 16],
+    pub actor: Pubkey,
+    pub previous_status: u8,
+    pub status: u8,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct AgreementDeclined {
+    pub version: u8,
     pub agreement_id: [u8; 16],
+    pub party: Pubkey,
+    pub actor: Pubkey,
+    pub direct: bool,
+    pub previous_status: u8,
+    pub status: u8,
+    pub timestamp: i64,
 }
 
 #[event]
 pub struct AgreementFulfilled {
+    pub version: u8,
+    pub agreement_id: [u8; 16],
+    pub actor: Pubkey,
+    pub previous_status: u8,
+    pub status: u8,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct AgreementClosed {
+    pub version: u8,
+    pub agreement_id: [u8; 16],
+    pub party: Pubkey, // closing party's AgentIdentity (its party PDA is closed too)
+    pub actor: Pubkey,
+    pub status: u8, // terminal status the agreement was closed in
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct AmendmentProposed {
+    pub version: u8,
+    pub agreement_id: [u8; 16],
+    pub amendment: Pubkey,
+    pub amendment_version: u16,
+    pub proposer: Pubkey,
+    pub actor: Pubkey,
+    pub phase: u8, // agreement status: Proposed applies at once, Active waits for all parties
+    pub previous_terms_hash: [u8; 32],
+    pub terms_hash: [u8; 32],
+    pub terms_uri: [u8; 64],
+    pub expires_at: i64,
+    pub required: u8,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct AmendmentSigned {
+    pub version: u8,
+    pub agreement_id: [u8; 16],
+    pub amendment: Pubkey,
+    pub amendment_version: u16,
+    pub party: Pubkey, // AgentIdentity PDA, or the raw pubkey when `direct`
+    pub actor: Pubkey,
+    pub direct: bool,
+    pub approvals: u8,
+    pub required: u8,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct AmendmentApplied {
+    pub version: u8,
+    pub agreement_id: [u8; 16],
+    pub amendment: Pubkey,
+    pub amendment_version: u16,
+    pub actor: Pubkey,
+    pub previous_terms_hash: [u8; 32],
+    pub terms_hash: [u8; 32],
+    pub signatures_reset: u8, // Proposed phase: parties that must sign again
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct AmendmentWithdrawn {
+    pub version: u8,
+    pub agreement_id: [u8; 16],
+    pub amendment: Pubkey,
+    pub amendment_version: u16,
+    pub actor: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct CounterProposed {
+    pub version: u8,
+    pub agreement_id: [u8; 16],
+    pub counter: Pubkey,
+    pub round: u16,
+    pub party: Pubkey,
+    pub actor: Pubkey,
+    pub base_terms_hash: [u8; 32],
+    pub terms_hash: [u8; 32],
+    pub terms_uri: [u8; 64],
+    pub expires_at: i64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct CounterProposalAccepted {
+    pub version: u8,
+    pub agreement_id: [u8; 16],
+    pub counter: Pubkey,
+    pub round: u16,
+    pub party: Pubkey,
+    pub amendment: Pubkey,
+    pub amendment_version: u16,
+    pub actor: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct CounterProposalRejected {
+    pub version: u8,
+    pub agreement_id: [u8; 16],
+    pub counter: Pubkey,
+    pub round: u16,
+    pub party: Pubkey,
+    pub actor: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct EscrowFunded {
+    pub version: u8,
+    pub agreement_id: [u8; 16],
+    pub party: Pubkey,
+    pub actor: Pubkey,
+    pub amount: u64,
+    pub party_escrow: u64,
+    pub escrow_total: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct EscrowReleased {
+    pub version: u8,
+    pub agreement_id: [u8; 16],
+    pub party: Pubkey, // AgentIdentity PDA, or the raw pubkey for direct parties
+    pub recipient: Pubkey,
+    pub actor: Pubkey,
+    pub amount: u64,
+    pub escrow_total: u64,
+    pub status: u8,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct TerminationProposed {
+    pub version: u8,
+    pub agreement_id: [u8; 16],
+    pub termination: Pubkey,
+    pub party: Pubkey,
+    pub actor: Pubkey,
+    pub shares: Vec<SettlementShare>,
+    pub required: u8,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct TerminationApproved {
+    pub version: u8,
+    pub agreement_id: [u8; 16],
+    pub termination: Pubkey,
+    pub party: Pubkey,
+    pub actor: Pubkey,
+    pub direct: bool,
+    pub approvals: u8,
+    pub required: u8,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct TerminationCancelled {
+    pub version: u8,
+    pub agreement_id: [u8; 16],
+    pub termination: Pubkey,
+    pub actor: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct AgreementTerminated {
+    pub version: u8,
+    pub agreement_id: [u8; 16],
+    pub termination: Pubkey,
+    pub actor: Pubkey,
+    pub previous_status: u8,
+    pub status: u8,
+    pub escrow_total: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct MilestonesSet {
+    pub version: u8,
+    pub agreement_id: [u8; 16],
+    pub schedule: Pubkey,
+    pub actor: Pubkey,
+    pub milestones: Vec<MilestoneTerms>,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct MilestoneSubmitted {
+    pub version: u8,
+    pub agreement_id: [u8; 16],
+    pub index: u8,
+    pub party: Pubkey,
+    pub actor: Pubkey,
+    pub previous_status: u8,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct MilestoneApproved {
+    pub version: u8,
+    pub agreement_id: [u8; 16],
+    pub index: u8,
+    pub party: Pubkey,
+    pub actor: Pubkey,
+    pub previous_status: u8,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct MilestoneDisputed {
+    pub version: u8,
     pub agreement_id: [u8; 16],
+    pub index: u8,
+    pub party: Pubkey,
+    pub actor: Pubkey,
+    pub previous_status: u8,
+    pub overdue: bool,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct MilestoneReleased {
+    pub version: u8,
+    pub agreement_id: [u8; 16],
+    pub index: u8,
+    pub payer: Pubkey,
+    pub payee: Pubkey,
+    pub recipient: Pubkey,
+    pub actor: Pubkey,
+    pub amount: u64,
+    pub released: u8,
+    pub escrow_total: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct RecurringPaymentSet {
+    pub version: u8,
+    pub agreement_id: [u8; 16],
+    pub recurring: Pubkey,
+    pub actor: Pubkey,
+    pub terms: RecurringTerms,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct RecurringPaymentCranked {
+    pub version: u8,
+    pub agreement_id: [u8; 16],
+    pub recurring: Pubkey,
+    pub payer: Pubkey,
+    pub payee: Pubkey,
+    pub recipient: Pubkey,
+    pub actor: Pubkey,
+    pub period_index: u16,
+    pub due_at: i64,
+    pub paid: bool,         // false = missed
+    pub amount: u64,        // 0 when missed
+    pub periods_paid: u16,
+    pub periods_missed: u16,
+    pub status: u8,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct RecurringPaymentCancelled {
+    pub version: u8,
+    pub agreement_id: [u8; 16],
+    pub recurring: Pubkey,
+    pub party: Pubkey,
+    pub actor: Pubkey,
+    pub periods_paid: u16,
+    pub periods_missed: u16,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct StreamFunded {
+    pub version: u8,
+    pub agreement_id: [u8; 16],
+    pub stream: Pubkey,
+    pub payer: Pubkey,      // AgentIdentity PDA
+    pub payee: Pubkey,      // AgentIdentity PDA, or the raw pubkey for direct parties
+    pub actor: Pubkey,
+    pub amount: u64,
+    pub start_at: i64,
+    pub end_at: i64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct VestedClaimed {
+    pub version: u8,
+    pub agreement_id: [u8; 16],
+    pub stream: Pubkey,
+    pub payee: Pubkey,
+    pub recipient: Pubkey,
+    pub actor: Pubkey,
+    pub amount: u64,
+    pub claimed: u64,       // total claimed so far
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct StreamEnded {
+    pub version: u8,
+    pub agreement_id: [u8; 16],
+    pub stream: Pubkey,
+    pub actor: Pubkey,
+    pub status: u8,         // STREAM_CANCELLED / STREAM_SETTLED
+    pub to_payee: u64,      // vested remainder paid out
+    pub to_payer: u64,      // unvested part returned
+    pub claimed: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct RevenueShareSet {
+    pub version: u8,
+    pub agreement_id: [u8; 16],
+    pub revenue_share: Pubkey,
+    pub receiver: Pubkey,
+    pub actor: Pubkey,
+    pub splits: Vec<RevenueSplit>,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct RevenueDistributed {
+    pub version: u8,
+    pub agreement_id: [u8; 16],
+    pub receiver: Pubkey,
+    pub party: Pubkey,      // AgentIdentity PDA, or the raw pubkey for direct parties
+    pub recipient: Pubkey,  // vault or wallet (SOL), token account (SPL)
+    pub mint: Pubkey,       // Pubkey::default() for SOL
+    pub actor: Pubkey,
+    pub amount: u64,
+    pub bps: u16,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct SafeSet {
+    pub version: u8,
+    pub agreement_id: [u8; 16],
+    pub safe: Pubkey,
+    pub actor: Pubkey,
+    pub terms: SafeTerms,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct SafeFunded {
+    pub version: u8,
+    pub agreement_id: [u8; 16],
+    pub safe: Pubkey,
+    pub investor: Pubkey,   // AgentIdentity PDA
+    pub actor: Pubkey,
+    pub amount: u64,
+    pub escrow_total: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct SafeConverted {
+    pub version: u8,
+    pub agreement_id: [u8; 16],
+    pub safe: Pubkey,
+    pub company: Pubkey,    // AgentIdentity PDA
+    pub recipient: Pubkey,
+    pub actor: Pubkey,
+    pub amount: u64,
+    pub mint: Pubkey,
+    pub round_valuation: u64,
+    pub conversion_valuation: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct SafeRefunded {
+    pub version: u8,
+    pub agreement_id: [u8; 16],
+    pub safe: Pubkey,
+    pub party: Pubkey,      // AgentIdentity PDA that refunded (company, or investor after expiry)
+    pub recipient: Pubkey,
+    pub actor: Pubkey,
+    pub amount: u64,
+    pub timestamp: i64,
 }
 
 #[event]
 pub struct VaultDeposit {
+    pub version: u8,
     pub agent_identity: Pubkey,
     pub authority: Pubkey,
     pub amount: u64,
     pub new_balance: u64,
+    pub actor: Pubkey,
+    pub total_deposited: u64,
+    pub timestamp: i64,
 }
 
 #[event]
 pub struct VaultWithdraw {
+    pub version: u8,
     pub agent_identity: Pubkey,
     pub authority: Pubkey,
     pub amount: u64,
     pub remaining_balance: u64,
+    pub actor: Pubkey,
+    pub total_withdrawn: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct AgentMigrated {
+    pub version: u8,
+    pub authority: Pubkey,
+    pub agent_key: Pubkey,
+    pub agent_identity: Pubkey,
+    pub actor: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct AgreementMigrated {
+    pub version: u8,
+    pub agreement_id: [u8; 16],
+    pub agreement_pda: Pubkey,
+    pub actor: Pubkey,
+    pub status: u8, // status carried over to aap-compressed
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct PartyMigrated {
+    pub version: u8,
+    pub agreement_id: [u8; 16],
+    pub party: Pubkey,
+    pub actor: Pubkey,
+    pub role: u8,
+    pub signed: bool,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct AgreementMaterialized {
+    pub version: u8,
+    pub agreement_id: [u8; 16],
+    pub agreement_pda: Pubkey,
+    pub actor: Pubkey,
+    pub status: u8, // status carried over from aap-compressed
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct PartyMaterialized {
+    pub version: u8,
+    pub agreement_id: [u8; 16],
+    pub party: Pubkey,
+    pub actor: Pubkey,
+    pub role: u8,
+    pub signed: bool,
+    pub timestamp: i64,
 }
@@ -0,0 +1,111 @@
+use anchor_lang::prelude::*;
+use crate::constants::{CONFIG_SEED, EVENT_VERSION};
+use crate::errors::AapError;
+use crate::events::AuthorityTransferred;
+use crate::state::{AgentIdentity, AgentVault, AuthorityTransfer, ProtocolConfig};
+
+/// Second step: the new authority takes over the identity and its vault.
+///
+/// Sub-agents copy their parent's authority at registration, so they are moved
+/// along: pass `[sub_agent_identity, sub_agent_vault]` pairs as remaining
+/// accounts (the vault PDA even if it was never created). Only sub-agents of
+/// this identity still under the previous authority are accepted.
+#[derive(Accounts)]
+pub struct AcceptAuthorityTransfer<'info> {
+    pub new_authority: Signer<'info>,
+
+    /// CHECK: Proposer of the transfer, refunded its rent
+    #[account(mut, address = transfer.authority)]
+    pub previous_authority: UncheckedAccount<'info>,
+
+    /// Still held by the proposer (not revoked and re-registered meanwhile)
+    #[account(
+        mut,
+        constraint = agent_identity.authority == transfer.authority @ AapError::Unauthorized,
+    )]
+    pub agent_identity: Account<'info, AgentIdentity>,
+
+    /// CHECK: Identity's vault PDA. Updated if it was ever initialized.
+    #[account(
+        mut,
+        seeds = [b"vault", agent_identity.key().as_ref()],
+        bump,
+    )]
+    pub vault: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        close = previous_authority,
+        seeds = [b"authority_transfer", agent_identity.key().as_ref()],
+        bump = transfer.bump,
+        has_one = new_authority @ AapError::Unauthorized,
+    )]
+    pub transfer: Account<'info, AuthorityTransfer>,
+
+    #[account(
+        seeds = [CONFIG_SEED],
+        bump = config.bump,
+        constraint = !config.paused @ AapError::ProtocolPaused,
+    )]
+    pub config: Account<'info, ProtocolConfig>,
+}
+
+pub fn handler<'info>(
+    ctx: Context<'_, '_, '_, 'info, AcceptAuthorityTransfer<'info>>,
+) -> Result<()> {
+    let identity_key = ctx.accounts.agent_identity.key();
+    let previous_authority = ctx.accounts.transfer.authority;
+    let new_authority = ctx.accounts.new_authority.key();
+
+    ctx.accounts.agent_identity.authority = new_authority;
+    let vault = set_vault_authority(&ctx.accounts.vault.to_account_info(), &new_authority)?;
+
+    require!(ctx.remaining_accounts.len() % 2 == 0, AapError::InvalidSubAgent);
+    let mut sub_agents: u8 = 0;
+    for pair in ctx.remaining_accounts.chunks(2) {
+        let (sub_info, sub_vault_info) = (&pair[0], &pair[1]);
+        require!(
+            sub_info.owner == &crate::ID && sub_info.is_writable,
+            AapError::InvalidSubAgent
+        );
+
+        let mut sub = AgentIdentity::try_deserialize(&mut &sub_info.try_borrow_data()?[..])?;
+        require!(
+            sub.parent == identity_key && sub.authority == previous_authority,
+            AapError::InvalidSubAgent
+        );
+        let (sub_vault, _) =
+            Pubkey::find_program_address(&[b"vault", sub_info.key.as_ref()], &crate::ID);
+        require_keys_eq!(sub_vault, sub_vault_info.key(), AapError::InvalidSubAgent);
+
+        sub.authority = new_authority;
+        sub.try_serialize(&mut &mut sub_info.try_borrow_mut_data()?[..])?;
+        set_vault_authority(sub_vault_info, &new_authority)?;
+        sub_agents = sub_agents.saturating_add(1);
+    }
+
+    emit!(AuthorityTransferred {
+        version: EVENT_VERSION,
+        agent_identity: identity_key,
+        previous_authority,
+        authority: new_authority,
+        actor: new_authority,
+        vault,
+        sub_agents,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+/// Point an initialized vault at `authority`; returns whether the vault exists.
+fn set_vault_authority(vault_info: &AccountInfo, authority: &Pubkey) -> Result<bool> {
+    if vault_info.owner != &crate::ID {
+        return Ok(false);
+    }
+    require!(vault_info.is_writable, AapError::InvalidSubAgent);
+    let mut vault = AgentVault::try_deserialize(&mut &vault_info.try_borrow_data()?[..])?;
+    vault.authority = *authority;
+    vault.try_serialize(&mut &mut vault_info.try_borrow_mut_data()?[..])?;
+    Ok(true)
+}
@@ -0,0 +1,157 @@
+use anchor_lang::prelude::*;
+use crate::constants::*;
+use crate::errors::AapError;
+use crate::events::CounterProposalAccepted;
+use crate::instructions::propose_amendment::{amend, Amend, NewTerms};
+use crate::state::{
+    AgentIdentity, Agreement, AgreementAmendment, AgreementParty, CounterProposal, Freeze,
+    ProtocolConfig,
+};
+
+/// The proposer takes a counter-proposal: it becomes amendment `version`,
+/// applied at once through the same path as `propose_amendment` while
+/// Proposed, so every signed party except the proposer is passed as a
+/// remaining account and signs again.
+#[derive(Accounts)]
+#[instruction(agreement_id: [u8; 16], version: u16)]
+pub struct AcceptCounterProposal<'info> {
+    #[account(mut)]
+    pub proposer_signer: Signer<'info>,
+
+    #[account(
+        constraint = proposer_identity.agent_key == proposer_signer.key() @ AapError::Unauthorized,
+    )]
+    pub proposer_identity: Account<'info, AgentIdentity>,
+
+    #[account(
+        mut,
+        seeds = [b"agreement", agreement_id.as_ref()],
+        bump = agreement.bump,
+        constraint = agreement.proposer == proposer_identity.key() @ AapError::Unauthorized,
+        constraint = agreement.status == STATUS_PROPOSED @ AapError::InvalidStatus,
+    )]
+    pub agreement: Account<'info, Agreement>,
+
+    #[account(
+        seeds = [b"party", agreement_id.as_ref(), proposer_identity.key().as_ref()],
+        bump = proposer_party.bump,
+    )]
+    pub proposer_party: Account<'info, AgreementParty>,
+
+    #[account(
+        mut,
+        constraint = counter.agreement == agreement.key() @ AapError::InvalidCounterProposal,
+        constraint = counter.status == COUNTER_PENDING @ AapError::CounterProposalNotPending,
+        constraint = counter.base_terms_hash == agreement.terms_hash @ AapError::StaleCounterProposal,
+    )]
+    pub counter: Account<'info, CounterProposal>,
+
+    /// Amendment `version - 1`; omitted for the first amendment
+    #[account(
+        seeds = [b"amendment", agreement.key().as_ref(), &version.saturating_sub(1).to_le_bytes()],
+        bump = previous_amendment.bump,
+    )]
+    pub previous_amendment: Option<Account<'info, AgreementAmendment>>,
+
+    #[account(
+        init,
+        payer = proposer_signer,
+        space = AgreementAmendment::space(agreement.num_parties),
+        seeds = [b"amendment", agreement.key().as_ref(), &version.to_le_bytes()],
+        bump,
+    )]
+    pub amendment: Account<'info, AgreementAmendment>,
+
+    pub system_program: Program<'info, System>,
+
+    /// CHECK: Freeze PDAs covering the proposer (own, parent's, authority's); must not exist
+    #[account(seeds = [b"freeze", proposer_identity.key().as_ref()], bump)]
+    pub agent_freeze: UncheckedAccount<'info>,
+
+    /// CHECK: See `agent_freeze`
+    #[account(seeds = [b"freeze", proposer_identity.parent.as_ref()], bump)]
+    pub parent_freeze: UncheckedAccount<'info>,
+
+    /// CHECK: See `agent_freeze`
+    #[account(seeds = [b"freeze", proposer_identity.authority.as_ref()], bump)]
+    pub authority_freeze: UncheckedAccount<'info>,
+
+    /// CHECK: The agreement's Safe PDA, whose funded investor keeps its
+    /// signature; empty for other agreements
+    #[account(seeds = [b"safe", agreement.key().as_ref()], bump)]
+    pub safe: UncheckedAccount<'info>,
+
+    #[account(
+        seeds = [CONFIG_SEED],
+        bump = config.bump,
+        constraint = !config.paused @ AapError::ProtocolPaused,
+    )]
+    pub config: Account<'info, ProtocolConfig>,
+}
+
+pub fn handler<'info>(
+    ctx: Context<'_, '_, '_, 'info, AcceptCounterProposal<'info>>,
+    agreement_id: [u8; 16],
+    version: u16,
+) -> Result<()> {
+    Freeze::require_unfrozen(&[
+        &ctx.accounts.agent_freeze,
+        &ctx.accounts.parent_freeze,
+        &ctx.accounts.authority_freeze,
+    ])?;
+
+    let clock = Clock::get()?;
+
+    aap_core::scope::require_can_sign(&ctx.accounts.proposer_identity.scope.into(), clock.unix_timestamp)
+        .map_err(AapError::from)?;
+
+    let proposer_party = ctx.accounts.proposer_party.key();
+    let agreement = &mut ctx.accounts.agreement;
+    aap_core::status::require_agreement_not_expired(agreement.expires_at, clock.unix_timestamp)
+        .map_err(AapError::from)?;
+    aap_core::amendment::require_next_version(
+        version,
+        ctx.accounts.previous_amendment.as_ref().map(|previous| previous.status),
+    )
+    .map_err(AapError::from)?;
+
+    let counter = &mut ctx.accounts.counter;
+    counter.status = COUNTER_ACCEPTED;
+    counter.amendment_version = version;
+    counter.resolved_at = clock.unix_timestamp;
+
+    // The countering party signs the new terms afterwards like everyone else
+    amend(
+        Amend {
+            agreement_id,
+            agreement,
+            amendment: &mut ctx.accounts.amendment,
+            amendment_bump: ctx.bumps.amendment,
+            proposer_party,
+            actor: ctx.accounts.proposer_signer.key(),
+            safe: &ctx.accounts.safe,
+            parties: ctx.remaining_accounts,
+        },
+        version,
+        NewTerms {
+            terms_hash: counter.terms_hash,
+            terms_uri: counter.terms_uri,
+            expires_at: counter.expires_at,
+        },
+        clock.unix_timestamp,
+    )?;
+
+    emit!(CounterProposalAccepted {
+        version: EVENT_VERSION,
+        agreement_id,
+        counter: counter.key(),
+        round: counter.round,
+        party: counter.agent_identity,
+        amendment: ctx.accounts.amendment.key(),
+        amendment_version: version,
+        actor: ctx.accounts.proposer_signer.key(),
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
@@ -2,7 +2,7 @@ use anchor_lang::prelude::*;
 use crate::constants::*;
 use crate::errors::AapError;
 use crate::events::PartyAdded;
-use crate::state::{AgentIdentity, Agreement, AgreementParty};
+use crate::state::{AgentIdentity, Agreement, AgreementParty, Freeze, ProtocolConfig};
 
 #[derive(Accounts)]
 #[instruction(agreement_id: [u8; 16])]
@@ -37,6 +37,25 @@ pub struct AddParty<'info> {
     pub party: Account<'info, AgreementParty>,
 
     pub system_program: Program<'info, System>,
+
+    /// CHECK: Freeze PDAs covering the proposer (own, parent's, authority's); must not exist
+    #[account(seeds = [b"freeze", proposer_identity.key().as_ref()], bump)]
+    pub agent_freeze: UncheckedAccount<'info>,
+
+    /// CHECK: See `agent_freeze`
+    #[account(seeds = [b"freeze", proposer_identity.parent.as_ref()], bump)]
+    pub parent_freeze: UncheckedAccount<'info>,
+
+    /// CHECK: See `agent_freeze`
+    #[account(seeds = [b"freeze", proposer_identity.authority.as_ref()], bump)]
+    pub authority_freeze: UncheckedAccount<'info>,
+
+    #[account(
+        seeds = [CONFIG_SEED],
+        bump = config.bump,
+        constraint = !config.paused @ AapError::ProtocolPaused,
+    )]
+    pub config: Account<'info, ProtocolConfig>,
 }
 
 pub fn handler(
@@ -44,14 +63,17 @@ pub fn handler(
     agreement_id: [u8; 16],
     role: u8,
 ) -> Result<()> {
-    // Validate party count
-    require!(
-        ctx.accounts.agreement.parties_added < ctx.accounts.agreement.num_parties,
-        AapError::MaxPartiesExceeded
-    );
+    Freeze::require_unfrozen(&[
+        &ctx.accounts.agent_freeze,
+        &ctx.accounts.parent_freeze,
+        &ctx.accounts.authority_freeze,
+    ])?;
 
-    // Validate role
-    require!(role <= MAX_ROLE, AapError::InvalidRole);
+    // Validate party count and role
+    let agreement = &ctx.accounts.agreement;
+    aap_core::parties::require_open_slot(agreement.parties_added, agreement.num_parties)
+        .map_err(AapError::from)?;
+    aap_core::parties::validate_role(role).map_err(AapError::from)?;
 
     let agreement_key = ctx.accounts.agreement.key();
     let party_identity_key = ctx.accounts.party_identity.key();
@@ -69,9 +91,14 @@ pub fn handler(
     ctx.accounts.agreement.parties_added += 1;
 
     emit!(PartyAdded {
+        version: EVENT_VERSION,
         agreement_id,
         party: ctx.accounts.party_identity.key(),
         role,
+        actor: ctx.accounts.proposer_signer.key(),
+        direct: false,
+        parties_added: ctx.accounts.agreement.parties_added,
+        timestamp: Clock::get()?.unix_timestamp,
     });
 
     Ok(())
@@ -2,11 +2,12 @@ use anchor_lang::prelude::*;
 use crate::constants::*;
 use crate::errors::AapError;
 use crate::events::PartyAdded;
-use crate::state::{AgentIdentity, Agreement, AgreementParty};
+use crate::state::{AgentIdentity, Agreement, AgreementParty, Freeze, ProtocolConfig};
 
 /// Add a party by raw pubkey — no identity registration required.
 /// The party PDA is seeded by the raw pubkey (not an identity PDA).
-/// Used for human-to-human agreements.
+/// Used for human-to-human agreements. Neither the proposer nor the wallet
+/// may be frozen (a wallet is covered by a freeze on its own key).
 #[derive(Accounts)]
 #[instruction(agreement_id: [u8; 16], party_pubkey: Pubkey)]
 pub struct AddPartyDirect<'info> {
@@ -37,6 +38,29 @@ pub struct AddPartyDirect<'info> {
     pub party: Account<'info, AgreementParty>,
 
     pub system_program: Program<'info, System>,
+
+    /// CHECK: Freeze PDAs covering the proposer (own, parent's, authority's); must not exist
+    #[account(seeds = [b"freeze", proposer_identity.key().as_ref()], bump)]
+    pub agent_freeze: UncheckedAccount<'info>,
+
+    /// CHECK: See `agent_freeze`
+    #[account(seeds = [b"freeze", proposer_identity.parent.as_ref()], bump)]
+    pub parent_freeze: UncheckedAccount<'info>,
+
+    /// CHECK: See `agent_freeze`
+    #[account(seeds = [b"freeze", proposer_identity.authority.as_ref()], bump)]
+    pub authority_freeze: UncheckedAccount<'info>,
+
+    /// CHECK: Freeze PDA of the wallet being added; must not exist
+    #[account(seeds = [b"freeze", party_pubkey.as_ref()], bump)]
+    pub party_freeze: UncheckedAccount<'info>,
+
+    #[account(
+        seeds = [CONFIG_SEED],
+        bump = config.bump,
+        constraint = !config.paused @ AapError::ProtocolPaused,
+    )]
+    pub config: Account<'info, ProtocolConfig>,
 }
 
 pub fn handler(
@@ -45,11 +69,17 @@ pub fn handler(
     party_pubkey: Pubkey,
     role: u8,
 ) -> Result<()> {
-    require!(
-        ctx.accounts.agreement.parties_added < ctx.accounts.agreement.num_parties,
-        AapError::MaxPartiesExceeded
-    );
-    require!(role <= MAX_ROLE, AapError::InvalidRole);
+    Freeze::require_unfrozen(&[
+        &ctx.accounts.agent_freeze,
+        &ctx.accounts.parent_freeze,
+        &ctx.accounts.authority_freeze,
+        &ctx.accounts.party_freeze,
+    ])?;
+
+    let agreement = &ctx.accounts.agreement;
+    aap_core::parties::require_open_slot(agreement.parties_added, agreement.num_parties)
+        .map_err(AapError::from)?;
+    aap_core::parties::validate_role(role).map_err(AapError::from)?;
 
     let agreement_key = ctx.accounts.agreement.key();
 
@@ -65,9 +95,14 @@ pub fn handler(
     ctx.accounts.agreement.parties_added += 1;
 
     emit!(PartyAdded {
+        version: EVENT_VERSION,
         agreement_id,
         party: party_pubkey,
         role,
+        actor: ctx.accounts.proposer_signer.key(),
+        direct: true,
+        parties_added: ctx.accounts.agreement.parties_added,
+        timestamp: Clock::get()?.unix_timestamp,
     });
 
     Ok(())
@@ -0,0 +1,56 @@
+use anchor_lang::prelude::*;
+use crate::constants::{CONFIG_SEED, EVENT_VERSION};
+use crate::errors::AapError;
+use crate::events::AuthorityActionApproved;
+use crate::state::{AuthorityAction, AuthoritySet, ProtocolConfig};
+
+#[derive(Accounts)]
+#[instruction(index: u64)]
+pub struct ApproveAuthorityAction<'info> {
+    pub member: Signer<'info>,
+
+    #[account(
+        seeds = [b"authority_set", authority_set.set_id.as_ref()],
+        bump = authority_set.bump,
+    )]
+    pub authority_set: Account<'info, AuthoritySet>,
+
+    #[account(
+        mut,
+        seeds = [b"authority_action", authority_set.key().as_ref(), index.to_le_bytes().as_ref()],
+        bump = action.bump,
+        has_one = authority_set,
+    )]
+    pub action: Account<'info, AuthorityAction>,
+
+    #[account(
+        seeds = [CONFIG_SEED],
+        bump = config.bump,
+        constraint = !config.paused @ AapError::ProtocolPaused,
+    )]
+    pub config: Account<'info, ProtocolConfig>,
+}
+
+pub fn handler(ctx: Context<ApproveAuthorityAction>, index: u64) -> Result<()> {
+    let set = &ctx.accounts.authority_set;
+    let member_index = set
+        .member_index(&ctx.accounts.member.key())
+        .ok_or(AapError::NotSetMember)?;
+
+    let action = &mut ctx.accounts.action;
+    require!(!action.has_approved(member_index), AapError::AlreadyApproved);
+    action.approve(member_index);
+
+    emit!(AuthorityActionApproved {
+        version: EVENT_VERSION,
+        authority_set: set.key(),
+        action: action.key(),
+        index,
+        actor: ctx.accounts.member.key(),
+        approvals: action.approval_count(),
+        threshold: set.threshold,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,108 @@
+use anchor_lang::prelude::*;
+use crate::constants::*;
+use crate::errors::AapError;
+use crate::events::{AgreementTerminated, TerminationApproved};
+use crate::state::{AgentIdentity, Agreement, AgreementParty, Freeze, ProtocolConfig, Termination};
+
+/// A party of the agreement approves a pending termination. The last approval
+/// moves the agreement to Terminated.
+#[derive(Accounts)]
+#[instruction(agreement_id: [u8; 16])]
+pub struct ApproveTermination<'info> {
+    pub signer: Signer<'info>,
+
+    #[account(
+        constraint = signer_identity.agent_key == signer.key() @ AapError::Unauthorized,
+    )]
+    pub signer_identity: Account<'info, AgentIdentity>,
+
+    #[account(
+        mut,
+        seeds = [b"agreement", agreement_id.as_ref()],
+        bump = agreement.bump,
+        constraint = agreement.status == STATUS_ACTIVE @ AapError::InvalidStatus,
+    )]
+    pub agreement: Account<'info, Agreement>,
+
+    #[account(
+        seeds = [b"party", agreement_id.as_ref(), signer_identity.key().as_ref()],
+        bump = party.bump,
+    )]
+    pub party: Account<'info, AgreementParty>,
+
+    #[account(
+        mut,
+        seeds = [b"termination", agreement.key().as_ref()],
+        bump = termination.bump,
+        constraint = termination.status == TERMINATION_PENDING @ AapError::TerminationNotPending,
+    )]
+    pub termination: Account<'info, Termination>,
+
+    /// CHECK: Freeze PDAs covering the signer (own, parent's, authority's); must not exist
+    #[account(seeds = [b"freeze", signer_identity.key().as_ref()], bump)]
+    pub agent_freeze: UncheckedAccount<'info>,
+
+    /// CHECK: See `agent_freeze`
+    #[account(seeds = [b"freeze", signer_identity.parent.as_ref()], bump)]
+    pub parent_freeze: UncheckedAccount<'info>,
+
+    /// CHECK: See `agent_freeze`
+    #[account(seeds = [b"freeze", signer_identity.authority.as_ref()], bump)]
+    pub authority_freeze: UncheckedAccount<'info>,
+
+    #[account(
+        seeds = [CONFIG_SEED],
+        bump = config.bump,
+        constraint = !config.paused @ AapError::ProtocolPaused,
+    )]
+    pub config: Account<'info, ProtocolConfig>,
+}
+
+pub fn handler(ctx: Context<ApproveTermination>, agreement_id: [u8; 16]) -> Result<()> {
+    Freeze::require_unfrozen(&[
+        &ctx.accounts.agent_freeze,
+        &ctx.accounts.parent_freeze,
+        &ctx.accounts.authority_freeze,
+    ])?;
+
+    let clock = Clock::get()?;
+
+    aap_core::scope::require_can_sign(&ctx.accounts.signer_identity.scope.into(), clock.unix_timestamp)
+        .map_err(AapError::from)?;
+
+    let party = ctx.accounts.party.key();
+    let termination = &mut ctx.accounts.termination;
+    require!(!termination.approvals.contains(&party), AapError::AlreadyApproved);
+    termination.approvals.push(party);
+
+    emit!(TerminationApproved {
+        version: EVENT_VERSION,
+        agreement_id,
+        termination: termination.key(),
+        party: ctx.accounts.signer_identity.key(),
+        actor: ctx.accounts.signer.key(),
+        direct: false,
+        approvals: termination.approvals.len() as u8,
+        required: termination.required,
+        timestamp: clock.unix_timestamp,
+    });
+
+    if termination.is_fully_approved() {
+        let agreement = &mut ctx.accounts.agreement;
+        let previous_status = agreement.status;
+        aap_core::status::require_transition(previous_status, STATUS_TERMINATED).map_err(AapError::from)?;
+        termination.apply(agreement, clock.unix_timestamp)?;
+        emit!(AgreementTerminated {
+            version: EVENT_VERSION,
+            agreement_id,
+            termination: termination.key(),
+            actor: ctx.accounts.signer.key(),
+            previous_status,
+            status: STATUS_TERMINATED,
+            escrow_total: agreement.escrow_total,
+            timestamp: clock.unix_timestamp,
+        });
+    }
+
+    Ok(())
+}
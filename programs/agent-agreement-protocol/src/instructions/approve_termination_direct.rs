@@ -0,0 +1,83 @@
+use anchor_lang::prelude::*;
+use crate::constants::*;
+use crate::errors::AapError;
+use crate::events::{AgreementTerminated, TerminationApproved};
+use crate::state::{Agreement, AgreementParty, ProtocolConfig, Termination};
+
+/// `approve_termination` for a party added with `add_party_direct`: the party
+/// PDA is seeded by the signer's wallet.
+#[derive(Accounts)]
+#[instruction(agreement_id: [u8; 16])]
+pub struct ApproveTerminationDirect<'info> {
+    pub signer: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"agreement", agreement_id.as_ref()],
+        bump = agreement.bump,
+        constraint = agreement.status == STATUS_ACTIVE @ AapError::InvalidStatus,
+    )]
+    pub agreement: Account<'info, Agreement>,
+
+    #[account(
+        seeds = [b"party", agreement_id.as_ref(), signer.key().as_ref()],
+        bump = party.bump,
+        constraint = party.agent_identity == signer.key() @ AapError::Unauthorized,
+    )]
+    pub party: Account<'info, AgreementParty>,
+
+    #[account(
+        mut,
+        seeds = [b"termination", agreement.key().as_ref()],
+        bump = termination.bump,
+        constraint = termination.status == TERMINATION_PENDING @ AapError::TerminationNotPending,
+    )]
+    pub termination: Account<'info, Termination>,
+
+    #[account(
+        seeds = [CONFIG_SEED],
+        bump = config.bump,
+        constraint = !config.paused @ AapError::ProtocolPaused,
+    )]
+    pub config: Account<'info, ProtocolConfig>,
+}
+
+pub fn handler(ctx: Context<ApproveTerminationDirect>, agreement_id: [u8; 16]) -> Result<()> {
+    let clock = Clock::get()?;
+
+    let party = ctx.accounts.party.key();
+    let termination = &mut ctx.accounts.termination;
+    require!(!termination.approvals.contains(&party), AapError::AlreadyApproved);
+    termination.approvals.push(party);
+
+    emit!(TerminationApproved {
+        version: EVENT_VERSION,
+        agreement_id,
+        termination: termination.key(),
+        party: ctx.accounts.signer.key(),
+        actor: ctx.accounts.signer.key(),
+        direct: true,
+        approvals: termination.approvals.len() as u8,
+        required: termination.required,
+        timestamp: clock.unix_timestamp,
+    });
+
+    if termination.is_fully_approved() {
+        let agreement = &mut ctx.accounts.agreement;
+        let previous_status = agreement.status;
+        aap_core::status::require_transition(previous_status, STATUS_TERMINATED).map_err(AapError::from)?;
+        termination.apply(agreement, clock.unix_timestamp)?;
+        emit!(AgreementTerminated {
+            version: EVENT_VERSION,
+            agreement_id,
+            termination: termination.key(),
+            actor: ctx.accounts.signer.key(),
+            previous_status,
+            status: STATUS_TERMINATED,
+            escrow_total: agreement.escrow_total,
+            timestamp: clock.unix_timestamp,
+        });
+    }
+
+    Ok(())
+}
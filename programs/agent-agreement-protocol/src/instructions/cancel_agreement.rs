@@ -1,8 +1,9 @@
 use anchor_lang::prelude::*;
+use aap_core::status::can_transition;
 use crate::constants::*;
 use crate::errors::AapError;
 use crate::events::AgreementCancelled;
-use crate::state::{AgentIdentity, Agreement};
+use crate::state::{AgentIdentity, Agreement, ProtocolConfig};
 
 #[derive(Accounts)]
 #[instruction(agreement_id: [u8; 16])]
@@ -23,18 +24,33 @@ pub struct CancelAgreement<'info> {
         seeds = [b"agreement", agreement_id.as_ref()],
         bump = agreement.bump,
         constraint = agreement.proposer == proposer_identity.key() @ AapError::Unauthorized,
-        constraint = agreement.status == STATUS_PROPOSED @ AapError::InvalidStatus,
+        constraint = can_transition(agreement.status, STATUS_CANCELLED) @ AapError::InvalidStatus,
     )]
     pub agreement: Account<'info, Agreement>,
+
+    #[account(
+        seeds = [CONFIG_SEED],
+        bump = config.bump,
+        constraint = !config.paused @ AapError::ProtocolPaused,
+    )]
+    pub config: Account<'info, ProtocolConfig>,
 }
 
 pub fn handler(
     ctx: Context<CancelAgreement>,
     agreement_id: [u8; 16],
 ) -> Result<()> {
+    let previous_status = ctx.accounts.agreement.status;
     ctx.accounts.agreement.status = STATUS_CANCELLED;
 
-    emit!(AgreementCancelled { agreement_id });
+    emit!(AgreementCancelled {
+        version: EVENT_VERSION,
+        agreement_id,
+        actor: ctx.accounts.signer.key(),
+        previous_status,
+        status: STATUS_CANCELLED,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
 
     Ok(())
 }
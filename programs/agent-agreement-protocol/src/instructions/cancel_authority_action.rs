@@ -0,0 +1,41 @@
+use anchor_lang::prelude::*;
+use crate::constants::{CONFIG_SEED, EVENT_VERSION};
+use crate::errors::AapError;
+use crate::events::AuthorityActionCancelled;
+use crate::state::{AuthorityAction, ProtocolConfig};
+
+/// The proposer withdraws an action that hasn't been executed.
+#[derive(Accounts)]
+pub struct CancelAuthorityAction<'info> {
+    #[account(mut)]
+    pub proposer: Signer<'info>,
+
+    #[account(
+        mut,
+        close = proposer,
+        has_one = proposer @ AapError::Unauthorized,
+    )]
+    pub action: Account<'info, AuthorityAction>,
+
+    #[account(
+        seeds = [CONFIG_SEED],
+        bump = config.bump,
+        constraint = !config.paused @ AapError::ProtocolPaused,
+    )]
+    pub config: Account<'info, ProtocolConfig>,
+}
+
+pub fn handler(ctx: Context<CancelAuthorityAction>) -> Result<()> {
+    let action = &ctx.accounts.action;
+
+    emit!(AuthorityActionCancelled {
+        version: EVENT_VERSION,
+        authority_set: action.authority_set,
+        action: action.key(),
+        index: action.index,
+        actor: ctx.accounts.proposer.key(),
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,42 @@
+use anchor_lang::prelude::*;
+use crate::constants::{CONFIG_SEED, EVENT_VERSION};
+use crate::errors::AapError;
+use crate::events::AuthorityTransferCancelled;
+use crate::state::{AuthorityTransfer, ProtocolConfig};
+
+/// Withdraw a pending transfer. Doesn't load the identity, so a transfer left
+/// behind by a revoked identity can still be closed.
+#[derive(Accounts)]
+pub struct CancelAuthorityTransfer<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        close = authority,
+        has_one = authority @ AapError::Unauthorized,
+    )]
+    pub transfer: Account<'info, AuthorityTransfer>,
+
+    #[account(
+        seeds = [CONFIG_SEED],
+        bump = config.bump,
+        constraint = !config.paused @ AapError::ProtocolPaused,
+    )]
+    pub config: Account<'info, ProtocolConfig>,
+}
+
+pub fn handler(ctx: Context<CancelAuthorityTransfer>) -> Result<()> {
+    let transfer = &ctx.accounts.transfer;
+
+    emit!(AuthorityTransferCancelled {
+        version: EVENT_VERSION,
+        agent_identity: transfer.agent_identity,
+        authority: transfer.authority,
+        new_authority: transfer.new_authority,
+        actor: ctx.accounts.authority.key(),
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,72 @@
+use anchor_lang::prelude::*;
+use crate::constants::*;
+use crate::errors::AapError;
+use crate::events::RecurringPaymentCancelled;
+use crate::state::{AgentIdentity, Agreement, AgreementParty, ProtocolConfig, RecurringPayment};
+
+/// The payer or payee (agent key or authority) stops a recurring payment.
+/// Periods already paid stay paid; the agreement itself is untouched.
+#[derive(Accounts)]
+#[instruction(agreement_id: [u8; 16])]
+pub struct CancelRecurringPayment<'info> {
+    pub signer: Signer<'info>,
+
+    #[account(
+        constraint = (
+            party_identity.agent_key == signer.key() ||
+            party_identity.authority == signer.key()
+        ) @ AapError::Unauthorized,
+    )]
+    pub party_identity: Account<'info, AgentIdentity>,
+
+    #[account(
+        seeds = [b"agreement", agreement_id.as_ref()],
+        bump = agreement.bump,
+    )]
+    pub agreement: Account<'info, Agreement>,
+
+    #[account(
+        seeds = [b"party", agreement_id.as_ref(), party_identity.key().as_ref()],
+        bump = party.bump,
+    )]
+    pub party: Account<'info, AgreementParty>,
+
+    #[account(
+        mut,
+        seeds = [b"recurring", agreement.key().as_ref()],
+        bump = recurring.bump,
+        constraint = (
+            recurring.payer == party.key() ||
+            recurring.payee == party.key()
+        ) @ AapError::Unauthorized,
+        constraint = recurring.status == RECURRING_ACTIVE @ AapError::RecurringPaymentNotActive,
+    )]
+    pub recurring: Account<'info, RecurringPayment>,
+
+    #[account(
+        seeds = [CONFIG_SEED],
+        bump = config.bump,
+        constraint = !config.paused @ AapError::ProtocolPaused,
+    )]
+    pub config: Account<'info, ProtocolConfig>,
+}
+
+pub fn handler(ctx: Context<CancelRecurringPayment>, agreement_id: [u8; 16]) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+    let recurring = &mut ctx.accounts.recurring;
+    recurring.status = RECURRING_CANCELLED;
+    recurring.ended_at = now;
+
+    emit!(RecurringPaymentCancelled {
+        version: EVENT_VERSION,
+        agreement_id,
+        recurring: recurring.key(),
+        party: ctx.accounts.party_identity.key(),
+        actor: ctx.accounts.signer.key(),
+        periods_paid: recurring.periods_paid,
+        periods_missed: recurring.periods_missed,
+        timestamp: now,
+    });
+
+    Ok(())
+}
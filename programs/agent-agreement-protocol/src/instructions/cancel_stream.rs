@@ -0,0 +1,128 @@
+use anchor_lang::prelude::*;
+use crate::constants::*;
+use crate::errors::AapError;
+use crate::events::StreamEnded;
+use crate::instructions::release_escrow::require_escrow_recipient;
+use crate::state::{AgentIdentity, Agreement, AgreementParty, EscrowStream, ProtocolConfig};
+
+/// The payer or payee (agent key or authority) stops a stream while the
+/// agreement is Active: the payee gets what has vested and is unclaimed, the
+/// payer gets the unvested part back.
+#[derive(Accounts)]
+#[instruction(agreement_id: [u8; 16])]
+pub struct CancelStream<'info> {
+    pub signer: Signer<'info>,
+
+    #[account(
+        constraint = (
+            party_identity.agent_key == signer.key() ||
+            party_identity.authority == signer.key()
+        ) @ AapError::Unauthorized,
+    )]
+    pub party_identity: Account<'info, AgentIdentity>,
+
+    #[account(
+        seeds = [b"agreement", agreement_id.as_ref()],
+        bump = agreement.bump,
+        constraint = agreement.status == STATUS_ACTIVE @ AapError::InvalidStatus,
+    )]
+    pub agreement: Account<'info, Agreement>,
+
+    #[account(
+        seeds = [b"party", agreement_id.as_ref(), party_identity.key().as_ref()],
+        bump = party.bump,
+        constraint = (
+            stream.payer == party.key() ||
+            stream.payee == party.key()
+        ) @ AapError::Unauthorized,
+    )]
+    pub party: Account<'info, AgreementParty>,
+
+    #[account(
+        mut,
+        seeds = [b"stream", agreement.key().as_ref()],
+        bump = stream.bump,
+        constraint = stream.status == STREAM_ACTIVE @ AapError::StreamNotActive,
+    )]
+    pub stream: Account<'info, EscrowStream>,
+
+    #[account(
+        constraint = payer_party.key() == stream.payer @ AapError::InvalidStream,
+    )]
+    pub payer_party: Account<'info, AgreementParty>,
+
+    #[account(
+        constraint = payee_party.key() == stream.payee @ AapError::InvalidStream,
+    )]
+    pub payee_party: Account<'info, AgreementParty>,
+
+    /// CHECK: The payer's AgentVault PDA, or the payer key itself for direct
+    /// parties; checked in the handler
+    #[account(mut)]
+    pub payer_recipient: UncheckedAccount<'info>,
+
+    /// CHECK: See `payer_recipient`, for the payee
+    #[account(mut)]
+    pub payee_recipient: UncheckedAccount<'info>,
+
+    #[account(
+        seeds = [CONFIG_SEED],
+        bump = config.bump,
+        constraint = !config.paused @ AapError::ProtocolPaused,
+    )]
+    pub config: Account<'info, ProtocolConfig>,
+}
+
+pub fn handler(ctx: Context<CancelStream>, agreement_id: [u8; 16]) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+    let (to_payee, to_payer) = end_stream(
+        &mut ctx.accounts.stream,
+        &ctx.accounts.payer_party,
+        &ctx.accounts.payer_recipient,
+        &ctx.accounts.payee_party,
+        &ctx.accounts.payee_recipient,
+        now,
+        STREAM_CANCELLED,
+    )?;
+
+    emit!(StreamEnded {
+        version: EVENT_VERSION,
+        agreement_id,
+        stream: ctx.accounts.stream.key(),
+        actor: ctx.accounts.signer.key(),
+        status: STREAM_CANCELLED,
+        to_payee,
+        to_payer,
+        claimed: ctx.accounts.stream.claimed,
+        timestamp: now,
+    });
+
+    Ok(())
+}
+
+/// Stops vesting at `cutoff`, pays out the vested remainder and returns the
+/// unvested part. Returns `(to_payee, to_payer)`.
+pub(crate) fn end_stream<'info>(
+    stream: &mut Account<'info, EscrowStream>,
+    payer_party: &AgreementParty,
+    payer_recipient: &AccountInfo<'info>,
+    payee_party: &AgreementParty,
+    payee_recipient: &AccountInfo<'info>,
+    cutoff: i64,
+    status: u8,
+) -> Result<(u64, u64)> {
+    require_escrow_recipient(payer_party, payer_recipient)?;
+    require_escrow_recipient(payee_party, payee_recipient)?;
+
+    let (to_payee, to_payer) =
+        aap_core::stream::split_at(stream.amount, stream.claimed, stream.start_at, stream.end_at, cutoff);
+    **stream.to_account_info().try_borrow_mut_lamports()? -= to_payee + to_payer;
+    **payee_recipient.try_borrow_mut_lamports()? += to_payee;
+    **payer_recipient.try_borrow_mut_lamports()? += to_payer;
+
+    stream.claimed += to_payee;
+    stream.refunded = to_payer;
+    stream.status = status;
+    stream.ended_at = cutoff;
+    Ok((to_payee, to_payer))
+}
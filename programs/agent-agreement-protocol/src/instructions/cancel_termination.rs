@@ -0,0 +1,63 @@
+use anchor_lang::prelude::*;
+use crate::constants::*;
+use crate::errors::AapError;
+use crate::events::TerminationCancelled;
+use crate::state::{AgentIdentity, Agreement, AgreementParty, ProtocolConfig, Termination};
+
+/// The proposer (agent key or authority) withdraws a pending termination and
+/// reclaims its rent, so a new split can be proposed.
+#[derive(Accounts)]
+#[instruction(agreement_id: [u8; 16])]
+pub struct CancelTermination<'info> {
+    #[account(mut)]
+    pub signer: Signer<'info>,
+
+    #[account(
+        constraint = (
+            proposer_identity.agent_key == signer.key() ||
+            proposer_identity.authority == signer.key()
+        ) @ AapError::Unauthorized,
+    )]
+    pub proposer_identity: Account<'info, AgentIdentity>,
+
+    #[account(
+        seeds = [b"agreement", agreement_id.as_ref()],
+        bump = agreement.bump,
+    )]
+    pub agreement: Account<'info, Agreement>,
+
+    #[account(
+        seeds = [b"party", agreement_id.as_ref(), proposer_identity.key().as_ref()],
+        bump = proposer_party.bump,
+    )]
+    pub proposer_party: Account<'info, AgreementParty>,
+
+    #[account(
+        mut,
+        close = signer,
+        seeds = [b"termination", agreement.key().as_ref()],
+        bump = termination.bump,
+        constraint = termination.proposer == proposer_party.key() @ AapError::Unauthorized,
+        constraint = termination.status == TERMINATION_PENDING @ AapError::TerminationNotPending,
+    )]
+    pub termination: Account<'info, Termination>,
+
+    #[account(
+        seeds = [CONFIG_SEED],
+        bump = config.bump,
+        constraint = !config.paused @ AapError::ProtocolPaused,
+    )]
+    pub config: Account<'info, ProtocolConfig>,
+}
+
+pub fn handler(ctx: Context<CancelTermination>, agreement_id: [u8; 16]) -> Result<()> {
+    emit!(TerminationCancelled {
+        version: EVENT_VERSION,
+        agreement_id,
+        termination: ctx.accounts.termination.key(),
+        actor: ctx.accounts.signer.key(),
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
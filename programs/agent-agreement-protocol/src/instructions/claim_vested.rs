@@ -0,0 +1,74 @@
+use anchor_lang::prelude::*;
+use crate::constants::*;
+use crate::errors::AapError;
+use crate::events::VestedClaimed;
+use crate::instructions::release_escrow::require_escrow_recipient;
+use crate::state::{Agreement, AgreementParty, EscrowStream, ProtocolConfig};
+
+/// Pays the payee what has vested on the stream so far. Permissionless, since
+/// the recipient is fixed: the payee's AgentVault, or its wallet for a direct
+/// party.
+#[derive(Accounts)]
+#[instruction(agreement_id: [u8; 16])]
+pub struct ClaimVested<'info> {
+    pub signer: Signer<'info>,
+
+    #[account(
+        seeds = [b"agreement", agreement_id.as_ref()],
+        bump = agreement.bump,
+        constraint = agreement.status == STATUS_ACTIVE @ AapError::InvalidStatus,
+    )]
+    pub agreement: Account<'info, Agreement>,
+
+    #[account(
+        mut,
+        seeds = [b"stream", agreement.key().as_ref()],
+        bump = stream.bump,
+        constraint = stream.status == STREAM_ACTIVE @ AapError::StreamNotActive,
+    )]
+    pub stream: Account<'info, EscrowStream>,
+
+    #[account(
+        constraint = payee_party.key() == stream.payee @ AapError::InvalidStream,
+    )]
+    pub payee_party: Account<'info, AgreementParty>,
+
+    /// CHECK: The payee's AgentVault PDA, or the payee key itself for direct
+    /// parties; checked in the handler
+    #[account(mut)]
+    pub recipient: UncheckedAccount<'info>,
+
+    #[account(
+        seeds = [CONFIG_SEED],
+        bump = config.bump,
+        constraint = !config.paused @ AapError::ProtocolPaused,
+    )]
+    pub config: Account<'info, ProtocolConfig>,
+}
+
+pub fn handler(ctx: Context<ClaimVested>, agreement_id: [u8; 16]) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+    require_escrow_recipient(&ctx.accounts.payee_party, &ctx.accounts.recipient)?;
+
+    let stream = &mut ctx.accounts.stream;
+    let amount = stream.claimable(now);
+    require!(amount > 0, AapError::InvalidAmount);
+
+    **stream.to_account_info().try_borrow_mut_lamports()? -= amount;
+    **ctx.accounts.recipient.to_account_info().try_borrow_mut_lamports()? += amount;
+    stream.claimed += amount;
+
+    emit!(VestedClaimed {
+        version: EVENT_VERSION,
+        agreement_id,
+        stream: stream.key(),
+        payee: ctx.accounts.payee_party.agent_identity,
+        recipient: ctx.accounts.recipient.key(),
+        actor: ctx.accounts.signer.key(),
+        amount,
+        claimed: stream.claimed,
+        timestamp: now,
+    });
+
+    Ok(())
+}
@@ -1,7 +1,9 @@
 use anchor_lang::prelude::*;
-use crate::constants::*;
+use aap_core::status::is_closable;
+use crate::constants::{CONFIG_SEED, EVENT_VERSION, STREAM_ACTIVE};
 use crate::errors::AapError;
-use crate::state::{AgentIdentity, Agreement, AgreementParty};
+use crate::events::AgreementClosed;
+use crate::state::{AgentIdentity, Agreement, AgreementParty, EscrowStream, ProtocolConfig};
 
 #[derive(Accounts)]
 #[instruction(agreement_id: [u8; 16])]
@@ -29,20 +31,43 @@ pub struct CloseAgreement<'info> {
         close = signer,
         seeds = [b"agreement", agreement_id.as_ref()],
         bump = agreement.bump,
-        constraint = (
-            agreement.status == STATUS_FULFILLED ||
-            agreement.status == STATUS_CANCELLED ||
-            agreement.status == STATUS_BREACHED
-        ) @ AapError::InvalidStatus,
+        constraint = is_closable(agreement.status) @ AapError::InvalidStatus,
+        constraint = agreement.escrow_total == 0 @ AapError::EscrowNotDistributed,
     )]
     pub agreement: Account<'info, Agreement>,
+
+    /// CHECK: EscrowStream PDA; if the agreement had a stream it must have ended
+    #[account(seeds = [b"stream", agreement.key().as_ref()], bump)]
+    pub stream: UncheckedAccount<'info>,
+
+    #[account(
+        seeds = [CONFIG_SEED],
+        bump = config.bump,
+        constraint = !config.paused @ AapError::ProtocolPaused,
+    )]
+    pub config: Account<'info, ProtocolConfig>,
 }
 
 pub fn handler(
-    _ctx: Context<CloseAgreement>,
-    _agreement_id: [u8; 16],
+    ctx: Context<CloseAgreement>,
+    agreement_id: [u8; 16],
 ) -> Result<()> {
+    let stream = &ctx.accounts.stream;
+    if !stream.data_is_empty() {
+        let stream = EscrowStream::try_deserialize(&mut &stream.try_borrow_data()?[..])?;
+        require!(stream.status != STREAM_ACTIVE, AapError::EscrowNotDistributed);
+    }
+
     // Accounts are closed via the `close` attribute
+    emit!(AgreementClosed {
+        version: EVENT_VERSION,
+        agreement_id,
+        party: ctx.accounts.signer_identity.key(),
+        actor: ctx.accounts.signer.key(),
+        status: ctx.accounts.agreement.status,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
     msg!("Agreement closed, rent reclaimed");
     Ok(())
 }
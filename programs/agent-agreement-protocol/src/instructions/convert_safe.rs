@@ -0,0 +1,114 @@
+use anchor_lang::prelude::*;
+use crate::constants::*;
+use crate::errors::AapError;
+use crate::events::{AgreementFulfilled, SafeConverted};
+use crate::instructions::release_escrow::require_escrow_recipient;
+use crate::state::{AgentIdentity, Agreement, AgreementParty, ProtocolConfig, Safe};
+
+/// The company (agent key or authority) converts a funded SAFE in a priced
+/// round: the valuation it converts at is recorded, the investment is paid
+/// from escrow to the company and the agreement is fulfilled.
+#[derive(Accounts)]
+#[instruction(agreement_id: [u8; 16])]
+pub struct ConvertSafe<'info> {
+    pub signer: Signer<'info>,
+
+    #[account(
+        constraint = (
+            company_identity.agent_key == signer.key() ||
+            company_identity.authority == signer.key()
+        ) @ AapError::Unauthorized,
+    )]
+    pub company_identity: Account<'info, AgentIdentity>,
+
+    #[account(
+        mut,
+        seeds = [b"agreement", agreement_id.as_ref()],
+        bump = agreement.bump,
+        constraint = agreement.status == STATUS_ACTIVE @ AapError::InvalidStatus,
+    )]
+    pub agreement: Account<'info, Agreement>,
+
+    #[account(
+        seeds = [b"party", agreement_id.as_ref(), company_identity.key().as_ref()],
+        bump = company_party.bump,
+    )]
+    pub company_party: Account<'info, AgreementParty>,
+
+    #[account(
+        mut,
+        seeds = [b"safe", agreement.key().as_ref()],
+        bump = safe.bump,
+        constraint = safe.company == company_party.key() @ AapError::Unauthorized,
+        constraint = safe.status == SAFE_FUNDED @ AapError::SafeNotFunded,
+    )]
+    pub safe: Account<'info, Safe>,
+
+    #[account(
+        mut,
+        constraint = investor_party.key() == safe.investor @ AapError::InvalidSafe,
+    )]
+    pub investor_party: Account<'info, AgreementParty>,
+
+    /// CHECK: The company's AgentVault PDA; checked in the handler
+    #[account(mut)]
+    pub recipient: UncheckedAccount<'info>,
+
+    #[account(
+        seeds = [CONFIG_SEED],
+        bump = config.bump,
+        constraint = !config.paused @ AapError::ProtocolPaused,
+    )]
+    pub config: Account<'info, ProtocolConfig>,
+}
+
+pub fn handler(ctx: Context<ConvertSafe>, agreement_id: [u8; 16], round_valuation: u64) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+    require_escrow_recipient(&ctx.accounts.company_party, &ctx.accounts.recipient)?;
+
+    let safe = &mut ctx.accounts.safe;
+    let conversion_valuation =
+        aap_core::safe::conversion_valuation(round_valuation, safe.valuation_cap, safe.discount_bps)
+            .map_err(AapError::from)?;
+
+    let amount = safe.investment_amount;
+    let investor = &mut ctx.accounts.investor_party;
+    let agreement = &mut ctx.accounts.agreement;
+    investor.escrow_deposited = investor.escrow_deposited.checked_sub(amount).ok_or(AapError::EscrowDistributionMismatch)?;
+    agreement.escrow_total = agreement.escrow_total.checked_sub(amount).ok_or(AapError::EscrowDistributionMismatch)?;
+    **agreement.to_account_info().try_borrow_mut_lamports()? -= amount;
+    **ctx.accounts.recipient.to_account_info().try_borrow_mut_lamports()? += amount;
+
+    safe.status = SAFE_CONVERTED;
+    safe.round_valuation = round_valuation;
+    safe.conversion_valuation = conversion_valuation;
+    safe.resolved_at = now;
+
+    emit!(SafeConverted {
+        version: EVENT_VERSION,
+        agreement_id,
+        safe: safe.key(),
+        company: ctx.accounts.company_identity.key(),
+        recipient: ctx.accounts.recipient.key(),
+        actor: ctx.accounts.signer.key(),
+        amount,
+        mint: safe.mint,
+        round_valuation,
+        conversion_valuation,
+        timestamp: now,
+    });
+
+    let previous_status = agreement.status;
+    aap_core::status::require_transition(previous_status, STATUS_FULFILLED).map_err(AapError::from)?;
+    agreement.status = STATUS_FULFILLED;
+    emit!(AgreementFulfilled {
+        version: EVENT_VERSION,
+        agreement_id,
+        actor: ctx.accounts.signer.key(),
+        previous_status,
+        status: STATUS_FULFILLED,
+        timestamp: now,
+    });
+
+    Ok(())
+}
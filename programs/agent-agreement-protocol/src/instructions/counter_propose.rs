@@ -0,0 +1,133 @@
+use anchor_lang::prelude::*;
+use crate::constants::*;
+use crate::errors::AapError;
+use crate::events::CounterProposed;
+use crate::state::{AgentIdentity, Agreement, AgreementParty, CounterProposal, Freeze, ProtocolConfig};
+
+/// An invited party that hasn't signed offers different terms or expiry
+/// instead. Escrow isn't negotiated here: each party funds its own with
+/// `fund_escrow`. The proposer answers with `accept_counter_proposal` or
+/// `reject_counter_proposal`.
+#[derive(Accounts)]
+#[instruction(agreement_id: [u8; 16], round: u16)]
+pub struct CounterPropose<'info> {
+    #[account(mut)]
+    pub signer: Signer<'info>,
+
+    #[account(
+        constraint = signer_identity.agent_key == signer.key() @ AapError::Unauthorized,
+    )]
+    pub signer_identity: Account<'info, AgentIdentity>,
+
+    #[account(
+        seeds = [b"agreement", agreement_id.as_ref()],
+        bump = agreement.bump,
+    )]
+    pub agreement: Account<'info, Agreement>,
+
+    #[account(
+        seeds = [b"party", agreement_id.as_ref(), signer_identity.key().as_ref()],
+        bump = party.bump,
+    )]
+    pub party: Account<'info, AgreementParty>,
+
+    /// The party's round `round - 1`; omitted for its first counter-proposal
+    #[account(
+        seeds = [b"counter", party.key().as_ref(), &round.saturating_sub(1).to_le_bytes()],
+        bump = previous_counter.bump,
+    )]
+    pub previous_counter: Option<Account<'info, CounterProposal>>,
+
+    #[account(
+        init,
+        payer = signer,
+        space = CounterProposal::LEN,
+        seeds = [b"counter", party.key().as_ref(), &round.to_le_bytes()],
+        bump,
+    )]
+    pub counter: Account<'info, CounterProposal>,
+
+    pub system_program: Program<'info, System>,
+
+    /// CHECK: Freeze PDAs covering the signer (own, parent's, authority's); must not exist
+    #[account(seeds = [b"freeze", signer_identity.key().as_ref()], bump)]
+    pub agent_freeze: UncheckedAccount<'info>,
+
+    /// CHECK: See `agent_freeze`
+    #[account(seeds = [b"freeze", signer_identity.parent.as_ref()], bump)]
+    pub parent_freeze: UncheckedAccount<'info>,
+
+    /// CHECK: See `agent_freeze`
+    #[account(seeds = [b"freeze", signer_identity.authority.as_ref()], bump)]
+    pub authority_freeze: UncheckedAccount<'info>,
+
+    #[account(
+        seeds = [CONFIG_SEED],
+        bump = config.bump,
+        constraint = !config.paused @ AapError::ProtocolPaused,
+    )]
+    pub config: Account<'info, ProtocolConfig>,
+}
+
+pub fn handler(
+    ctx: Context<CounterPropose>,
+    agreement_id: [u8; 16],
+    round: u16,
+    terms_hash: [u8; 32],
+    terms_uri: [u8; 64],
+    expires_at: i64,
+) -> Result<()> {
+    Freeze::require_unfrozen(&[
+        &ctx.accounts.agent_freeze,
+        &ctx.accounts.parent_freeze,
+        &ctx.accounts.authority_freeze,
+    ])?;
+
+    let clock = Clock::get()?;
+
+    aap_core::scope::require_can_sign(&ctx.accounts.signer_identity.scope.into(), clock.unix_timestamp)
+        .map_err(AapError::from)?;
+
+    let agreement = &ctx.accounts.agreement;
+    let party = &ctx.accounts.party;
+    aap_core::counter::require_counterable(agreement.status, party.role, party.signed)
+        .map_err(AapError::from)?;
+    aap_core::status::require_agreement_not_expired(agreement.expires_at, clock.unix_timestamp)
+        .map_err(AapError::from)?;
+    aap_core::counter::require_next_round(
+        round,
+        ctx.accounts.previous_counter.as_ref().map(|previous| previous.status),
+    )
+    .map_err(AapError::from)?;
+
+    let counter = &mut ctx.accounts.counter;
+    counter.agreement = agreement.key();
+    counter.party = party.key();
+    counter.agent_identity = ctx.accounts.signer_identity.key();
+    counter.round = round;
+    counter.status = COUNTER_PENDING;
+    counter.base_terms_hash = agreement.terms_hash;
+    counter.terms_hash = terms_hash;
+    counter.terms_uri = terms_uri;
+    counter.expires_at = expires_at;
+    counter.amendment_version = 0;
+    counter.proposed_at = clock.unix_timestamp;
+    counter.resolved_at = 0;
+    counter.bump = ctx.bumps.counter;
+
+    emit!(CounterProposed {
+        version: EVENT_VERSION,
+        agreement_id,
+        counter: counter.key(),
+        round,
+        party: counter.agent_identity,
+        actor: ctx.accounts.signer.key(),
+        base_terms_hash: counter.base_terms_hash,
+        terms_hash,
+        terms_uri,
+        expires_at,
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
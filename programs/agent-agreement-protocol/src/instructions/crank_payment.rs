@@ -0,0 +1,171 @@
+use anchor_lang::prelude::*;
+use crate::constants::*;
+use crate::errors::AapError;
+use crate::events::{AgreementFulfilled, RecurringPaymentCranked};
+use crate::instructions::release_escrow::require_escrow_recipient;
+use crate::state::{
+    AgentIdentity, AgentVault, Agreement, AgreementParty, Freeze, ProtocolConfig, RecurringPayment,
+};
+
+/// Processes the next due period of a recurring payment. Permissionless.
+///
+/// The period is paid from the payer's vault if its delegation can commit
+/// the total paid so far including this period (within `max_commit_lamports`,
+/// as for escrow on one agreement) and the vault's available balance,
+/// which excludes `total_committed`, covers it. Otherwise it fails until the
+/// next period starts, after which the period is recorded as missed. Nothing
+/// is pulled while the payer is frozen. When
+/// the last period is processed the schedule completes, and if none was
+/// missed the agreement is fulfilled.
+#[derive(Accounts)]
+#[instruction(agreement_id: [u8; 16])]
+pub struct CrankPayment<'info> {
+    pub signer: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"agreement", agreement_id.as_ref()],
+        bump = agreement.bump,
+        constraint = agreement.status == STATUS_ACTIVE @ AapError::InvalidStatus,
+    )]
+    pub agreement: Account<'info, Agreement>,
+
+    #[account(
+        mut,
+        seeds = [b"recurring", agreement.key().as_ref()],
+        bump = recurring.bump,
+        constraint = recurring.status == RECURRING_ACTIVE @ AapError::RecurringPaymentNotActive,
+    )]
+    pub recurring: Account<'info, RecurringPayment>,
+
+    #[account(
+        constraint = payer_party.key() == recurring.payer @ AapError::InvalidRecurringPayment,
+        constraint = payer_party.agreement == agreement.key() @ AapError::PartyNotInAgreement,
+    )]
+    pub payer_party: Account<'info, AgreementParty>,
+
+    #[account(
+        constraint = payer_identity.key() == payer_party.agent_identity @ AapError::InvalidRecurringPayment,
+    )]
+    pub payer_identity: Account<'info, AgentIdentity>,
+
+    #[account(
+        mut,
+        seeds = [b"vault", payer_identity.key().as_ref()],
+        bump = payer_vault.bump,
+    )]
+    pub payer_vault: Account<'info, AgentVault>,
+
+    #[account(
+        constraint = payee_party.key() == recurring.payee @ AapError::InvalidRecurringPayment,
+        constraint = payee_party.agreement == agreement.key() @ AapError::PartyNotInAgreement,
+    )]
+    pub payee_party: Account<'info, AgreementParty>,
+
+    /// CHECK: The payee's AgentVault PDA, or the payee key itself for direct
+    /// parties; checked in the handler
+    #[account(mut)]
+    pub recipient: UncheckedAccount<'info>,
+
+    /// CHECK: Freeze PDAs covering the payer (own, parent's, authority's); must not exist
+    #[account(seeds = [b"freeze", payer_identity.key().as_ref()], bump)]
+    pub agent_freeze: UncheckedAccount<'info>,
+
+    /// CHECK: See `agent_freeze`
+    #[account(seeds = [b"freeze", payer_identity.parent.as_ref()], bump)]
+    pub parent_freeze: UncheckedAccount<'info>,
+
+    /// CHECK: See `agent_freeze`
+    #[account(seeds = [b"freeze", payer_identity.authority.as_ref()], bump)]
+    pub authority_freeze: UncheckedAccount<'info>,
+
+    #[account(
+        seeds = [CONFIG_SEED],
+        bump = config.bump,
+        constraint = !config.paused @ AapError::ProtocolPaused,
+    )]
+    pub config: Account<'info, ProtocolConfig>,
+}
+
+pub fn handler(ctx: Context<CrankPayment>, agreement_id: [u8; 16]) -> Result<()> {
+    Freeze::require_unfrozen(&[
+        &ctx.accounts.agent_freeze,
+        &ctx.accounts.parent_freeze,
+        &ctx.accounts.authority_freeze,
+    ])?;
+
+    let now = Clock::get()?.unix_timestamp;
+    require_escrow_recipient(&ctx.accounts.payee_party, &ctx.accounts.recipient)?;
+
+    let recurring = &mut ctx.accounts.recurring;
+    let due = aap_core::recurring::next_due_period(
+        recurring.start_at,
+        recurring.period,
+        recurring.processed(),
+        recurring.num_periods,
+        now,
+    )
+    .map_err(AapError::from)?;
+
+    let amount = recurring.amount;
+    let total = aap_core::recurring::total_paid(amount, recurring.periods_paid + 1).map_err(AapError::from)?;
+    let commit = aap_core::scope::require_can_commit(&ctx.accounts.payer_identity.scope.into(), now, total);
+    let vault = &mut ctx.accounts.payer_vault;
+    let rent_exempt = Rent::get()?.minimum_balance(AgentVault::LEN);
+    let available = vault.available_balance(vault.to_account_info().lamports(), rent_exempt);
+    let paid = commit.is_ok() && amount <= available;
+
+    if paid {
+        **vault.to_account_info().try_borrow_mut_lamports()? -= amount;
+        **ctx.accounts.recipient.to_account_info().try_borrow_mut_lamports()? += amount;
+        recurring.periods_paid += 1;
+        recurring.last_paid_at = now;
+    } else {
+        // Still payable late until the next period starts
+        if !due.lapsed {
+            commit.map_err(AapError::from)?;
+            return err!(AapError::InsufficientVaultBalance);
+        }
+        recurring.periods_missed += 1;
+    }
+
+    if recurring.processed() == recurring.num_periods {
+        recurring.status = RECURRING_COMPLETED;
+        recurring.ended_at = now;
+    }
+
+    emit!(RecurringPaymentCranked {
+        version: EVENT_VERSION,
+        agreement_id,
+        recurring: recurring.key(),
+        payer: ctx.accounts.payer_identity.key(),
+        payee: ctx.accounts.payee_party.agent_identity,
+        recipient: ctx.accounts.recipient.key(),
+        actor: ctx.accounts.signer.key(),
+        period_index: due.index,
+        due_at: due.due_at,
+        paid,
+        amount: if paid { amount } else { 0 },
+        periods_paid: recurring.periods_paid,
+        periods_missed: recurring.periods_missed,
+        status: recurring.status,
+        timestamp: now,
+    });
+
+    if recurring.status == RECURRING_COMPLETED && recurring.periods_missed == 0 {
+        let agreement = &mut ctx.accounts.agreement;
+        let previous_status = agreement.status;
+        aap_core::status::require_transition(previous_status, STATUS_FULFILLED).map_err(AapError::from)?;
+        agreement.status = STATUS_FULFILLED;
+        emit!(AgreementFulfilled {
+            version: EVENT_VERSION,
+            agreement_id,
+            actor: ctx.accounts.signer.key(),
+            previous_status,
+            status: STATUS_FULFILLED,
+            timestamp: now,
+        });
+    }
+
+    Ok(())
+}
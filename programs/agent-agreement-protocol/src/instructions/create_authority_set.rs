@@ -0,0 +1,68 @@
+use anchor_lang::prelude::*;
+use crate::constants::{CONFIG_SEED, EVENT_VERSION};
+use crate::errors::AapError;
+use crate::events::AuthoritySetCreated;
+use crate::state::{AuthoritySet, ProtocolConfig};
+
+/// Create an M-of-N council. Its signer PDA can then be registered as, or
+/// transferred in as, an identity's authority. The payer needn't be a member.
+#[derive(Accounts)]
+#[instruction(set_id: [u8; 16], members: Vec<Pubkey>)]
+pub struct CreateAuthoritySet<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = AuthoritySet::space(members.len()),
+        seeds = [b"authority_set", set_id.as_ref()],
+        bump,
+    )]
+    pub authority_set: Account<'info, AuthoritySet>,
+
+    pub system_program: Program<'info, System>,
+
+    #[account(
+        seeds = [CONFIG_SEED],
+        bump = config.bump,
+        constraint = !config.paused @ AapError::ProtocolPaused,
+    )]
+    pub config: Account<'info, ProtocolConfig>,
+}
+
+pub fn handler(
+    ctx: Context<CreateAuthoritySet>,
+    set_id: [u8; 16],
+    members: Vec<Pubkey>,
+    threshold: u8,
+) -> Result<()> {
+    require!(AuthoritySet::is_valid(&members, threshold), AapError::InvalidAuthoritySet);
+
+    let address = ctx.accounts.authority_set.key();
+    let (signer, signer_bump) =
+        Pubkey::find_program_address(&[b"authority_signer", address.as_ref()], &crate::ID);
+
+    let now = Clock::get()?.unix_timestamp;
+    let set = &mut ctx.accounts.authority_set;
+    set.set_id = set_id;
+    set.members = members.clone();
+    set.threshold = threshold;
+    set.action_count = 0;
+    set.created_at = now;
+    set.bump = ctx.bumps.authority_set;
+    set.signer_bump = signer_bump;
+
+    emit!(AuthoritySetCreated {
+        version: EVENT_VERSION,
+        authority_set: address,
+        signer,
+        set_id,
+        members,
+        threshold,
+        actor: ctx.accounts.payer.key(),
+        timestamp: now,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,84 @@
+use anchor_lang::prelude::*;
+use aap_core::status::can_transition;
+use crate::constants::*;
+use crate::errors::AapError;
+use crate::events::AgreementDeclined;
+use crate::state::{AgentIdentity, Agreement, AgreementParty, Decline, ProtocolConfig};
+
+/// An invited party that hasn't signed refuses the agreement, which becomes
+/// Rejected (terminal, closable). The signer pays for the `Decline` record.
+#[derive(Accounts)]
+#[instruction(agreement_id: [u8; 16])]
+pub struct DeclineAgreement<'info> {
+    #[account(mut)]
+    pub signer: Signer<'info>,
+
+    /// Party's AgentIdentity — signer must be the agent_key or authority
+    #[account(
+        constraint = (
+            party_identity.agent_key == signer.key() ||
+            party_identity.authority == signer.key()
+        ) @ AapError::Unauthorized,
+    )]
+    pub party_identity: Account<'info, AgentIdentity>,
+
+    #[account(
+        mut,
+        seeds = [b"agreement", agreement_id.as_ref()],
+        bump = agreement.bump,
+        constraint = can_transition(agreement.status, STATUS_REJECTED) @ AapError::InvalidStatus,
+    )]
+    pub agreement: Account<'info, Agreement>,
+
+    #[account(
+        seeds = [b"party", agreement_id.as_ref(), party_identity.key().as_ref()],
+        bump = party.bump,
+        constraint = party.role != ROLE_PROPOSER @ AapError::InvalidRole,
+        constraint = !party.signed @ AapError::AlreadySigned,
+    )]
+    pub party: Account<'info, AgreementParty>,
+
+    #[account(
+        init,
+        payer = signer,
+        space = Decline::LEN,
+        seeds = [b"decline", party.key().as_ref()],
+        bump,
+    )]
+    pub decline: Account<'info, Decline>,
+
+    pub system_program: Program<'info, System>,
+
+    #[account(
+        seeds = [CONFIG_SEED],
+        bump = config.bump,
+        constraint = !config.paused @ AapError::ProtocolPaused,
+    )]
+    pub config: Account<'info, ProtocolConfig>,
+}
+
+pub fn handler(ctx: Context<DeclineAgreement>, agreement_id: [u8; 16]) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+
+    let decline = &mut ctx.accounts.decline;
+    decline.agreement = ctx.accounts.agreement.key();
+    decline.party = ctx.accounts.party.key();
+    decline.declined_at = now;
+    decline.bump = ctx.bumps.decline;
+
+    let previous_status = ctx.accounts.agreement.status;
+    ctx.accounts.agreement.status = STATUS_REJECTED;
+
+    emit!(AgreementDeclined {
+        version: EVENT_VERSION,
+        agreement_id,
+        party: ctx.accounts.party_identity.key(),
+        actor: ctx.accounts.signer.key(),
+        direct: false,
+        previous_status,
+        status: STATUS_REJECTED,
+        timestamp: now,
+    });
+
+    Ok(())
+}
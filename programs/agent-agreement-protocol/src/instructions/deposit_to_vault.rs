@@ -1,6 +1,7 @@
 use anchor_lang::prelude::*;
+use crate::constants::{CONFIG_SEED, EVENT_VERSION};
 use anchor_lang::system_program;
-use crate::state::{AgentIdentity, AgentVault};
+use crate::state::{AgentIdentity, AgentVault, ProtocolConfig};
 use crate::errors::AapError;
 use crate::events::VaultDeposit;
 
@@ -24,6 +25,13 @@ pub struct DepositToVault<'info> {
     pub vault: Account<'info, AgentVault>,
 
     pub system_program: Program<'info, System>,
+
+    #[account(
+        seeds = [CONFIG_SEED],
+        bump = config.bump,
+        constraint = !config.paused @ AapError::ProtocolPaused,
+    )]
+    pub config: Account<'info, ProtocolConfig>,
 }
 
 pub fn handler(ctx: Context<DepositToVault>, amount: u64) -> Result<()> {
@@ -56,10 +64,14 @@ pub fn handler(ctx: Context<DepositToVault>, amount: u64) -> Result<()> {
     vault.total_deposited = vault.total_deposited.checked_add(amount).unwrap();
 
     emit!(VaultDeposit {
+        version: EVENT_VERSION,
         agent_identity: ctx.accounts.agent_identity.key(),
         authority: ctx.accounts.authority.key(),
         amount,
         new_balance: vault.to_account_info().lamports(),
+        actor: ctx.accounts.authority.key(),
+        total_deposited: vault.total_deposited,
+        timestamp: Clock::get()?.unix_timestamp,
     });
 
     Ok(())
@@ -0,0 +1,105 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{self, Transfer};
+use crate::constants::*;
+use crate::errors::AapError;
+use crate::events::RevenueDistributed;
+use crate::instructions::release_escrow::require_escrow_recipient;
+use crate::state::{Agreement, AgreementParty, ProtocolConfig, RevenueShare};
+
+/// Pays out all SOL held by the revenue receiver by the agreement's splits.
+/// Permissionless while the agreement is Active. Pass, for each split in
+/// order, its AgreementParty PDA and recipient (the party's AgentVault, or
+/// the wallet of a direct party) as remaining accounts.
+#[derive(Accounts)]
+#[instruction(agreement_id: [u8; 16])]
+pub struct Distribute<'info> {
+    pub signer: Signer<'info>,
+
+    #[account(
+        seeds = [b"agreement", agreement_id.as_ref()],
+        bump = agreement.bump,
+        constraint = agreement.status == STATUS_ACTIVE @ AapError::InvalidStatus,
+    )]
+    pub agreement: Account<'info, Agreement>,
+
+    #[account(
+        mut,
+        seeds = [b"revenue_share", agreement.key().as_ref()],
+        bump = revenue_share.bump,
+    )]
+    pub revenue_share: Account<'info, RevenueShare>,
+
+    /// CHECK: System-owned PDA that collects the revenue; signs its payouts
+    #[account(
+        mut,
+        seeds = [b"revenue", agreement.key().as_ref()],
+        bump = revenue_share.receiver_bump,
+    )]
+    pub receiver: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+
+    #[account(
+        seeds = [CONFIG_SEED],
+        bump = config.bump,
+        constraint = !config.paused @ AapError::ProtocolPaused,
+    )]
+    pub config: Account<'info, ProtocolConfig>,
+}
+
+pub fn handler<'info>(
+    ctx: Context<'_, '_, '_, 'info, Distribute<'info>>,
+    agreement_id: [u8; 16],
+) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+    let revenue_share = &ctx.accounts.revenue_share;
+    let accounts = ctx.remaining_accounts;
+    require!(accounts.len() == revenue_share.splits.len() * 2, AapError::InvalidDistribution);
+
+    // The receiver holds no data, so all of its lamports are revenue
+    let receiver = &ctx.accounts.receiver;
+    let amount = receiver.lamports();
+    require!(amount > 0, AapError::InvalidAmount);
+
+    let agreement_key = ctx.accounts.agreement.key();
+    let bump = [revenue_share.receiver_bump];
+    let seeds: &[&[u8]] = &[b"revenue", agreement_key.as_ref(), &bump];
+    let parts = aap_core::revenue::split_amount(amount, &revenue_share.bps());
+    for ((split, part), pair) in revenue_share.splits.iter().zip(parts).zip(accounts.chunks(2)) {
+        let (party_info, recipient) = (&pair[0], &pair[1]);
+        require!(party_info.key() == split.party, AapError::InvalidDistribution);
+        let party = AgreementParty::load_member(party_info, &agreement_key)?;
+        require_escrow_recipient(&party, recipient)?;
+        if part == 0 {
+            continue;
+        }
+
+        system_program::transfer(
+            CpiContext::new_with_signer(
+                ctx.accounts.system_program.to_account_info(),
+                Transfer { from: receiver.to_account_info(), to: recipient.clone() },
+                &[seeds],
+            ),
+            part,
+        )?;
+
+        emit!(RevenueDistributed {
+            version: EVENT_VERSION,
+            agreement_id,
+            receiver: receiver.key(),
+            party: party.agent_identity,
+            recipient: recipient.key(),
+            mint: Pubkey::default(),
+            actor: ctx.accounts.signer.key(),
+            amount: part,
+            bps: split.bps,
+            timestamp: now,
+        });
+    }
+
+    let revenue_share = &mut ctx.accounts.revenue_share;
+    revenue_share.distributed_lamports = revenue_share.distributed_lamports.saturating_add(amount);
+    revenue_share.distributions += 1;
+
+    Ok(())
+}
@@ -0,0 +1,122 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Token, TokenAccount, Transfer};
+use crate::constants::*;
+use crate::errors::AapError;
+use crate::events::RevenueDistributed;
+use crate::state::{AgentIdentity, Agreement, AgreementParty, ProtocolConfig, RevenueShare};
+
+/// Pays out all tokens in one of the revenue receiver's token accounts by the
+/// agreement's splits. Permissionless while the agreement is Active. Pass, for
+/// each split in order, its AgreementParty PDA, the party's AgentIdentity (or
+/// the wallet of a direct party) and a token account of the same mint as
+/// remaining accounts. Identity parties are paid to an account owned by their
+/// authority, since vaults only hold SOL.
+#[derive(Accounts)]
+#[instruction(agreement_id: [u8; 16])]
+pub struct DistributeTokens<'info> {
+    pub signer: Signer<'info>,
+
+    #[account(
+        seeds = [b"agreement", agreement_id.as_ref()],
+        bump = agreement.bump,
+        constraint = agreement.status == STATUS_ACTIVE @ AapError::InvalidStatus,
+    )]
+    pub agreement: Account<'info, Agreement>,
+
+    #[account(
+        mut,
+        seeds = [b"revenue_share", agreement.key().as_ref()],
+        bump = revenue_share.bump,
+    )]
+    pub revenue_share: Account<'info, RevenueShare>,
+
+    /// CHECK: System-owned PDA that collects the revenue; signs its payouts
+    #[account(
+        seeds = [b"revenue", agreement.key().as_ref()],
+        bump = revenue_share.receiver_bump,
+    )]
+    pub receiver: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        token::authority = receiver,
+    )]
+    pub receiver_tokens: Account<'info, TokenAccount>,
+
+    pub token_program: Program<'info, Token>,
+
+    #[account(
+        seeds = [CONFIG_SEED],
+        bump = config.bump,
+        constraint = !config.paused @ AapError::ProtocolPaused,
+    )]
+    pub config: Account<'info, ProtocolConfig>,
+}
+
+pub fn handler<'info>(
+    ctx: Context<'_, '_, 'info, 'info, DistributeTokens<'info>>,
+    agreement_id: [u8; 16],
+) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+    let revenue_share = &ctx.accounts.revenue_share;
+    let accounts = ctx.remaining_accounts;
+    require!(accounts.len() == revenue_share.splits.len() * 3, AapError::InvalidDistribution);
+
+    let source = &ctx.accounts.receiver_tokens;
+    let amount = source.amount;
+    require!(amount > 0, AapError::InvalidAmount);
+
+    let agreement_key = ctx.accounts.agreement.key();
+    let bump = [revenue_share.receiver_bump];
+    let seeds: &[&[u8]] = &[b"revenue", agreement_key.as_ref(), &bump];
+    let parts = aap_core::revenue::split_amount(amount, &revenue_share.bps());
+    for ((split, part), group) in revenue_share.splits.iter().zip(parts).zip(accounts.chunks(3)) {
+        let (party_info, owner_info, recipient) = (&group[0], &group[1], &group[2]);
+        require!(party_info.key() == split.party, AapError::InvalidDistribution);
+        let party = AgreementParty::load_member(party_info, &agreement_key)?;
+        require!(owner_info.key() == party.agent_identity, AapError::InvalidDistribution);
+        let owner = if owner_info.owner == &crate::ID {
+            AgentIdentity::try_deserialize(&mut &owner_info.try_borrow_data()?[..])?.authority
+        } else {
+            party.agent_identity
+        };
+        let recipient_tokens = Account::<TokenAccount>::try_from(recipient)?;
+        require!(
+            recipient_tokens.owner == owner && recipient_tokens.mint == source.mint,
+            AapError::InvalidDistribution
+        );
+        if part == 0 {
+            continue;
+        }
+
+        token::transfer(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: source.to_account_info(),
+                    to: recipient.clone(),
+                    authority: ctx.accounts.receiver.to_account_info(),
+                },
+                &[seeds],
+            ),
+            part,
+        )?;
+
+        emit!(RevenueDistributed {
+            version: EVENT_VERSION,
+            agreement_id,
+            receiver: ctx.accounts.receiver.key(),
+            party: party.agent_identity,
+            recipient: recipient.key(),
+            mint: source.mint,
+            actor: ctx.accounts.signer.key(),
+            amount: part,
+            bps: split.bps,
+            timestamp: now,
+        });
+    }
+
+    ctx.accounts.revenue_share.distributions += 1;
+
+    Ok(())
+}
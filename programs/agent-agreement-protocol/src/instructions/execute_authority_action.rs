@@ -0,0 +1,92 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::{instruction::{AccountMeta, Instruction}, program::invoke_signed};
+use crate::constants::EVENT_VERSION;
+use crate::errors::AapError;
+use crate::events::AuthorityActionExecuted;
+use crate::state::{AuthorityAction, AuthoritySet};
+
+/// Once `threshold` members approved, any member runs the action: the stored
+/// instruction is invoked on this program with the set's signer PDA signing,
+/// then the action is closed. Pass the action's accounts, in order, as
+/// remaining accounts (other signers it lists must sign this transaction).
+#[derive(Accounts)]
+#[instruction(index: u64)]
+pub struct ExecuteAuthorityAction<'info> {
+    pub member: Signer<'info>,
+
+    #[account(
+        seeds = [b"authority_set", authority_set.set_id.as_ref()],
+        bump = authority_set.bump,
+    )]
+    pub authority_set: Account<'info, AuthoritySet>,
+
+    /// CHECK: Proposer of the action, refunded its rent
+    #[account(mut, address = action.proposer)]
+    pub proposer: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        close = proposer,
+        seeds = [b"authority_action", authority_set.key().as_ref(), index.to_le_bytes().as_ref()],
+        bump = action.bump,
+        has_one = authority_set,
+    )]
+    pub action: Account<'info, AuthorityAction>,
+}
+
+pub fn handler<'info>(
+    ctx: Context<'_, '_, '_, 'info, ExecuteAuthorityAction<'info>>,
+    index: u64,
+) -> Result<()> {
+    let set = &ctx.accounts.authority_set;
+    require!(
+        set.member_index(&ctx.accounts.member.key()).is_some(),
+        AapError::NotSetMember
+    );
+
+    let action = &ctx.accounts.action;
+    let approvals = action.approval_count();
+    require!(approvals >= set.threshold, AapError::ThresholdNotReached);
+
+    require!(
+        ctx.remaining_accounts.len() == action.accounts.len()
+            && ctx
+                .remaining_accounts
+                .iter()
+                .zip(&action.accounts)
+                .all(|(info, a)| info.key == &a.pubkey),
+        AapError::ActionAccountMismatch
+    );
+
+    let ix = Instruction {
+        program_id: crate::ID,
+        accounts: action
+            .accounts
+            .iter()
+            .map(|a| AccountMeta {
+                pubkey: a.pubkey,
+                is_signer: a.is_signer,
+                is_writable: a.is_writable,
+            })
+            .collect(),
+        data: action.data.clone(),
+    };
+    let set_key = set.key();
+    invoke_signed(
+        &ix,
+        ctx.remaining_accounts,
+        &[&[b"authority_signer", set_key.as_ref(), &[set.signer_bump]]],
+    )?;
+
+    emit!(AuthorityActionExecuted {
+        version: EVENT_VERSION,
+        authority_set: set_key,
+        action: action.key(),
+        index,
+        actor: ctx.accounts.member.key(),
+        approvals,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,48 @@
+use anchor_lang::prelude::*;
+use crate::constants::EVENT_VERSION;
+use crate::errors::AapError;
+use crate::events::FreezeApplied;
+use crate::state::{AgentIdentity, Freeze};
+
+/// Freeze one agent and, through their `parent`, its sub-agents.
+#[derive(Accounts)]
+pub struct FreezeAgent<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        has_one = authority @ AapError::Unauthorized,
+    )]
+    pub agent_identity: Account<'info, AgentIdentity>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = Freeze::LEN,
+        seeds = [b"freeze", agent_identity.key().as_ref()],
+        bump,
+    )]
+    pub freeze: Account<'info, Freeze>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<FreezeAgent>) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+    let freeze = &mut ctx.accounts.freeze;
+    freeze.target = ctx.accounts.agent_identity.key();
+    freeze.authority = ctx.accounts.authority.key();
+    freeze.frozen_at = now;
+    freeze.bump = ctx.bumps.freeze;
+
+    emit!(FreezeApplied {
+        version: EVENT_VERSION,
+        target: freeze.target,
+        authority: freeze.authority,
+        actor: ctx.accounts.authority.key(),
+        authority_wide: false,
+        timestamp: now,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,42 @@
+use anchor_lang::prelude::*;
+use crate::constants::EVENT_VERSION;
+use crate::events::FreezeApplied;
+use crate::state::Freeze;
+
+/// Freeze every agent the signer is authority of, current and future.
+#[derive(Accounts)]
+pub struct FreezeAuthority<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = Freeze::LEN,
+        seeds = [b"freeze", authority.key().as_ref()],
+        bump,
+    )]
+    pub freeze: Account<'info, Freeze>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<FreezeAuthority>) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+    let freeze = &mut ctx.accounts.freeze;
+    freeze.target = ctx.accounts.authority.key();
+    freeze.authority = ctx.accounts.authority.key();
+    freeze.frozen_at = now;
+    freeze.bump = ctx.bumps.freeze;
+
+    emit!(FreezeApplied {
+        version: EVENT_VERSION,
+        target: freeze.target,
+        authority: freeze.authority,
+        actor: ctx.accounts.authority.key(),
+        authority_wide: true,
+        timestamp: now,
+    });
+
+    Ok(())
+}
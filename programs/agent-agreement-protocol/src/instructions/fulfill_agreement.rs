@@ -1,8 +1,9 @@
 use anchor_lang::prelude::*;
+use aap_core::status::can_transition;
 use crate::constants::*;
 use crate::errors::AapError;
 use crate::events::AgreementFulfilled;
-use crate::state::{AgentIdentity, Agreement, AgreementParty};
+use crate::state::{AgentIdentity, Agreement, AgreementParty, EscrowStream, ProtocolConfig};
 
 #[derive(Accounts)]
 #[instruction(agreement_id: [u8; 16])]
@@ -29,20 +30,53 @@ pub struct FulfillAgreement<'info> {
         mut,
         seeds = [b"agreement", agreement_id.as_ref()],
         bump = agreement.bump,
-        constraint = agreement.status == STATUS_ACTIVE @ AapError::InvalidStatus,
+        constraint = can_transition(agreement.status, STATUS_FULFILLED) @ AapError::InvalidStatus,
     )]
     pub agreement: Account<'info, Agreement>,
+
+    /// CHECK: MilestoneSchedule PDA; must not exist, since a schedule fulfills
+    /// the agreement through `release_milestone`
+    #[account(
+        seeds = [b"milestones", agreement.key().as_ref()],
+        bump,
+        constraint = milestones.data_is_empty() @ AapError::MilestonesPending,
+    )]
+    pub milestones: UncheckedAccount<'info>,
+
+    /// CHECK: EscrowStream PDA; if the agreement has a stream it must have
+    /// ended, or a single party could vest all of it to the payee early
+    #[account(seeds = [b"stream", agreement.key().as_ref()], bump)]
+    pub stream: UncheckedAccount<'info>,
+
+    #[account(
+        seeds = [CONFIG_SEED],
+        bump = config.bump,
+        constraint = !config.paused @ AapError::ProtocolPaused,
+    )]
+    pub config: Account<'info, ProtocolConfig>,
 }
 
 pub fn handler(
     ctx: Context<FulfillAgreement>,
     _agreement_id: [u8; 16],
 ) -> Result<()> {
+    let stream = &ctx.accounts.stream;
+    if !stream.data_is_empty() {
+        let stream = EscrowStream::try_deserialize(&mut &stream.try_borrow_data()?[..])?;
+        require!(stream.status != STREAM_ACTIVE, AapError::StreamPending);
+    }
+
     let agreement = &mut ctx.accounts.agreement;
+    let previous_status = agreement.status;
     agreement.status = STATUS_FULFILLED;
 
     emit!(AgreementFulfilled {
+        version: EVENT_VERSION,
         agreement_id: agreement.agreement_id,
+        actor: ctx.accounts.signer.key(),
+        previous_status,
+        status: STATUS_FULFILLED,
+        timestamp: Clock::get()?.unix_timestamp,
     });
 
     Ok(())
@@ -0,0 +1,108 @@
+use anchor_lang::prelude::*;
+use crate::constants::*;
+use crate::errors::AapError;
+use crate::events::EscrowFunded;
+use crate::state::{AgentIdentity, AgentVault, Agreement, AgreementParty, Freeze, ProtocolConfig};
+
+/// A party's agent moves SOL from its AgentVault into escrow, held as
+/// lamports on the Agreement PDA. Its total deposit on the agreement must stay
+/// within the delegation's `max_commit_lamports`.
+#[derive(Accounts)]
+#[instruction(agreement_id: [u8; 16])]
+pub struct FundEscrow<'info> {
+    pub signer: Signer<'info>,
+
+    #[account(
+        constraint = signer_identity.agent_key == signer.key() @ AapError::Unauthorized,
+    )]
+    pub signer_identity: Account<'info, AgentIdentity>,
+
+    #[account(
+        mut,
+        seeds = [b"agreement", agreement_id.as_ref()],
+        bump = agreement.bump,
+    )]
+    pub agreement: Account<'info, Agreement>,
+
+    #[account(
+        mut,
+        seeds = [b"party", agreement_id.as_ref(), signer_identity.key().as_ref()],
+        bump = party.bump,
+    )]
+    pub party: Account<'info, AgreementParty>,
+
+    #[account(
+        mut,
+        seeds = [b"vault", signer_identity.key().as_ref()],
+        bump = vault.bump,
+    )]
+    pub vault: Account<'info, AgentVault>,
+
+    /// CHECK: Freeze PDAs covering the signer (own, parent's, authority's); must not exist
+    #[account(seeds = [b"freeze", signer_identity.key().as_ref()], bump)]
+    pub agent_freeze: UncheckedAccount<'info>,
+
+    /// CHECK: See `agent_freeze`
+    #[account(seeds = [b"freeze", signer_identity.parent.as_ref()], bump)]
+    pub parent_freeze: UncheckedAccount<'info>,
+
+    /// CHECK: See `agent_freeze`
+    #[account(seeds = [b"freeze", signer_identity.authority.as_ref()], bump)]
+    pub authority_freeze: UncheckedAccount<'info>,
+
+    #[account(
+        seeds = [CONFIG_SEED],
+        bump = config.bump,
+        constraint = !config.paused @ AapError::ProtocolPaused,
+    )]
+    pub config: Account<'info, ProtocolConfig>,
+}
+
+pub fn handler(ctx: Context<FundEscrow>, agreement_id: [u8; 16], amount: u64) -> Result<()> {
+    Freeze::require_unfrozen(&[
+        &ctx.accounts.agent_freeze,
+        &ctx.accounts.parent_freeze,
+        &ctx.accounts.authority_freeze,
+    ])?;
+    require!(amount > 0, AapError::InvalidAmount);
+
+    let clock = Clock::get()?;
+    let agreement = &mut ctx.accounts.agreement;
+    let party = &mut ctx.accounts.party;
+
+    aap_core::escrow::require_fundable(agreement.status).map_err(AapError::from)?;
+    aap_core::status::require_agreement_not_expired(agreement.expires_at, clock.unix_timestamp)
+        .map_err(AapError::from)?;
+    let party_escrow = party.escrow_deposited.checked_add(amount).ok_or(AapError::InvalidAmount)?;
+    aap_core::scope::require_can_commit(
+        &ctx.accounts.signer_identity.scope.into(),
+        clock.unix_timestamp,
+        party_escrow,
+    )
+    .map_err(AapError::from)?;
+
+    let vault = &mut ctx.accounts.vault;
+    let rent_exempt = Rent::get()?.minimum_balance(AgentVault::LEN);
+    let available = vault.available_balance(vault.to_account_info().lamports(), rent_exempt);
+    require!(amount <= available, AapError::InsufficientVaultBalance);
+
+    // Both accounts are owned by this program
+    **vault.to_account_info().try_borrow_mut_lamports()? -= amount;
+    **agreement.to_account_info().try_borrow_mut_lamports()? += amount;
+
+    party.escrow_deposited = party_escrow;
+    agreement.escrow_total = agreement.escrow_total.checked_add(amount).ok_or(AapError::InvalidAmount)?;
+
+    emit!(EscrowFunded {
+        version: EVENT_VERSION,
+        agreement_id,
+        party: ctx.accounts.signer_identity.key(),
+        actor: ctx.accounts.signer.key(),
+        amount,
+        party_escrow,
+        escrow_total: agreement.escrow_total,
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,148 @@
+use std::collections::BTreeSet;
+
+use anchor_lang::prelude::*;
+use crate::constants::*;
+use crate::errors::AapError;
+use crate::events::StreamFunded;
+use crate::state::{
+    AgentIdentity, AgentVault, Agreement, AgreementParty, EscrowStream, Freeze, ProtocolConfig,
+};
+
+/// A party's agent streams SOL from its AgentVault to another party of an
+/// Active agreement. The amount vests linearly from activation, when the last
+/// party signed, until the agreement expires, and must be within the
+/// delegation's `max_commit_lamports`. Every AgreementParty is passed as a
+/// remaining account so the activation time can be read from them.
+#[derive(Accounts)]
+#[instruction(agreement_id: [u8; 16])]
+pub struct FundStream<'info> {
+    #[account(mut)]
+    pub signer: Signer<'info>,
+
+    #[account(
+        constraint = signer_identity.agent_key == signer.key() @ AapError::Unauthorized,
+    )]
+    pub signer_identity: Account<'info, AgentIdentity>,
+
+    #[account(
+        seeds = [b"agreement", agreement_id.as_ref()],
+        bump = agreement.bump,
+        constraint = agreement.status == STATUS_ACTIVE @ AapError::InvalidStatus,
+    )]
+    pub agreement: Account<'info, Agreement>,
+
+    #[account(
+        seeds = [b"party", agreement_id.as_ref(), signer_identity.key().as_ref()],
+        bump = party.bump,
+    )]
+    pub party: Account<'info, AgreementParty>,
+
+    #[account(
+        seeds = [b"party", agreement_id.as_ref(), payee_party.agent_identity.as_ref()],
+        bump = payee_party.bump,
+        constraint = payee_party.key() != party.key() @ AapError::InvalidStream,
+    )]
+    pub payee_party: Account<'info, AgreementParty>,
+
+    #[account(
+        init,
+        payer = signer,
+        space = EscrowStream::LEN,
+        seeds = [b"stream", agreement.key().as_ref()],
+        bump,
+    )]
+    pub stream: Account<'info, EscrowStream>,
+
+    #[account(
+        mut,
+        seeds = [b"vault", signer_identity.key().as_ref()],
+        bump = vault.bump,
+    )]
+    pub vault: Account<'info, AgentVault>,
+
+    pub system_program: Program<'info, System>,
+
+    /// CHECK: Freeze PDAs covering the signer (own, parent's, authority's); must not exist
+    #[account(seeds = [b"freeze", signer_identity.key().as_ref()], bump)]
+    pub agent_freeze: UncheckedAccount<'info>,
+
+    /// CHECK: See `agent_freeze`
+    #[account(seeds = [b"freeze", signer_identity.parent.as_ref()], bump)]
+    pub parent_freeze: UncheckedAccount<'info>,
+
+    /// CHECK: See `agent_freeze`
+    #[account(seeds = [b"freeze", signer_identity.authority.as_ref()], bump)]
+    pub authority_freeze: UncheckedAccount<'info>,
+
+    #[account(
+        seeds = [CONFIG_SEED],
+        bump = config.bump,
+        constraint = !config.paused @ AapError::ProtocolPaused,
+    )]
+    pub config: Account<'info, ProtocolConfig>,
+}
+
+pub fn handler<'info>(
+    ctx: Context<'_, '_, '_, 'info, FundStream<'info>>,
+    agreement_id: [u8; 16],
+    amount: u64,
+) -> Result<()> {
+    Freeze::require_unfrozen(&[
+        &ctx.accounts.agent_freeze,
+        &ctx.accounts.parent_freeze,
+        &ctx.accounts.authority_freeze,
+    ])?;
+
+    let now = Clock::get()?.unix_timestamp;
+    let end_at = ctx.accounts.agreement.expires_at;
+    aap_core::stream::validate_stream(amount, now, end_at).map_err(AapError::from)?;
+    aap_core::scope::require_can_commit(&ctx.accounts.signer_identity.scope.into(), now, amount)
+        .map_err(AapError::from)?;
+
+    let agreement_key = ctx.accounts.agreement.key();
+    let parties = ctx.remaining_accounts;
+    require!(parties.len() == ctx.accounts.agreement.num_parties as usize, AapError::InvalidStream);
+    let mut seen = BTreeSet::new();
+    let mut start_at = 0;
+    for info in parties {
+        require!(seen.insert(info.key()), AapError::InvalidStream);
+        start_at = start_at.max(AgreementParty::load_member(info, &agreement_key)?.signed_at);
+    }
+
+    let vault = &mut ctx.accounts.vault;
+    let rent_exempt = Rent::get()?.minimum_balance(AgentVault::LEN);
+    let available = vault.available_balance(vault.to_account_info().lamports(), rent_exempt);
+    require!(amount <= available, AapError::InsufficientVaultBalance);
+
+    let stream = &mut ctx.accounts.stream;
+    // Both accounts are owned by this program
+    **vault.to_account_info().try_borrow_mut_lamports()? -= amount;
+    **stream.to_account_info().try_borrow_mut_lamports()? += amount;
+
+    stream.agreement = ctx.accounts.agreement.key();
+    stream.payer = ctx.accounts.party.key();
+    stream.payee = ctx.accounts.payee_party.key();
+    stream.amount = amount;
+    stream.claimed = 0;
+    stream.start_at = start_at;
+    stream.end_at = end_at;
+    stream.status = STREAM_ACTIVE;
+    stream.refunded = 0;
+    stream.ended_at = 0;
+    stream.bump = ctx.bumps.stream;
+
+    emit!(StreamFunded {
+        version: EVENT_VERSION,
+        agreement_id,
+        stream: stream.key(),
+        payer: ctx.accounts.signer_identity.key(),
+        payee: ctx.accounts.payee_party.agent_identity,
+        actor: ctx.accounts.signer.key(),
+        amount,
+        start_at,
+        end_at,
+        timestamp: now,
+    });
+
+    Ok(())
+}
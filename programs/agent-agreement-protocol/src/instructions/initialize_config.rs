@@ -0,0 +1,55 @@
+use anchor_lang::prelude::*;
+use crate::constants::*;
+use crate::errors::AapError;
+use crate::events::ConfigUpdated;
+use crate::program::AgentAgreementProtocol;
+use crate::state::{ConfigParams, ProtocolConfig};
+
+/// Create the singleton config. Only the program's upgrade authority can, so
+/// nobody can front-run the deployer; `params.admin` manages it afterwards.
+#[derive(Accounts)]
+pub struct InitializeConfig<'info> {
+    #[account(mut)]
+    pub upgrade_authority: Signer<'info>,
+
+    #[account(
+        init,
+        payer = upgrade_authority,
+        space = ProtocolConfig::LEN,
+        seeds = [CONFIG_SEED],
+        bump,
+    )]
+    pub config: Account<'info, ProtocolConfig>,
+
+    #[account(constraint = program.programdata_address()? == Some(program_data.key()))]
+    pub program: Program<'info, AgentAgreementProtocol>,
+
+    #[account(
+        constraint = program_data.upgrade_authority_address == Some(upgrade_authority.key()) @ AapError::Unauthorized,
+    )]
+    pub program_data: Account<'info, ProgramData>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<InitializeConfig>, params: ConfigParams) -> Result<()> {
+    aap_core::parties::validate_party_limit(params.max_parties).map_err(AapError::from)?;
+
+    let config = &mut ctx.accounts.config;
+    config.apply(params);
+    config.paused = false;
+    config.bump = ctx.bumps.config;
+
+    emit!(ConfigUpdated {
+        version: EVENT_VERSION,
+        admin: config.admin,
+        actor: ctx.accounts.upgrade_authority.key(),
+        paused: config.paused,
+        max_parties: config.max_parties,
+        fee_lamports: config.fee_lamports,
+        treasury: config.treasury,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,90 @@
+use anchor_lang::prelude::*;
+use crate::constants::*;
+use crate::events::AgreementMaterialized;
+use crate::migration::AgreementSnapshot;
+use crate::state::{Agreement, DecompressionMarker};
+
+/// Recreate an Agreement PDA from a compressed agreement.
+/// Only callable via CPI from `aap_compressed::decompress_agreement`,
+/// which nullifies the compressed account and signs with its migration PDA.
+/// Leaves a DecompressionMarker that `materialize_party` requires.
+#[derive(Accounts)]
+#[instruction(snapshot: AgreementSnapshot)]
+pub struct MaterializeAgreement<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    #[account(
+        seeds = [MIGRATION_SEED],
+        bump,
+        seeds::program = AAP_COMPRESSED_PROGRAM_ID,
+    )]
+    pub migration_authority: Signer<'info>,
+
+    /// CHECK: Proposer's AgentIdentity PDA — only its address is recorded
+    #[account(
+        seeds = [b"agent", snapshot.proposer_agent_key.as_ref()],
+        bump,
+    )]
+    pub proposer_identity: UncheckedAccount<'info>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = Agreement::LEN,
+        seeds = [b"agreement", snapshot.agreement_id.as_ref()],
+        bump,
+    )]
+    pub agreement: Account<'info, Agreement>,
+
+    pub system_program: Program<'info, System>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = DecompressionMarker::LEN,
+        seeds = [b"decompressed", agreement.key().as_ref()],
+        bump,
+    )]
+    pub marker: Account<'info, DecompressionMarker>,
+}
+
+pub fn handler(
+    ctx: Context<MaterializeAgreement>,
+    snapshot: AgreementSnapshot,
+) -> Result<()> {
+    let agreement = &mut ctx.accounts.agreement;
+    agreement.agreement_id = snapshot.agreement_id;
+    agreement.agreement_type = snapshot.agreement_type;
+    agreement.status = snapshot.status;
+    agreement.visibility = snapshot.visibility;
+    agreement.proposer = ctx.accounts.proposer_identity.key();
+    agreement.terms_hash = snapshot.terms_hash;
+    agreement.terms_uri = snapshot.terms_uri;
+    agreement.escrow_vault = Pubkey::default();
+    agreement.escrow_mint = Pubkey::default();
+    agreement.escrow_total = 0;
+    agreement.num_parties = snapshot.num_parties;
+    agreement.num_signed = snapshot.num_signed;
+    agreement.parties_added = snapshot.parties_added;
+    agreement.created_at = snapshot.created_at;
+    agreement.expires_at = snapshot.expires_at;
+    agreement.bump = ctx.bumps.agreement;
+
+    let now = Clock::get()?.unix_timestamp;
+    let marker = &mut ctx.accounts.marker;
+    marker.agreement = agreement.key();
+    marker.decompressed_at = now;
+    marker.bump = ctx.bumps.marker;
+
+    emit!(AgreementMaterialized {
+        version: EVENT_VERSION,
+        agreement_id: snapshot.agreement_id,
+        agreement_pda: agreement.key(),
+        actor: ctx.accounts.migration_authority.key(),
+        status: snapshot.status,
+        timestamp: now,
+    });
+
+    Ok(())
+}
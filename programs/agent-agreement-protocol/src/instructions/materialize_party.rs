@@ -0,0 +1,93 @@
+use anchor_lang::prelude::*;
+use crate::constants::*;
+use crate::errors::AapError;
+use crate::events::PartyMaterialized;
+use crate::migration::PartySnapshot;
+use crate::state::{AgentIdentity, Agreement, AgreementParty};
+
+/// Recreate an AgreementParty PDA from a compressed party.
+/// Only callable via CPI from `aap_compressed::decompress_party`.
+/// The agreement must have been materialized first (its DecompressionMarker
+/// exists), and `payer`, the signer of `decompress_party`, must be the
+/// agreement proposer's agent key or authority.
+#[derive(Accounts)]
+#[instruction(snapshot: PartySnapshot)]
+pub struct MaterializeParty<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    #[account(
+        seeds = [MIGRATION_SEED],
+        bump,
+        seeds::program = AAP_COMPRESSED_PROGRAM_ID,
+    )]
+    pub migration_authority: Signer<'info>,
+
+    #[account(
+        seeds = [b"agreement", snapshot.agreement_id.as_ref()],
+        bump = agreement.bump,
+    )]
+    pub agreement: Account<'info, Agreement>,
+
+    /// CHECK: Party's AgentIdentity PDA — only its address is recorded
+    #[account(
+        seeds = [b"agent", snapshot.agent_key.as_ref()],
+        bump,
+    )]
+    pub agent_identity: UncheckedAccount<'info>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = AgreementParty::LEN,
+        seeds = [b"party", snapshot.agreement_id.as_ref(), agent_identity.key().as_ref()],
+        bump,
+    )]
+    pub party: Account<'info, AgreementParty>,
+
+    pub system_program: Program<'info, System>,
+
+    #[account(address = agreement.proposer @ AapError::Unauthorized)]
+    pub proposer_identity: Account<'info, AgentIdentity>,
+
+    /// CHECK: DecompressionMarker PDA; only `materialize_agreement` gives it data
+    #[account(
+        seeds = [b"decompressed", agreement.key().as_ref()],
+        bump,
+        constraint = !marker.data_is_empty() @ AapError::AgreementNotDecompressed,
+    )]
+    pub marker: UncheckedAccount<'info>,
+}
+
+pub fn handler(
+    ctx: Context<MaterializeParty>,
+    snapshot: PartySnapshot,
+) -> Result<()> {
+    let payer = ctx.accounts.payer.key();
+    let proposer = &ctx.accounts.proposer_identity;
+    require!(
+        payer == proposer.agent_key || payer == proposer.authority,
+        AapError::Unauthorized
+    );
+
+    let party = &mut ctx.accounts.party;
+    party.agreement = ctx.accounts.agreement.key();
+    party.agent_identity = ctx.accounts.agent_identity.key();
+    party.role = snapshot.role;
+    party.signed = snapshot.signed;
+    party.signed_at = snapshot.signed_at;
+    party.escrow_deposited = 0;
+    party.bump = ctx.bumps.party;
+
+    emit!(PartyMaterialized {
+        version: EVENT_VERSION,
+        agreement_id: snapshot.agreement_id,
+        party: ctx.accounts.agent_identity.key(),
+        actor: ctx.accounts.migration_authority.key(),
+        role: snapshot.role,
+        signed: snapshot.signed,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,162 @@
+use anchor_lang::prelude::*;
+use crate::constants::*;
+use crate::errors::AapError;
+use crate::events::AgentMigrated;
+use crate::migration::{invoke_import, AgentSnapshot, ImportCpiAccounts, IMPORT_AGENT_DISCRIMINATOR};
+use crate::state::{AgentIdentity, AgentKeyLookup, AgentVault, MigrationTombstone, ProtocolConfig};
+
+/// Move an AgentIdentity into aap-compressed.
+/// Closes the PDA (and its vault, if any), records a tombstone,
+/// and CPIs into `aap_compressed::import_agent`.
+/// Sub-agents can only be migrated after their parent.
+/// The compressed identity is addressed by `registered_agent_key`, like the
+/// V1 PDA, and keeps the current agent key; a rotated identity's key lookup
+/// is closed with it.
+#[derive(Accounts)]
+#[instruction(registered_agent_key: Pubkey)]
+pub struct MigrateAgent<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        close = authority,
+        seeds = [b"agent", registered_agent_key.as_ref()],
+        bump = agent_identity.bump,
+        has_one = authority @ AapError::Unauthorized,
+    )]
+    pub agent_identity: Account<'info, AgentIdentity>,
+
+    /// Parent's tombstone — required when agent_identity is a sub-agent
+    #[account(
+        constraint = parent_tombstone.kind == TOMBSTONE_KIND_AGENT @ AapError::InvalidTombstone,
+        constraint = parent_tombstone.original == agent_identity.parent @ AapError::InvalidTombstone,
+    )]
+    pub parent_tombstone: Option<Account<'info, MigrationTombstone>>,
+
+    /// CHECK: Agent's vault PDA. Closed to the authority if it was ever initialized.
+    #[account(
+        mut,
+        seeds = [b"vault", agent_identity.key().as_ref()],
+        bump,
+    )]
+    pub vault: UncheckedAccount<'info>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = MigrationTombstone::LEN,
+        seeds = [b"tombstone", agent_identity.key().as_ref()],
+        bump,
+    )]
+    pub tombstone: Account<'info, MigrationTombstone>,
+
+    /// CHECK: PDA signer trusted by aap-compressed import instructions
+    #[account(seeds = [MIGRATION_SEED], bump)]
+    pub migration_authority: UncheckedAccount<'info>,
+
+    /// CHECK: Address-checked aap-compressed program
+    #[account(address = AAP_COMPRESSED_PROGRAM_ID)]
+    pub compressed_program: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+
+    /// Lookup for a rotated identity's current key, closed with it
+    #[account(
+        mut,
+        close = authority,
+        seeds = [b"agent", agent_identity.agent_key.as_ref()],
+        bump = current_key_lookup.bump,
+        constraint = current_key_lookup.identity == agent_identity.key() @ AapError::Unauthorized,
+    )]
+    pub current_key_lookup: Option<Account<'info, AgentKeyLookup>>,
+
+    #[account(
+        seeds = [CONFIG_SEED],
+        bump = config.bump,
+        constraint = !config.paused @ AapError::ProtocolPaused,
+    )]
+    pub config: Account<'info, ProtocolConfig>,
+}
+
+pub fn handler<'info>(
+    ctx: Context<'_, '_, '_, 'info, MigrateAgent<'info>>,
+    registered_agent_key: Pubkey,
+    light_args: Vec<u8>,
+) -> Result<()> {
+    let identity = &ctx.accounts.agent_identity;
+
+    if identity.is_rotated(&identity.key()) {
+        require!(ctx.accounts.current_key_lookup.is_some(), AapError::KeyLookupRequired);
+    }
+
+    // Resolve the parent's agent_key so the compressed side can derive its address
+    let parent_agent_key = if identity.parent == Pubkey::default() {
+        Pubkey::default()
+    } else {
+        let parent = ctx
+            .accounts
+            .parent_tombstone
+            .as_ref()
+            .ok_or(AapError::InvalidTombstone)?;
+        Pubkey::from(parent.key)
+    };
+
+    // Close the vault so its lamports aren't stranded behind a closed identity
+    let vault_info = ctx.accounts.vault.to_account_info();
+    if vault_info.owner == &crate::ID {
+        let vault = AgentVault::try_deserialize(&mut &vault_info.try_borrow_data()?[..])?;
+        require!(vault.total_committed == 0, AapError::VaultHasCommitments);
+
+        // Same as Anchor's `close`: drain lamports, hand back to the system program
+        let authority_info = ctx.accounts.authority.to_account_info();
+        **authority_info.try_borrow_mut_lamports()? += vault_info.lamports();
+        **vault_info.try_borrow_mut_lamports()? = 0;
+        vault_info.assign(&anchor_lang::system_program::ID);
+        vault_info.resize(0)?;
+    }
+
+    let clock = Clock::get()?;
+
+    let tombstone = &mut ctx.accounts.tombstone;
+    tombstone.original = identity.key();
+    tombstone.kind = TOMBSTONE_KIND_AGENT;
+    tombstone.key = registered_agent_key.to_bytes();
+    tombstone.authority = identity.authority;
+    tombstone.migrated_at = clock.unix_timestamp;
+    tombstone.bump = ctx.bumps.tombstone;
+
+    let snapshot = AgentSnapshot {
+        authority: identity.authority,
+        agent_key: identity.agent_key,
+        metadata_hash: identity.metadata_hash,
+        scope: identity.scope,
+        parent_agent_key,
+        created_at: identity.created_at,
+        registered_agent_key,
+    };
+
+    invoke_import(
+        IMPORT_AGENT_DISCRIMINATOR,
+        &light_args,
+        &snapshot,
+        ImportCpiAccounts {
+            payer: &ctx.accounts.authority.to_account_info(),
+            migration_authority: &ctx.accounts.migration_authority.to_account_info(),
+            migration_authority_bump: ctx.bumps.migration_authority,
+            compressed_program: &ctx.accounts.compressed_program.to_account_info(),
+            remaining_accounts: ctx.remaining_accounts,
+        },
+    )?;
+
+    emit!(AgentMigrated {
+        version: EVENT_VERSION,
+        authority: identity.authority,
+        agent_key: identity.agent_key,
+        agent_identity: identity.key(),
+        actor: ctx.accounts.authority.key(),
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
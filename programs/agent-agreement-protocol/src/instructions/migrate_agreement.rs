@@ -0,0 +1,190 @@
+use anchor_lang::prelude::*;
+use crate::constants::*;
+use crate::errors::AapError;
+use crate::events::AgreementMigrated;
+use crate::migration::{
+    invoke_import, AgreementSnapshot, ImportCpiAccounts, IMPORT_AGREEMENT_DISCRIMINATOR,
+};
+use crate::state::{Agreement, AgreementAmendment, EscrowStream, MigrationTombstone, ProtocolConfig};
+
+/// Move an Agreement into aap-compressed, preserving status, counts and timestamps.
+/// The proposer's identity must already be migrated; parties follow via `migrate_party`.
+/// Agreements holding escrow can't be migrated (compressed accounts can't hold tokens).
+/// V2 has no termination, milestones, recurring payments, revenue shares or
+/// SAFEs, so an agreement that has any of them stays in V1; an ended stream
+/// is fine, and so is SOL sent to the revenue receiver of an agreement that
+/// has no revenue share. Negotiation records stay in V1 too and would come back to life if
+/// the agreement were decompressed, so the latest amendment
+/// (`amendment_version`, 0 for none) must not be pending, and Proposed
+/// agreements (which may have pending counter-proposals) and Rejected ones
+/// (which have a Decline) can't be migrated.
+#[derive(Accounts)]
+#[instruction(agreement_id: [u8; 16], amendment_version: u16)]
+pub struct MigrateAgreement<'info> {
+    #[account(mut)]
+    pub signer: Signer<'info>,
+
+    /// Proposer identity's tombstone — signer must be the authority that migrated it
+    #[account(
+        constraint = proposer_tombstone.kind == TOMBSTONE_KIND_AGENT @ AapError::InvalidTombstone,
+        constraint = proposer_tombstone.authority == signer.key() @ AapError::Unauthorized,
+    )]
+    pub proposer_tombstone: Account<'info, MigrationTombstone>,
+
+    #[account(
+        mut,
+        close = signer,
+        seeds = [b"agreement", agreement_id.as_ref()],
+        bump = agreement.bump,
+        constraint = agreement.proposer == proposer_tombstone.original @ AapError::InvalidTombstone,
+        constraint = agreement.escrow_total == 0 @ AapError::EscrowNotDistributed,
+        constraint = (
+            agreement.status != STATUS_PROPOSED &&
+            agreement.status != STATUS_REJECTED
+        ) @ AapError::PendingNegotiation,
+    )]
+    pub agreement: Account<'info, Agreement>,
+
+    #[account(
+        init,
+        payer = signer,
+        space = MigrationTombstone::LEN,
+        seeds = [b"tombstone", agreement.key().as_ref()],
+        bump,
+    )]
+    pub tombstone: Account<'info, MigrationTombstone>,
+
+    /// CHECK: PDA signer trusted by aap-compressed import instructions
+    #[account(seeds = [MIGRATION_SEED], bump)]
+    pub migration_authority: UncheckedAccount<'info>,
+
+    /// CHECK: Address-checked aap-compressed program
+    #[account(address = AAP_COMPRESSED_PROGRAM_ID)]
+    pub compressed_program: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+
+    /// CHECK: Termination PDA; must not exist
+    #[account(seeds = [b"termination", agreement.key().as_ref()], bump)]
+    pub termination: UncheckedAccount<'info>,
+
+    /// CHECK: MilestoneSchedule PDA; must not exist
+    #[account(seeds = [b"milestones", agreement.key().as_ref()], bump)]
+    pub milestones: UncheckedAccount<'info>,
+
+    /// CHECK: RecurringPayment PDA; must not exist
+    #[account(seeds = [b"recurring", agreement.key().as_ref()], bump)]
+    pub recurring: UncheckedAccount<'info>,
+
+    /// CHECK: RevenueShare PDA; must not exist
+    #[account(seeds = [b"revenue_share", agreement.key().as_ref()], bump)]
+    pub revenue_share: UncheckedAccount<'info>,
+
+    /// CHECK: Safe PDA; must not exist
+    #[account(seeds = [b"safe", agreement.key().as_ref()], bump)]
+    pub safe: UncheckedAccount<'info>,
+
+    /// CHECK: EscrowStream PDA; if the agreement had a stream it must have ended
+    #[account(seeds = [b"stream", agreement.key().as_ref()], bump)]
+    pub stream: UncheckedAccount<'info>,
+
+    /// CHECK: AgreementAmendment `amendment_version`; must exist unless it
+    /// is 0, and must not be pending
+    #[account(seeds = [b"amendment", agreement.key().as_ref(), &amendment_version.to_le_bytes()], bump)]
+    pub latest_amendment: UncheckedAccount<'info>,
+
+    /// CHECK: AgreementAmendment `amendment_version + 1`; must not exist
+    #[account(
+        seeds = [b"amendment", agreement.key().as_ref(), &amendment_version.wrapping_add(1).to_le_bytes()],
+        bump,
+    )]
+    pub next_amendment: UncheckedAccount<'info>,
+
+    #[account(
+        seeds = [CONFIG_SEED],
+        bump = config.bump,
+        constraint = !config.paused @ AapError::ProtocolPaused,
+    )]
+    pub config: Account<'info, ProtocolConfig>,
+}
+
+pub fn handler<'info>(
+    ctx: Context<'_, '_, '_, 'info, MigrateAgreement<'info>>,
+    agreement_id: [u8; 16],
+    amendment_version: u16,
+    light_args: Vec<u8>,
+) -> Result<()> {
+    let accounts = &ctx.accounts;
+    require!(
+        [&accounts.termination, &accounts.milestones, &accounts.recurring, &accounts.revenue_share, &accounts.safe]
+            .iter()
+            .all(|extension| extension.owner != &crate::ID),
+        AapError::AgreementHasExtensions
+    );
+    let stream = &accounts.stream;
+    if !stream.data_is_empty() {
+        let stream = EscrowStream::try_deserialize(&mut &stream.try_borrow_data()?[..])?;
+        require!(stream.status != STREAM_ACTIVE, AapError::EscrowNotDistributed);
+    }
+    // Versions are contiguous, so the one after the latest is empty
+    require!(accounts.next_amendment.data_is_empty(), AapError::PendingNegotiation);
+    let latest = &accounts.latest_amendment;
+    if amendment_version > 0 {
+        require!(!latest.data_is_empty(), AapError::PendingNegotiation);
+        let latest = AgreementAmendment::try_deserialize(&mut &latest.try_borrow_data()?[..])?;
+        require!(latest.status != AMENDMENT_PENDING, AapError::PendingNegotiation);
+    }
+
+    let agreement = &ctx.accounts.agreement;
+    let clock = Clock::get()?;
+
+    let mut key = [0u8; 32];
+    key[..16].copy_from_slice(&agreement_id);
+
+    let tombstone = &mut ctx.accounts.tombstone;
+    tombstone.original = agreement.key();
+    tombstone.kind = TOMBSTONE_KIND_AGREEMENT;
+    tombstone.key = key;
+    tombstone.authority = ctx.accounts.signer.key();
+    tombstone.migrated_at = clock.unix_timestamp;
+    tombstone.bump = ctx.bumps.tombstone;
+
+    let snapshot = AgreementSnapshot {
+        agreement_id,
+        agreement_type: agreement.agreement_type,
+        status: agreement.status,
+        visibility: agreement.visibility,
+        proposer_agent_key: Pubkey::from(ctx.accounts.proposer_tombstone.key),
+        terms_hash: agreement.terms_hash,
+        terms_uri: agreement.terms_uri,
+        num_parties: agreement.num_parties,
+        num_signed: agreement.num_signed,
+        parties_added: agreement.parties_added,
+        created_at: agreement.created_at,
+        expires_at: agreement.expires_at,
+    };
+
+    invoke_import(
+        IMPORT_AGREEMENT_DISCRIMINATOR,
+        &light_args,
+        &snapshot,
+        ImportCpiAccounts {
+            payer: &ctx.accounts.signer.to_account_info(),
+            migration_authority: &ctx.accounts.migration_authority.to_account_info(),
+            migration_authority_bump: ctx.bumps.migration_authority,
+            compressed_program: &ctx.accounts.compressed_program.to_account_info(),
+            remaining_accounts: ctx.remaining_accounts,
+        },
+    )?;
+
+    emit!(AgreementMigrated {
+        version: EVENT_VERSION,
+        agreement_id,
+        agreement_pda: agreement.key(),
+        actor: ctx.accounts.signer.key(),
+        status: agreement.status,
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,117 @@
+use anchor_lang::prelude::*;
+use crate::constants::*;
+use crate::errors::AapError;
+use crate::events::PartyMigrated;
+use crate::migration::{invoke_import, ImportCpiAccounts, PartySnapshot, IMPORT_PARTY_DISCRIMINATOR};
+use crate::state::{AgreementParty, MigrationTombstone, ProtocolConfig};
+
+/// Move an AgreementParty into aap-compressed, preserving role and signature.
+/// Both the agreement and the party's identity must already be migrated, so
+/// parties added with `add_party_direct` (no identity) can't be migrated.
+#[derive(Accounts)]
+#[instruction(agreement_id: [u8; 16])]
+pub struct MigrateParty<'info> {
+    #[account(mut)]
+    pub signer: Signer<'info>,
+
+    #[account(
+        constraint = agreement_tombstone.kind == TOMBSTONE_KIND_AGREEMENT @ AapError::InvalidTombstone,
+    )]
+    pub agreement_tombstone: Account<'info, MigrationTombstone>,
+
+    /// Signer must have migrated either the party's identity or the agreement
+    #[account(
+        constraint = identity_tombstone.kind == TOMBSTONE_KIND_AGENT @ AapError::InvalidTombstone,
+        constraint = (
+            identity_tombstone.authority == signer.key() ||
+            agreement_tombstone.authority == signer.key()
+        ) @ AapError::Unauthorized,
+    )]
+    pub identity_tombstone: Account<'info, MigrationTombstone>,
+
+    #[account(
+        mut,
+        close = signer,
+        seeds = [b"party", agreement_id.as_ref(), identity_tombstone.original.as_ref()],
+        bump = party.bump,
+        constraint = party.agreement == agreement_tombstone.original @ AapError::InvalidTombstone,
+        constraint = party.escrow_deposited == 0 @ AapError::EscrowNotDistributed,
+    )]
+    pub party: Account<'info, AgreementParty>,
+
+    #[account(
+        init,
+        payer = signer,
+        space = MigrationTombstone::LEN,
+        seeds = [b"tombstone", party.key().as_ref()],
+        bump,
+    )]
+    pub tombstone: Account<'info, MigrationTombstone>,
+
+    /// CHECK: PDA signer trusted by aap-compressed import instructions
+    #[account(seeds = [MIGRATION_SEED], bump)]
+    pub migration_authority: UncheckedAccount<'info>,
+
+    /// CHECK: Address-checked aap-compressed program
+    #[account(address = AAP_COMPRESSED_PROGRAM_ID)]
+    pub compressed_program: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+
+    #[account(
+        seeds = [CONFIG_SEED],
+        bump = config.bump,
+        constraint = !config.paused @ AapError::ProtocolPaused,
+    )]
+    pub config: Account<'info, ProtocolConfig>,
+}
+
+pub fn handler<'info>(
+    ctx: Context<'_, '_, '_, 'info, MigrateParty<'info>>,
+    agreement_id: [u8; 16],
+    light_args: Vec<u8>,
+) -> Result<()> {
+    let party = &ctx.accounts.party;
+    let clock = Clock::get()?;
+
+    let tombstone = &mut ctx.accounts.tombstone;
+    tombstone.original = party.key();
+    tombstone.kind = TOMBSTONE_KIND_PARTY;
+    tombstone.key = ctx.accounts.agreement_tombstone.key;
+    tombstone.authority = ctx.accounts.signer.key();
+    tombstone.migrated_at = clock.unix_timestamp;
+    tombstone.bump = ctx.bumps.tombstone;
+
+    let snapshot = PartySnapshot {
+        agreement_id,
+        agent_key: Pubkey::from(ctx.accounts.identity_tombstone.key),
+        role: party.role,
+        signed: party.signed,
+        signed_at: party.signed_at,
+    };
+
+    invoke_import(
+        IMPORT_PARTY_DISCRIMINATOR,
+        &light_args,
+        &snapshot,
+        ImportCpiAccounts {
+            payer: &ctx.accounts.signer.to_account_info(),
+            migration_authority: &ctx.accounts.migration_authority.to_account_info(),
+            migration_authority_bump: ctx.bumps.migration_authority,
+            compressed_program: &ctx.accounts.compressed_program.to_account_info(),
+            remaining_accounts: ctx.remaining_accounts,
+        },
+    )?;
+
+    emit!(PartyMigrated {
+        version: EVENT_VERSION,
+        agreement_id,
+        party: ctx.accounts.identity_tombstone.original,
+        actor: ctx.accounts.signer.key(),
+        role: party.role,
+        signed: party.signed,
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
@@ -12,7 +12,65 @@ pub mod deposit_to_vault;
 pub mod withdraw_from_vault;
 pub mod add_party_direct;
 pub mod sign_agreement_direct;
+pub mod migrate_agent;
+pub mod migrate_agreement;
+pub mod migrate_party;
+pub mod materialize_agreement;
+pub mod materialize_party;
+pub mod rotate_agent_key;
+pub mod propose_authority_transfer;
+pub mod accept_authority_transfer;
+pub mod cancel_authority_transfer;
+pub mod create_authority_set;
+pub mod propose_authority_action;
+pub mod approve_authority_action;
+pub mod execute_authority_action;
+pub mod cancel_authority_action;
+pub mod freeze_agent;
+pub mod unfreeze_agent;
+pub mod freeze_authority;
+pub mod unfreeze_authority;
+pub mod initialize_config;
+pub mod update_config;
+pub mod set_paused;
+pub mod propose_amendment;
+pub mod sign_amendment;
+pub mod sign_amendment_direct;
+pub mod withdraw_amendment;
+pub mod counter_propose;
+pub mod accept_counter_proposal;
+pub mod reject_counter_proposal;
+pub mod decline_agreement;
+pub mod decline_agreement_direct;
+pub mod remove_party;
+pub mod set_num_parties;
+pub mod fund_escrow;
+pub mod release_escrow;
+pub mod propose_termination;
+pub mod approve_termination;
+pub mod approve_termination_direct;
+pub mod cancel_termination;
+pub mod set_milestones;
+pub mod submit_milestone;
+pub mod approve_milestone;
+pub mod dispute_milestone;
+pub mod release_milestone;
+pub mod set_recurring_payment;
+pub mod crank_payment;
+pub mod cancel_recurring_payment;
+pub mod fund_stream;
+pub mod claim_vested;
+pub mod cancel_stream;
+pub mod settle_stream;
+pub mod set_revenue_share;
+pub mod distribute;
+pub mod distribute_tokens;
+pub mod set_safe;
+pub mod sign_safe;
+pub mod convert_safe;
+pub mod refund_safe;
 
+#[allow(ambiguous_glob_reexports)]
 pub use register_agent::*;
 pub use update_delegation::*;
 pub use register_sub_agent::*;
@@ -27,3 +85,60 @@ pub use deposit_to_vault::*;
 pub use withdraw_from_vault::*;
 pub use add_party_direct::*;
 pub use sign_agreement_direct::*;
+pub use migrate_agent::*;
+pub use migrate_agreement::*;
+pub use migrate_party::*;
+pub use materialize_agreement::*;
+pub use materialize_party::*;
+pub use rotate_agent_key::*;
+pub use propose_authority_transfer::*;
+pub use accept_authority_transfer::*;
+pub use cancel_authority_transfer::*;
+pub use create_authority_set::*;
+pub use propose_authority_action::*;
+pub use approve_authority_action::*;
+pub use execute_authority_action::*;
+pub use cancel_authority_action::*;
+pub use freeze_agent::*;
+pub use unfreeze_agent::*;
+pub use freeze_authority::*;
+pub use unfreeze_authority::*;
+pub use initialize_config::*;
+pub use update_config::*;
+pub use set_paused::*;
+pub use propose_amendment::*;
+pub use sign_amendment::*;
+pub use sign_amendment_direct::*;
+pub use withdraw_amendment::*;
+pub use counter_propose::*;
+pub use accept_counter_proposal::*;
+pub use reject_counter_proposal::*;
+pub use decline_agreement::*;
+pub use decline_agreement_direct::*;
+pub use remove_party::*;
+pub use set_num_parties::*;
+pub use fund_escrow::*;
+pub use release_escrow::*;
+pub use propose_termination::*;
+pub use approve_termination::*;
+pub use approve_termination_direct::*;
+pub use cancel_termination::*;
+pub use set_milestones::*;
+pub use submit_milestone::*;
+pub use approve_milestone::*;
+pub use dispute_milestone::*;
+pub use release_milestone::*;
+pub use set_recurring_payment::*;
+pub use crank_payment::*;
+pub use cancel_recurring_payment::*;
+pub use fund_stream::*;
+pub use claim_vested::*;
+pub use cancel_stream::*;
+pub use settle_stream::*;
+pub use set_revenue_share::*;
+pub use distribute::*;
+pub use distribute_tokens::*;
+pub use set_safe::*;
+pub use sign_safe::*;
+pub use convert_safe::*;
+pub use refund_safe::*;
@@ -1,8 +1,10 @@
 use anchor_lang::prelude::*;
+use anchor_lang::system_program;
+use aap_core::{AgreementType, Visibility};
 use crate::constants::*;
 use crate::errors::AapError;
 use crate::events::AgreementProposed;
-use crate::state::{AgentIdentity, Agreement, AgreementParty};
+use crate::state::{AgentIdentity, Agreement, AgreementParty, Freeze, ProtocolConfig};
 
 #[derive(Accounts)]
 #[instruction(agreement_id: [u8; 16])]
@@ -34,8 +36,32 @@ pub struct ProposeAgreement<'info> {
     pub proposer_party: Account<'info, AgreementParty>,
 
     pub system_program: Program<'info, System>,
+
+    /// CHECK: Freeze PDAs covering the proposer (own, parent's, authority's); must not exist
+    #[account(seeds = [b"freeze", proposer_identity.key().as_ref()], bump)]
+    pub agent_freeze: UncheckedAccount<'info>,
+
+    /// CHECK: See `agent_freeze`
+    #[account(seeds = [b"freeze", proposer_identity.parent.as_ref()], bump)]
+    pub parent_freeze: UncheckedAccount<'info>,
+
+    /// CHECK: See `agent_freeze`
+    #[account(seeds = [b"freeze", proposer_identity.authority.as_ref()], bump)]
+    pub authority_freeze: UncheckedAccount<'info>,
+
+    #[account(
+        seeds = [CONFIG_SEED],
+        bump = config.bump,
+        constraint = !config.paused @ AapError::ProtocolPaused,
+    )]
+    pub config: Account<'info, ProtocolConfig>,
+
+    /// CHECK: Fee recipient, pinned to `config.treasury`
+    #[account(mut, address = config.treasury @ AapError::InvalidTreasury)]
+    pub treasury: UncheckedAccount<'info>,
 }
 
+#[allow(clippy::too_many_arguments)]
 pub fn handler(
     ctx: Context<ProposeAgreement>,
     agreement_id: [u8; 16],
@@ -46,43 +72,41 @@ pub fn handler(
     num_parties: u8,
     expires_at: i64,
 ) -> Result<()> {
-    let identity = &ctx.accounts.proposer_identity;
-
-    // Validate delegation is not expired
-    if identity.scope.expires_at != 0 {
-        let clock = Clock::get()?;
-        require!(
-            identity.scope.expires_at > clock.unix_timestamp,
-            AapError::DelegationExpired
-        );
-    }
+    Freeze::require_unfrozen(&[
+        &ctx.accounts.agent_freeze,
+        &ctx.accounts.parent_freeze,
+        &ctx.accounts.authority_freeze,
+    ])?;
 
-    // Validate can_sign_agreements
-    require!(
-        identity.scope.can_sign_agreements,
-        AapError::CannotSignAgreements
-    );
-
-    // Validate agreement type
-    require!(
-        agreement_type <= MAX_AGREEMENT_TYPE,
-        AapError::InvalidAgreementType
-    );
-
-    // Validate visibility
-    require!(
-        visibility <= VISIBILITY_PRIVATE,
-        AapError::InvalidVisibility
-    );
-
-    // Validate num_parties
-    require!(
-        num_parties >= MIN_PARTIES && num_parties <= MAX_PARTIES,
-        AapError::InvalidPartyCount
-    );
+    let identity = &ctx.accounts.proposer_identity;
 
     let clock = Clock::get()?;
 
+    // Validate delegation is not expired and can_sign_agreements
+    aap_core::scope::require_can_sign(&identity.scope.into(), clock.unix_timestamp)
+        .map_err(AapError::from)?;
+
+    // Validate agreement type, visibility and num_parties
+    AgreementType::try_from(agreement_type).map_err(AapError::from)?;
+    Visibility::try_from(visibility).map_err(AapError::from)?;
+    aap_core::parties::validate_party_count(num_parties, ctx.accounts.config.max_parties)
+        .map_err(AapError::from)?;
+
+    // Protocol fee, paid by the proposer's signer
+    let fee = ctx.accounts.config.fee_lamports;
+    if fee > 0 {
+        system_program::transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                system_program::Transfer {
+                    from: ctx.accounts.proposer_signer.to_account_info(),
+                    to: ctx.accounts.treasury.to_account_info(),
+                },
+            ),
+            fee,
+        )?;
+    }
+
     // Initialize agreement
     let agreement = &mut ctx.accounts.agreement;
     agreement.agreement_id = agreement_id;
@@ -113,9 +137,19 @@ pub fn handler(
     party.bump = ctx.bumps.proposer_party;
 
     emit!(AgreementProposed {
+        version: EVENT_VERSION,
         agreement_id,
         proposer: ctx.accounts.proposer_identity.key(),
         agreement_pda: ctx.accounts.agreement.key(),
+        actor: ctx.accounts.proposer_signer.key(),
+        agreement_type,
+        visibility,
+        terms_hash,
+        terms_uri,
+        num_parties,
+        expires_at,
+        status: STATUS_PROPOSED,
+        timestamp: clock.unix_timestamp,
     });
 
     Ok(())
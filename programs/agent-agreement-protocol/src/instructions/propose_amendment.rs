@@ -0,0 +1,248 @@
+use anchor_lang::prelude::*;
+use crate::constants::*;
+use crate::errors::AapError;
+use crate::events::{AmendmentApplied, AmendmentProposed};
+use crate::state::{
+    AgentIdentity, Agreement, AgreementAmendment, AgreementParty, Freeze, ProtocolConfig, Safe,
+};
+
+/// The proposer replaces the agreement's terms with amendment `version`.
+///
+/// While the agreement is Proposed the new terms apply at once and the
+/// signatures collected so far are reset: pass every signed party PDA except
+/// the proposer's, and a funded SAFE investor's, as remaining accounts. While
+/// Active, the amendment stays pending until every party signs it
+/// (`sign_amendment`).
+#[derive(Accounts)]
+#[instruction(agreement_id: [u8; 16], version: u16)]
+pub struct ProposeAmendment<'info> {
+    #[account(mut)]
+    pub proposer_signer: Signer<'info>,
+
+    #[account(
+        constraint = proposer_identity.agent_key == proposer_signer.key() @ AapError::Unauthorized,
+    )]
+    pub proposer_identity: Account<'info, AgentIdentity>,
+
+    #[account(
+        mut,
+        seeds = [b"agreement", agreement_id.as_ref()],
+        bump = agreement.bump,
+        constraint = agreement.proposer == proposer_identity.key() @ AapError::Unauthorized,
+    )]
+    pub agreement: Account<'info, Agreement>,
+
+    #[account(
+        seeds = [b"party", agreement_id.as_ref(), proposer_identity.key().as_ref()],
+        bump = proposer_party.bump,
+    )]
+    pub proposer_party: Account<'info, AgreementParty>,
+
+    /// Amendment `version - 1`; omitted for the first amendment
+    #[account(
+        seeds = [b"amendment", agreement.key().as_ref(), &version.saturating_sub(1).to_le_bytes()],
+        bump = previous_amendment.bump,
+    )]
+    pub previous_amendment: Option<Account<'info, AgreementAmendment>>,
+
+    #[account(
+        init,
+        payer = proposer_signer,
+        space = AgreementAmendment::space(agreement.num_parties),
+        seeds = [b"amendment", agreement.key().as_ref(), &version.to_le_bytes()],
+        bump,
+    )]
+    pub amendment: Account<'info, AgreementAmendment>,
+
+    pub system_program: Program<'info, System>,
+
+    /// CHECK: Freeze PDAs covering the proposer (own, parent's, authority's); must not exist
+    #[account(seeds = [b"freeze", proposer_identity.key().as_ref()], bump)]
+    pub agent_freeze: UncheckedAccount<'info>,
+
+    /// CHECK: See `agent_freeze`
+    #[account(seeds = [b"freeze", proposer_identity.parent.as_ref()], bump)]
+    pub parent_freeze: UncheckedAccount<'info>,
+
+    /// CHECK: See `agent_freeze`
+    #[account(seeds = [b"freeze", proposer_identity.authority.as_ref()], bump)]
+    pub authority_freeze: UncheckedAccount<'info>,
+
+    /// CHECK: The agreement's Safe PDA, whose funded investor keeps its
+    /// signature; empty for other agreements
+    #[account(seeds = [b"safe", agreement.key().as_ref()], bump)]
+    pub safe: UncheckedAccount<'info>,
+
+    #[account(
+        seeds = [CONFIG_SEED],
+        bump = config.bump,
+        constraint = !config.paused @ AapError::ProtocolPaused,
+    )]
+    pub config: Account<'info, ProtocolConfig>,
+}
+
+pub fn handler<'info>(
+    ctx: Context<'_, '_, '_, 'info, ProposeAmendment<'info>>,
+    agreement_id: [u8; 16],
+    version: u16,
+    terms_hash: [u8; 32],
+    terms_uri: [u8; 64],
+    expires_at: i64,
+) -> Result<()> {
+    Freeze::require_unfrozen(&[
+        &ctx.accounts.agent_freeze,
+        &ctx.accounts.parent_freeze,
+        &ctx.accounts.authority_freeze,
+    ])?;
+
+    let clock = Clock::get()?;
+
+    aap_core::scope::require_can_sign(&ctx.accounts.proposer_identity.scope.into(), clock.unix_timestamp)
+        .map_err(AapError::from)?;
+
+    let proposer_party = ctx.accounts.proposer_party.key();
+    let agreement = &mut ctx.accounts.agreement;
+    aap_core::amendment::require_amendable(agreement.status).map_err(AapError::from)?;
+    aap_core::status::require_agreement_not_expired(agreement.expires_at, clock.unix_timestamp)
+        .map_err(AapError::from)?;
+    aap_core::amendment::require_next_version(
+        version,
+        ctx.accounts.previous_amendment.as_ref().map(|previous| previous.status),
+    )
+    .map_err(AapError::from)?;
+
+    amend(
+        Amend {
+            agreement_id,
+            agreement,
+            amendment: &mut ctx.accounts.amendment,
+            amendment_bump: ctx.bumps.amendment,
+            proposer_party,
+            actor: ctx.accounts.proposer_signer.key(),
+            safe: &ctx.accounts.safe,
+            parties: ctx.remaining_accounts,
+        },
+        version,
+        NewTerms { terms_hash, terms_uri, expires_at },
+        clock.unix_timestamp,
+    )
+}
+
+/// Terms an amendment puts in place.
+pub(crate) struct NewTerms {
+    pub terms_hash: [u8; 32],
+    pub terms_uri: [u8; 64],
+    pub expires_at: i64,
+}
+
+/// Accounts an amendment is recorded with. `parties` are the signed parties
+/// other than the proposer and a funded SAFE investor, reset when it applies
+/// at once; `safe` is the agreement's Safe PDA.
+pub(crate) struct Amend<'a, 'info> {
+    pub agreement_id: [u8; 16],
+    pub agreement: &'a mut Account<'info, Agreement>,
+    pub amendment: &'a mut Account<'info, AgreementAmendment>,
+    pub amendment_bump: u8,
+    pub proposer_party: Pubkey,
+    pub actor: Pubkey,
+    pub safe: &'a AccountInfo<'info>,
+    pub parties: &'a [AccountInfo<'info>],
+}
+
+/// Records amendment `version` with the proposer's approval. While the
+/// agreement is Proposed it applies at once and every other signature is
+/// reset; while Active it stays pending. Shared with `accept_counter_proposal`.
+pub(crate) fn amend(accounts: Amend, version: u16, terms: NewTerms, now: i64) -> Result<()> {
+    let Amend { agreement_id, agreement, amendment, amendment_bump, proposer_party, actor, safe, parties } = accounts;
+
+    amendment.agreement = agreement.key();
+    amendment.version = version;
+    amendment.proposer = agreement.proposer;
+    amendment.phase = agreement.status;
+    amendment.status = AMENDMENT_PENDING;
+    amendment.previous_terms_hash = agreement.terms_hash;
+    amendment.terms_hash = terms.terms_hash;
+    amendment.terms_uri = terms.terms_uri;
+    amendment.expires_at = terms.expires_at;
+    amendment.required = agreement.num_parties;
+    amendment.approvals = vec![proposer_party];
+    amendment.proposed_at = now;
+    amendment.resolved_at = 0;
+    amendment.bump = amendment_bump;
+
+    emit!(AmendmentProposed {
+        version: EVENT_VERSION,
+        agreement_id,
+        amendment: amendment.key(),
+        amendment_version: version,
+        proposer: agreement.proposer,
+        actor,
+        phase: amendment.phase,
+        previous_terms_hash: amendment.previous_terms_hash,
+        terms_hash: terms.terms_hash,
+        terms_uri: terms.terms_uri,
+        expires_at: terms.expires_at,
+        required: amendment.required,
+        timestamp: now,
+    });
+
+    if aap_core::amendment::requires_all_parties(amendment.phase) {
+        return Ok(());
+    }
+
+    // Proposed: everyone but the proposer signed the old terms and signs again,
+    // except a SAFE investor whose signature already moved its money to escrow
+    let kept = Safe::funded_investor(safe)?;
+    let signatures_reset = reset_signatures(&agreement.key(), &proposer_party, kept.as_ref(), parties)?;
+    let still_signed = 1 + kept.is_some() as u8;
+    require!(
+        signatures_reset == agreement.num_signed - still_signed,
+        AapError::InvalidPartyReset
+    );
+    agreement.num_signed = still_signed;
+    amendment.apply(agreement, now);
+
+    emit!(AmendmentApplied {
+        version: EVENT_VERSION,
+        agreement_id,
+        amendment: amendment.key(),
+        amendment_version: version,
+        actor,
+        previous_terms_hash: amendment.previous_terms_hash,
+        terms_hash: terms.terms_hash,
+        signatures_reset,
+        timestamp: now,
+    });
+
+    Ok(())
+}
+
+/// Unsign each passed party of `agreement`; returns how many were reset.
+/// A party passed twice is unsigned by then and rejected, as is `kept`.
+pub(crate) fn reset_signatures(
+    agreement: &Pubkey,
+    proposer_party: &Pubkey,
+    kept: Option<&Pubkey>,
+    parties: &[AccountInfo],
+) -> Result<u8> {
+    let mut reset: u8 = 0;
+    for info in parties {
+        require!(
+            info.owner == &crate::ID
+                && info.is_writable
+                && info.key != proposer_party
+                && Some(info.key) != kept,
+            AapError::InvalidPartyReset
+        );
+        let mut party = AgreementParty::try_deserialize(&mut &info.try_borrow_data()?[..])?;
+        require!(
+            party.agreement == *agreement && party.signed,
+            AapError::InvalidPartyReset
+        );
+        party.signed = false;
+        party.signed_at = 0;
+        party.try_serialize(&mut &mut info.try_borrow_mut_data()?[..])?;
+        reset = reset.saturating_add(1);
+    }
+    Ok(reset)
+}
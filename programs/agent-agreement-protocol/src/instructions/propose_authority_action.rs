@@ -0,0 +1,94 @@
+use anchor_lang::prelude::*;
+use crate::constants::{CONFIG_SEED, EVENT_VERSION, MAX_ACTION_ACCOUNTS, MAX_ACTION_DATA_LEN};
+use crate::errors::AapError;
+use crate::events::AuthorityActionProposed;
+use crate::state::{ActionAccount, AuthorityAction, AuthoritySet, ProtocolConfig};
+
+/// A member proposes a V1 instruction for the set to run as its signer PDA
+/// (e.g. `update_delegation`, `revoke_agent`, `withdraw_from_vault`). The
+/// proposer's approval is counted straight away.
+#[derive(Accounts)]
+#[instruction(accounts: Vec<ActionAccount>, data: Vec<u8>)]
+pub struct ProposeAuthorityAction<'info> {
+    #[account(mut)]
+    pub member: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"authority_set", authority_set.set_id.as_ref()],
+        bump = authority_set.bump,
+    )]
+    pub authority_set: Account<'info, AuthoritySet>,
+
+    #[account(
+        init,
+        payer = member,
+        space = AuthorityAction::space(accounts.len(), data.len()),
+        seeds = [
+            b"authority_action",
+            authority_set.key().as_ref(),
+            authority_set.action_count.to_le_bytes().as_ref(),
+        ],
+        bump,
+    )]
+    pub action: Account<'info, AuthorityAction>,
+
+    pub system_program: Program<'info, System>,
+
+    #[account(
+        seeds = [CONFIG_SEED],
+        bump = config.bump,
+        constraint = !config.paused @ AapError::ProtocolPaused,
+    )]
+    pub config: Account<'info, ProtocolConfig>,
+}
+
+pub fn handler(
+    ctx: Context<ProposeAuthorityAction>,
+    accounts: Vec<ActionAccount>,
+    data: Vec<u8>,
+) -> Result<()> {
+    let member_index = ctx
+        .accounts
+        .authority_set
+        .member_index(&ctx.accounts.member.key())
+        .ok_or(AapError::NotSetMember)?;
+
+    // An action that lists itself could re-enter `execute_authority_action`
+    let action_key = ctx.accounts.action.key();
+    require!(
+        accounts.len() <= MAX_ACTION_ACCOUNTS
+            && (8..=MAX_ACTION_DATA_LEN).contains(&data.len())
+            && accounts.iter().all(|a| a.pubkey != action_key),
+        AapError::InvalidAction
+    );
+
+    let now = Clock::get()?.unix_timestamp;
+    let set = &mut ctx.accounts.authority_set;
+    let index = set.action_count;
+    set.action_count = index.checked_add(1).unwrap();
+
+    let action = &mut ctx.accounts.action;
+    action.authority_set = set.key();
+    action.index = index;
+    action.proposer = ctx.accounts.member.key();
+    action.accounts = accounts;
+    action.data = data;
+    action.approvals = 0;
+    action.approve(member_index);
+    action.proposed_at = now;
+    action.bump = ctx.bumps.action;
+
+    emit!(AuthorityActionProposed {
+        version: EVENT_VERSION,
+        authority_set: action.authority_set,
+        action: action_key,
+        index,
+        actor: action.proposer,
+        discriminator: action.data[..8].try_into().unwrap(),
+        approvals: action.approval_count(),
+        timestamp: now,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,61 @@
+use anchor_lang::prelude::*;
+use crate::constants::{CONFIG_SEED, EVENT_VERSION};
+use crate::errors::AapError;
+use crate::events::AuthorityTransferProposed;
+use crate::state::{AgentIdentity, AuthorityTransfer, ProtocolConfig};
+
+/// First step of handing an identity to a new authority. Nothing changes
+/// until `new_authority` accepts; the current authority can cancel before that.
+#[derive(Accounts)]
+#[instruction(new_authority: Pubkey)]
+pub struct ProposeAuthorityTransfer<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        has_one = authority @ AapError::Unauthorized,
+    )]
+    pub agent_identity: Account<'info, AgentIdentity>,
+
+    /// One pending transfer per identity
+    #[account(
+        init,
+        payer = authority,
+        space = AuthorityTransfer::LEN,
+        seeds = [b"authority_transfer", agent_identity.key().as_ref()],
+        bump,
+    )]
+    pub transfer: Account<'info, AuthorityTransfer>,
+
+    pub system_program: Program<'info, System>,
+
+    #[account(
+        seeds = [CONFIG_SEED],
+        bump = config.bump,
+        constraint = !config.paused @ AapError::ProtocolPaused,
+    )]
+    pub config: Account<'info, ProtocolConfig>,
+}
+
+pub fn handler(ctx: Context<ProposeAuthorityTransfer>, new_authority: Pubkey) -> Result<()> {
+    require_keys_neq!(new_authority, ctx.accounts.authority.key(), AapError::InvalidNewAuthority);
+
+    let now = Clock::get()?.unix_timestamp;
+    let transfer = &mut ctx.accounts.transfer;
+    transfer.agent_identity = ctx.accounts.agent_identity.key();
+    transfer.authority = ctx.accounts.authority.key();
+    transfer.new_authority = new_authority;
+    transfer.proposed_at = now;
+    transfer.bump = ctx.bumps.transfer;
+
+    emit!(AuthorityTransferProposed {
+        version: EVENT_VERSION,
+        agent_identity: transfer.agent_identity,
+        authority: transfer.authority,
+        new_authority,
+        actor: ctx.accounts.authority.key(),
+        timestamp: now,
+    });
+
+    Ok(())
+}
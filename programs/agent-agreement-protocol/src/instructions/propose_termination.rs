@@ -0,0 +1,127 @@
+use anchor_lang::prelude::*;
+use std::collections::BTreeSet;
+use crate::constants::*;
+use crate::errors::AapError;
+use crate::events::TerminationProposed;
+use crate::state::{
+    AgentIdentity, Agreement, AgreementParty, Freeze, ProtocolConfig, SettlementShare, Termination,
+};
+
+/// A party proposes ending an Active agreement early and how its escrow is
+/// split. Pass every party PDA of the agreement as remaining accounts, in the
+/// same order as `amounts`; the amounts must add up to the escrow held. The
+/// proposer's approval is counted at once.
+#[derive(Accounts)]
+#[instruction(agreement_id: [u8; 16])]
+pub struct ProposeTermination<'info> {
+    #[account(mut)]
+    pub signer: Signer<'info>,
+
+    #[account(
+        constraint = signer_identity.agent_key == signer.key() @ AapError::Unauthorized,
+    )]
+    pub signer_identity: Account<'info, AgentIdentity>,
+
+    #[account(
+        seeds = [b"agreement", agreement_id.as_ref()],
+        bump = agreement.bump,
+        constraint = agreement.status == STATUS_ACTIVE @ AapError::InvalidStatus,
+    )]
+    pub agreement: Account<'info, Agreement>,
+
+    #[account(
+        seeds = [b"party", agreement_id.as_ref(), signer_identity.key().as_ref()],
+        bump = party.bump,
+    )]
+    pub party: Account<'info, AgreementParty>,
+
+    #[account(
+        init,
+        payer = signer,
+        space = Termination::space(agreement.num_parties),
+        seeds = [b"termination", agreement.key().as_ref()],
+        bump,
+    )]
+    pub termination: Account<'info, Termination>,
+
+    pub system_program: Program<'info, System>,
+
+    /// CHECK: Freeze PDAs covering the signer (own, parent's, authority's); must not exist
+    #[account(seeds = [b"freeze", signer_identity.key().as_ref()], bump)]
+    pub agent_freeze: UncheckedAccount<'info>,
+
+    /// CHECK: See `agent_freeze`
+    #[account(seeds = [b"freeze", signer_identity.parent.as_ref()], bump)]
+    pub parent_freeze: UncheckedAccount<'info>,
+
+    /// CHECK: See `agent_freeze`
+    #[account(seeds = [b"freeze", signer_identity.authority.as_ref()], bump)]
+    pub authority_freeze: UncheckedAccount<'info>,
+
+    #[account(
+        seeds = [CONFIG_SEED],
+        bump = config.bump,
+        constraint = !config.paused @ AapError::ProtocolPaused,
+    )]
+    pub config: Account<'info, ProtocolConfig>,
+}
+
+pub fn handler<'info>(
+    ctx: Context<'_, '_, '_, 'info, ProposeTermination<'info>>,
+    agreement_id: [u8; 16],
+    amounts: Vec<u64>,
+) -> Result<()> {
+    Freeze::require_unfrozen(&[
+        &ctx.accounts.agent_freeze,
+        &ctx.accounts.parent_freeze,
+        &ctx.accounts.authority_freeze,
+    ])?;
+
+    let clock = Clock::get()?;
+
+    aap_core::scope::require_can_sign(&ctx.accounts.signer_identity.scope.into(), clock.unix_timestamp)
+        .map_err(AapError::from)?;
+
+    let agreement = &ctx.accounts.agreement;
+    let agreement_key = agreement.key();
+    let parties = ctx.remaining_accounts;
+    require!(
+        amounts.len() == agreement.num_parties as usize && parties.len() == amounts.len(),
+        AapError::InvalidSettlement
+    );
+    aap_core::escrow::validate_settlement(&amounts, agreement.escrow_total).map_err(AapError::from)?;
+
+    let mut seen = BTreeSet::new();
+    let mut shares = Vec::with_capacity(amounts.len());
+    for (info, amount) in parties.iter().zip(amounts) {
+        require!(info.owner == &crate::ID && seen.insert(info.key()), AapError::InvalidSettlement);
+        let party = AgreementParty::try_deserialize(&mut &info.try_borrow_data()?[..])?;
+        require!(party.agreement == agreement_key, AapError::InvalidSettlement);
+        shares.push(SettlementShare { party: info.key(), amount, released: false });
+    }
+
+    let party = ctx.accounts.party.key();
+    let termination = &mut ctx.accounts.termination;
+    termination.agreement = agreement_key;
+    termination.proposer = party;
+    termination.status = TERMINATION_PENDING;
+    termination.required = agreement.num_parties;
+    termination.shares = shares;
+    termination.approvals = vec![party];
+    termination.proposed_at = clock.unix_timestamp;
+    termination.resolved_at = 0;
+    termination.bump = ctx.bumps.termination;
+
+    emit!(TerminationProposed {
+        version: EVENT_VERSION,
+        agreement_id,
+        termination: termination.key(),
+        party: ctx.accounts.signer_identity.key(),
+        actor: ctx.accounts.signer.key(),
+        shares: termination.shares.clone(),
+        required: termination.required,
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,105 @@
+use anchor_lang::prelude::*;
+use crate::constants::*;
+use crate::errors::AapError;
+use crate::events::SafeRefunded;
+use crate::instructions::release_escrow::require_escrow_recipient;
+use crate::state::{AgentIdentity, Agreement, AgreementParty, ProtocolConfig, Safe};
+
+/// Returns a funded SAFE's investment from escrow to the investor, e.g. when
+/// the company winds down without a priced round. The company (agent key or
+/// authority) can refund at any time; the investor only once the agreement
+/// has expired unconverted. The agreement stays Active for the parties to
+/// end as usual.
+#[derive(Accounts)]
+#[instruction(agreement_id: [u8; 16])]
+pub struct RefundSafe<'info> {
+    pub signer: Signer<'info>,
+
+    #[account(
+        constraint = (
+            party_identity.agent_key == signer.key() ||
+            party_identity.authority == signer.key()
+        ) @ AapError::Unauthorized,
+    )]
+    pub party_identity: Account<'info, AgentIdentity>,
+
+    #[account(
+        mut,
+        seeds = [b"agreement", agreement_id.as_ref()],
+        bump = agreement.bump,
+        constraint = agreement.status == STATUS_ACTIVE @ AapError::InvalidStatus,
+    )]
+    pub agreement: Account<'info, Agreement>,
+
+    #[account(
+        seeds = [b"party", agreement_id.as_ref(), party_identity.key().as_ref()],
+        bump = party.bump,
+        constraint = (
+            safe.company == party.key() ||
+            safe.investor == party.key()
+        ) @ AapError::Unauthorized,
+    )]
+    pub party: Account<'info, AgreementParty>,
+
+    #[account(
+        mut,
+        seeds = [b"safe", agreement.key().as_ref()],
+        bump = safe.bump,
+        constraint = safe.status == SAFE_FUNDED @ AapError::SafeNotFunded,
+    )]
+    pub safe: Account<'info, Safe>,
+
+    #[account(
+        mut,
+        constraint = investor_party.key() == safe.investor @ AapError::InvalidSafe,
+    )]
+    pub investor_party: Account<'info, AgreementParty>,
+
+    /// CHECK: The investor's AgentVault PDA; checked in the handler
+    #[account(mut)]
+    pub recipient: UncheckedAccount<'info>,
+
+    #[account(
+        seeds = [CONFIG_SEED],
+        bump = config.bump,
+    )]
+    pub config: Account<'info, ProtocolConfig>,
+}
+
+pub fn handler(ctx: Context<RefundSafe>, agreement_id: [u8; 16]) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+    let agreement = &mut ctx.accounts.agreement;
+    let safe = &mut ctx.accounts.safe;
+
+    if ctx.accounts.party.key() != safe.company {
+        // The investor waits for the agreement to expire
+        require!(
+            agreement.expires_at != 0 && agreement.expires_at <= now,
+            AapError::Unauthorized
+        );
+    }
+    require_escrow_recipient(&ctx.accounts.investor_party, &ctx.accounts.recipient)?;
+
+    let amount = safe.investment_amount;
+    let investor = &mut ctx.accounts.investor_party;
+    investor.escrow_deposited = investor.escrow_deposited.checked_sub(amount).ok_or(AapError::EscrowDistributionMismatch)?;
+    agreement.escrow_total = agreement.escrow_total.checked_sub(amount).ok_or(AapError::EscrowDistributionMismatch)?;
+    **agreement.to_account_info().try_borrow_mut_lamports()? -= amount;
+    **ctx.accounts.recipient.to_account_info().try_borrow_mut_lamports()? += amount;
+
+    safe.status = SAFE_REFUNDED;
+    safe.resolved_at = now;
+
+    emit!(SafeRefunded {
+        version: EVENT_VERSION,
+        agreement_id,
+        safe: safe.key(),
+        party: ctx.accounts.party_identity.key(),
+        recipient: ctx.accounts.recipient.key(),
+        actor: ctx.accounts.signer.key(),
+        amount,
+        timestamp: now,
+    });
+
+    Ok(())
+}
@@ -1,7 +1,8 @@
 use anchor_lang::prelude::*;
+use crate::constants::{CONFIG_SEED, EVENT_VERSION};
 use crate::errors::AapError;
 use crate::events::AgentRegistered;
-use crate::state::{AgentIdentity, DelegationScope};
+use crate::state::{AgentIdentity, DelegationScope, ProtocolConfig};
 
 #[derive(Accounts)]
 #[instruction(agent_key: Pubkey)]
@@ -19,6 +20,13 @@ pub struct RegisterAgent<'info> {
     pub agent_identity: Account<'info, AgentIdentity>,
 
     pub system_program: Program<'info, System>,
+
+    #[account(
+        seeds = [CONFIG_SEED],
+        bump = config.bump,
+        constraint = !config.paused @ AapError::ProtocolPaused,
+    )]
+    pub config: Account<'info, ProtocolConfig>,
 }
 
 pub fn handler(
@@ -30,13 +38,8 @@ pub fn handler(
     // Agent key can equal authority (human signer mode) or differ (delegated agent mode)
 
     // Validate scope expiration
-    if scope.expires_at != 0 {
-        let clock = Clock::get()?;
-        require!(
-            scope.expires_at > clock.unix_timestamp,
-            AapError::ScopeExpired
-        );
-    }
+    let now = Clock::get()?.unix_timestamp;
+    aap_core::scope::validate_new_scope(&scope.into(), now).map_err(AapError::from)?;
 
     let identity = &mut ctx.accounts.agent_identity;
     identity.authority = ctx.accounts.authority.key();
@@ -44,13 +47,19 @@ pub fn handler(
     identity.metadata_hash = metadata_hash;
     identity.scope = scope;
     identity.parent = Pubkey::default();
-    identity.created_at = Clock::get()?.unix_timestamp;
+    identity.created_at = now;
     identity.bump = ctx.bumps.agent_identity;
 
     emit!(AgentRegistered {
+        version: EVENT_VERSION,
         authority: ctx.accounts.authority.key(),
         agent_key,
         agent_identity: ctx.accounts.agent_identity.key(),
+        actor: ctx.accounts.authority.key(),
+        parent: Pubkey::default(),
+        metadata_hash,
+        scope,
+        timestamp: now,
     });
 
     Ok(())
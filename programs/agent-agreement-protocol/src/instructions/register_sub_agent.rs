@@ -1,7 +1,8 @@
 use anchor_lang::prelude::*;
+use crate::constants::{CONFIG_SEED, EVENT_VERSION};
 use crate::errors::AapError;
 use crate::events::AgentRegistered;
-use crate::state::{AgentIdentity, DelegationScope};
+use crate::state::{AgentIdentity, DelegationScope, ProtocolConfig};
 
 #[derive(Accounts)]
 #[instruction(sub_agent_key: Pubkey)]
@@ -24,6 +25,13 @@ pub struct RegisterSubAgent<'info> {
     pub sub_agent_identity: Account<'info, AgentIdentity>,
 
     pub system_program: Program<'info, System>,
+
+    #[account(
+        seeds = [CONFIG_SEED],
+        bump = config.bump,
+        constraint = !config.paused @ AapError::ProtocolPaused,
+    )]
+    pub config: Account<'info, ProtocolConfig>,
 }
 
 pub fn handler(
@@ -34,53 +42,15 @@ pub fn handler(
 ) -> Result<()> {
     let parent = &ctx.accounts.parent_identity;
 
-    // Parent must not be a sub-agent itself (max 2 levels)
-    require!(
-        parent.parent == Pubkey::default(),
-        AapError::MaxDelegationDepth
-    );
-
-    // Parent delegation must not be expired
-    if parent.scope.expires_at != 0 {
-        let clock = Clock::get()?;
-        require!(
-            parent.scope.expires_at > clock.unix_timestamp,
-            AapError::DelegationExpired
-        );
-    }
-
-    // Sub-agent scope cannot exceed parent scope
-    if parent.scope.can_sign_agreements {
-        // ok, sub can have can_sign_agreements = true or false
-    } else {
-        require!(
-            !scope.can_sign_agreements,
-            AapError::SubAgentScopeExceedsParent
-        );
-    }
-
-    if parent.scope.can_commit_funds {
-        if parent.scope.max_commit_lamports > 0 {
-            require!(
-                scope.max_commit_lamports <= parent.scope.max_commit_lamports,
-                AapError::SubAgentScopeExceedsParent
-            );
-        }
-    } else {
-        require!(
-            !scope.can_commit_funds,
-            AapError::SubAgentScopeExceedsParent
-        );
-    }
-
-    // Validate scope expiration
-    if scope.expires_at != 0 {
-        let clock = Clock::get()?;
-        require!(
-            scope.expires_at > clock.unix_timestamp,
-            AapError::ScopeExpired
-        );
-    }
+    // Max 2 levels, parent not expired, sub-agent scope within parent, scope not expired
+    let now = Clock::get()?.unix_timestamp;
+    aap_core::scope::validate_sub_agent(
+        &parent.scope.into(),
+        parent.parent != Pubkey::default(),
+        &scope.into(),
+        now,
+    )
+    .map_err(AapError::from)?;
 
     let identity = &mut ctx.accounts.sub_agent_identity;
     identity.authority = parent.authority;
@@ -88,13 +58,19 @@ pub fn handler(
     identity.metadata_hash = metadata_hash;
     identity.scope = scope;
     identity.parent = ctx.accounts.parent_identity.key();
-    identity.created_at = Clock::get()?.unix_timestamp;
+    identity.created_at = now;
     identity.bump = ctx.bumps.sub_agent_identity;
 
     emit!(AgentRegistered {
+        version: EVENT_VERSION,
         authority: parent.authority,
         agent_key: sub_agent_key,
         agent_identity: ctx.accounts.sub_agent_identity.key(),
+        actor: ctx.accounts.parent_agent_signer.key(),
+        parent: ctx.accounts.parent_identity.key(),
+        metadata_hash,
+        scope,
+        timestamp: now,
     });
 
     Ok(())
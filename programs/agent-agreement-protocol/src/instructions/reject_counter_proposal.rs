@@ -0,0 +1,61 @@
+use anchor_lang::prelude::*;
+use crate::constants::*;
+use crate::errors::AapError;
+use crate::events::CounterProposalRejected;
+use crate::state::{AgentIdentity, Agreement, CounterProposal, ProtocolConfig};
+
+/// The proposer (agent key or authority) turns a counter-proposal down. The
+/// party can then sign the current terms or counter again.
+#[derive(Accounts)]
+#[instruction(agreement_id: [u8; 16])]
+pub struct RejectCounterProposal<'info> {
+    pub signer: Signer<'info>,
+
+    #[account(
+        constraint = (
+            proposer_identity.agent_key == signer.key() ||
+            proposer_identity.authority == signer.key()
+        ) @ AapError::Unauthorized,
+    )]
+    pub proposer_identity: Account<'info, AgentIdentity>,
+
+    #[account(
+        seeds = [b"agreement", agreement_id.as_ref()],
+        bump = agreement.bump,
+        constraint = agreement.proposer == proposer_identity.key() @ AapError::Unauthorized,
+    )]
+    pub agreement: Account<'info, Agreement>,
+
+    #[account(
+        mut,
+        constraint = counter.agreement == agreement.key() @ AapError::InvalidCounterProposal,
+        constraint = counter.status == COUNTER_PENDING @ AapError::CounterProposalNotPending,
+    )]
+    pub counter: Account<'info, CounterProposal>,
+
+    #[account(
+        seeds = [CONFIG_SEED],
+        bump = config.bump,
+        constraint = !config.paused @ AapError::ProtocolPaused,
+    )]
+    pub config: Account<'info, ProtocolConfig>,
+}
+
+pub fn handler(ctx: Context<RejectCounterProposal>, agreement_id: [u8; 16]) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+    let counter = &mut ctx.accounts.counter;
+    counter.status = COUNTER_REJECTED;
+    counter.resolved_at = now;
+
+    emit!(CounterProposalRejected {
+        version: EVENT_VERSION,
+        agreement_id,
+        counter: counter.key(),
+        round: counter.round,
+        party: counter.agent_identity,
+        actor: ctx.accounts.signer.key(),
+        timestamp: now,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,101 @@
+use anchor_lang::prelude::*;
+use crate::constants::*;
+use crate::errors::AapError;
+use crate::events::EscrowReleased;
+use crate::state::{Agreement, AgreementParty, ProtocolConfig, Termination};
+
+/// Pays a party its escrow once the agreement has ended: its own deposit back
+/// (Fulfilled, Cancelled, Rejected) or its settlement share (Terminated, which
+/// needs the `termination` account). Permissionless, since the recipient is
+/// fixed: the party's AgentVault, or the wallet of a direct party.
+#[derive(Accounts)]
+#[instruction(agreement_id: [u8; 16])]
+pub struct ReleaseEscrow<'info> {
+    pub signer: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"agreement", agreement_id.as_ref()],
+        bump = agreement.bump,
+        constraint = aap_core::escrow::is_releasable(agreement.status) @ AapError::InvalidStatus,
+    )]
+    pub agreement: Account<'info, Agreement>,
+
+    #[account(
+        mut,
+        seeds = [b"party", agreement_id.as_ref(), party.agent_identity.as_ref()],
+        bump = party.bump,
+    )]
+    pub party: Account<'info, AgreementParty>,
+
+    #[account(
+        mut,
+        seeds = [b"termination", agreement.key().as_ref()],
+        bump = termination.bump,
+        constraint = termination.status == TERMINATION_APPLIED @ AapError::InvalidStatus,
+    )]
+    pub termination: Option<Account<'info, Termination>>,
+
+    /// CHECK: The party's AgentVault PDA, or the party key itself for direct
+    /// parties; checked in the handler
+    #[account(mut)]
+    pub recipient: UncheckedAccount<'info>,
+
+    #[account(
+        seeds = [CONFIG_SEED],
+        bump = config.bump,
+    )]
+    pub config: Account<'info, ProtocolConfig>,
+}
+
+pub fn handler(ctx: Context<ReleaseEscrow>, agreement_id: [u8; 16]) -> Result<()> {
+    let party_key = ctx.accounts.party.key();
+    let party = &mut ctx.accounts.party;
+    let recipient = &ctx.accounts.recipient;
+
+    require_escrow_recipient(party, recipient)?;
+
+    let agreement = &mut ctx.accounts.agreement;
+    let amount = if agreement.status == STATUS_TERMINATED {
+        let termination = ctx.accounts.termination.as_mut().ok_or(AapError::InvalidSettlement)?;
+        let share = termination
+            .shares
+            .iter_mut()
+            .find(|share| share.party == party_key && !share.released)
+            .ok_or(AapError::InvalidSettlement)?;
+        share.released = true;
+        share.amount
+    } else {
+        party.escrow_deposited
+    };
+    require!(amount > 0, AapError::InvalidAmount);
+
+    **agreement.to_account_info().try_borrow_mut_lamports()? -= amount;
+    **recipient.to_account_info().try_borrow_mut_lamports()? += amount;
+
+    party.escrow_deposited = 0;
+    agreement.escrow_total = agreement.escrow_total.checked_sub(amount).ok_or(AapError::EscrowDistributionMismatch)?;
+
+    emit!(EscrowReleased {
+        version: EVENT_VERSION,
+        agreement_id,
+        party: party.agent_identity,
+        recipient: recipient.key(),
+        actor: ctx.accounts.signer.key(),
+        amount,
+        escrow_total: agreement.escrow_total,
+        status: agreement.status,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+/// Identity parties are paid into their vault; a direct party's key is a wallet.
+pub(crate) fn require_escrow_recipient(party: &AgreementParty, recipient: &AccountInfo) -> Result<()> {
+    let (vault, _) = Pubkey::find_program_address(&[b"vault", party.agent_identity.as_ref()], &crate::ID);
+    let is_vault = recipient.key() == vault && recipient.owner == &crate::ID;
+    let is_wallet = recipient.key() == party.agent_identity && recipient.owner == &System::id();
+    require!(is_vault || is_wallet, AapError::InvalidEscrowRecipient);
+    Ok(())
+}
@@ -0,0 +1,112 @@
+use anchor_lang::prelude::*;
+use crate::constants::*;
+use crate::errors::AapError;
+use crate::events::{AgreementFulfilled, MilestoneReleased};
+use crate::instructions::release_escrow::require_escrow_recipient;
+use crate::state::{Agreement, AgreementParty, MilestoneSchedule, ProtocolConfig};
+
+/// Pays an approved milestone from the payer's escrow to the payee's vault (or
+/// wallet, for a direct payee). Permissionless. Releasing the last milestone
+/// fulfills the agreement.
+#[derive(Accounts)]
+#[instruction(agreement_id: [u8; 16])]
+pub struct ReleaseMilestone<'info> {
+    pub signer: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"agreement", agreement_id.as_ref()],
+        bump = agreement.bump,
+        constraint = agreement.status == STATUS_ACTIVE @ AapError::InvalidStatus,
+    )]
+    pub agreement: Account<'info, Agreement>,
+
+    #[account(
+        mut,
+        seeds = [b"milestones", agreement.key().as_ref()],
+        bump = schedule.bump,
+    )]
+    pub schedule: Account<'info, MilestoneSchedule>,
+
+    #[account(
+        mut,
+        seeds = [b"party", agreement_id.as_ref(), payer_party.agent_identity.as_ref()],
+        bump = payer_party.bump,
+    )]
+    pub payer_party: Account<'info, AgreementParty>,
+
+    #[account(
+        seeds = [b"party", agreement_id.as_ref(), payee_party.agent_identity.as_ref()],
+        bump = payee_party.bump,
+    )]
+    pub payee_party: Account<'info, AgreementParty>,
+
+    /// CHECK: The payee's AgentVault PDA, or the payee key itself for direct
+    /// parties; checked in the handler
+    #[account(mut)]
+    pub recipient: UncheckedAccount<'info>,
+
+    #[account(
+        seeds = [CONFIG_SEED],
+        bump = config.bump,
+        constraint = !config.paused @ AapError::ProtocolPaused,
+    )]
+    pub config: Account<'info, ProtocolConfig>,
+}
+
+pub fn handler(ctx: Context<ReleaseMilestone>, agreement_id: [u8; 16], index: u8) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+    let payer_key = ctx.accounts.payer_party.key();
+    let payee_key = ctx.accounts.payee_party.key();
+    require_escrow_recipient(&ctx.accounts.payee_party, &ctx.accounts.recipient)?;
+
+    let schedule = &mut ctx.accounts.schedule;
+    let milestone = schedule.milestones.get_mut(index as usize).ok_or(AapError::InvalidMilestone)?;
+    require!(
+        milestone.payer == payer_key && milestone.payee == payee_key,
+        AapError::InvalidMilestone
+    );
+    aap_core::milestone::require_releasable(milestone.status).map_err(AapError::from)?;
+
+    let amount = milestone.amount;
+    let payer_party = &mut ctx.accounts.payer_party;
+    require!(payer_party.escrow_deposited >= amount, AapError::MilestoneUnfunded);
+    milestone.status = MILESTONE_RELEASED;
+    schedule.released += 1;
+
+    let agreement = &mut ctx.accounts.agreement;
+    **agreement.to_account_info().try_borrow_mut_lamports()? -= amount;
+    **ctx.accounts.recipient.to_account_info().try_borrow_mut_lamports()? += amount;
+    payer_party.escrow_deposited -= amount;
+    agreement.escrow_total = agreement.escrow_total.checked_sub(amount).ok_or(AapError::EscrowDistributionMismatch)?;
+
+    emit!(MilestoneReleased {
+        version: EVENT_VERSION,
+        agreement_id,
+        index,
+        payer: payer_party.agent_identity,
+        payee: ctx.accounts.payee_party.agent_identity,
+        recipient: ctx.accounts.recipient.key(),
+        actor: ctx.accounts.signer.key(),
+        amount,
+        released: schedule.released,
+        escrow_total: agreement.escrow_total,
+        timestamp: now,
+    });
+
+    if schedule.is_complete() {
+        let previous_status = agreement.status;
+        aap_core::status::require_transition(previous_status, STATUS_FULFILLED).map_err(AapError::from)?;
+        agreement.status = STATUS_FULFILLED;
+        emit!(AgreementFulfilled {
+            version: EVENT_VERSION,
+            agreement_id,
+            actor: ctx.accounts.signer.key(),
+            previous_status,
+            status: STATUS_FULFILLED,
+            timestamp: now,
+        });
+    }
+
+    Ok(())
+}
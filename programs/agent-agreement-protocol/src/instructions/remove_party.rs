@@ -0,0 +1,126 @@
+use anchor_lang::prelude::*;
+use crate::constants::*;
+use crate::errors::AapError;
+use crate::events::PartyRemoved;
+use crate::state::{
+    AgentIdentity, Agreement, AgreementParty, Freeze, MilestoneSchedule, ProtocolConfig, RecurringPayment,
+    RevenueShare, Safe,
+};
+
+/// The proposer takes an unsigned party off a Proposed agreement, freeing its
+/// slot for `add_party`. Works for identity and direct parties alike, unless
+/// the agreement's milestones, recurring payment, revenue share or SAFE name
+/// the party, since those terms are fixed once set.
+#[derive(Accounts)]
+#[instruction(agreement_id: [u8; 16])]
+pub struct RemoveParty<'info> {
+    #[account(mut)]
+    pub proposer_signer: Signer<'info>,
+
+    #[account(
+        constraint = proposer_identity.agent_key == proposer_signer.key() @ AapError::Unauthorized,
+    )]
+    pub proposer_identity: Account<'info, AgentIdentity>,
+
+    #[account(
+        mut,
+        seeds = [b"agreement", agreement_id.as_ref()],
+        bump = agreement.bump,
+        constraint = agreement.proposer == proposer_identity.key() @ AapError::Unauthorized,
+        constraint = agreement.status == STATUS_PROPOSED @ AapError::InvalidStatus,
+    )]
+    pub agreement: Account<'info, Agreement>,
+
+    /// Rent goes back to the proposer, who paid it in `add_party`
+    #[account(
+        mut,
+        close = proposer_signer,
+        seeds = [b"party", agreement_id.as_ref(), party.agent_identity.as_ref()],
+        bump = party.bump,
+        constraint = party.role != ROLE_PROPOSER @ AapError::InvalidRole,
+        constraint = !party.signed @ AapError::AlreadySigned,
+        constraint = party.escrow_deposited == 0 @ AapError::EscrowNotDistributed,
+    )]
+    pub party: Account<'info, AgreementParty>,
+
+    /// CHECK: Freeze PDAs covering the proposer (own, parent's, authority's); must not exist
+    #[account(seeds = [b"freeze", proposer_identity.key().as_ref()], bump)]
+    pub agent_freeze: UncheckedAccount<'info>,
+
+    /// CHECK: See `agent_freeze`
+    #[account(seeds = [b"freeze", proposer_identity.parent.as_ref()], bump)]
+    pub parent_freeze: UncheckedAccount<'info>,
+
+    /// CHECK: See `agent_freeze`
+    #[account(seeds = [b"freeze", proposer_identity.authority.as_ref()], bump)]
+    pub authority_freeze: UncheckedAccount<'info>,
+
+    /// CHECK: The agreement's MilestoneSchedule, RecurringPayment, RevenueShare
+    /// and Safe PDAs; empty unless set, and then must not name the party
+    #[account(seeds = [b"milestones", agreement.key().as_ref()], bump)]
+    pub milestones: UncheckedAccount<'info>,
+
+    /// CHECK: See `milestones`
+    #[account(seeds = [b"recurring", agreement.key().as_ref()], bump)]
+    pub recurring: UncheckedAccount<'info>,
+
+    /// CHECK: See `milestones`
+    #[account(seeds = [b"revenue_share", agreement.key().as_ref()], bump)]
+    pub revenue_share: UncheckedAccount<'info>,
+
+    /// CHECK: See `milestones`
+    #[account(seeds = [b"safe", agreement.key().as_ref()], bump)]
+    pub safe: UncheckedAccount<'info>,
+
+    #[account(
+        seeds = [CONFIG_SEED],
+        bump = config.bump,
+        constraint = !config.paused @ AapError::ProtocolPaused,
+    )]
+    pub config: Account<'info, ProtocolConfig>,
+}
+
+pub fn handler(ctx: Context<RemoveParty>, agreement_id: [u8; 16]) -> Result<()> {
+    Freeze::require_unfrozen(&[
+        &ctx.accounts.agent_freeze,
+        &ctx.accounts.parent_freeze,
+        &ctx.accounts.authority_freeze,
+    ])?;
+
+    let accounts = &ctx.accounts;
+    let party = accounts.party.key();
+    require_not_named::<MilestoneSchedule>(&accounts.milestones, |schedule| {
+        schedule.milestones.iter().any(|milestone| milestone.payer == party || milestone.payee == party)
+    })?;
+    require_not_named::<RecurringPayment>(&accounts.recurring, |recurring| {
+        recurring.payer == party || recurring.payee == party
+    })?;
+    require_not_named::<RevenueShare>(&accounts.revenue_share, |share| {
+        share.splits.iter().any(|split| split.party == party)
+    })?;
+    require_not_named::<Safe>(&accounts.safe, |safe| safe.investor == party || safe.company == party)?;
+
+    let agreement = &mut ctx.accounts.agreement;
+    agreement.parties_added -= 1;
+
+    emit!(PartyRemoved {
+        version: EVENT_VERSION,
+        agreement_id,
+        party: ctx.accounts.party.agent_identity,
+        role: ctx.accounts.party.role,
+        actor: ctx.accounts.proposer_signer.key(),
+        parties_added: agreement.parties_added,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+/// Fails if the extension at `info` exists and `names` says it refers to the party.
+fn require_not_named<T: AccountDeserialize>(info: &AccountInfo, names: impl Fn(&T) -> bool) -> Result<()> {
+    if !info.data_is_empty() {
+        let terms = T::try_deserialize(&mut &info.try_borrow_data()?[..])?;
+        require!(!names(&terms), AapError::PartyInPaymentTerms);
+    }
+    Ok(())
+}
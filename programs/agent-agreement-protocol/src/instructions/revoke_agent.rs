@@ -1,7 +1,8 @@
 use anchor_lang::prelude::*;
+use crate::constants::{CONFIG_SEED, EVENT_VERSION};
 use crate::errors::AapError;
 use crate::events::AgentRevoked;
-use crate::state::AgentIdentity;
+use crate::state::{AgentIdentity, AgentKeyLookup, ProtocolConfig};
 
 #[derive(Accounts)]
 pub struct RevokeAgent<'info> {
@@ -14,14 +15,39 @@ pub struct RevokeAgent<'info> {
         has_one = authority @ AapError::Unauthorized,
     )]
     pub agent_identity: Account<'info, AgentIdentity>,
+
+    /// Lookup for a rotated identity's current key, closed with it
+    #[account(
+        mut,
+        close = authority,
+        seeds = [b"agent", agent_identity.agent_key.as_ref()],
+        bump = current_key_lookup.bump,
+        constraint = current_key_lookup.identity == agent_identity.key() @ AapError::Unauthorized,
+    )]
+    pub current_key_lookup: Option<Account<'info, AgentKeyLookup>>,
+
+    #[account(
+        seeds = [CONFIG_SEED],
+        bump = config.bump,
+        constraint = !config.paused @ AapError::ProtocolPaused,
+    )]
+    pub config: Account<'info, ProtocolConfig>,
 }
 
 pub fn handler(ctx: Context<RevokeAgent>) -> Result<()> {
     let agent_key = ctx.accounts.agent_identity.agent_key;
 
+    if ctx.accounts.agent_identity.is_rotated(&ctx.accounts.agent_identity.key()) {
+        require!(ctx.accounts.current_key_lookup.is_some(), AapError::KeyLookupRequired);
+    }
+
     emit!(AgentRevoked {
+        version: EVENT_VERSION,
         authority: ctx.accounts.authority.key(),
         agent_key,
+        agent_identity: ctx.accounts.agent_identity.key(),
+        actor: ctx.accounts.authority.key(),
+        timestamp: Clock::get()?.unix_timestamp,
     });
 
     Ok(())
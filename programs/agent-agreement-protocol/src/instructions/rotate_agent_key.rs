@@ -0,0 +1,96 @@
+use anchor_lang::prelude::*;
+use crate::constants::{CONFIG_SEED, EVENT_VERSION};
+use crate::errors::AapError;
+use crate::events::AgentKeyRotated;
+use crate::state::{AgentIdentity, AgentKeyLookup, ProtocolConfig};
+
+/// Replace an identity's agent key in place. The identity keeps its address
+/// (derived from the key it was registered with), so agreements, parties,
+/// vaults and sub-agents that reference it stay valid. The new key gets an
+/// AgentKeyLookup at `["agent", new_agent_key]` pointing back at the identity,
+/// except the key it was registered with: the identity itself is at that
+/// address, so rotating back to it needs no lookup.
+#[derive(Accounts)]
+#[instruction(new_agent_key: Pubkey)]
+pub struct RotateAgentKey<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        has_one = authority @ AapError::Unauthorized,
+    )]
+    pub agent_identity: Account<'info, AgentIdentity>,
+
+    /// Lookup for the key being replaced — required once the identity has been rotated
+    #[account(
+        mut,
+        close = authority,
+        seeds = [b"agent", agent_identity.agent_key.as_ref()],
+        bump = current_key_lookup.bump,
+        constraint = current_key_lookup.identity == agent_identity.key() @ AapError::Unauthorized,
+    )]
+    pub current_key_lookup: Option<Account<'info, AgentKeyLookup>>,
+
+    /// Fails if the new key already has an identity or lookup; omitted when
+    /// rotating back to the registered key
+    #[account(
+        init,
+        payer = authority,
+        space = AgentKeyLookup::LEN,
+        seeds = [b"agent", new_agent_key.as_ref()],
+        bump,
+    )]
+    pub new_key_lookup: Option<Account<'info, AgentKeyLookup>>,
+
+    /// Optional proof that the new key is held
+    pub new_agent_signer: Option<Signer<'info>>,
+
+    pub system_program: Program<'info, System>,
+
+    #[account(
+        seeds = [CONFIG_SEED],
+        bump = config.bump,
+        constraint = !config.paused @ AapError::ProtocolPaused,
+    )]
+    pub config: Account<'info, ProtocolConfig>,
+}
+
+pub fn handler(ctx: Context<RotateAgentKey>, new_agent_key: Pubkey) -> Result<()> {
+    let identity_key = ctx.accounts.agent_identity.key();
+    let previous_agent_key = ctx.accounts.agent_identity.agent_key;
+
+    // A stale lookup would keep the old key from ever registering again
+    if ctx.accounts.agent_identity.is_rotated(&identity_key) {
+        require!(ctx.accounts.current_key_lookup.is_some(), AapError::KeyLookupRequired);
+    }
+
+    let co_signed = match &ctx.accounts.new_agent_signer {
+        Some(signer) => {
+            require_keys_eq!(signer.key(), new_agent_key, AapError::Unauthorized);
+            true
+        }
+        None => false,
+    };
+
+    ctx.accounts.agent_identity.agent_key = new_agent_key;
+
+    if ctx.accounts.agent_identity.is_rotated(&identity_key) {
+        let lookup = ctx.accounts.new_key_lookup.as_mut().ok_or(AapError::NewKeyLookupRequired)?;
+        lookup.identity = identity_key;
+        lookup.bump = ctx.bumps.new_key_lookup.ok_or(AapError::NewKeyLookupRequired)?;
+    }
+
+    emit!(AgentKeyRotated {
+        version: EVENT_VERSION,
+        authority: ctx.accounts.authority.key(),
+        agent_identity: identity_key,
+        previous_agent_key,
+        agent_key: new_agent_key,
+        actor: ctx.accounts.authority.key(),
+        co_signed,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
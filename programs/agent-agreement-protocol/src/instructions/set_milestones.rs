@@ -0,0 +1,124 @@
+use anchor_lang::prelude::*;
+use std::collections::BTreeSet;
+use crate::constants::*;
+use crate::errors::AapError;
+use crate::events::MilestonesSet;
+use crate::state::{
+    AgentIdentity, Agreement, AgreementParty, Freeze, Milestone, MilestoneSchedule, MilestoneTerms,
+    ProtocolConfig,
+};
+
+/// The proposer attaches a milestone schedule to a Proposed agreement before
+/// anyone else signs. Payer and payee are AgreementParty PDAs of the
+/// agreement, each passed once as a remaining account; each release is paid
+/// from the payer's escrow.
+#[derive(Accounts)]
+#[instruction(agreement_id: [u8; 16], milestones: Vec<MilestoneTerms>)]
+pub struct SetMilestones<'info> {
+    #[account(mut)]
+    pub proposer_signer: Signer<'info>,
+
+    #[account(
+        constraint = proposer_identity.agent_key == proposer_signer.key() @ AapError::Unauthorized,
+    )]
+    pub proposer_identity: Account<'info, AgentIdentity>,
+
+    #[account(
+        seeds = [b"agreement", agreement_id.as_ref()],
+        bump = agreement.bump,
+        constraint = agreement.proposer == proposer_identity.key() @ AapError::Unauthorized,
+        constraint = agreement.status == STATUS_PROPOSED @ AapError::InvalidStatus,
+        constraint = agreement.num_signed == 1 @ AapError::MilestonesLocked,
+    )]
+    pub agreement: Account<'info, Agreement>,
+
+    #[account(
+        init,
+        payer = proposer_signer,
+        space = MilestoneSchedule::space(milestones.len()),
+        seeds = [b"milestones", agreement.key().as_ref()],
+        bump,
+    )]
+    pub schedule: Account<'info, MilestoneSchedule>,
+
+    pub system_program: Program<'info, System>,
+
+    /// CHECK: Freeze PDAs covering the proposer (own, parent's, authority's); must not exist
+    #[account(seeds = [b"freeze", proposer_identity.key().as_ref()], bump)]
+    pub agent_freeze: UncheckedAccount<'info>,
+
+    /// CHECK: See `agent_freeze`
+    #[account(seeds = [b"freeze", proposer_identity.parent.as_ref()], bump)]
+    pub parent_freeze: UncheckedAccount<'info>,
+
+    /// CHECK: See `agent_freeze`
+    #[account(seeds = [b"freeze", proposer_identity.authority.as_ref()], bump)]
+    pub authority_freeze: UncheckedAccount<'info>,
+
+    #[account(
+        seeds = [CONFIG_SEED],
+        bump = config.bump,
+        constraint = !config.paused @ AapError::ProtocolPaused,
+    )]
+    pub config: Account<'info, ProtocolConfig>,
+}
+
+pub fn handler(
+    ctx: Context<SetMilestones>,
+    agreement_id: [u8; 16],
+    milestones: Vec<MilestoneTerms>,
+) -> Result<()> {
+    Freeze::require_unfrozen(&[
+        &ctx.accounts.agent_freeze,
+        &ctx.accounts.parent_freeze,
+        &ctx.accounts.authority_freeze,
+    ])?;
+
+    let clock = Clock::get()?;
+
+    aap_core::scope::require_can_sign(&ctx.accounts.proposer_identity.scope.into(), clock.unix_timestamp)
+        .map_err(AapError::from)?;
+    aap_core::milestone::validate_schedule(milestones.len()).map_err(AapError::from)?;
+    let agreement = ctx.accounts.agreement.key();
+    let mut parties = BTreeSet::new();
+    for info in ctx.remaining_accounts {
+        AgreementParty::load_member(info, &agreement)?;
+        parties.insert(info.key());
+    }
+    for terms in &milestones {
+        require!(terms.amount > 0 && terms.payer != terms.payee, AapError::InvalidMilestone);
+        require!(
+            parties.contains(&terms.payer) && parties.contains(&terms.payee),
+            AapError::PartyNotInAgreement
+        );
+    }
+
+    let schedule = &mut ctx.accounts.schedule;
+    schedule.agreement = agreement;
+    schedule.milestones = milestones
+        .iter()
+        .map(|terms| Milestone {
+            payer: terms.payer,
+            payee: terms.payee,
+            description_hash: terms.description_hash,
+            amount: terms.amount,
+            due_at: terms.due_at,
+            status: MILESTONE_PENDING,
+            submitted_at: 0,
+            resolved_at: 0,
+        })
+        .collect();
+    schedule.released = 0;
+    schedule.bump = ctx.bumps.schedule;
+
+    emit!(MilestonesSet {
+        version: EVENT_VERSION,
+        agreement_id,
+        schedule: schedule.key(),
+        actor: ctx.accounts.proposer_signer.key(),
+        milestones,
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,94 @@
+use anchor_lang::prelude::*;
+use crate::constants::*;
+use crate::errors::AapError;
+use crate::events::{AgreementActivated, PartyCountChanged};
+use crate::state::{AgentIdentity, Agreement, Freeze, ProtocolConfig};
+
+/// The proposer changes how many parties a Proposed agreement needs, within
+/// `MIN_PARTIES..=config.max_parties` and not below the parties already added.
+/// Shrinking to exactly the signed parties activates the agreement.
+#[derive(Accounts)]
+#[instruction(agreement_id: [u8; 16])]
+pub struct SetNumParties<'info> {
+    pub proposer_signer: Signer<'info>,
+
+    #[account(
+        constraint = proposer_identity.agent_key == proposer_signer.key() @ AapError::Unauthorized,
+    )]
+    pub proposer_identity: Account<'info, AgentIdentity>,
+
+    #[account(
+        mut,
+        seeds = [b"agreement", agreement_id.as_ref()],
+        bump = agreement.bump,
+        constraint = agreement.proposer == proposer_identity.key() @ AapError::Unauthorized,
+        constraint = agreement.status == STATUS_PROPOSED @ AapError::InvalidStatus,
+    )]
+    pub agreement: Account<'info, Agreement>,
+
+    /// CHECK: Freeze PDAs covering the proposer (own, parent's, authority's); must not exist
+    #[account(seeds = [b"freeze", proposer_identity.key().as_ref()], bump)]
+    pub agent_freeze: UncheckedAccount<'info>,
+
+    /// CHECK: See `agent_freeze`
+    #[account(seeds = [b"freeze", proposer_identity.parent.as_ref()], bump)]
+    pub parent_freeze: UncheckedAccount<'info>,
+
+    /// CHECK: See `agent_freeze`
+    #[account(seeds = [b"freeze", proposer_identity.authority.as_ref()], bump)]
+    pub authority_freeze: UncheckedAccount<'info>,
+
+    #[account(
+        seeds = [CONFIG_SEED],
+        bump = config.bump,
+        constraint = !config.paused @ AapError::ProtocolPaused,
+    )]
+    pub config: Account<'info, ProtocolConfig>,
+}
+
+pub fn handler(ctx: Context<SetNumParties>, agreement_id: [u8; 16], num_parties: u8) -> Result<()> {
+    Freeze::require_unfrozen(&[
+        &ctx.accounts.agent_freeze,
+        &ctx.accounts.parent_freeze,
+        &ctx.accounts.authority_freeze,
+    ])?;
+
+    let clock = Clock::get()?;
+    let agreement = &mut ctx.accounts.agreement;
+    aap_core::parties::validate_party_resize(
+        num_parties,
+        agreement.parties_added,
+        ctx.accounts.config.max_parties,
+    )
+    .map_err(AapError::from)?;
+
+    let previous_num_parties = agreement.num_parties;
+    agreement.num_parties = num_parties;
+
+    emit!(PartyCountChanged {
+        version: EVENT_VERSION,
+        agreement_id,
+        actor: ctx.accounts.proposer_signer.key(),
+        previous_num_parties,
+        num_parties,
+        parties_added: agreement.parties_added,
+        num_signed: agreement.num_signed,
+        timestamp: clock.unix_timestamp,
+    });
+
+    if aap_core::parties::is_fully_signed(agreement.num_signed, agreement.num_parties) {
+        aap_core::status::require_agreement_not_expired(agreement.expires_at, clock.unix_timestamp)
+            .map_err(AapError::from)?;
+        agreement.status = STATUS_ACTIVE;
+        emit!(AgreementActivated {
+            version: EVENT_VERSION,
+            agreement_id,
+            actor: ctx.accounts.proposer_signer.key(),
+            previous_status: STATUS_PROPOSED,
+            status: STATUS_ACTIVE,
+            timestamp: clock.unix_timestamp,
+        });
+    }
+
+    Ok(())
+}
@@ -0,0 +1,38 @@
+use anchor_lang::prelude::*;
+use crate::constants::*;
+use crate::errors::AapError;
+use crate::events::ConfigUpdated;
+use crate::state::ProtocolConfig;
+
+/// Global kill switch for incidents. Freezes, admin instructions and the exits
+/// (`withdraw_from_vault`, `release_escrow`, `refund_safe`) keep working.
+#[derive(Accounts)]
+pub struct SetPaused<'info> {
+    pub admin: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [CONFIG_SEED],
+        bump = config.bump,
+        has_one = admin @ AapError::Unauthorized,
+    )]
+    pub config: Account<'info, ProtocolConfig>,
+}
+
+pub fn handler(ctx: Context<SetPaused>, paused: bool) -> Result<()> {
+    let config = &mut ctx.accounts.config;
+    config.paused = paused;
+
+    emit!(ConfigUpdated {
+        version: EVENT_VERSION,
+        admin: config.admin,
+        actor: ctx.accounts.admin.key(),
+        paused,
+        max_parties: config.max_parties,
+        fee_lamports: config.fee_lamports,
+        treasury: config.treasury,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
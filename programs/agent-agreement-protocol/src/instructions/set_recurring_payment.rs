@@ -0,0 +1,128 @@
+use anchor_lang::prelude::*;
+use crate::constants::*;
+use crate::errors::AapError;
+use crate::events::RecurringPaymentSet;
+use crate::state::{
+    AgentIdentity, Agreement, AgreementParty, Freeze, ProtocolConfig, RecurringPayment, RecurringTerms,
+};
+
+/// The proposer makes a Proposed agreement a recurring payment before anyone
+/// else signs. Payer and payee are AgreementParty PDAs; the payer needs an
+/// identity, since payments come from its vault, and the whole schedule
+/// (`amount * num_periods`) must be within its `max_commit_lamports`.
+#[derive(Accounts)]
+#[instruction(agreement_id: [u8; 16], terms: RecurringTerms)]
+pub struct SetRecurringPayment<'info> {
+    #[account(mut)]
+    pub proposer_signer: Signer<'info>,
+
+    #[account(
+        constraint = proposer_identity.agent_key == proposer_signer.key() @ AapError::Unauthorized,
+    )]
+    pub proposer_identity: Account<'info, AgentIdentity>,
+
+    #[account(
+        seeds = [b"agreement", agreement_id.as_ref()],
+        bump = agreement.bump,
+        constraint = agreement.proposer == proposer_identity.key() @ AapError::Unauthorized,
+        constraint = agreement.status == STATUS_PROPOSED @ AapError::InvalidStatus,
+        constraint = agreement.num_signed == 1 @ AapError::PaymentTermsLocked,
+    )]
+    pub agreement: Account<'info, Agreement>,
+
+    #[account(
+        init,
+        payer = proposer_signer,
+        space = RecurringPayment::LEN,
+        seeds = [b"recurring", agreement.key().as_ref()],
+        bump,
+    )]
+    pub recurring: Account<'info, RecurringPayment>,
+
+    #[account(
+        constraint = payer_party.key() == terms.payer @ AapError::InvalidRecurringPayment,
+        constraint = payer_party.agreement == agreement.key() @ AapError::PartyNotInAgreement,
+    )]
+    pub payer_party: Account<'info, AgreementParty>,
+
+    #[account(
+        constraint = payer_identity.key() == payer_party.agent_identity @ AapError::InvalidRecurringPayment,
+    )]
+    pub payer_identity: Account<'info, AgentIdentity>,
+
+    #[account(
+        constraint = payee_party.key() == terms.payee @ AapError::InvalidRecurringPayment,
+        constraint = payee_party.agreement == agreement.key() @ AapError::PartyNotInAgreement,
+    )]
+    pub payee_party: Account<'info, AgreementParty>,
+
+    pub system_program: Program<'info, System>,
+
+    /// CHECK: Freeze PDAs covering the proposer (own, parent's, authority's); must not exist
+    #[account(seeds = [b"freeze", proposer_identity.key().as_ref()], bump)]
+    pub agent_freeze: UncheckedAccount<'info>,
+
+    /// CHECK: See `agent_freeze`
+    #[account(seeds = [b"freeze", proposer_identity.parent.as_ref()], bump)]
+    pub parent_freeze: UncheckedAccount<'info>,
+
+    /// CHECK: See `agent_freeze`
+    #[account(seeds = [b"freeze", proposer_identity.authority.as_ref()], bump)]
+    pub authority_freeze: UncheckedAccount<'info>,
+
+    #[account(
+        seeds = [CONFIG_SEED],
+        bump = config.bump,
+        constraint = !config.paused @ AapError::ProtocolPaused,
+    )]
+    pub config: Account<'info, ProtocolConfig>,
+}
+
+pub fn handler(
+    ctx: Context<SetRecurringPayment>,
+    agreement_id: [u8; 16],
+    terms: RecurringTerms,
+) -> Result<()> {
+    Freeze::require_unfrozen(&[
+        &ctx.accounts.agent_freeze,
+        &ctx.accounts.parent_freeze,
+        &ctx.accounts.authority_freeze,
+    ])?;
+
+    let clock = Clock::get()?;
+
+    aap_core::scope::require_can_sign(&ctx.accounts.proposer_identity.scope.into(), clock.unix_timestamp)
+        .map_err(AapError::from)?;
+    aap_core::recurring::validate_terms(terms.amount, terms.period, terms.num_periods)
+        .map_err(AapError::from)?;
+    require!(terms.payer != terms.payee, AapError::InvalidRecurringPayment);
+    let total = aap_core::recurring::total_paid(terms.amount, terms.num_periods).map_err(AapError::from)?;
+    aap_core::scope::require_can_commit(&ctx.accounts.payer_identity.scope.into(), clock.unix_timestamp, total)
+        .map_err(AapError::from)?;
+
+    let recurring = &mut ctx.accounts.recurring;
+    recurring.agreement = ctx.accounts.agreement.key();
+    recurring.payer = terms.payer;
+    recurring.payee = terms.payee;
+    recurring.amount = terms.amount;
+    recurring.period = terms.period;
+    recurring.num_periods = terms.num_periods;
+    recurring.start_at = terms.start_at;
+    recurring.periods_paid = 0;
+    recurring.periods_missed = 0;
+    recurring.status = RECURRING_ACTIVE;
+    recurring.last_paid_at = 0;
+    recurring.ended_at = 0;
+    recurring.bump = ctx.bumps.recurring;
+
+    emit!(RecurringPaymentSet {
+        version: EVENT_VERSION,
+        agreement_id,
+        recurring: recurring.key(),
+        actor: ctx.accounts.proposer_signer.key(),
+        terms,
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
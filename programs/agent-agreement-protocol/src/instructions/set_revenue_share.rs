@@ -0,0 +1,112 @@
+use anchor_lang::prelude::*;
+use std::collections::BTreeSet;
+use crate::constants::*;
+use crate::errors::AapError;
+use crate::events::RevenueShareSet;
+use crate::state::{
+    AgentIdentity, Agreement, AgreementParty, Freeze, ProtocolConfig, RevenueShare, RevenueSplit,
+};
+
+/// The proposer sets the basis-point splits of a Proposed revenue-share
+/// agreement before anyone else signs. Parties are AgreementParty PDAs of the
+/// agreement, passed as remaining accounts in split order; each may appear
+/// once and the splits must add up to 10000.
+#[derive(Accounts)]
+#[instruction(agreement_id: [u8; 16], splits: Vec<RevenueSplit>)]
+pub struct SetRevenueShare<'info> {
+    #[account(mut)]
+    pub proposer_signer: Signer<'info>,
+
+    #[account(
+        constraint = proposer_identity.agent_key == proposer_signer.key() @ AapError::Unauthorized,
+    )]
+    pub proposer_identity: Account<'info, AgentIdentity>,
+
+    #[account(
+        seeds = [b"agreement", agreement_id.as_ref()],
+        bump = agreement.bump,
+        constraint = agreement.proposer == proposer_identity.key() @ AapError::Unauthorized,
+        constraint = agreement.agreement_type == AGREEMENT_TYPE_REVENUE_SHARE @ AapError::InvalidAgreementType,
+        constraint = agreement.status == STATUS_PROPOSED @ AapError::InvalidStatus,
+        constraint = agreement.num_signed == 1 @ AapError::PaymentTermsLocked,
+    )]
+    pub agreement: Account<'info, Agreement>,
+
+    #[account(
+        init,
+        payer = proposer_signer,
+        space = RevenueShare::space(splits.len()),
+        seeds = [b"revenue_share", agreement.key().as_ref()],
+        bump,
+    )]
+    pub revenue_share: Account<'info, RevenueShare>,
+
+    pub system_program: Program<'info, System>,
+
+    /// CHECK: Freeze PDAs covering the proposer (own, parent's, authority's); must not exist
+    #[account(seeds = [b"freeze", proposer_identity.key().as_ref()], bump)]
+    pub agent_freeze: UncheckedAccount<'info>,
+
+    /// CHECK: See `agent_freeze`
+    #[account(seeds = [b"freeze", proposer_identity.parent.as_ref()], bump)]
+    pub parent_freeze: UncheckedAccount<'info>,
+
+    /// CHECK: See `agent_freeze`
+    #[account(seeds = [b"freeze", proposer_identity.authority.as_ref()], bump)]
+    pub authority_freeze: UncheckedAccount<'info>,
+
+    #[account(
+        seeds = [CONFIG_SEED],
+        bump = config.bump,
+        constraint = !config.paused @ AapError::ProtocolPaused,
+    )]
+    pub config: Account<'info, ProtocolConfig>,
+}
+
+pub fn handler(
+    ctx: Context<SetRevenueShare>,
+    agreement_id: [u8; 16],
+    splits: Vec<RevenueSplit>,
+) -> Result<()> {
+    Freeze::require_unfrozen(&[
+        &ctx.accounts.agent_freeze,
+        &ctx.accounts.parent_freeze,
+        &ctx.accounts.authority_freeze,
+    ])?;
+
+    let clock = Clock::get()?;
+
+    aap_core::scope::require_can_sign(&ctx.accounts.proposer_identity.scope.into(), clock.unix_timestamp)
+        .map_err(AapError::from)?;
+    let bps: Vec<u16> = splits.iter().map(|split| split.bps).collect();
+    aap_core::revenue::validate_splits(&bps).map_err(AapError::from)?;
+    let mut seen = BTreeSet::new();
+    require!(splits.iter().all(|split| seen.insert(split.party)), AapError::InvalidRevenueShare);
+
+    let agreement = ctx.accounts.agreement.key();
+    require!(ctx.remaining_accounts.len() == splits.len(), AapError::InvalidRevenueShare);
+    for (split, info) in splits.iter().zip(ctx.remaining_accounts) {
+        require!(info.key() == split.party, AapError::InvalidRevenueShare);
+        AgreementParty::load_member(info, &agreement)?;
+    }
+    let (receiver, receiver_bump) = Pubkey::find_program_address(&[b"revenue", agreement.as_ref()], &crate::ID);
+    let revenue_share = &mut ctx.accounts.revenue_share;
+    revenue_share.agreement = agreement;
+    revenue_share.splits = splits.clone();
+    revenue_share.receiver_bump = receiver_bump;
+    revenue_share.distributed_lamports = 0;
+    revenue_share.distributions = 0;
+    revenue_share.bump = ctx.bumps.revenue_share;
+
+    emit!(RevenueShareSet {
+        version: EVENT_VERSION,
+        agreement_id,
+        revenue_share: revenue_share.key(),
+        receiver,
+        actor: ctx.accounts.proposer_signer.key(),
+        splits,
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,127 @@
+use anchor_lang::prelude::*;
+use crate::constants::*;
+use crate::errors::AapError;
+use crate::events::SafeSet;
+use crate::state::{AgentIdentity, Agreement, AgreementParty, Freeze, ProtocolConfig, Safe, SafeTerms};
+
+/// The proposer sets the typed terms of a Proposed SAFE agreement before
+/// anyone else signs. Investor and company are AgreementParty PDAs of the
+/// agreement; the investor must be another party with an identity, since it
+/// funds the escrow from its vault when it signs.
+#[derive(Accounts)]
+#[instruction(agreement_id: [u8; 16], terms: SafeTerms)]
+pub struct SetSafe<'info> {
+    #[account(mut)]
+    pub proposer_signer: Signer<'info>,
+
+    #[account(
+        constraint = proposer_identity.agent_key == proposer_signer.key() @ AapError::Unauthorized,
+    )]
+    pub proposer_identity: Account<'info, AgentIdentity>,
+
+    #[account(
+        seeds = [b"agreement", agreement_id.as_ref()],
+        bump = agreement.bump,
+        constraint = agreement.proposer == proposer_identity.key() @ AapError::Unauthorized,
+        constraint = agreement.agreement_type == AGREEMENT_TYPE_SAFE @ AapError::InvalidAgreementType,
+        constraint = agreement.status == STATUS_PROPOSED @ AapError::InvalidStatus,
+        constraint = agreement.num_signed == 1 @ AapError::PaymentTermsLocked,
+    )]
+    pub agreement: Account<'info, Agreement>,
+
+    #[account(
+        init,
+        payer = proposer_signer,
+        space = Safe::LEN,
+        seeds = [b"safe", agreement.key().as_ref()],
+        bump,
+    )]
+    pub safe: Account<'info, Safe>,
+
+    #[account(
+        constraint = investor_party.key() == terms.investor @ AapError::InvalidSafe,
+        constraint = investor_party.agreement == agreement.key() @ AapError::PartyNotInAgreement,
+    )]
+    pub investor_party: Account<'info, AgreementParty>,
+
+    /// The investor's AgentIdentity; parties added with `add_party_direct` have none
+    #[account(
+        constraint = investor_identity.key() == investor_party.agent_identity @ AapError::InvalidSafe,
+    )]
+    pub investor_identity: Account<'info, AgentIdentity>,
+
+    #[account(
+        constraint = company_party.key() == terms.company @ AapError::InvalidSafe,
+        constraint = company_party.agreement == agreement.key() @ AapError::PartyNotInAgreement,
+    )]
+    pub company_party: Account<'info, AgreementParty>,
+
+    pub system_program: Program<'info, System>,
+
+    /// CHECK: Freeze PDAs covering the proposer (own, parent's, authority's); must not exist
+    #[account(seeds = [b"freeze", proposer_identity.key().as_ref()], bump)]
+    pub agent_freeze: UncheckedAccount<'info>,
+
+    /// CHECK: See `agent_freeze`
+    #[account(seeds = [b"freeze", proposer_identity.parent.as_ref()], bump)]
+    pub parent_freeze: UncheckedAccount<'info>,
+
+    /// CHECK: See `agent_freeze`
+    #[account(seeds = [b"freeze", proposer_identity.authority.as_ref()], bump)]
+    pub authority_freeze: UncheckedAccount<'info>,
+
+    #[account(
+        seeds = [CONFIG_SEED],
+        bump = config.bump,
+        constraint = !config.paused @ AapError::ProtocolPaused,
+    )]
+    pub config: Account<'info, ProtocolConfig>,
+}
+
+pub fn handler(ctx: Context<SetSafe>, agreement_id: [u8; 16], terms: SafeTerms) -> Result<()> {
+    Freeze::require_unfrozen(&[
+        &ctx.accounts.agent_freeze,
+        &ctx.accounts.parent_freeze,
+        &ctx.accounts.authority_freeze,
+    ])?;
+
+    let clock = Clock::get()?;
+
+    aap_core::scope::require_can_sign(&ctx.accounts.proposer_identity.scope.into(), clock.unix_timestamp)
+        .map_err(AapError::from)?;
+    aap_core::safe::validate_terms(terms.investment_amount, terms.discount_bps).map_err(AapError::from)?;
+    let (proposer_party, _) = Pubkey::find_program_address(
+        &[b"party", agreement_id.as_ref(), ctx.accounts.proposer_identity.key().as_ref()],
+        &crate::ID,
+    );
+    require!(
+        terms.investor != terms.company && terms.investor != proposer_party,
+        AapError::InvalidSafe
+    );
+
+    let safe = &mut ctx.accounts.safe;
+    safe.agreement = ctx.accounts.agreement.key();
+    safe.investor = terms.investor;
+    safe.company = terms.company;
+    safe.investment_amount = terms.investment_amount;
+    safe.valuation_cap = terms.valuation_cap;
+    safe.discount_bps = terms.discount_bps;
+    safe.mint = terms.mint;
+    safe.status = SAFE_UNFUNDED;
+    safe.funded_at = 0;
+    safe.round_valuation = 0;
+    safe.conversion_valuation = 0;
+    safe.resolved_at = 0;
+    safe.bump = ctx.bumps.safe;
+
+    emit!(SafeSet {
+        version: EVENT_VERSION,
+        agreement_id,
+        safe: safe.key(),
+        actor: ctx.accounts.proposer_signer.key(),
+        terms,
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
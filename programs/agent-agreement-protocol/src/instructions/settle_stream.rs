@@ -0,0 +1,102 @@
+use anchor_lang::prelude::*;
+use crate::constants::*;
+use crate::errors::AapError;
+use crate::events::StreamEnded;
+use crate::instructions::cancel_stream::end_stream;
+use crate::state::{Agreement, AgreementParty, EscrowStream, ProtocolConfig, Termination};
+
+/// Pays out a stream once the agreement has ended. Fulfilled (reached through
+/// milestones or recurring payments, since `fulfill_agreement` refuses an
+/// active stream) vests the whole stream to the payee; Terminated stops vesting when the termination was
+/// applied (needs the `termination` account) and returns the unvested part
+/// to the payer. Permissionless, since both recipients are fixed.
+#[derive(Accounts)]
+#[instruction(agreement_id: [u8; 16])]
+pub struct SettleStream<'info> {
+    pub signer: Signer<'info>,
+
+    #[account(
+        seeds = [b"agreement", agreement_id.as_ref()],
+        bump = agreement.bump,
+        constraint = (
+            agreement.status == STATUS_FULFILLED ||
+            agreement.status == STATUS_TERMINATED
+        ) @ AapError::InvalidStatus,
+    )]
+    pub agreement: Account<'info, Agreement>,
+
+    #[account(
+        mut,
+        seeds = [b"stream", agreement.key().as_ref()],
+        bump = stream.bump,
+        constraint = stream.status == STREAM_ACTIVE @ AapError::StreamNotActive,
+    )]
+    pub stream: Account<'info, EscrowStream>,
+
+    #[account(
+        seeds = [b"termination", agreement.key().as_ref()],
+        bump = termination.bump,
+        constraint = termination.status == TERMINATION_APPLIED @ AapError::InvalidStatus,
+    )]
+    pub termination: Option<Account<'info, Termination>>,
+
+    #[account(
+        constraint = payer_party.key() == stream.payer @ AapError::InvalidStream,
+    )]
+    pub payer_party: Account<'info, AgreementParty>,
+
+    #[account(
+        constraint = payee_party.key() == stream.payee @ AapError::InvalidStream,
+    )]
+    pub payee_party: Account<'info, AgreementParty>,
+
+    /// CHECK: The payer's AgentVault PDA, or the payer key itself for direct
+    /// parties; checked in the handler
+    #[account(mut)]
+    pub payer_recipient: UncheckedAccount<'info>,
+
+    /// CHECK: See `payer_recipient`, for the payee
+    #[account(mut)]
+    pub payee_recipient: UncheckedAccount<'info>,
+
+    #[account(
+        seeds = [CONFIG_SEED],
+        bump = config.bump,
+        constraint = !config.paused @ AapError::ProtocolPaused,
+    )]
+    pub config: Account<'info, ProtocolConfig>,
+}
+
+pub fn handler(ctx: Context<SettleStream>, agreement_id: [u8; 16]) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+    let cutoff = if ctx.accounts.agreement.status == STATUS_TERMINATED {
+        let termination = ctx.accounts.termination.as_ref().ok_or(AapError::InvalidSettlement)?;
+        termination.resolved_at
+    } else {
+        ctx.accounts.stream.end_at
+    };
+
+    let (to_payee, to_payer) = end_stream(
+        &mut ctx.accounts.stream,
+        &ctx.accounts.payer_party,
+        &ctx.accounts.payer_recipient,
+        &ctx.accounts.payee_party,
+        &ctx.accounts.payee_recipient,
+        cutoff,
+        STREAM_SETTLED,
+    )?;
+
+    emit!(StreamEnded {
+        version: EVENT_VERSION,
+        agreement_id,
+        stream: ctx.accounts.stream.key(),
+        actor: ctx.accounts.signer.key(),
+        status: STREAM_SETTLED,
+        to_payee,
+        to_payer,
+        claimed: ctx.accounts.stream.claimed,
+        timestamp: now,
+    });
+
+    Ok(())
+}
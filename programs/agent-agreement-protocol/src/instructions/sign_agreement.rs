@@ -2,7 +2,7 @@ use anchor_lang::prelude::*;
 use crate::constants::*;
 use crate::errors::AapError;
 use crate::events::{AgreementSigned, AgreementActivated};
-use crate::state::{AgentIdentity, Agreement, AgreementParty};
+use crate::state::{AgentIdentity, Agreement, AgreementParty, Freeze, ProtocolConfig, Safe};
 
 #[derive(Accounts)]
 #[instruction(agreement_id: [u8; 16])]
@@ -29,40 +29,57 @@ pub struct SignAgreement<'info> {
         constraint = !party.signed @ AapError::AlreadySigned,
     )]
     pub party: Account<'info, AgreementParty>,
+
+    /// CHECK: Freeze PDAs covering the signer (own, parent's, authority's); must not exist
+    #[account(seeds = [b"freeze", signer_identity.key().as_ref()], bump)]
+    pub agent_freeze: UncheckedAccount<'info>,
+
+    /// CHECK: See `agent_freeze`
+    #[account(seeds = [b"freeze", signer_identity.parent.as_ref()], bump)]
+    pub parent_freeze: UncheckedAccount<'info>,
+
+    /// CHECK: See `agent_freeze`
+    #[account(seeds = [b"freeze", signer_identity.authority.as_ref()], bump)]
+    pub authority_freeze: UncheckedAccount<'info>,
+
+    /// CHECK: Safe PDA of a SAFE agreement, whose investor signs with
+    /// `sign_safe` instead; empty for other agreements
+    #[account(seeds = [b"safe", agreement.key().as_ref()], bump)]
+    pub safe: UncheckedAccount<'info>,
+
+    #[account(
+        seeds = [CONFIG_SEED],
+        bump = config.bump,
+        constraint = !config.paused @ AapError::ProtocolPaused,
+    )]
+    pub config: Account<'info, ProtocolConfig>,
 }
 
 pub fn handler(
     ctx: Context<SignAgreement>,
     agreement_id: [u8; 16],
 ) -> Result<()> {
+    Freeze::require_unfrozen(&[
+        &ctx.accounts.agent_freeze,
+        &ctx.accounts.parent_freeze,
+        &ctx.accounts.authority_freeze,
+    ])?;
+
     let identity = &ctx.accounts.signer_identity;
 
-    // Validate delegation is not expired
-    if identity.scope.expires_at != 0 {
-        let clock = Clock::get()?;
-        require!(
-            identity.scope.expires_at > clock.unix_timestamp,
-            AapError::DelegationExpired
-        );
-    }
+    let clock = Clock::get()?;
 
-    // Validate can_sign_agreements
-    require!(
-        identity.scope.can_sign_agreements,
-        AapError::CannotSignAgreements
-    );
+    // Validate delegation is not expired and can_sign_agreements
+    aap_core::scope::require_can_sign(&identity.scope.into(), clock.unix_timestamp)
+        .map_err(AapError::from)?;
 
     // Check agreement expiry
-    let agreement = &ctx.accounts.agreement;
-    if agreement.expires_at != 0 {
-        let clock = Clock::get()?;
-        require!(
-            agreement.expires_at > clock.unix_timestamp,
-            AapError::AgreementExpired
-        );
-    }
-
-    let clock = Clock::get()?;
+    aap_core::status::require_agreement_not_expired(
+        ctx.accounts.agreement.expires_at,
+        clock.unix_timestamp,
+    )
+    .map_err(AapError::from)?;
+    Safe::require_not_investor(&ctx.accounts.safe, &ctx.accounts.party.key())?;
 
     // Update party
     let party = &mut ctx.accounts.party;
@@ -74,14 +91,27 @@ pub fn handler(
     agreement.num_signed += 1;
 
     emit!(AgreementSigned {
+        version: EVENT_VERSION,
         agreement_id,
         party: ctx.accounts.signer_identity.key(),
+        actor: ctx.accounts.signer.key(),
+        direct: false,
+        num_signed: agreement.num_signed,
+        num_parties: agreement.num_parties,
+        timestamp: clock.unix_timestamp,
     });
 
     // Check if all parties signed
-    if agreement.num_signed == agreement.num_parties {
+    if aap_core::parties::is_fully_signed(agreement.num_signed, agreement.num_parties) {
         agreement.status = STATUS_ACTIVE;
-        emit!(AgreementActivated { agreement_id });
+        emit!(AgreementActivated {
+            version: EVENT_VERSION,
+            agreement_id,
+            actor: ctx.accounts.signer.key(),
+            previous_status: STATUS_PROPOSED,
+            status: STATUS_ACTIVE,
+            timestamp: clock.unix_timestamp,
+        });
     }
 
     Ok(())
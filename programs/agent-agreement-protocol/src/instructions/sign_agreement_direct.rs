@@ -2,7 +2,7 @@ use anchor_lang::prelude::*;
 use crate::constants::*;
 use crate::errors::AapError;
 use crate::events::{AgreementSigned, AgreementActivated};
-use crate::state::{Agreement, AgreementParty};
+use crate::state::{Agreement, AgreementParty, Freeze, ProtocolConfig, Safe};
 
 /// Sign an agreement directly with a wallet — no identity registration required.
 /// The party PDA must be seeded by the signer's pubkey.
@@ -28,23 +28,40 @@ pub struct SignAgreementDirect<'info> {
         constraint = !party.signed @ AapError::AlreadySigned,
     )]
     pub party: Account<'info, AgreementParty>,
+
+    /// CHECK: Freeze PDA of the signing wallet (`freeze_authority`); must not exist
+    #[account(seeds = [b"freeze", signer.key().as_ref()], bump)]
+    pub signer_freeze: UncheckedAccount<'info>,
+
+    /// CHECK: Safe PDA of a SAFE agreement, whose investor signs with
+    /// `sign_safe` instead; empty for other agreements
+    #[account(seeds = [b"safe", agreement.key().as_ref()], bump)]
+    pub safe: UncheckedAccount<'info>,
+
+    #[account(
+        seeds = [CONFIG_SEED],
+        bump = config.bump,
+        constraint = !config.paused @ AapError::ProtocolPaused,
+    )]
+    pub config: Account<'info, ProtocolConfig>,
 }
 
 pub fn handler(
     ctx: Context<SignAgreementDirect>,
     agreement_id: [u8; 16],
 ) -> Result<()> {
-    // Check agreement expiry
-    if ctx.accounts.agreement.expires_at != 0 {
-        let clock = Clock::get()?;
-        require!(
-            ctx.accounts.agreement.expires_at > clock.unix_timestamp,
-            AapError::AgreementExpired
-        );
-    }
+    Freeze::require_unfrozen(&[&ctx.accounts.signer_freeze])?;
 
     let clock = Clock::get()?;
 
+    // Check agreement expiry
+    aap_core::status::require_agreement_not_expired(
+        ctx.accounts.agreement.expires_at,
+        clock.unix_timestamp,
+    )
+    .map_err(AapError::from)?;
+    Safe::require_not_investor(&ctx.accounts.safe, &ctx.accounts.party.key())?;
+
     let party = &mut ctx.accounts.party;
     party.signed = true;
     party.signed_at = clock.unix_timestamp;
@@ -53,13 +70,26 @@ pub fn handler(
     agreement.num_signed += 1;
 
     emit!(AgreementSigned {
+        version: EVENT_VERSION,
         agreement_id,
         party: ctx.accounts.signer.key(),
+        actor: ctx.accounts.signer.key(),
+        direct: true,
+        num_signed: agreement.num_signed,
+        num_parties: agreement.num_parties,
+        timestamp: clock.unix_timestamp,
     });
 
-    if agreement.num_signed == agreement.num_parties {
+    if aap_core::parties::is_fully_signed(agreement.num_signed, agreement.num_parties) {
         agreement.status = STATUS_ACTIVE;
-        emit!(AgreementActivated { agreement_id });
+        emit!(AgreementActivated {
+            version: EVENT_VERSION,
+            agreement_id,
+            actor: ctx.accounts.signer.key(),
+            previous_status: STATUS_PROPOSED,
+            status: STATUS_ACTIVE,
+            timestamp: clock.unix_timestamp,
+        });
     }
 
     Ok(())
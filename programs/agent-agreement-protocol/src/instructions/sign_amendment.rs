@@ -0,0 +1,113 @@
+use anchor_lang::prelude::*;
+use crate::constants::*;
+use crate::errors::AapError;
+use crate::events::{AmendmentApplied, AmendmentSigned};
+use crate::state::{
+    AgentIdentity, Agreement, AgreementAmendment, AgreementParty, Freeze, ProtocolConfig,
+};
+
+/// A party of an Active agreement signs a pending amendment. The last
+/// signature applies it.
+#[derive(Accounts)]
+#[instruction(agreement_id: [u8; 16], version: u16)]
+pub struct SignAmendment<'info> {
+    pub signer: Signer<'info>,
+
+    #[account(
+        constraint = signer_identity.agent_key == signer.key() @ AapError::Unauthorized,
+    )]
+    pub signer_identity: Account<'info, AgentIdentity>,
+
+    #[account(
+        mut,
+        seeds = [b"agreement", agreement_id.as_ref()],
+        bump = agreement.bump,
+        constraint = agreement.status == STATUS_ACTIVE @ AapError::InvalidStatus,
+    )]
+    pub agreement: Account<'info, Agreement>,
+
+    #[account(
+        seeds = [b"party", agreement_id.as_ref(), signer_identity.key().as_ref()],
+        bump = party.bump,
+    )]
+    pub party: Account<'info, AgreementParty>,
+
+    #[account(
+        mut,
+        seeds = [b"amendment", agreement.key().as_ref(), &version.to_le_bytes()],
+        bump = amendment.bump,
+        constraint = amendment.status == AMENDMENT_PENDING @ AapError::AmendmentNotPending,
+    )]
+    pub amendment: Account<'info, AgreementAmendment>,
+
+    /// CHECK: Freeze PDAs covering the signer (own, parent's, authority's); must not exist
+    #[account(seeds = [b"freeze", signer_identity.key().as_ref()], bump)]
+    pub agent_freeze: UncheckedAccount<'info>,
+
+    /// CHECK: See `agent_freeze`
+    #[account(seeds = [b"freeze", signer_identity.parent.as_ref()], bump)]
+    pub parent_freeze: UncheckedAccount<'info>,
+
+    /// CHECK: See `agent_freeze`
+    #[account(seeds = [b"freeze", signer_identity.authority.as_ref()], bump)]
+    pub authority_freeze: UncheckedAccount<'info>,
+
+    #[account(
+        seeds = [CONFIG_SEED],
+        bump = config.bump,
+        constraint = !config.paused @ AapError::ProtocolPaused,
+    )]
+    pub config: Account<'info, ProtocolConfig>,
+}
+
+pub fn handler(
+    ctx: Context<SignAmendment>,
+    agreement_id: [u8; 16],
+    version: u16,
+) -> Result<()> {
+    Freeze::require_unfrozen(&[
+        &ctx.accounts.agent_freeze,
+        &ctx.accounts.parent_freeze,
+        &ctx.accounts.authority_freeze,
+    ])?;
+
+    let clock = Clock::get()?;
+
+    aap_core::scope::require_can_sign(&ctx.accounts.signer_identity.scope.into(), clock.unix_timestamp)
+        .map_err(AapError::from)?;
+
+    let party = ctx.accounts.party.key();
+    let amendment = &mut ctx.accounts.amendment;
+    require!(!amendment.approvals.contains(&party), AapError::AlreadySigned);
+    amendment.approvals.push(party);
+
+    emit!(AmendmentSigned {
+        version: EVENT_VERSION,
+        agreement_id,
+        amendment: amendment.key(),
+        amendment_version: version,
+        party: ctx.accounts.signer_identity.key(),
+        actor: ctx.accounts.signer.key(),
+        direct: false,
+        approvals: amendment.approvals.len() as u8,
+        required: amendment.required,
+        timestamp: clock.unix_timestamp,
+    });
+
+    if amendment.is_fully_signed() {
+        amendment.apply(&mut ctx.accounts.agreement, clock.unix_timestamp);
+        emit!(AmendmentApplied {
+            version: EVENT_VERSION,
+            agreement_id,
+            amendment: amendment.key(),
+            amendment_version: version,
+            actor: ctx.accounts.signer.key(),
+            previous_terms_hash: amendment.previous_terms_hash,
+            terms_hash: amendment.terms_hash,
+            signatures_reset: 0,
+            timestamp: clock.unix_timestamp,
+        });
+    }
+
+    Ok(())
+}
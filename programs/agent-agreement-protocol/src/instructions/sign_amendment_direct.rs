@@ -0,0 +1,86 @@
+use anchor_lang::prelude::*;
+use crate::constants::*;
+use crate::errors::AapError;
+use crate::events::{AmendmentApplied, AmendmentSigned};
+use crate::state::{Agreement, AgreementAmendment, AgreementParty, ProtocolConfig};
+
+/// `sign_amendment` for a party added with `add_party_direct`: the party PDA
+/// is seeded by the signer's wallet.
+#[derive(Accounts)]
+#[instruction(agreement_id: [u8; 16], version: u16)]
+pub struct SignAmendmentDirect<'info> {
+    pub signer: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"agreement", agreement_id.as_ref()],
+        bump = agreement.bump,
+        constraint = agreement.status == STATUS_ACTIVE @ AapError::InvalidStatus,
+    )]
+    pub agreement: Account<'info, Agreement>,
+
+    #[account(
+        seeds = [b"party", agreement_id.as_ref(), signer.key().as_ref()],
+        bump = party.bump,
+        constraint = party.agent_identity == signer.key() @ AapError::Unauthorized,
+    )]
+    pub party: Account<'info, AgreementParty>,
+
+    #[account(
+        mut,
+        seeds = [b"amendment", agreement.key().as_ref(), &version.to_le_bytes()],
+        bump = amendment.bump,
+        constraint = amendment.status == AMENDMENT_PENDING @ AapError::AmendmentNotPending,
+    )]
+    pub amendment: Account<'info, AgreementAmendment>,
+
+    #[account(
+        seeds = [CONFIG_SEED],
+        bump = config.bump,
+        constraint = !config.paused @ AapError::ProtocolPaused,
+    )]
+    pub config: Account<'info, ProtocolConfig>,
+}
+
+pub fn handler(
+    ctx: Context<SignAmendmentDirect>,
+    agreement_id: [u8; 16],
+    version: u16,
+) -> Result<()> {
+    let clock = Clock::get()?;
+
+    let party = ctx.accounts.party.key();
+    let amendment = &mut ctx.accounts.amendment;
+    require!(!amendment.approvals.contains(&party), AapError::AlreadySigned);
+    amendment.approvals.push(party);
+
+    emit!(AmendmentSigned {
+        version: EVENT_VERSION,
+        agreement_id,
+        amendment: amendment.key(),
+        amendment_version: version,
+        party: ctx.accounts.signer.key(),
+        actor: ctx.accounts.signer.key(),
+        direct: true,
+        approvals: amendment.approvals.len() as u8,
+        required: amendment.required,
+        timestamp: clock.unix_timestamp,
+    });
+
+    if amendment.is_fully_signed() {
+        amendment.apply(&mut ctx.accounts.agreement, clock.unix_timestamp);
+        emit!(AmendmentApplied {
+            version: EVENT_VERSION,
+            agreement_id,
+            amendment: amendment.key(),
+            amendment_version: version,
+            actor: ctx.accounts.signer.key(),
+            previous_terms_hash: amendment.previous_terms_hash,
+            terms_hash: amendment.terms_hash,
+            signatures_reset: 0,
+            timestamp: clock.unix_timestamp,
+        });
+    }
+
+    Ok(())
+}
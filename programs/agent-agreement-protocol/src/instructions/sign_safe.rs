@@ -0,0 +1,148 @@
+use anchor_lang::prelude::*;
+use crate::constants::*;
+use crate::errors::AapError;
+use crate::events::{AgreementActivated, AgreementSigned, SafeFunded};
+use crate::state::{
+    AgentIdentity, AgentVault, Agreement, AgreementParty, Freeze, ProtocolConfig, Safe,
+};
+
+/// The SAFE's investor signs the agreement and, in the same step, moves the
+/// investment from its AgentVault into the agreement's escrow. Its total
+/// deposit must stay within the delegation's `max_commit_lamports`.
+#[derive(Accounts)]
+#[instruction(agreement_id: [u8; 16])]
+pub struct SignSafe<'info> {
+    pub signer: Signer<'info>,
+
+    #[account(
+        constraint = signer_identity.agent_key == signer.key() @ AapError::Unauthorized,
+    )]
+    pub signer_identity: Account<'info, AgentIdentity>,
+
+    #[account(
+        mut,
+        seeds = [b"agreement", agreement_id.as_ref()],
+        bump = agreement.bump,
+        constraint = agreement.status == STATUS_PROPOSED @ AapError::InvalidStatus,
+    )]
+    pub agreement: Account<'info, Agreement>,
+
+    #[account(
+        mut,
+        seeds = [b"party", agreement_id.as_ref(), signer_identity.key().as_ref()],
+        bump = party.bump,
+        constraint = !party.signed @ AapError::AlreadySigned,
+    )]
+    pub party: Account<'info, AgreementParty>,
+
+    #[account(
+        mut,
+        seeds = [b"safe", agreement.key().as_ref()],
+        bump = safe.bump,
+        constraint = safe.investor == party.key() @ AapError::Unauthorized,
+        constraint = safe.status == SAFE_UNFUNDED @ AapError::InvalidStatus,
+    )]
+    pub safe: Account<'info, Safe>,
+
+    #[account(
+        mut,
+        seeds = [b"vault", signer_identity.key().as_ref()],
+        bump = vault.bump,
+    )]
+    pub vault: Account<'info, AgentVault>,
+
+    /// CHECK: Freeze PDAs covering the signer (own, parent's, authority's); must not exist
+    #[account(seeds = [b"freeze", signer_identity.key().as_ref()], bump)]
+    pub agent_freeze: UncheckedAccount<'info>,
+
+    /// CHECK: See `agent_freeze`
+    #[account(seeds = [b"freeze", signer_identity.parent.as_ref()], bump)]
+    pub parent_freeze: UncheckedAccount<'info>,
+
+    /// CHECK: See `agent_freeze`
+    #[account(seeds = [b"freeze", signer_identity.authority.as_ref()], bump)]
+    pub authority_freeze: UncheckedAccount<'info>,
+
+    #[account(
+        seeds = [CONFIG_SEED],
+        bump = config.bump,
+        constraint = !config.paused @ AapError::ProtocolPaused,
+    )]
+    pub config: Account<'info, ProtocolConfig>,
+}
+
+pub fn handler(ctx: Context<SignSafe>, agreement_id: [u8; 16]) -> Result<()> {
+    Freeze::require_unfrozen(&[
+        &ctx.accounts.agent_freeze,
+        &ctx.accounts.parent_freeze,
+        &ctx.accounts.authority_freeze,
+    ])?;
+
+    let clock = Clock::get()?;
+    let scope = ctx.accounts.signer_identity.scope.into();
+
+    aap_core::scope::require_can_sign(&scope, clock.unix_timestamp).map_err(AapError::from)?;
+    aap_core::status::require_agreement_not_expired(ctx.accounts.agreement.expires_at, clock.unix_timestamp)
+        .map_err(AapError::from)?;
+
+    let amount = ctx.accounts.safe.investment_amount;
+    let party = &mut ctx.accounts.party;
+    let party_escrow = party.escrow_deposited.checked_add(amount).ok_or(AapError::InvalidAmount)?;
+    aap_core::scope::require_can_commit(&scope, clock.unix_timestamp, party_escrow).map_err(AapError::from)?;
+
+    let vault = &mut ctx.accounts.vault;
+    let rent_exempt = Rent::get()?.minimum_balance(AgentVault::LEN);
+    let available = vault.available_balance(vault.to_account_info().lamports(), rent_exempt);
+    require!(amount <= available, AapError::InsufficientVaultBalance);
+
+    let agreement = &mut ctx.accounts.agreement;
+    // Both accounts are owned by this program
+    **vault.to_account_info().try_borrow_mut_lamports()? -= amount;
+    **agreement.to_account_info().try_borrow_mut_lamports()? += amount;
+
+    party.escrow_deposited = party_escrow;
+    party.signed = true;
+    party.signed_at = clock.unix_timestamp;
+    agreement.escrow_total = agreement.escrow_total.checked_add(amount).ok_or(AapError::InvalidAmount)?;
+    agreement.num_signed += 1;
+
+    let safe = &mut ctx.accounts.safe;
+    safe.status = SAFE_FUNDED;
+    safe.funded_at = clock.unix_timestamp;
+
+    emit!(SafeFunded {
+        version: EVENT_VERSION,
+        agreement_id,
+        safe: safe.key(),
+        investor: ctx.accounts.signer_identity.key(),
+        actor: ctx.accounts.signer.key(),
+        amount,
+        escrow_total: agreement.escrow_total,
+        timestamp: clock.unix_timestamp,
+    });
+
+    emit!(AgreementSigned {
+        version: EVENT_VERSION,
+        agreement_id,
+        party: ctx.accounts.signer_identity.key(),
+        actor: ctx.accounts.signer.key(),
+        direct: false,
+        num_signed: agreement.num_signed,
+        num_parties: agreement.num_parties,
+        timestamp: clock.unix_timestamp,
+    });
+
+    if aap_core::parties::is_fully_signed(agreement.num_signed, agreement.num_parties) {
+        agreement.status = STATUS_ACTIVE;
+        emit!(AgreementActivated {
+            version: EVENT_VERSION,
+            agreement_id,
+            actor: ctx.accounts.signer.key(),
+            previous_status: STATUS_PROPOSED,
+            status: STATUS_ACTIVE,
+            timestamp: clock.unix_timestamp,
+        });
+    }
+
+    Ok(())
+}
@@ -0,0 +1,91 @@
+use anchor_lang::prelude::*;
+use crate::constants::*;
+use crate::errors::AapError;
+use crate::events::MilestoneSubmitted;
+use crate::state::{AgentIdentity, Agreement, AgreementParty, Freeze, MilestoneSchedule, ProtocolConfig};
+
+/// The payee's agent submits milestone `index` as delivered, or again after
+/// a dispute.
+#[derive(Accounts)]
+#[instruction(agreement_id: [u8; 16])]
+pub struct SubmitMilestone<'info> {
+    pub signer: Signer<'info>,
+
+    #[account(
+        constraint = signer_identity.agent_key == signer.key() @ AapError::Unauthorized,
+    )]
+    pub signer_identity: Account<'info, AgentIdentity>,
+
+    #[account(
+        seeds = [b"agreement", agreement_id.as_ref()],
+        bump = agreement.bump,
+        constraint = agreement.status == STATUS_ACTIVE @ AapError::InvalidStatus,
+    )]
+    pub agreement: Account<'info, Agreement>,
+
+    #[account(
+        seeds = [b"party", agreement_id.as_ref(), signer_identity.key().as_ref()],
+        bump = party.bump,
+    )]
+    pub party: Account<'info, AgreementParty>,
+
+    #[account(
+        mut,
+        seeds = [b"milestones", agreement.key().as_ref()],
+        bump = schedule.bump,
+    )]
+    pub schedule: Account<'info, MilestoneSchedule>,
+
+    /// CHECK: Freeze PDAs covering the signer (own, parent's, authority's); must not exist
+    #[account(seeds = [b"freeze", signer_identity.key().as_ref()], bump)]
+    pub agent_freeze: UncheckedAccount<'info>,
+
+    /// CHECK: See `agent_freeze`
+    #[account(seeds = [b"freeze", signer_identity.parent.as_ref()], bump)]
+    pub parent_freeze: UncheckedAccount<'info>,
+
+    /// CHECK: See `agent_freeze`
+    #[account(seeds = [b"freeze", signer_identity.authority.as_ref()], bump)]
+    pub authority_freeze: UncheckedAccount<'info>,
+
+    #[account(
+        seeds = [CONFIG_SEED],
+        bump = config.bump,
+        constraint = !config.paused @ AapError::ProtocolPaused,
+    )]
+    pub config: Account<'info, ProtocolConfig>,
+}
+
+pub fn handler(ctx: Context<SubmitMilestone>, agreement_id: [u8; 16], index: u8) -> Result<()> {
+    Freeze::require_unfrozen(&[
+        &ctx.accounts.agent_freeze,
+        &ctx.accounts.parent_freeze,
+        &ctx.accounts.authority_freeze,
+    ])?;
+
+    let clock = Clock::get()?;
+
+    aap_core::scope::require_can_sign(&ctx.accounts.signer_identity.scope.into(), clock.unix_timestamp)
+        .map_err(AapError::from)?;
+
+    let party = ctx.accounts.party.key();
+    let milestone = ctx.accounts.schedule.milestones.get_mut(index as usize).ok_or(AapError::InvalidMilestone)?;
+    require!(milestone.payee == party, AapError::Unauthorized);
+    aap_core::milestone::require_submittable(milestone.status).map_err(AapError::from)?;
+
+    let previous_status = milestone.status;
+    milestone.status = MILESTONE_SUBMITTED;
+    milestone.submitted_at = clock.unix_timestamp;
+
+    emit!(MilestoneSubmitted {
+        version: EVENT_VERSION,
+        agreement_id,
+        index,
+        party: ctx.accounts.signer_identity.key(),
+        actor: ctx.accounts.signer.key(),
+        previous_status,
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,42 @@
+use anchor_lang::prelude::*;
+use crate::constants::EVENT_VERSION;
+use crate::errors::AapError;
+use crate::events::FreezeLifted;
+use crate::state::{AgentIdentity, Freeze};
+
+/// Lift an agent freeze. Checked against the identity's current authority,
+/// which also receives the rent.
+#[derive(Accounts)]
+pub struct UnfreezeAgent<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        has_one = authority @ AapError::Unauthorized,
+    )]
+    pub agent_identity: Account<'info, AgentIdentity>,
+
+    #[account(
+        mut,
+        close = authority,
+        seeds = [b"freeze", agent_identity.key().as_ref()],
+        bump = freeze.bump,
+    )]
+    pub freeze: Account<'info, Freeze>,
+}
+
+pub fn handler(ctx: Context<UnfreezeAgent>) -> Result<()> {
+    let freeze = &ctx.accounts.freeze;
+
+    emit!(FreezeLifted {
+        version: EVENT_VERSION,
+        target: freeze.target,
+        authority: ctx.accounts.authority.key(),
+        actor: ctx.accounts.authority.key(),
+        authority_wide: false,
+        frozen_at: freeze.frozen_at,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
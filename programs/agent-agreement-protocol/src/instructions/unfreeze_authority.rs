@@ -0,0 +1,34 @@
+use anchor_lang::prelude::*;
+use crate::constants::EVENT_VERSION;
+use crate::events::FreezeLifted;
+use crate::state::Freeze;
+
+#[derive(Accounts)]
+pub struct UnfreezeAuthority<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        close = authority,
+        seeds = [b"freeze", authority.key().as_ref()],
+        bump = freeze.bump,
+    )]
+    pub freeze: Account<'info, Freeze>,
+}
+
+pub fn handler(ctx: Context<UnfreezeAuthority>) -> Result<()> {
+    let freeze = &ctx.accounts.freeze;
+
+    emit!(FreezeLifted {
+        version: EVENT_VERSION,
+        target: freeze.target,
+        authority: freeze.authority,
+        actor: ctx.accounts.authority.key(),
+        authority_wide: true,
+        frozen_at: freeze.frozen_at,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,39 @@
+use anchor_lang::prelude::*;
+use crate::constants::*;
+use crate::errors::AapError;
+use crate::events::ConfigUpdated;
+use crate::state::{ConfigParams, ProtocolConfig};
+
+/// Replace the config's settable fields, including handing over `admin`.
+#[derive(Accounts)]
+pub struct UpdateConfig<'info> {
+    pub admin: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [CONFIG_SEED],
+        bump = config.bump,
+        has_one = admin @ AapError::Unauthorized,
+    )]
+    pub config: Account<'info, ProtocolConfig>,
+}
+
+pub fn handler(ctx: Context<UpdateConfig>, params: ConfigParams) -> Result<()> {
+    aap_core::parties::validate_party_limit(params.max_parties).map_err(AapError::from)?;
+
+    let config = &mut ctx.accounts.config;
+    config.apply(params);
+
+    emit!(ConfigUpdated {
+        version: EVENT_VERSION,
+        admin: config.admin,
+        actor: ctx.accounts.admin.key(),
+        paused: config.paused,
+        max_parties: config.max_parties,
+        fee_lamports: config.fee_lamports,
+        treasury: config.treasury,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
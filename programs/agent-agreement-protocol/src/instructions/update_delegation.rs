@@ -1,7 +1,8 @@
 use anchor_lang::prelude::*;
+use crate::constants::{CONFIG_SEED, EVENT_VERSION};
 use crate::errors::AapError;
 use crate::events::DelegationUpdated;
-use crate::state::{AgentIdentity, DelegationScope};
+use crate::state::{AgentIdentity, DelegationScope, ProtocolConfig};
 
 #[derive(Accounts)]
 pub struct UpdateDelegation<'info> {
@@ -12,25 +13,34 @@ pub struct UpdateDelegation<'info> {
         has_one = authority @ AapError::Unauthorized,
     )]
     pub agent_identity: Account<'info, AgentIdentity>,
+
+    #[account(
+        seeds = [CONFIG_SEED],
+        bump = config.bump,
+        constraint = !config.paused @ AapError::ProtocolPaused,
+    )]
+    pub config: Account<'info, ProtocolConfig>,
 }
 
 pub fn handler(
     ctx: Context<UpdateDelegation>,
     new_scope: DelegationScope,
 ) -> Result<()> {
-    if new_scope.expires_at != 0 {
-        let clock = Clock::get()?;
-        require!(
-            new_scope.expires_at > clock.unix_timestamp,
-            AapError::ScopeExpired
-        );
-    }
+    let now = Clock::get()?.unix_timestamp;
+    aap_core::scope::validate_new_scope(&new_scope.into(), now).map_err(AapError::from)?;
 
+    let previous_scope = ctx.accounts.agent_identity.scope;
     ctx.accounts.agent_identity.scope = new_scope;
 
     emit!(DelegationUpdated {
+        version: EVENT_VERSION,
         authority: ctx.accounts.authority.key(),
         agent_identity: ctx.accounts.agent_identity.key(),
+        agent_key: ctx.accounts.agent_identity.agent_key,
+        actor: ctx.accounts.authority.key(),
+        previous_scope,
+        scope: new_scope,
+        timestamp: now,
     });
 
     Ok(())
@@ -0,0 +1,65 @@
+use anchor_lang::prelude::*;
+use crate::constants::*;
+use crate::errors::AapError;
+use crate::events::AmendmentWithdrawn;
+use crate::state::{AgentIdentity, Agreement, AgreementAmendment, ProtocolConfig};
+
+/// The proposer (agent key or authority) drops a pending amendment. The record
+/// stays as history and the next version can be proposed.
+#[derive(Accounts)]
+#[instruction(agreement_id: [u8; 16], version: u16)]
+pub struct WithdrawAmendment<'info> {
+    pub signer: Signer<'info>,
+
+    #[account(
+        constraint = (
+            proposer_identity.agent_key == signer.key() ||
+            proposer_identity.authority == signer.key()
+        ) @ AapError::Unauthorized,
+    )]
+    pub proposer_identity: Account<'info, AgentIdentity>,
+
+    #[account(
+        seeds = [b"agreement", agreement_id.as_ref()],
+        bump = agreement.bump,
+        constraint = agreement.proposer == proposer_identity.key() @ AapError::Unauthorized,
+    )]
+    pub agreement: Account<'info, Agreement>,
+
+    #[account(
+        mut,
+        seeds = [b"amendment", agreement.key().as_ref(), &version.to_le_bytes()],
+        bump = amendment.bump,
+        constraint = amendment.status == AMENDMENT_PENDING @ AapError::AmendmentNotPending,
+    )]
+    pub amendment: Account<'info, AgreementAmendment>,
+
+    #[account(
+        seeds = [CONFIG_SEED],
+        bump = config.bump,
+        constraint = !config.paused @ AapError::ProtocolPaused,
+    )]
+    pub config: Account<'info, ProtocolConfig>,
+}
+
+pub fn handler(
+    ctx: Context<WithdrawAmendment>,
+    agreement_id: [u8; 16],
+    version: u16,
+) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+    let amendment = &mut ctx.accounts.amendment;
+    amendment.status = AMENDMENT_WITHDRAWN;
+    amendment.resolved_at = now;
+
+    emit!(AmendmentWithdrawn {
+        version: EVENT_VERSION,
+        agreement_id,
+        amendment: amendment.key(),
+        amendment_version: version,
+        actor: ctx.accounts.signer.key(),
+        timestamp: now,
+    });
+
+    Ok(())
+}
@@ -1,8 +1,13 @@
 use anchor_lang::prelude::*;
-use crate::state::{AgentIdentity, AgentVault};
+use crate::constants::{CONFIG_SEED, EVENT_VERSION};
+use crate::state::{AgentIdentity, AgentVault, ProtocolConfig};
 use crate::errors::AapError;
 use crate::events::VaultWithdraw;
 
+/// The authority withdraws available vault funds to any `destination`, or to
+/// itself when it is omitted. A council's signer PDA can't move lamports on,
+/// so it names a member's wallet. `destination` is last so callers built
+/// before it existed still work.
 #[derive(Accounts)]
 pub struct WithdrawFromVault<'info> {
     #[account(mut)]
@@ -22,6 +27,16 @@ pub struct WithdrawFromVault<'info> {
     pub vault: Account<'info, AgentVault>,
 
     pub system_program: Program<'info, System>,
+
+    #[account(
+        seeds = [CONFIG_SEED],
+        bump = config.bump,
+    )]
+    pub config: Account<'info, ProtocolConfig>,
+
+    /// CHECK: Any account chosen by the authority; it only receives lamports
+    #[account(mut)]
+    pub destination: Option<UncheckedAccount<'info>>,
 }
 
 pub fn handler(ctx: Context<WithdrawFromVault>, amount: u64) -> Result<()> {
@@ -34,18 +49,26 @@ pub fn handler(ctx: Context<WithdrawFromVault>, amount: u64) -> Result<()> {
 
     require!(amount <= available, AapError::InsufficientVaultBalance);
 
-    // Transfer SOL from vault PDA to authority
+    // Transfer SOL from vault PDA to the destination
     // For PDA transfers, we modify lamports directly
     **vault.to_account_info().try_borrow_mut_lamports()? -= amount;
-    **ctx.accounts.authority.to_account_info().try_borrow_mut_lamports()? += amount;
+    let destination = match &ctx.accounts.destination {
+        Some(destination) => destination.to_account_info(),
+        None => ctx.accounts.authority.to_account_info(),
+    };
+    **destination.try_borrow_mut_lamports()? += amount;
 
     vault.total_withdrawn = vault.total_withdrawn.checked_add(amount).unwrap();
 
     emit!(VaultWithdraw {
+        version: EVENT_VERSION,
         agent_identity: ctx.accounts.agent_identity.key(),
         authority: ctx.accounts.authority.key(),
         amount,
         remaining_balance: vault.to_account_info().lamports(),
+        actor: ctx.accounts.authority.key(),
+        total_withdrawn: vault.total_withdrawn,
+        timestamp: Clock::get()?.unix_timestamp,
     });
 
     Ok(())
@@ -4,10 +4,12 @@ pub mod constants;
 pub mod errors;
 pub mod events;
 pub mod instructions;
+pub mod migration;
 pub mod state;
 
 use instructions::*;
-use state::DelegationScope;
+use migration::{AgreementSnapshot, PartySnapshot};
+use state::{ActionAccount, ConfigParams, DelegationScope, MilestoneTerms, RecurringTerms, RevenueSplit, SafeTerms};
 
 declare_id!("BzHyb5Eevigb6cyfJT5cd27zVhu92sY5isvmHUYe6NwZ");
 
@@ -44,6 +46,7 @@ pub mod agent_agreement_protocol {
         instructions::revoke_agent::handler(ctx)
     }
 
+    #[allow(clippy::too_many_arguments)]
     pub fn propose_agreement(
         ctx: Context<ProposeAgreement>,
         agreement_id: [u8; 16],
@@ -133,4 +136,377 @@ pub mod agent_agreement_protocol {
     ) -> Result<()> {
         instructions::sign_agreement_direct::handler(ctx, agreement_id)
     }
+
+    /// Move an agent identity into aap-compressed and leave a tombstone.
+    pub fn migrate_agent<'info>(
+        ctx: Context<'_, '_, '_, 'info, MigrateAgent<'info>>,
+        registered_agent_key: Pubkey,
+        light_args: Vec<u8>,
+    ) -> Result<()> {
+        instructions::migrate_agent::handler(ctx, registered_agent_key, light_args)
+    }
+
+    /// Move an agreement into aap-compressed and leave a tombstone.
+    pub fn migrate_agreement<'info>(
+        ctx: Context<'_, '_, '_, 'info, MigrateAgreement<'info>>,
+        agreement_id: [u8; 16],
+        amendment_version: u16,
+        light_args: Vec<u8>,
+    ) -> Result<()> {
+        instructions::migrate_agreement::handler(ctx, agreement_id, amendment_version, light_args)
+    }
+
+    /// Move an agreement party into aap-compressed and leave a tombstone.
+    pub fn migrate_party<'info>(
+        ctx: Context<'_, '_, '_, 'info, MigrateParty<'info>>,
+        agreement_id: [u8; 16],
+        light_args: Vec<u8>,
+    ) -> Result<()> {
+        instructions::migrate_party::handler(ctx, agreement_id, light_args)
+    }
+
+    /// Recreate an agreement from aap-compressed (CPI from `decompress_agreement`).
+    pub fn materialize_agreement(
+        ctx: Context<MaterializeAgreement>,
+        snapshot: AgreementSnapshot,
+    ) -> Result<()> {
+        instructions::materialize_agreement::handler(ctx, snapshot)
+    }
+
+    /// Recreate an agreement party from aap-compressed (CPI from `decompress_party`).
+    pub fn materialize_party(
+        ctx: Context<MaterializeParty>,
+        snapshot: PartySnapshot,
+    ) -> Result<()> {
+        instructions::materialize_party::handler(ctx, snapshot)
+    }
+
+    /// Replace an identity's agent key, keeping the identity address.
+    pub fn rotate_agent_key(
+        ctx: Context<RotateAgentKey>,
+        new_agent_key: Pubkey,
+    ) -> Result<()> {
+        instructions::rotate_agent_key::handler(ctx, new_agent_key)
+    }
+
+    /// Offer an identity, its vault and sub-agents to a new authority.
+    pub fn propose_authority_transfer(
+        ctx: Context<ProposeAuthorityTransfer>,
+        new_authority: Pubkey,
+    ) -> Result<()> {
+        instructions::propose_authority_transfer::handler(ctx, new_authority)
+    }
+
+    /// Take over a proposed identity; sub-agents and their vaults as remaining accounts.
+    pub fn accept_authority_transfer<'info>(
+        ctx: Context<'_, '_, '_, 'info, AcceptAuthorityTransfer<'info>>,
+    ) -> Result<()> {
+        instructions::accept_authority_transfer::handler(ctx)
+    }
+
+    /// Withdraw a pending authority transfer.
+    pub fn cancel_authority_transfer(ctx: Context<CancelAuthorityTransfer>) -> Result<()> {
+        instructions::cancel_authority_transfer::handler(ctx)
+    }
+
+    /// Create an M-of-N authority set whose signer PDA can hold identities.
+    pub fn create_authority_set(
+        ctx: Context<CreateAuthoritySet>,
+        set_id: [u8; 16],
+        members: Vec<Pubkey>,
+        threshold: u8,
+    ) -> Result<()> {
+        instructions::create_authority_set::handler(ctx, set_id, members, threshold)
+    }
+
+    /// Propose an instruction for an authority set to run as its signer PDA.
+    pub fn propose_authority_action(
+        ctx: Context<ProposeAuthorityAction>,
+        accounts: Vec<ActionAccount>,
+        data: Vec<u8>,
+    ) -> Result<()> {
+        instructions::propose_authority_action::handler(ctx, accounts, data)
+    }
+
+    pub fn approve_authority_action(
+        ctx: Context<ApproveAuthorityAction>,
+        index: u64,
+    ) -> Result<()> {
+        instructions::approve_authority_action::handler(ctx, index)
+    }
+
+    /// Run an approved action; its accounts as remaining accounts.
+    pub fn execute_authority_action<'info>(
+        ctx: Context<'_, '_, '_, 'info, ExecuteAuthorityAction<'info>>,
+        index: u64,
+    ) -> Result<()> {
+        instructions::execute_authority_action::handler(ctx, index)
+    }
+
+    /// Withdraw a pending action (proposer only).
+    pub fn cancel_authority_action(ctx: Context<CancelAuthorityAction>) -> Result<()> {
+        instructions::cancel_authority_action::handler(ctx)
+    }
+
+    /// Stop an agent and its sub-agents from proposing, adding parties or signing.
+    pub fn freeze_agent(ctx: Context<FreezeAgent>) -> Result<()> {
+        instructions::freeze_agent::handler(ctx)
+    }
+
+    pub fn unfreeze_agent(ctx: Context<UnfreezeAgent>) -> Result<()> {
+        instructions::unfreeze_agent::handler(ctx)
+    }
+
+    /// Freeze every agent held by the signing authority.
+    pub fn freeze_authority(ctx: Context<FreezeAuthority>) -> Result<()> {
+        instructions::freeze_authority::handler(ctx)
+    }
+
+    pub fn unfreeze_authority(ctx: Context<UnfreezeAuthority>) -> Result<()> {
+        instructions::unfreeze_authority::handler(ctx)
+    }
+
+    /// Create the protocol config (program upgrade authority only).
+    pub fn initialize_config(ctx: Context<InitializeConfig>, params: ConfigParams) -> Result<()> {
+        instructions::initialize_config::handler(ctx, params)
+    }
+
+    /// Change admin, party limit, fee or treasury (admin only).
+    pub fn update_config(ctx: Context<UpdateConfig>, params: ConfigParams) -> Result<()> {
+        instructions::update_config::handler(ctx, params)
+    }
+
+    /// Pause or resume the protocol (admin only).
+    pub fn set_paused(ctx: Context<SetPaused>, paused: bool) -> Result<()> {
+        instructions::set_paused::handler(ctx, paused)
+    }
+
+    /// Amend the agreement's terms (proposer only). Applied at once while
+    /// Proposed; pending until every party signs while Active.
+    pub fn propose_amendment<'info>(
+        ctx: Context<'_, '_, '_, 'info, ProposeAmendment<'info>>,
+        agreement_id: [u8; 16],
+        version: u16,
+        terms_hash: [u8; 32],
+        terms_uri: [u8; 64],
+        expires_at: i64,
+    ) -> Result<()> {
+        instructions::propose_amendment::handler(ctx, agreement_id, version, terms_hash, terms_uri, expires_at)
+    }
+
+    pub fn sign_amendment(ctx: Context<SignAmendment>, agreement_id: [u8; 16], version: u16) -> Result<()> {
+        instructions::sign_amendment::handler(ctx, agreement_id, version)
+    }
+
+    pub fn sign_amendment_direct(
+        ctx: Context<SignAmendmentDirect>,
+        agreement_id: [u8; 16],
+        version: u16,
+    ) -> Result<()> {
+        instructions::sign_amendment_direct::handler(ctx, agreement_id, version)
+    }
+
+    pub fn withdraw_amendment(
+        ctx: Context<WithdrawAmendment>,
+        agreement_id: [u8; 16],
+        version: u16,
+    ) -> Result<()> {
+        instructions::withdraw_amendment::handler(ctx, agreement_id, version)
+    }
+
+    /// An unsigned invited party offers other terms or expiry while Proposed.
+    pub fn counter_propose(
+        ctx: Context<CounterPropose>,
+        agreement_id: [u8; 16],
+        round: u16,
+        terms_hash: [u8; 32],
+        terms_uri: [u8; 64],
+        expires_at: i64,
+    ) -> Result<()> {
+        instructions::counter_propose::handler(ctx, agreement_id, round, terms_hash, terms_uri, expires_at)
+    }
+
+    /// Apply a counter-proposal as amendment `version`; signed parties as remaining accounts.
+    pub fn accept_counter_proposal<'info>(
+        ctx: Context<'_, '_, '_, 'info, AcceptCounterProposal<'info>>,
+        agreement_id: [u8; 16],
+        version: u16,
+    ) -> Result<()> {
+        instructions::accept_counter_proposal::handler(ctx, agreement_id, version)
+    }
+
+    pub fn reject_counter_proposal(ctx: Context<RejectCounterProposal>, agreement_id: [u8; 16]) -> Result<()> {
+        instructions::reject_counter_proposal::handler(ctx, agreement_id)
+    }
+
+    /// An unsigned invited party refuses; the agreement becomes Rejected.
+    pub fn decline_agreement(ctx: Context<DeclineAgreement>, agreement_id: [u8; 16]) -> Result<()> {
+        instructions::decline_agreement::handler(ctx, agreement_id)
+    }
+
+    pub fn decline_agreement_direct(ctx: Context<DeclineAgreementDirect>, agreement_id: [u8; 16]) -> Result<()> {
+        instructions::decline_agreement_direct::handler(ctx, agreement_id)
+    }
+
+    /// Proposer removes an unsigned party while Proposed, closing its record.
+    pub fn remove_party(ctx: Context<RemoveParty>, agreement_id: [u8; 16]) -> Result<()> {
+        instructions::remove_party::handler(ctx, agreement_id)
+    }
+
+    /// Proposer changes `num_parties` while Proposed.
+    pub fn set_num_parties(ctx: Context<SetNumParties>, agreement_id: [u8; 16], num_parties: u8) -> Result<()> {
+        instructions::set_num_parties::handler(ctx, agreement_id, num_parties)
+    }
+
+    /// A party's agent moves SOL from its vault into the agreement's escrow.
+    pub fn fund_escrow(ctx: Context<FundEscrow>, agreement_id: [u8; 16], amount: u64) -> Result<()> {
+        instructions::fund_escrow::handler(ctx, agreement_id, amount)
+    }
+
+    /// Pays a party its escrow deposit or settlement share once the agreement has ended. Permissionless.
+    pub fn release_escrow(ctx: Context<ReleaseEscrow>, agreement_id: [u8; 16]) -> Result<()> {
+        instructions::release_escrow::handler(ctx, agreement_id)
+    }
+
+    /// A party proposes terminating an Active agreement with a split of its escrow.
+    /// Remaining accounts: every party PDA, in the order of `amounts`.
+    pub fn propose_termination<'info>(
+        ctx: Context<'_, '_, '_, 'info, ProposeTermination<'info>>,
+        agreement_id: [u8; 16],
+        amounts: Vec<u64>,
+    ) -> Result<()> {
+        instructions::propose_termination::handler(ctx, agreement_id, amounts)
+    }
+
+    /// A party approves a pending termination; the last approval terminates the agreement.
+    pub fn approve_termination(ctx: Context<ApproveTermination>, agreement_id: [u8; 16]) -> Result<()> {
+        instructions::approve_termination::handler(ctx, agreement_id)
+    }
+
+    /// Approve a termination as a direct (wallet) party.
+    pub fn approve_termination_direct(ctx: Context<ApproveTerminationDirect>, agreement_id: [u8; 16]) -> Result<()> {
+        instructions::approve_termination_direct::handler(ctx, agreement_id)
+    }
+
+    /// Proposer withdraws a pending termination.
+    pub fn cancel_termination(ctx: Context<CancelTermination>, agreement_id: [u8; 16]) -> Result<()> {
+        instructions::cancel_termination::handler(ctx, agreement_id)
+    }
+
+    /// Proposer attaches a milestone schedule before any other party signs.
+    pub fn set_milestones(
+        ctx: Context<SetMilestones>,
+        agreement_id: [u8; 16],
+        milestones: Vec<MilestoneTerms>,
+    ) -> Result<()> {
+        instructions::set_milestones::handler(ctx, agreement_id, milestones)
+    }
+
+    /// Payee submits a milestone as delivered.
+    pub fn submit_milestone(ctx: Context<SubmitMilestone>, agreement_id: [u8; 16], index: u8) -> Result<()> {
+        instructions::submit_milestone::handler(ctx, agreement_id, index)
+    }
+
+    /// Payer approves a milestone for release.
+    pub fn approve_milestone(ctx: Context<ApproveMilestone>, agreement_id: [u8; 16], index: u8) -> Result<()> {
+        instructions::approve_milestone::handler(ctx, agreement_id, index)
+    }
+
+    /// Payer disputes a submitted or overdue milestone.
+    pub fn dispute_milestone(ctx: Context<DisputeMilestone>, agreement_id: [u8; 16], index: u8) -> Result<()> {
+        instructions::dispute_milestone::handler(ctx, agreement_id, index)
+    }
+
+    /// Pays an approved milestone from escrow; the last one fulfills the agreement. Permissionless.
+    pub fn release_milestone(ctx: Context<ReleaseMilestone>, agreement_id: [u8; 16], index: u8) -> Result<()> {
+        instructions::release_milestone::handler(ctx, agreement_id, index)
+    }
+
+    /// Proposer makes the agreement a recurring payment before any other party signs.
+    pub fn set_recurring_payment(
+        ctx: Context<SetRecurringPayment>,
+        agreement_id: [u8; 16],
+        terms: RecurringTerms,
+    ) -> Result<()> {
+        instructions::set_recurring_payment::handler(ctx, agreement_id, terms)
+    }
+
+    /// Pays (or records as missed) the next due period from the payer's vault. Permissionless.
+    pub fn crank_payment(ctx: Context<CrankPayment>, agreement_id: [u8; 16]) -> Result<()> {
+        instructions::crank_payment::handler(ctx, agreement_id)
+    }
+
+    /// Payer or payee stops a recurring payment.
+    pub fn cancel_recurring_payment(ctx: Context<CancelRecurringPayment>, agreement_id: [u8; 16]) -> Result<()> {
+        instructions::cancel_recurring_payment::handler(ctx, agreement_id)
+    }
+
+    /// Streams SOL from the signer's vault, vesting to a payee until the agreement expires.
+    pub fn fund_stream<'info>(
+        ctx: Context<'_, '_, '_, 'info, FundStream<'info>>,
+        agreement_id: [u8; 16],
+        amount: u64,
+    ) -> Result<()> {
+        instructions::fund_stream::handler(ctx, agreement_id, amount)
+    }
+
+    /// Pays the payee what has vested on the stream. Permissionless.
+    pub fn claim_vested(ctx: Context<ClaimVested>, agreement_id: [u8; 16]) -> Result<()> {
+        instructions::claim_vested::handler(ctx, agreement_id)
+    }
+
+    /// Payer or payee stops a stream; the unvested part goes back to the payer.
+    pub fn cancel_stream(ctx: Context<CancelStream>, agreement_id: [u8; 16]) -> Result<()> {
+        instructions::cancel_stream::handler(ctx, agreement_id)
+    }
+
+    /// Pays out a stream after the agreement is fulfilled or terminated. Permissionless.
+    pub fn settle_stream(ctx: Context<SettleStream>, agreement_id: [u8; 16]) -> Result<()> {
+        instructions::settle_stream::handler(ctx, agreement_id)
+    }
+
+    /// Proposer sets the basis-point splits of a revenue-share agreement before any other party signs.
+    pub fn set_revenue_share(
+        ctx: Context<SetRevenueShare>,
+        agreement_id: [u8; 16],
+        splits: Vec<RevenueSplit>,
+    ) -> Result<()> {
+        instructions::set_revenue_share::handler(ctx, agreement_id, splits)
+    }
+
+    /// Pays out the SOL held by the revenue receiver by the splits. Permissionless.
+    pub fn distribute<'info>(
+        ctx: Context<'_, '_, '_, 'info, Distribute<'info>>,
+        agreement_id: [u8; 16],
+    ) -> Result<()> {
+        instructions::distribute::handler(ctx, agreement_id)
+    }
+
+    /// Pays out one of the revenue receiver's token accounts by the splits. Permissionless.
+    pub fn distribute_tokens<'info>(
+        ctx: Context<'_, '_, 'info, 'info, DistributeTokens<'info>>,
+        agreement_id: [u8; 16],
+    ) -> Result<()> {
+        instructions::distribute_tokens::handler(ctx, agreement_id)
+    }
+
+    /// Proposer sets the typed terms of a SAFE agreement before any other party signs.
+    pub fn set_safe(ctx: Context<SetSafe>, agreement_id: [u8; 16], terms: SafeTerms) -> Result<()> {
+        instructions::set_safe::handler(ctx, agreement_id, terms)
+    }
+
+    /// SAFE investor signs and funds the escrow with its investment.
+    pub fn sign_safe(ctx: Context<SignSafe>, agreement_id: [u8; 16]) -> Result<()> {
+        instructions::sign_safe::handler(ctx, agreement_id)
+    }
+
+    /// Company converts a funded SAFE in a priced round; fulfills the agreement.
+    pub fn convert_safe(ctx: Context<ConvertSafe>, agreement_id: [u8; 16], round_valuation: u64) -> Result<()> {
+        instructions::convert_safe::handler(ctx, agreement_id, round_valuation)
+    }
+
+    /// Returns a funded SAFE's investment to the investor.
+    pub fn refund_safe(ctx: Context<RefundSafe>, agreement_id: [u8; 16]) -> Result<()> {
+        instructions::refund_safe::handler(ctx, agreement_id)
+    }
 }
@@ -0,0 +1,108 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::{instruction::Instruction, program::invoke_signed};
+use crate::constants::*;
+use crate::state::DelegationScope;
+
+// Anchor discriminators of the aap-compressed import instructions:
+// sha256("global:<name>")[..8]
+pub const IMPORT_AGENT_DISCRIMINATOR: [u8; 8] = [196, 225, 147, 5, 254, 130, 180, 92];
+pub const IMPORT_AGREEMENT_DISCRIMINATOR: [u8; 8] = [30, 209, 113, 145, 222, 162, 30, 235];
+pub const IMPORT_PARTY_DISCRIMINATOR: [u8; 8] = [39, 40, 149, 149, 96, 220, 218, 54];
+
+// Snapshots mirror aap_compressed::{AgentSnapshot, AgreementSnapshot, PartySnapshot}
+// and are used in both directions (migrate → import, decompress → materialize).
+// Identities are referenced by agent_key; each program maps that to its own address.
+// Field order is the borsh wire format — keep both sides in sync.
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct AgentSnapshot {
+    pub authority: Pubkey,
+    pub agent_key: Pubkey,
+    pub metadata_hash: [u8; 32],
+    pub scope: DelegationScope,
+    pub parent_agent_key: Pubkey, // Pubkey::default() if no parent
+    pub created_at: i64,
+    pub registered_agent_key: Pubkey, // key the identity's address derives from; differs from agent_key once rotated
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct AgreementSnapshot {
+    pub agreement_id: [u8; 16],
+    pub agreement_type: u8,
+    pub status: u8,
+    pub visibility: u8,
+    pub proposer_agent_key: Pubkey,
+    pub terms_hash: [u8; 32],
+    pub terms_uri: [u8; 64],
+    pub num_parties: u8,
+    pub num_signed: u8,
+    pub parties_added: u8,
+    pub created_at: i64,
+    pub expires_at: i64,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct PartySnapshot {
+    pub agreement_id: [u8; 16],
+    pub agent_key: Pubkey,
+    pub role: u8,
+    pub signed: bool,
+    pub signed_at: i64,
+}
+
+/// Accounts shared by every CPI into an aap-compressed import instruction.
+pub struct ImportCpiAccounts<'a, 'info> {
+    pub payer: &'a AccountInfo<'info>,
+    pub migration_authority: &'a AccountInfo<'info>,
+    pub migration_authority_bump: u8,
+    pub compressed_program: &'a AccountInfo<'info>,
+    /// Light system accounts and trees, forwarded untouched.
+    pub remaining_accounts: &'a [AccountInfo<'info>],
+}
+
+/// CPI into an aap-compressed `import_*` instruction.
+///
+/// `light_args` is the client-built, borsh-encoded
+/// `(ValidityProof, PackedAddressTreeInfo, output_state_tree_index)` prefix.
+/// The snapshot is appended here from on-chain data, so the client can't alter it.
+pub fn invoke_import<T: AnchorSerialize>(
+    discriminator: [u8; 8],
+    light_args: &[u8],
+    snapshot: &T,
+    accounts: ImportCpiAccounts,
+) -> Result<()> {
+    let mut data = discriminator.to_vec();
+    data.extend_from_slice(light_args);
+    snapshot.serialize(&mut data)?;
+
+    let mut metas = vec![
+        AccountMeta::new(accounts.payer.key(), true),
+        AccountMeta::new_readonly(accounts.migration_authority.key(), true),
+    ];
+    metas.extend(accounts.remaining_accounts.iter().map(|account| {
+        if account.is_writable {
+            AccountMeta::new(account.key(), account.is_signer)
+        } else {
+            AccountMeta::new_readonly(account.key(), account.is_signer)
+        }
+    }));
+
+    let mut infos = vec![
+        accounts.payer.clone(),
+        accounts.migration_authority.clone(),
+    ];
+    infos.extend_from_slice(accounts.remaining_accounts);
+    infos.push(accounts.compressed_program.clone());
+
+    invoke_signed(
+        &Instruction {
+            program_id: AAP_COMPRESSED_PROGRAM_ID,
+            accounts: metas,
+            data,
+        },
+        &infos,
+        &[&[MIGRATION_SEED, &[accounts.migration_authority_bump]]],
+    )?;
+
+    Ok(())
+}
@@ -13,6 +13,12 @@ pub struct AgentIdentity {
 
 impl AgentIdentity {
     pub const LEN: usize = 8 + 32 + 32 + 32 + 18 + 32 + 8 + 1; // 163 bytes
+
+    /// Whether `agent_key` was rotated away from the key this identity's
+    /// address (`address`) was derived from. Rotated keys have an AgentKeyLookup.
+    pub fn is_rotated(&self, address: &Pubkey) -> bool {
+        Pubkey::find_program_address(&[b"agent", self.agent_key.as_ref()], &crate::ID).0 != *address
+    }
 }
 
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, InitSpace)]
@@ -22,3 +28,14 @@ pub struct DelegationScope {
     pub max_commit_lamports: u64,  // 8 bytes — max value per agreement (0 = unlimited)
     pub expires_at: i64,           // 8 bytes — 0 = never expires
 }
+
+impl From<DelegationScope> for aap_core::Scope {
+    fn from(s: DelegationScope) -> Self {
+        aap_core::Scope {
+            can_sign_agreements: s.can_sign_agreements,
+            can_commit_funds: s.can_commit_funds,
+            max_commit_lamports: s.max_commit_lamports,
+            expires_at: s.expires_at,
+        }
+    }
+}
@@ -0,0 +1,15 @@
+use anchor_lang::prelude::*;
+
+/// Points an agent key at the identity it was rotated into.
+/// Seeds: ["agent", agent_key] — the address a fresh identity for that key
+/// would take, so a key held by a rotated identity can't register a second one.
+/// Closed when the identity rotates away from the key or is revoked.
+#[account]
+pub struct AgentKeyLookup {
+    pub identity: Pubkey,          // 32 bytes — AgentIdentity now holding the key
+    pub bump: u8,                  // 1 byte
+}
+
+impl AgentKeyLookup {
+    pub const LEN: usize = 8 + 32 + 1; // 41 bytes
+}
@@ -0,0 +1,47 @@
+use anchor_lang::prelude::*;
+use crate::constants::AMENDMENT_APPLIED;
+use super::Agreement;
+
+/// One version of an agreement's terms after the original (version 0, kept on
+/// the Agreement at proposal). Versions are contiguous from 1, so walking
+/// ["amendment", agreement, 1], [.., 2], … until a missing PDA yields the full
+/// history; each records the hash it replaced. Amendments proposed while the
+/// agreement is Proposed apply at once; while Active they stay pending until
+/// every party has signed. Never closed, so the history outlives the agreement.
+/// Seeds: ["amendment", agreement, version (u16 LE)]
+#[account]
+pub struct AgreementAmendment {
+    pub agreement: Pubkey,             // 32 bytes — Agreement PDA
+    pub version: u16,                  // 2 bytes — 1-based
+    pub proposer: Pubkey,              // 32 bytes — AgentIdentity PDA (the agreement's proposer)
+    pub phase: u8,                     // 1 byte — agreement status when proposed (Proposed / Active)
+    pub status: u8,                    // 1 byte — AMENDMENT_PENDING / APPLIED / WITHDRAWN
+    pub previous_terms_hash: [u8; 32], // 32 bytes — terms this version replaces
+    pub terms_hash: [u8; 32],          // 32 bytes
+    pub terms_uri: [u8; 64],           // 64 bytes
+    pub expires_at: i64,               // 8 bytes — new agreement expiry (0 = none)
+    pub required: u8,                  // 1 byte — signatures needed (num_parties when proposed)
+    pub approvals: Vec<Pubkey>,        // 4 + 32 * required bytes — AgreementParty PDAs that signed
+    pub proposed_at: i64,              // 8 bytes
+    pub resolved_at: i64,              // 8 bytes — 0 while pending
+    pub bump: u8,                      // 1 byte
+}
+
+impl AgreementAmendment {
+    pub fn space(num_parties: u8) -> usize {
+        8 + 32 + 2 + 32 + 1 + 1 + 32 + 32 + 64 + 8 + 1 + 4 + 32 * num_parties as usize + 8 + 8 + 1
+    }
+
+    pub fn is_fully_signed(&self) -> bool {
+        aap_core::parties::is_fully_signed(self.approvals.len() as u8, self.required)
+    }
+
+    /// Make this version the agreement's current terms.
+    pub fn apply(&mut self, agreement: &mut Agreement, now: i64) {
+        agreement.terms_hash = self.terms_hash;
+        agreement.terms_uri = self.terms_uri;
+        agreement.expires_at = self.expires_at;
+        self.status = AMENDMENT_APPLIED;
+        self.resolved_at = now;
+    }
+}
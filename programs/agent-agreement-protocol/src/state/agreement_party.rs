@@ -1,4 +1,5 @@
 use anchor_lang::prelude::*;
+use crate::errors::AapError;
 
 #[account]
 pub struct AgreementParty {
@@ -13,4 +14,13 @@ pub struct AgreementParty {
 
 impl AgreementParty {
     pub const LEN: usize = 8 + 32 + 32 + 1 + 1 + 8 + 8 + 1; // 91 bytes
+
+    /// Reads a party passed as a remaining account, failing with
+    /// `PartyNotInAgreement` unless it is an AgreementParty of `agreement`.
+    pub fn load_member(info: &AccountInfo, agreement: &Pubkey) -> Result<AgreementParty> {
+        require!(info.owner == &crate::ID, AapError::PartyNotInAgreement);
+        let party = AgreementParty::try_deserialize(&mut &info.try_borrow_data()?[..])?;
+        require!(party.agreement == *agreement, AapError::PartyNotInAgreement);
+        Ok(party)
+    }
 }
@@ -0,0 +1,42 @@
+use anchor_lang::prelude::*;
+
+/// A V1 instruction an `AuthoritySet` wants to run as its signer PDA, pending
+/// until `threshold` members approve. Executing invokes it and closes this
+/// account; the proposer can cancel it before that.
+/// Seeds: ["authority_action", authority_set, index (u64 LE)]
+#[account]
+pub struct AuthorityAction {
+    pub authority_set: Pubkey,       // 32 bytes
+    pub index: u64,                  // 8 bytes — authority_set.action_count at proposal
+    pub proposer: Pubkey,            // 32 bytes — member who proposed, refunded on close
+    pub accounts: Vec<ActionAccount>, // 4 + 34 * n bytes — instruction accounts, in order
+    pub data: Vec<u8>,               // 4 + n bytes — instruction data (discriminator + args)
+    pub approvals: u16,              // 2 bytes — bitmap over authority_set.members
+    pub proposed_at: i64,            // 8 bytes
+    pub bump: u8,                    // 1 byte
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ActionAccount {
+    pub pubkey: Pubkey,    // 32 bytes
+    pub is_signer: bool,   // 1 byte — the set's signer PDA is signed for by the program
+    pub is_writable: bool, // 1 byte
+}
+
+impl AuthorityAction {
+    pub fn space(num_accounts: usize, data_len: usize) -> usize {
+        8 + 32 + 8 + 32 + 4 + 34 * num_accounts + 4 + data_len + 2 + 8 + 1
+    }
+
+    pub fn has_approved(&self, member_index: usize) -> bool {
+        self.approvals & (1 << member_index) != 0
+    }
+
+    pub fn approve(&mut self, member_index: usize) {
+        self.approvals |= 1 << member_index;
+    }
+
+    pub fn approval_count(&self) -> u8 {
+        self.approvals.count_ones() as u8
+    }
+}
@@ -0,0 +1,41 @@
+use anchor_lang::prelude::*;
+
+/// An M-of-N council that can hold an identity instead of a single human.
+/// The council acts through its signer PDA ["authority_signer", authority_set],
+/// which is the key stored as `authority`: approved `AuthorityAction`s are
+/// executed as a CPI back into this program signed by that PDA, so every
+/// `authority` check accepts it unchanged. Members are fixed; move identities
+/// to a new set (through an action) to change the council.
+/// Seeds: ["authority_set", set_id]
+#[account]
+pub struct AuthoritySet {
+    pub set_id: [u8; 16],          // 16 bytes — caller-chosen id
+    pub members: Vec<Pubkey>,      // 4 + 32 * n bytes — at most MAX_SET_MEMBERS, distinct
+    pub threshold: u8,             // 1 byte — approvals needed to execute
+    pub action_count: u64,         // 8 bytes — index of the next AuthorityAction
+    pub created_at: i64,           // 8 bytes
+    pub bump: u8,                  // 1 byte
+    pub signer_bump: u8,           // 1 byte — bump of the signer PDA
+}
+
+impl AuthoritySet {
+    pub fn space(num_members: usize) -> usize {
+        8 + 16 + 4 + 32 * num_members + 1 + 8 + 8 + 1 + 1
+    }
+
+    /// 1..=MAX_SET_MEMBERS distinct members and 1 <= threshold <= members.
+    pub fn is_valid(members: &[Pubkey], threshold: u8) -> bool {
+        let distinct = members
+            .iter()
+            .enumerate()
+            .all(|(i, m)| !members[..i].contains(m));
+        distinct
+            && (1..=crate::constants::MAX_SET_MEMBERS).contains(&members.len())
+            && threshold >= 1
+            && threshold as usize <= members.len()
+    }
+
+    pub fn member_index(&self, key: &Pubkey) -> Option<usize> {
+        self.members.iter().position(|m| m == key)
+    }
+}
@@ -0,0 +1,17 @@
+use anchor_lang::prelude::*;
+
+/// A pending hand-over of an identity (and its vault and sub-agents) to a
+/// new authority, created by the current authority and closed on accept or cancel.
+/// Seeds: ["authority_transfer", agent_identity.key()]
+#[account]
+pub struct AuthorityTransfer {
+    pub agent_identity: Pubkey,    // 32 bytes — identity being transferred
+    pub authority: Pubkey,         // 32 bytes — current authority, refunded on close
+    pub new_authority: Pubkey,     // 32 bytes — must sign the accept
+    pub proposed_at: i64,          // 8 bytes
+    pub bump: u8,                  // 1 byte
+}
+
+impl AuthorityTransfer {
+    pub const LEN: usize = 8 + 32 + 32 + 32 + 8 + 1; // 113 bytes
+}
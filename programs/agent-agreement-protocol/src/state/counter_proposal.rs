@@ -0,0 +1,28 @@
+use anchor_lang::prelude::*;
+
+/// Alternative terms an invited party offers while the agreement is Proposed.
+/// Rounds are numbered per party from 1 and contiguous, like amendment
+/// versions; a party can open the next round once the proposer has answered
+/// the previous one. Accepting turns the offer into an applied
+/// `AgreementAmendment`. Never closed, so the negotiation stays on-chain.
+/// Seeds: ["counter", party (AgreementParty PDA), round (u16 LE)]
+#[account]
+pub struct CounterProposal {
+    pub agreement: Pubkey,          // 32 bytes — Agreement PDA
+    pub party: Pubkey,              // 32 bytes — AgreementParty PDA of the countering party
+    pub agent_identity: Pubkey,     // 32 bytes — its AgentIdentity PDA
+    pub round: u16,                 // 2 bytes — 1-based, per party
+    pub status: u8,                 // 1 byte — COUNTER_PENDING / ACCEPTED / REJECTED
+    pub base_terms_hash: [u8; 32],  // 32 bytes — terms being countered; accept fails once they change
+    pub terms_hash: [u8; 32],       // 32 bytes
+    pub terms_uri: [u8; 64],        // 64 bytes
+    pub expires_at: i64,            // 8 bytes — suggested agreement expiry (0 = none)
+    pub amendment_version: u16,     // 2 bytes — amendment created on accept (0 otherwise)
+    pub proposed_at: i64,           // 8 bytes
+    pub resolved_at: i64,           // 8 bytes — 0 while pending
+    pub bump: u8,                   // 1 byte
+}
+
+impl CounterProposal {
+    pub const LEN: usize = 8 + 32 + 32 + 32 + 2 + 1 + 32 + 32 + 64 + 8 + 2 + 8 + 8 + 1; // 262 bytes
+}
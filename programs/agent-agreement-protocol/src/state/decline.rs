@@ -0,0 +1,17 @@
+use anchor_lang::prelude::*;
+
+/// Records which invited party declined an agreement, and when. Kept out of
+/// AgreementParty so live party accounts keep their layout; like amendments,
+/// it outlives the agreement it refers to.
+/// Seeds: ["decline", agreement_party]
+#[account]
+pub struct Decline {
+    pub agreement: Pubkey,         // 32 bytes — Agreement PDA
+    pub party: Pubkey,             // 32 bytes — AgreementParty PDA that declined
+    pub declined_at: i64,          // 8 bytes
+    pub bump: u8,                  // 1 byte
+}
+
+impl Decline {
+    pub const LEN: usize = 8 + 32 + 32 + 8 + 1; // 81 bytes
+}
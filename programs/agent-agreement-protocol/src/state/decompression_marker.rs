@@ -0,0 +1,16 @@
+use anchor_lang::prelude::*;
+
+/// Left behind by `materialize_agreement`, so `materialize_party` only adds
+/// parties to agreements that really came out of aap-compressed and not to
+/// native V1 agreements with the same id.
+/// Seeds: ["decompressed", agreement]
+#[account]
+pub struct DecompressionMarker {
+    pub agreement: Pubkey,         // 32 bytes — materialized Agreement PDA
+    pub decompressed_at: i64,      // 8 bytes
+    pub bump: u8,                  // 1 byte
+}
+
+impl DecompressionMarker {
+    pub const LEN: usize = 8 + 32 + 8 + 1; // 49 bytes
+}
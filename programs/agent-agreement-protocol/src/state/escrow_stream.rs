@@ -0,0 +1,31 @@
+use anchor_lang::prelude::*;
+
+/// SOL escrow that vests linearly to the payee from `start_at` until the
+/// agreement's `expires_at`, held as lamports on this PDA. The payer funds it
+/// from its AgentVault once the agreement is Active; the payee claims what
+/// has vested at any time. Cancelling the stream, or the agreement ending,
+/// pays out the vested remainder and returns the unvested part to the payer.
+/// Seeds: ["stream", agreement]
+#[account]
+pub struct EscrowStream {
+    pub agreement: Pubkey,         // 32 bytes — Agreement PDA
+    pub payer: Pubkey,             // 32 bytes — AgreementParty PDA that funded it
+    pub payee: Pubkey,             // 32 bytes — AgreementParty PDA it vests to
+    pub amount: u64,               // 8 bytes — lamports streamed in total
+    pub claimed: u64,              // 8 bytes — paid to the payee so far
+    pub start_at: i64,             // 8 bytes — funded; vesting starts
+    pub end_at: i64,               // 8 bytes — agreement expires_at; fully vested
+    pub status: u8,                // 1 byte — STREAM_ACTIVE / CANCELLED / SETTLED
+    pub refunded: u64,             // 8 bytes — unvested part returned to the payer
+    pub ended_at: i64,             // 8 bytes — vesting stopped; 0 while active
+    pub bump: u8,                  // 1 byte
+}
+
+impl EscrowStream {
+    pub const LEN: usize = 8 + 32 + 32 + 32 + 8 + 8 + 8 + 8 + 1 + 8 + 8 + 1; // 154 bytes
+
+    /// Vested and not yet claimed at `now`.
+    pub fn claimable(&self, now: i64) -> u64 {
+        aap_core::stream::vested_amount(self.amount, self.start_at, self.end_at, now).saturating_sub(self.claimed)
+    }
+}
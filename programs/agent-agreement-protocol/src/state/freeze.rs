@@ -0,0 +1,27 @@
+use anchor_lang::prelude::*;
+use crate::errors::AapError;
+
+/// Kill switch set by an authority. While it exists, the covered agents can't
+/// propose, add parties or sign, and recurring payments aren't pulled from
+/// their vaults; nothing else is touched, so closing it restores them as they
+/// were.
+/// Seeds: ["freeze", target] — target is an AgentIdentity PDA (covers the agent
+/// and its sub-agents) or an authority key (covers every agent it holds).
+#[account]
+pub struct Freeze {
+    pub target: Pubkey,            // 32 bytes — identity PDA or authority key
+    pub authority: Pubkey,         // 32 bytes — authority that set it
+    pub frozen_at: i64,            // 8 bytes
+    pub bump: u8,                  // 1 byte
+}
+
+impl Freeze {
+    pub const LEN: usize = 8 + 32 + 32 + 8 + 1; // 81 bytes
+
+    /// Fails with `AgentFrozen` if any of an agent's freeze PDAs (own, parent's,
+    /// authority's) exists. Only this program can create accounts it owns there.
+    pub fn require_unfrozen(freezes: &[&AccountInfo]) -> Result<()> {
+        require!(freezes.iter().all(|f| f.owner != &crate::ID), AapError::AgentFrozen);
+        Ok(())
+    }
+}
@@ -0,0 +1,19 @@
+use anchor_lang::prelude::*;
+
+/// Left behind when a V1 PDA is migrated into aap-compressed.
+/// Seeds: ["tombstone", original.key()]
+/// Later migrations use it to map V1 PDAs onto compressed addresses
+/// (e.g. an agreement's proposer identity → its agent_key).
+#[account]
+pub struct MigrationTombstone {
+    pub original: Pubkey,          // 32 bytes — the closed V1 PDA
+    pub kind: u8,                  // 1 byte — 0=Agent, 1=Agreement, 2=Party
+    pub key: [u8; 32],             // 32 bytes — registered agent_key (Agent), agreement_id padded (Agreement/Party)
+    pub authority: Pubkey,         // 32 bytes — who performed the migration
+    pub migrated_at: i64,          // 8 bytes
+    pub bump: u8,                  // 1 byte
+}
+
+impl MigrationTombstone {
+    pub const LEN: usize = 8 + 32 + 1 + 32 + 32 + 8 + 1; // 114 bytes
+}
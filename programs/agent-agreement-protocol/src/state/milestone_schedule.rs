@@ -0,0 +1,52 @@
+use anchor_lang::prelude::*;
+use crate::constants::MILESTONE_RELEASED;
+
+/// Optional payment plan of an agreement: each milestone pays part of its
+/// payer's escrow to the payee once approved. Set by the proposer before the
+/// other parties sign, so they sign the plan with the terms; the agreement is
+/// fulfilled when the last milestone is released.
+/// Seeds: ["milestones", agreement]
+#[account]
+pub struct MilestoneSchedule {
+    pub agreement: Pubkey,              // 32 bytes — Agreement PDA
+    pub milestones: Vec<Milestone>,     // 4 + 129 * n bytes — fixed at creation
+    pub released: u8,                   // 1 byte — milestones paid out
+    pub bump: u8,                       // 1 byte
+}
+
+impl MilestoneSchedule {
+    pub fn space(num_milestones: usize) -> usize {
+        8 + 32 + 4 + Milestone::LEN * num_milestones + 1 + 1
+    }
+
+    pub fn is_complete(&self) -> bool {
+        self.milestones.iter().all(|milestone| milestone.status == MILESTONE_RELEASED)
+    }
+}
+
+/// One deliverable and the escrow it releases.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Milestone {
+    pub payer: Pubkey,              // AgreementParty PDA whose escrow pays
+    pub payee: Pubkey,              // AgreementParty PDA that is paid
+    pub description_hash: [u8; 32], // SHA-256 of the deliverable's description
+    pub amount: u64,                // lamports
+    pub due_at: i64,                // 0 = no due date
+    pub status: u8,                 // MILESTONE_PENDING / SUBMITTED / APPROVED / DISPUTED / RELEASED
+    pub submitted_at: i64,          // 0 until submitted
+    pub resolved_at: i64,           // approval or dispute; 0 until then
+}
+
+impl Milestone {
+    pub const LEN: usize = 32 + 32 + 32 + 8 + 8 + 1 + 8 + 8;
+}
+
+/// A milestone as the proposer sets it.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct MilestoneTerms {
+    pub payer: Pubkey,
+    pub payee: Pubkey,
+    pub description_hash: [u8; 32],
+    pub amount: u64,
+    pub due_at: i64,
+}
@@ -1,9 +1,43 @@
 pub mod agent_identity;
+pub mod agent_key_lookup;
 pub mod agent_vault;
 pub mod agreement;
+pub mod agreement_amendment;
 pub mod agreement_party;
+pub mod authority_action;
+pub mod authority_set;
+pub mod authority_transfer;
+pub mod counter_proposal;
+pub mod decline;
+pub mod decompression_marker;
+pub mod escrow_stream;
+pub mod freeze;
+pub mod migration_tombstone;
+pub mod milestone_schedule;
+pub mod protocol_config;
+pub mod recurring_payment;
+pub mod revenue_share;
+pub mod safe;
+pub mod termination;
 
 pub use agent_identity::*;
+pub use agent_key_lookup::*;
 pub use agent_vault::*;
 pub use agreement::*;
+pub use agreement_amendment::*;
 pub use agreement_party::*;
+pub use authority_action::*;
+pub use authority_set::*;
+pub use authority_transfer::*;
+pub use counter_proposal::*;
+pub use decline::*;
+pub use decompression_marker::*;
+pub use escrow_stream::*;
+pub use freeze::*;
+pub use migration_tombstone::*;
+pub use milestone_schedule::*;
+pub use protocol_config::*;
+pub use recurring_payment::*;
+pub use revenue_share::*;
+pub use safe::*;
+pub use termination::*;
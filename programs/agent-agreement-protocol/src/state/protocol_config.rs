@@ -0,0 +1,35 @@
+use anchor_lang::prelude::*;
+
+/// Singleton protocol settings. Created once by the program's upgrade
+/// authority, then changed by `admin`. Every user-facing instruction of both
+/// programs loads it: aap-compressed reads this same account.
+/// Seeds: ["config"]
+#[account]
+pub struct ProtocolConfig {
+    pub admin: Pubkey,             // 32 bytes — may update the config and pause
+    pub paused: bool,              // 1 byte — rejects everything but freezes and admin instructions
+    pub max_parties: u8,           // 1 byte — upper bound for num_parties (MAX_PARTIES by default)
+    pub fee_lamports: u64,         // 8 bytes — charged to the proposer per agreement (0 = none)
+    pub treasury: Pubkey,          // 32 bytes — receives the fee
+    pub bump: u8,                  // 1 byte
+}
+
+impl ProtocolConfig {
+    pub const LEN: usize = 8 + 32 + 1 + 1 + 8 + 32 + 1; // 83 bytes
+
+    pub fn apply(&mut self, params: ConfigParams) {
+        self.admin = params.admin;
+        self.max_parties = params.max_parties;
+        self.fee_lamports = params.fee_lamports;
+        self.treasury = params.treasury;
+    }
+}
+
+/// Settable fields of `ProtocolConfig`; `paused` has its own instruction.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ConfigParams {
+    pub admin: Pubkey,
+    pub max_parties: u8,
+    pub fee_lamports: u64,
+    pub treasury: Pubkey,
+}
@@ -0,0 +1,43 @@
+use anchor_lang::prelude::*;
+
+/// Pays `amount` from the payer's AgentVault to the payee every `period`
+/// seconds from `start_at`, `num_periods` times. Set by the proposer before
+/// the other parties sign; each period is pulled by the permissionless
+/// `crank_payment` while the agreement is Active.
+/// Seeds: ["recurring", agreement]
+#[account]
+pub struct RecurringPayment {
+    pub agreement: Pubkey,         // 32 bytes — Agreement PDA
+    pub payer: Pubkey,             // 32 bytes — AgreementParty PDA whose vault pays
+    pub payee: Pubkey,             // 32 bytes — AgreementParty PDA that is paid
+    pub amount: u64,               // 8 bytes — lamports per period
+    pub period: i64,               // 8 bytes — seconds
+    pub num_periods: u16,          // 2 bytes
+    pub start_at: i64,             // 8 bytes — first period falls due
+    pub periods_paid: u16,         // 2 bytes
+    pub periods_missed: u16,       // 2 bytes — lapsed without enough funds
+    pub status: u8,                // 1 byte — RECURRING_ACTIVE / COMPLETED / CANCELLED
+    pub last_paid_at: i64,         // 8 bytes — 0 before the first payment
+    pub ended_at: i64,             // 8 bytes — completion or cancellation; 0 while active
+    pub bump: u8,                  // 1 byte
+}
+
+impl RecurringPayment {
+    pub const LEN: usize = 8 + 32 + 32 + 32 + 8 + 8 + 2 + 8 + 2 + 2 + 1 + 8 + 8 + 1; // 152 bytes
+
+    /// Periods paid or missed so far.
+    pub fn processed(&self) -> u16 {
+        self.periods_paid + self.periods_missed
+    }
+}
+
+/// A recurring payment as the proposer sets it.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct RecurringTerms {
+    pub payer: Pubkey,
+    pub payee: Pubkey,
+    pub amount: u64,
+    pub period: i64,
+    pub num_periods: u16,
+    pub start_at: i64,
+}
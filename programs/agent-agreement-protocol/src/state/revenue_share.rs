@@ -0,0 +1,38 @@
+use anchor_lang::prelude::*;
+
+/// Basis-point splits of a revenue-share agreement. Revenue is sent to the
+/// agreement's receiver PDA — SOL to the address itself, SPL tokens to token
+/// accounts it owns — and `distribute` / `distribute_tokens` pay it out by
+/// these splits while the agreement is Active. Set by the proposer before the
+/// other parties sign, so they sign the splits with the terms.
+/// Seeds: ["revenue_share", agreement]; receiver: ["revenue", agreement]
+#[account]
+pub struct RevenueShare {
+    pub agreement: Pubkey,              // 32 bytes — Agreement PDA
+    pub splits: Vec<RevenueSplit>,      // 4 + 34 * n bytes — fixed at creation
+    pub receiver_bump: u8,              // 1 byte
+    pub distributed_lamports: u64,      // 8 bytes — SOL paid out so far
+    pub distributions: u32,             // 4 bytes — SOL and token distributions so far
+    pub bump: u8,                       // 1 byte
+}
+
+impl RevenueShare {
+    pub fn space(num_splits: usize) -> usize {
+        8 + 32 + 4 + RevenueSplit::LEN * num_splits + 1 + 8 + 4 + 1
+    }
+
+    pub fn bps(&self) -> Vec<u16> {
+        self.splits.iter().map(|split| split.bps).collect()
+    }
+}
+
+/// One party's share of every distribution.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct RevenueSplit {
+    pub party: Pubkey,              // AgreementParty PDA
+    pub bps: u16,                   // basis points of each distribution
+}
+
+impl RevenueSplit {
+    pub const LEN: usize = 32 + 2;
+}
@@ -0,0 +1,63 @@
+use anchor_lang::prelude::*;
+use crate::constants::SAFE_FUNDED;
+use crate::errors::AapError;
+
+/// Typed terms of a SAFE agreement (`AGREEMENT_TYPE_SAFE`). Set by the
+/// proposer before the other parties sign. The investor signs with
+/// `sign_safe`, which moves `investment_amount` from its vault into the
+/// agreement's escrow; the escrow is held until the company converts it in a
+/// priced round (`convert_safe`) or returns it (`refund_safe`).
+/// Seeds: ["safe", agreement]
+#[account]
+pub struct Safe {
+    pub agreement: Pubkey,             // 32 bytes — Agreement PDA
+    pub investor: Pubkey,              // 32 bytes — AgreementParty PDA that invests
+    pub company: Pubkey,               // 32 bytes — AgreementParty PDA that receives it
+    pub investment_amount: u64,        // 8 bytes — lamports
+    pub valuation_cap: u64,            // 8 bytes — 0 = uncapped
+    pub discount_bps: u16,             // 2 bytes — discount on the round valuation
+    pub mint: Pubkey,                  // 32 bytes — token the investment converts into
+    pub status: u8,                    // 1 byte — SAFE_UNFUNDED / FUNDED / CONVERTED / REFUNDED
+    pub funded_at: i64,                // 8 bytes — 0 until the investor signs
+    pub round_valuation: u64,          // 8 bytes — priced round at conversion; 0 until then
+    pub conversion_valuation: u64,     // 8 bytes — after cap and discount; 0 until converted
+    pub resolved_at: i64,              // 8 bytes — conversion or refund; 0 until then
+    pub bump: u8,                      // 1 byte
+}
+
+impl Safe {
+    pub const LEN: usize = 8 + 32 + 32 + 32 + 8 + 8 + 2 + 32 + 1 + 8 + 8 + 8 + 8 + 1; // 188 bytes
+
+    /// Refuses plain signatures from the investor, who must sign with
+    /// `sign_safe`. `safe` is the agreement's Safe PDA, empty if it has none.
+    pub fn require_not_investor(safe: &AccountInfo, party: &Pubkey) -> Result<()> {
+        if safe.data_is_empty() {
+            return Ok(());
+        }
+        let safe = Safe::try_deserialize(&mut &safe.try_borrow_data()?[..])?;
+        require!(safe.investor != *party, AapError::SafeSignatureRequired);
+        Ok(())
+    }
+
+    /// The investor's AgreementParty if the SAFE is funded: its signature paid
+    /// the escrow, so it survives terms changes that reset the others. `safe`
+    /// is the agreement's Safe PDA, empty if it has none.
+    pub fn funded_investor(safe: &AccountInfo) -> Result<Option<Pubkey>> {
+        if safe.data_is_empty() {
+            return Ok(None);
+        }
+        let safe = Safe::try_deserialize(&mut &safe.try_borrow_data()?[..])?;
+        Ok((safe.status == SAFE_FUNDED).then_some(safe.investor))
+    }
+}
+
+/// A SAFE as the proposer sets it.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct SafeTerms {
+    pub investor: Pubkey,
+    pub company: Pubkey,
+    pub investment_amount: u64,
+    pub valuation_cap: u64,
+    pub discount_bps: u16,
+    pub mint: Pubkey,
+}
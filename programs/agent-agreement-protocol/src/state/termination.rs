@@ -0,0 +1,59 @@
+use anchor_lang::prelude::*;
+use crate::constants::*;
+use crate::errors::AapError;
+use super::Agreement;
+
+/// A proposed early end of an Active agreement together with a split of its
+/// escrow. Every party must approve; the last approval moves the agreement to
+/// Terminated and `release_escrow` then pays each share. One per agreement: a
+/// pending termination can be cancelled and proposed again, an applied one
+/// stays as the record the payouts read.
+/// Seeds: ["termination", agreement]
+#[account]
+pub struct Termination {
+    pub agreement: Pubkey,              // 32 bytes — Agreement PDA
+    pub proposer: Pubkey,               // 32 bytes — AgreementParty PDA that proposed it
+    pub status: u8,                     // 1 byte — TERMINATION_PENDING / APPLIED
+    pub required: u8,                   // 1 byte — approvals needed (num_parties)
+    pub shares: Vec<SettlementShare>,   // 4 + 41 * required bytes — one per party, sums to escrow_total
+    pub approvals: Vec<Pubkey>,         // 4 + 32 * required bytes — AgreementParty PDAs that approved
+    pub proposed_at: i64,               // 8 bytes
+    pub resolved_at: i64,               // 8 bytes — 0 while pending
+    pub bump: u8,                       // 1 byte
+}
+
+impl Termination {
+    pub fn space(num_parties: u8) -> usize {
+        let n = num_parties as usize;
+        8 + 32 + 32 + 1 + 1 + 4 + SettlementShare::LEN * n + 4 + 32 * n + 8 + 8 + 1
+    }
+
+    pub fn is_fully_approved(&self) -> bool {
+        aap_core::parties::is_fully_signed(self.approvals.len() as u8, self.required)
+    }
+
+    /// End the agreement; its escrow is now paid out per `shares`. Fails with
+    /// `StaleTermination` if the escrow changed since the split was proposed,
+    /// so the proposer cancels and proposes it again.
+    pub fn apply(&mut self, agreement: &mut Agreement, now: i64) -> Result<()> {
+        let amounts: Vec<u64> = self.shares.iter().map(|share| share.amount).collect();
+        aap_core::escrow::validate_settlement(&amounts, agreement.escrow_total)
+            .map_err(|_| AapError::StaleTermination)?;
+        agreement.status = STATUS_TERMINATED;
+        self.status = TERMINATION_APPLIED;
+        self.resolved_at = now;
+        Ok(())
+    }
+}
+
+/// Escrow paid to one party when the agreement is terminated.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct SettlementShare {
+    pub party: Pubkey,  // AgreementParty PDA
+    pub amount: u64,    // lamports
+    pub released: bool, // paid out by release_escrow
+}
+
+impl SettlementShare {
+    pub const LEN: usize = 32 + 8 + 1;
+}
@@ -12,6 +12,11 @@ import {
   findAgentIdentityPDA,
   findAgreementPDA,
   findAgreementPartyPDA,
+  findConfigPDA,
+  findFreezePDA,
+  findMilestonesPDA,
+  findStreamPDA,
+  findSafePDA,
 } from "./pda";
 import {
   RegisterAgentParams,
@@ -22,6 +27,7 @@ import {
   AgreementParty,
   AgreementStatus,
   PartyRole,
+  ProtocolConfig,
 } from "./types";
 
 /**
@@ -94,6 +100,16 @@ export class AAPClient {
     }
   }
 
+  async getConfig(): Promise<ProtocolConfig | null> {
+    const [pda] = findConfigPDA(this.programId);
+    try {
+      const account = await (this.program.account as any).protocolConfig.fetch(pda);
+      return account as unknown as ProtocolConfig;
+    } catch {
+      return null;
+    }
+  }
+
   async getAllAgents(): Promise<{ pubkey: PublicKey; account: AgentIdentity }[]> {
     const accounts = await (this.program.account as any).agentIdentity.all();
     return accounts.map((a: any) => ({
@@ -127,6 +143,34 @@ export class AAPClient {
     }));
   }
 
+  // ── Account helpers ──
+
+  /** Freeze PDAs of an identity, its parent and its authority. */
+  private async freezeAccounts(agentIdentityPDA: PublicKey): Promise<{
+    agentFreeze: PublicKey;
+    parentFreeze: PublicKey;
+    authorityFreeze: PublicKey;
+  }> {
+    const identity = await this.getAgentIdentityByPDA(agentIdentityPDA);
+    if (!identity) {
+      throw new Error(`Agent identity ${agentIdentityPDA.toBase58()} not found`);
+    }
+    return {
+      agentFreeze: findFreezePDA(agentIdentityPDA, this.programId)[0],
+      parentFreeze: findFreezePDA(identity.parent, this.programId)[0],
+      authorityFreeze: findFreezePDA(identity.authority, this.programId)[0],
+    };
+  }
+
+  /** The treasury named in the config, which receives the agreement fee. */
+  private async treasury(): Promise<PublicKey> {
+    const config = await this.getConfig();
+    if (!config) {
+      throw new Error("Protocol config not initialized");
+    }
+    return config.treasury;
+  }
+
   // ── Write Methods ──
 
   async registerAgent(
@@ -148,6 +192,7 @@ export class AAPClient {
         authority,
         agentIdentity: agentIdentityPDA,
         systemProgram: SystemProgram.programId,
+        config: findConfigPDA(this.programId)[0],
       })
       .rpc();
 
@@ -162,6 +207,8 @@ export class AAPClient {
     const agreementIdArr = Array.from(params.agreementId);
     const [agreementPDA] = findAgreementPDA(agreementIdArr, this.programId);
     const [partyPDA] = findAgreementPartyPDA(agreementIdArr, proposerIdentityPDA, this.programId);
+    const freeze = await this.freezeAccounts(proposerIdentityPDA);
+    const treasury = await this.treasury();
 
     const tx = await this.program.methods
       .proposeAgreement(
@@ -179,6 +226,9 @@ export class AAPClient {
         agreement: agreementPDA,
         proposerParty: partyPDA,
         systemProgram: SystemProgram.programId,
+        ...freeze,
+        config: findConfigPDA(this.programId)[0],
+        treasury,
       })
       .rpc();
 
@@ -193,6 +243,7 @@ export class AAPClient {
     const agreementIdArr = Array.from(params.agreementId);
     const [agreementPDA] = findAgreementPDA(agreementIdArr, this.programId);
     const [partyPDA] = findAgreementPartyPDA(agreementIdArr, params.partyIdentity, this.programId);
+    const freeze = await this.freezeAccounts(proposerIdentityPDA);
 
     const tx = await this.program.methods
       .addParty(agreementIdArr, params.role)
@@ -203,6 +254,8 @@ export class AAPClient {
         partyIdentity: params.partyIdentity,
         party: partyPDA,
         systemProgram: SystemProgram.programId,
+        ...freeze,
+        config: findConfigPDA(this.programId)[0],
       })
       .rpc();
 
@@ -217,6 +270,7 @@ export class AAPClient {
     const agreementIdArr = Array.from(agreementId);
     const [agreementPDA] = findAgreementPDA(agreementIdArr, this.programId);
     const [partyPDA] = findAgreementPartyPDA(agreementIdArr, signerIdentityPDA, this.programId);
+    const freeze = await this.freezeAccounts(signerIdentityPDA);
 
     const tx = await this.program.methods
       .signAgreement(agreementIdArr)
@@ -225,6 +279,9 @@ export class AAPClient {
         signerIdentity: signerIdentityPDA,
         agreement: agreementPDA,
         party: partyPDA,
+        ...freeze,
+        safe: findSafePDA(agreementPDA, this.programId)[0],
+        config: findConfigPDA(this.programId)[0],
       })
       .rpc();
 
@@ -246,6 +303,7 @@ export class AAPClient {
         signer: signerKey,
         proposerIdentity: proposerIdentityPDA,
         agreement: agreementPDA,
+        config: findConfigPDA(this.programId)[0],
       })
       .rpc();
 
@@ -269,6 +327,9 @@ export class AAPClient {
         signerIdentity: signerIdentityPDA,
         signerParty: partyPDA,
         agreement: agreementPDA,
+        milestones: findMilestonesPDA(agreementPDA, this.programId)[0],
+        stream: findStreamPDA(agreementPDA, this.programId)[0],
+        config: findConfigPDA(this.programId)[0],
       })
       .rpc();
 
@@ -292,6 +353,8 @@ export class AAPClient {
         signerIdentity: signerIdentityPDA,
         signerParty: partyPDA,
         agreement: agreementPDA,
+        stream: findStreamPDA(agreementPDA, this.programId)[0],
+        config: findConfigPDA(this.programId)[0],
       })
       .rpc();
 
@@ -319,6 +382,7 @@ export class AAPClient {
         authority,
         agentIdentity: agentIdentityPDA,
         systemProgram: SystemProgram.programId,
+        config: findConfigPDA(this.programId)[0],
       })
       .transaction();
   }
@@ -331,6 +395,8 @@ export class AAPClient {
     const agreementIdArr = Array.from(params.agreementId);
     const [agreementPDA] = findAgreementPDA(agreementIdArr, this.programId);
     const [partyPDA] = findAgreementPartyPDA(agreementIdArr, proposerIdentityPDA, this.programId);
+    const freeze = await this.freezeAccounts(proposerIdentityPDA);
+    const treasury = await this.treasury();
 
     const transaction = await this.program.methods
       .proposeAgreement(
@@ -348,6 +414,9 @@ export class AAPClient {
         agreement: agreementPDA,
         proposerParty: partyPDA,
         systemProgram: SystemProgram.programId,
+        ...freeze,
+        config: findConfigPDA(this.programId)[0],
+        treasury,
       })
       .transaction();
 
@@ -362,6 +431,7 @@ export class AAPClient {
     const agreementIdArr = Array.from(agreementId);
     const [agreementPDA] = findAgreementPDA(agreementIdArr, this.programId);
     const [partyPDA] = findAgreementPartyPDA(agreementIdArr, signerIdentityPDA, this.programId);
+    const freeze = await this.freezeAccounts(signerIdentityPDA);
 
     return await this.program.methods
       .signAgreement(agreementIdArr)
@@ -370,6 +440,9 @@ export class AAPClient {
         signerIdentity: signerIdentityPDA,
         agreement: agreementPDA,
         party: partyPDA,
+        ...freeze,
+        safe: findSafePDA(agreementPDA, this.programId)[0],
+        config: findConfigPDA(this.programId)[0],
       })
       .transaction();
   }
@@ -5,6 +5,11 @@ export {
   findAgreementPDA,
   findAgreementPartyPDA,
   findEscrowVaultPDA,
+  findConfigPDA,
+  findFreezePDA,
+  findMilestonesPDA,
+  findStreamPDA,
+  findSafePDA,
 } from "./pda";
 export {
   AgreementType,
@@ -15,6 +20,7 @@ export {
   AgentIdentity,
   Agreement,
   AgreementParty,
+  ProtocolConfig,
   RegisterAgentParams,
   ProposeAgreementParams,
   AddPartyParams,
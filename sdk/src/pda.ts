@@ -48,3 +48,49 @@ export function findEscrowVaultPDA(
     programId
   );
 }
+
+export function findConfigPDA(
+  programId: PublicKey = PROGRAM_ID
+): [PublicKey, number] {
+  return PublicKey.findProgramAddressSync([Buffer.from("config")], programId);
+}
+
+export function findFreezePDA(
+  target: PublicKey,
+  programId: PublicKey = PROGRAM_ID
+): [PublicKey, number] {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from("freeze"), target.toBuffer()],
+    programId
+  );
+}
+
+export function findMilestonesPDA(
+  agreement: PublicKey,
+  programId: PublicKey = PROGRAM_ID
+): [PublicKey, number] {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from("milestones"), agreement.toBuffer()],
+    programId
+  );
+}
+
+export function findStreamPDA(
+  agreement: PublicKey,
+  programId: PublicKey = PROGRAM_ID
+): [PublicKey, number] {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from("stream"), agreement.toBuffer()],
+    programId
+  );
+}
+
+export function findSafePDA(
+  agreement: PublicKey,
+  programId: PublicKey = PROGRAM_ID
+): [PublicKey, number] {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from("safe"), agreement.toBuffer()],
+    programId
+  );
+}
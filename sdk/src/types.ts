@@ -79,6 +79,15 @@ export interface AgreementParty {
   bump: number;
 }
 
+export interface ProtocolConfig {
+  admin: PublicKey;
+  paused: boolean;
+  maxParties: number;
+  feeLamports: bigint;
+  treasury: PublicKey;
+  bump: number;
+}
+
 // ── Instruction params ──
 
 export interface RegisterAgentParams {
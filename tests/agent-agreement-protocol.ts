@@ -15,6 +15,11 @@ const VISIBILITY_PUBLIC = 0;
 const VISIBILITY_PRIVATE = 1;
 const ROLE_PROPOSER = 0;
 const ROLE_COUNTERPARTY = 1;
+const MAX_PARTIES = 8;
+
+const BPF_LOADER_UPGRADEABLE_ID = new PublicKey(
+  "BPFLoaderUpgradeab1e11111111111111111111111"
+);
 
 function generateAgreementId(): number[] {
   return Array.from({ length: 16 }, () => Math.floor(Math.random() * 256));
@@ -55,6 +60,10 @@ function findAgreementPda(
   );
 }
 
+function findConfigPda(programId: PublicKey): [PublicKey, number] {
+  return PublicKey.findProgramAddressSync([Buffer.from("config")], programId);
+}
+
 function findPartyPda(
   agreementId: number[],
   identityPda: PublicKey,
@@ -85,8 +94,33 @@ describe("Agent Agreement Protocol", () => {
   // PDAs
   let identityPdaA: PublicKey;
   let identityPdaB: PublicKey;
+  const [configPda] = findConfigPda(program.programId);
 
   before(async () => {
+    // Every instruction takes the ProtocolConfig; the deployer (the
+    // provider wallet) is the upgrade authority that may create it
+    if (!(await provider.connection.getAccountInfo(configPda))) {
+      const [programData] = PublicKey.findProgramAddressSync(
+        [program.programId.toBuffer()],
+        BPF_LOADER_UPGRADEABLE_ID
+      );
+      await program.methods
+        .initializeConfig({
+          admin: provider.wallet.publicKey,
+          maxParties: MAX_PARTIES,
+          feeLamports: new BN(0),
+          treasury: provider.wallet.publicKey,
+        })
+        .accounts({
+          upgradeAuthority: provider.wallet.publicKey,
+          config: configPda,
+          program: program.programId,
+          programData,
+          systemProgram: SystemProgram.programId,
+        })
+        .rpc();
+    }
+
     authorityA = Keypair.generate();
     agentKeyA = Keypair.generate();
     authorityB = Keypair.generate();
@@ -131,6 +165,7 @@ describe("Agent Agreement Protocol", () => {
           authority: authorityA.publicKey,
           agentIdentity: identityPdaA,
           systemProgram: SystemProgram.programId,
+          config: configPda,
         })
         .signers([authorityA])
         .rpc();
@@ -159,6 +194,7 @@ describe("Agent Agreement Protocol", () => {
           authority: authorityB.publicKey,
           agentIdentity: identityPdaB,
           systemProgram: SystemProgram.programId,
+          config: configPda,
         })
         .signers([authorityB])
         .rpc();
@@ -191,6 +227,7 @@ describe("Agent Agreement Protocol", () => {
           authority: humanWallet.publicKey,
           agentIdentity: humanPda,
           systemProgram: SystemProgram.programId,
+          config: configPda,
         })
         .signers([humanWallet])
         .rpc();
@@ -218,6 +255,7 @@ describe("Agent Agreement Protocol", () => {
             authority: authorityA.publicKey,
             agentIdentity: tempPda,
             systemProgram: SystemProgram.programId,
+            config: configPda,
           })
           .signers([authorityA])
           .rpc();
@@ -240,6 +278,7 @@ describe("Agent Agreement Protocol", () => {
         .accounts({
           authority: authorityA.publicKey,
           agentIdentity: identityPdaA,
+          config: configPda,
         })
         .signers([authorityA])
         .rpc();
@@ -262,6 +301,7 @@ describe("Agent Agreement Protocol", () => {
           .accounts({
             authority: authorityB.publicKey,
             agentIdentity: identityPdaA,
+            config: configPda,
           })
           .signers([authorityB])
           .rpc();
@@ -297,6 +337,7 @@ describe("Agent Agreement Protocol", () => {
           parentIdentity: identityPdaA,
           subAgentIdentity: subAgentPda,
           systemProgram: SystemProgram.programId,
+          config: configPda,
         })
         .signers([agentKeyA])
         .rpc();
@@ -340,6 +381,7 @@ describe("Agent Agreement Protocol", () => {
             parentIdentity: subAgentPda,
             subAgentIdentity: subSubPda,
             systemProgram: SystemProgram.programId,
+            config: configPda,
           })
           .signers([subAgentKey])
           .rpc();
@@ -370,6 +412,7 @@ describe("Agent Agreement Protocol", () => {
             parentIdentity: identityPdaA,
             subAgentIdentity: badSubPda,
             systemProgram: SystemProgram.programId,
+            config: configPda,
           })
           .signers([agentKeyA])
           .rpc();
@@ -405,6 +448,7 @@ describe("Agent Agreement Protocol", () => {
           authority: authorityA.publicKey,
           agentIdentity: tempIdentityPda,
           systemProgram: SystemProgram.programId,
+          config: configPda,
         })
         .signers([authorityA])
         .rpc();
@@ -420,6 +464,7 @@ describe("Agent Agreement Protocol", () => {
         .accounts({
           authority: authorityA.publicKey,
           agentIdentity: tempIdentityPda,
+          config: configPda,
         })
         .signers([authorityA])
         .rpc();
@@ -442,6 +487,7 @@ describe("Agent Agreement Protocol", () => {
           .accounts({
             authority: authorityB.publicKey,
             agentIdentity: identityPdaA,
+            config: configPda,
           })
           .signers([authorityB])
           .rpc();
@@ -488,6 +534,8 @@ describe("Agent Agreement Protocol", () => {
           agreement: agreementPda,
           proposerParty: proposerPartyPda,
           systemProgram: SystemProgram.programId,
+          config: configPda,
+          treasury: provider.wallet.publicKey,
         })
         .signers([agentKeyA])
         .rpc();
@@ -532,6 +580,8 @@ describe("Agent Agreement Protocol", () => {
             agreement: badPda,
             proposerParty: badPartyPda,
             systemProgram: SystemProgram.programId,
+            config: configPda,
+            treasury: provider.wallet.publicKey,
           })
           .signers([agentKeyA])
           .rpc();
@@ -566,6 +616,8 @@ describe("Agent Agreement Protocol", () => {
             agreement: badPda,
             proposerParty: badPartyPda,
             systemProgram: SystemProgram.programId,
+            config: configPda,
+            treasury: provider.wallet.publicKey,
           })
           .signers([agentKeyA])
           .rpc();
@@ -613,6 +665,8 @@ describe("Agent Agreement Protocol", () => {
           agreement: agreementPda,
           proposerParty: proposerPartyPda,
           systemProgram: SystemProgram.programId,
+          config: configPda,
+          treasury: provider.wallet.publicKey,
         })
         .signers([agentKeyA])
         .rpc();
@@ -628,6 +682,7 @@ describe("Agent Agreement Protocol", () => {
           partyIdentity: identityPdaB,
           party: counterpartyPda,
           systemProgram: SystemProgram.programId,
+          config: configPda,
         })
         .signers([agentKeyA])
         .rpc();
@@ -667,6 +722,8 @@ describe("Agent Agreement Protocol", () => {
           agreement: extraPda,
           proposerParty: extraProposerPda,
           systemProgram: SystemProgram.programId,
+          config: configPda,
+          treasury: provider.wallet.publicKey,
         })
         .signers([agentKeyA])
         .rpc();
@@ -687,6 +744,7 @@ describe("Agent Agreement Protocol", () => {
             partyIdentity: identityPdaB,
             party: extraCounterpartyPda,
             systemProgram: SystemProgram.programId,
+            config: configPda,
           })
           .signers([agentKeyB])
           .rpc();
@@ -734,6 +792,8 @@ describe("Agent Agreement Protocol", () => {
           agreement: agreementPda,
           proposerParty: proposerPartyPda,
           systemProgram: SystemProgram.programId,
+          config: configPda,
+          treasury: provider.wallet.publicKey,
         })
         .signers([agentKeyA])
         .rpc();
@@ -747,6 +807,7 @@ describe("Agent Agreement Protocol", () => {
           partyIdentity: identityPdaB,
           party: counterpartyPda,
           systemProgram: SystemProgram.programId,
+          config: configPda,
         })
         .signers([agentKeyA])
         .rpc();
@@ -760,6 +821,7 @@ describe("Agent Agreement Protocol", () => {
           signerIdentity: identityPdaB,
           agreement: agreementPda,
           party: counterpartyPda,
+          config: configPda,
         })
         .signers([agentKeyB])
         .rpc();
@@ -783,6 +845,7 @@ describe("Agent Agreement Protocol", () => {
             signerIdentity: identityPdaB,
             agreement: agreementPda,
             party: counterpartyPda,
+            config: configPda,
           })
           .signers([agentKeyB])
           .rpc();
@@ -826,6 +889,8 @@ describe("Agent Agreement Protocol", () => {
           agreement: agreementPda,
           proposerParty: proposerPartyPda,
           systemProgram: SystemProgram.programId,
+          config: configPda,
+          treasury: provider.wallet.publicKey,
         })
         .signers([agentKeyA])
         .rpc();
@@ -838,6 +903,7 @@ describe("Agent Agreement Protocol", () => {
           signer: agentKeyA.publicKey,
           proposerIdentity: identityPdaA,
           agreement: agreementPda,
+          config: configPda,
         })
         .signers([agentKeyA])
         .rpc();
@@ -871,6 +937,8 @@ describe("Agent Agreement Protocol", () => {
           agreement: newPda,
           proposerParty: newPartyPda,
           systemProgram: SystemProgram.programId,
+          config: configPda,
+          treasury: provider.wallet.publicKey,
         })
         .signers([agentKeyA])
         .rpc();
@@ -881,6 +949,7 @@ describe("Agent Agreement Protocol", () => {
           signer: authorityA.publicKey,
           proposerIdentity: identityPdaA,
           agreement: newPda,
+          config: configPda,
         })
         .signers([authorityA])
         .rpc();
@@ -914,6 +983,8 @@ describe("Agent Agreement Protocol", () => {
           agreement: newPda,
           proposerParty: newPartyPda,
           systemProgram: SystemProgram.programId,
+          config: configPda,
+          treasury: provider.wallet.publicKey,
         })
         .signers([agentKeyA])
         .rpc();
@@ -925,6 +996,7 @@ describe("Agent Agreement Protocol", () => {
             signer: agentKeyB.publicKey,
             proposerIdentity: identityPdaB,
             agreement: newPda,
+            config: configPda,
           })
           .signers([agentKeyB])
           .rpc();
@@ -972,6 +1044,8 @@ describe("Agent Agreement Protocol", () => {
           agreement: agreementPda,
           proposerParty: proposerPartyPda,
           systemProgram: SystemProgram.programId,
+          config: configPda,
+          treasury: provider.wallet.publicKey,
         })
         .signers([agentKeyA])
         .rpc();
@@ -985,6 +1059,7 @@ describe("Agent Agreement Protocol", () => {
           partyIdentity: identityPdaB,
           party: counterpartyPda,
           systemProgram: SystemProgram.programId,
+          config: configPda,
         })
         .signers([agentKeyA])
         .rpc();
@@ -996,6 +1071,7 @@ describe("Agent Agreement Protocol", () => {
           signerIdentity: identityPdaB,
           agreement: agreementPda,
           party: counterpartyPda,
+          config: configPda,
         })
         .signers([agentKeyB])
         .rpc();
@@ -1009,6 +1085,7 @@ describe("Agent Agreement Protocol", () => {
           signerIdentity: identityPdaA,
           signerParty: proposerPartyPda,
           agreement: agreementPda,
+          config: configPda,
         })
         .signers([agentKeyA])
         .rpc();
@@ -1026,6 +1103,7 @@ describe("Agent Agreement Protocol", () => {
             signerIdentity: identityPdaA,
             signerParty: proposerPartyPda,
             agreement: agreementPda,
+            config: configPda,
           })
           .signers([agentKeyA])
           .rpc();
@@ -1073,6 +1151,8 @@ describe("Agent Agreement Protocol", () => {
           agreement: agreementPda,
           proposerParty: proposerPartyPda,
           systemProgram: SystemProgram.programId,
+          config: configPda,
+          treasury: provider.wallet.publicKey,
         })
         .signers([agentKeyA])
         .rpc();
@@ -1086,6 +1166,7 @@ describe("Agent Agreement Protocol", () => {
           partyIdentity: identityPdaB,
           party: counterpartyPda,
           systemProgram: SystemProgram.programId,
+          config: configPda,
         })
         .signers([agentKeyA])
         .rpc();
@@ -1097,6 +1178,7 @@ describe("Agent Agreement Protocol", () => {
           signerIdentity: identityPdaB,
           agreement: agreementPda,
           party: counterpartyPda,
+          config: configPda,
         })
         .signers([agentKeyB])
         .rpc();
@@ -1108,6 +1190,7 @@ describe("Agent Agreement Protocol", () => {
           signerIdentity: identityPdaA,
           signerParty: proposerPartyPda,
           agreement: agreementPda,
+          config: configPda,
         })
         .signers([agentKeyA])
         .rpc();
@@ -1125,6 +1208,7 @@ describe("Agent Agreement Protocol", () => {
           signerIdentity: identityPdaA,
           signerParty: proposerPartyPda,
           agreement: agreementPda,
+          config: configPda,
         })
         .signers([authorityA])
         .rpc();
@@ -1185,6 +1269,7 @@ describe("Agent Agreement Protocol", () => {
           authority: authX.publicKey,
           agentIdentity: idPdaX,
           systemProgram: SystemProgram.programId,
+          config: configPda,
         })
         .signers([authX])
         .rpc();
@@ -1200,6 +1285,7 @@ describe("Agent Agreement Protocol", () => {
           authority: authY.publicKey,
           agentIdentity: idPdaY,
           systemProgram: SystemProgram.programId,
+          config: configPda,
         })
         .signers([authY])
         .rpc();
@@ -1226,6 +1312,8 @@ describe("Agent Agreement Protocol", () => {
           agreement: agPda,
           proposerParty: partyXPda,
           systemProgram: SystemProgram.programId,
+          config: configPda,
+          treasury: provider.wallet.publicKey,
         })
         .signers([agentX])
         .rpc();
@@ -1240,6 +1328,7 @@ describe("Agent Agreement Protocol", () => {
           partyIdentity: idPdaY,
           party: partyYPda,
           systemProgram: SystemProgram.programId,
+          config: configPda,
         })
         .signers([agentX])
         .rpc();
@@ -1252,6 +1341,7 @@ describe("Agent Agreement Protocol", () => {
           signerIdentity: idPdaY,
           agreement: agPda,
           party: partyYPda,
+          config: configPda,
         })
         .signers([agentY])
         .rpc();
@@ -1267,6 +1357,7 @@ describe("Agent Agreement Protocol", () => {
           signerIdentity: idPdaX,
           signerParty: partyXPda,
           agreement: agPda,
+          config: configPda,
         })
         .signers([agentX])
         .rpc();
@@ -1284,6 +1375,7 @@ describe("Agent Agreement Protocol", () => {
           signerIdentity: idPdaX,
           signerParty: partyXPda,
           agreement: agPda,
+          config: configPda,
         })
         .signers([authX])
         .rpc();
@@ -1325,6 +1417,7 @@ describe("Agent Agreement Protocol", () => {
           authority: authC.publicKey,
           agentIdentity: idPdaC,
           systemProgram: SystemProgram.programId,
+          config: configPda,
         })
         .signers([authC])
         .rpc();
@@ -1349,6 +1442,8 @@ describe("Agent Agreement Protocol", () => {
           agreement: agPda,
           proposerParty: partyCPda,
           systemProgram: SystemProgram.programId,
+          config: configPda,
+          treasury: provider.wallet.publicKey,
         })
         .signers([agentC])
         .rpc();
@@ -1360,6 +1455,7 @@ describe("Agent Agreement Protocol", () => {
           signer: agentC.publicKey,
           proposerIdentity: idPdaC,
           agreement: agPda,
+          config: configPda,
         })
         .signers([agentC])
         .rpc();
@@ -1376,6 +1472,7 @@ describe("Agent Agreement Protocol", () => {
           signerIdentity: idPdaC,
           signerParty: partyCPda,
           agreement: agPda,
+          config: configPda,
         })
         .signers([authC])
         .rpc();
@@ -1412,6 +1509,7 @@ describe("Agent Agreement Protocol", () => {
           parentIdentity: identityPdaA,
           subAgentIdentity: subPda,
           systemProgram: SystemProgram.programId,
+          config: configPda,
         })
         .signers([agentKeyA])
         .rpc();
@@ -1438,6 +1536,8 @@ describe("Agent Agreement Protocol", () => {
           agreement: agPda,
           proposerParty: subPartyPda,
           systemProgram: SystemProgram.programId,
+          config: configPda,
+          treasury: provider.wallet.publicKey,
         })
         .signers([subKey])
         .rpc();
@@ -1452,6 +1552,7 @@ describe("Agent Agreement Protocol", () => {
           partyIdentity: identityPdaB,
           party: bPartyPda,
           systemProgram: SystemProgram.programId,
+          config: configPda,
         })
         .signers([subKey])
         .rpc();
@@ -1464,6 +1565,7 @@ describe("Agent Agreement Protocol", () => {
           signerIdentity: identityPdaB,
           agreement: agPda,
           party: bPartyPda,
+          config: configPda,
         })
         .signers([agentKeyB])
         .rpc();
@@ -1498,6 +1600,7 @@ describe("Agent Agreement Protocol", () => {
           authority: humanA.publicKey,
           agentIdentity: humanAPda,
           systemProgram: SystemProgram.programId,
+          config: configPda,
         })
         .signers([humanA])
         .rpc();
@@ -1527,6 +1630,8 @@ describe("Agent Agreement Protocol", () => {
           agreement: agPda,
           proposerParty: proposerPartyPda,
           systemProgram: SystemProgram.programId,
+          config: configPda,
+          treasury: provider.wallet.publicKey,
         })
         .signers([humanA])
         .rpc();
@@ -1545,6 +1650,7 @@ describe("Agent Agreement Protocol", () => {
           agreement: agPda,
           party: counterpartyPartyPda,
           systemProgram: SystemProgram.programId,
+          config: configPda,
         })
         .signers([humanA])
         .rpc();
@@ -1564,6 +1670,7 @@ describe("Agent Agreement Protocol", () => {
           signer: humanB.publicKey,
           agreement: agPda,
           party: counterpartyPartyPda,
+          config: configPda,
         })
         .signers([humanB])
         .rpc();